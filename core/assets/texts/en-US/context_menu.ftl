@@ -1,7 +1,10 @@
 context-menu-play = Play
+context-menu-loop = Loop
 context-menu-rewind = Rewind
 context-menu-forward = Forward
 context-menu-back = Back
+context-menu-playback = Playback
+context-menu-quality = Quality
 context-menu-quality-low = Quality: Low
 context-menu-quality-medium = Quality: Medium
 context-menu-quality-high = Quality: High
@@ -13,7 +13,6 @@ package flash.display {
     [Ruffle(Abstract)]
     public class Stage extends DisplayObjectContainer {
         private var _colorCorrection:String = ColorCorrection.DEFAULT;
-        private var _mouseLock:Boolean = false;
         private var _nativeWindow:NativeWindow;
         private var _fullScreenSourceRect:Rectangle;
 
@@ -276,16 +275,10 @@ package flash.display {
         }
 
         [API("678")]
-        public function get mouseLock():Boolean {
-            stub_getter("flash.display.Stage", "mouseLock");
-            return this._mouseLock;
-        }
+        public native function get mouseLock():Boolean;
 
         [API("678")]
-        public function set mouseLock(value:Boolean):void {
-            stub_setter("flash.display.Stage", "mouseLock");
-            this._mouseLock = value;
-        }
+        public native function set mouseLock(value:Boolean):void;
 
         [API("668")]
         public static function get supportsOrientationChange():Boolean {
@@ -1,7 +1,7 @@
 //! `flash.display.Stage` builtin/prototype
 
 use crate::avm2::activation::Activation;
-use crate::avm2::error::make_error_2008;
+use crate::avm2::error::{illegal_operation_error, make_error_2008};
 use crate::avm2::object::VectorObject;
 use crate::avm2::parameters::ParametersExt;
 use crate::avm2::value::Value;
@@ -238,6 +238,38 @@ pub fn set_frame_rate<'gc>(
     Ok(Value::Undefined)
 }
 
+/// Implement `mouseLock`'s getter
+///
+/// The lock may be released by the user at any time, so this asks the UI backend.
+pub fn get_mouse_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    _args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    Ok(activation.context.ui.mouse_locked().into())
+}
+
+/// Implement `mouseLock`'s setter
+pub fn set_mouse_lock<'gc>(
+    activation: &mut Activation<'_, 'gc>,
+    _this: Value<'gc>,
+    args: &[Value<'gc>],
+) -> Result<Value<'gc>, Error<'gc>> {
+    let locked = args.get_bool(0);
+    // The mouse can only be locked in interactive fullscreen.
+    if locked
+        && activation.context.stage.display_state() != StageDisplayState::FullScreenInteractive
+    {
+        return Err(Error::avm_error(illegal_operation_error(
+            activation,
+            "Error #2190: The attempted operation is not supported in this context.",
+            2190,
+        )?));
+    }
+    activation.context.ui.set_mouse_lock(locked);
+    Ok(Value::Undefined)
+}
+
 pub fn get_show_default_context_menu<'gc>(
     activation: &mut Activation<'_, 'gc>,
     _this: Value<'gc>,
@@ -46,7 +46,13 @@ pub fn get_version<'gc>(
     };
     Ok(AvmString::new_utf8(
         activation.gc(),
-        format!("{os} {},0,0,0", activation.avm2().player_version),
+        format!(
+            "{os} {}",
+            activation
+                .context
+                .system
+                .get_version_numbers(activation.avm2().player_version)
+        ),
     )
     .into())
 }
@@ -86,6 +86,7 @@ pub fn make_context_menu_state<'gc>(
                                     separator_before: separator_before || i == 0,
                                     caption: caption.to_string(),
                                     checked: false,
+                                    submenu: None,
                                 },
                                 context_menu::ContextMenuCallback::Avm2 { item },
                             );
@@ -66,6 +66,13 @@ pub trait UiBackend: Any {
 
     fn set_mouse_visible(&mut self, visible: bool);
 
+    /// Locks the mouse pointer in place, so that the movie only receives relative mouse movement.
+    /// Backends may release the lock at any time, e.g. when the user presses Escape.
+    fn set_mouse_lock(&mut self, locked: bool);
+
+    /// Whether the mouse pointer is locked, see [`UiBackend::set_mouse_lock`].
+    fn mouse_locked(&self) -> bool;
+
     /// Changes the mouse cursor image.
     fn set_mouse_cursor(&mut self, cursor: MouseCursor);
 
@@ -172,6 +179,12 @@ impl UiBackend for NullUiBackend {
 
     fn set_mouse_visible(&mut self, _visible: bool) {}
 
+    fn set_mouse_lock(&mut self, _locked: bool) {}
+
+    fn mouse_locked(&self) -> bool {
+        false
+    }
+
     fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
 
     fn clipboard_content(&mut self) -> String {
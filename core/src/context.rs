@@ -26,6 +26,7 @@ use crate::input::InputManager;
 use crate::library::Library;
 use crate::loader::LoadManager;
 use crate::local_connection::LocalConnections;
+use crate::locale::{get_current_date_time, FakeTime};
 use crate::net_connection::NetConnections;
 use crate::player::PostFrameCallback;
 use crate::player::{MouseData, Player};
@@ -41,6 +42,7 @@ use crate::timer::Timers;
 use crate::vminterface::Instantiator;
 use crate::PlayerMode;
 use async_channel::Sender;
+use chrono::{DateTime, Utc};
 use core::fmt;
 use gc_arena::{Collect, Mutation};
 use rand::rngs::SmallRng;
@@ -181,6 +183,9 @@ pub struct UpdateContext<'gc> {
     /// The instant at which the SWF was launched.
     pub start_time: Instant,
 
+    /// The clock used instead of the system clock, if time is faked.
+    pub fake_time: Option<FakeTime>,
+
     /// The instant at which the current update started.
     pub update_start: Instant,
 
@@ -453,6 +458,22 @@ impl<'gc> UpdateContext<'gc> {
         self.set_root_movie(movie);
     }
 
+    /// The time since the SWF was launched, as seen by `getTimer`.
+    pub fn time_since_start(&self) -> Duration {
+        match self.fake_time {
+            Some(fake_time) => fake_time.elapsed(),
+            None => Instant::now().duration_since(self.start_time),
+        }
+    }
+
+    /// The current date and time, as seen by `Date`.
+    pub fn current_date_time(&self) -> DateTime<Utc> {
+        match self.fake_time {
+            Some(fake_time) => fake_time.date_time(),
+            None => get_current_date_time(),
+        }
+    }
+
     pub fn send_notification(&self, notification: PlayerNotification) {
         if let Some(notification_sender) = self.notification_sender {
             if let Err(e) = notification_sender.try_send(notification) {
@@ -21,6 +21,9 @@ pub struct ContextMenuState<'gc> {
     info: Vec<ContextMenuItem>,
     callbacks: Vec<ContextMenuCallback<'gc>>,
     object: Option<DisplayObject<'gc>>,
+    /// Whether the movie allows zooming the view from the menu,
+    /// which is up to the frontend as it's in charge of the view.
+    zoom: bool,
 }
 
 impl<'gc> ContextMenuState<'gc> {
@@ -49,6 +52,10 @@ impl<'gc> ContextMenuState<'gc> {
         self.object = object;
     }
 
+    pub fn zoom(&self) -> bool {
+        self.zoom
+    }
+
     pub fn build_builtin_items(
         &mut self,
         item_flags: BuiltInItemFlags,
@@ -66,9 +73,13 @@ impl<'gc> ContextMenuState<'gc> {
             }
         }
 
+        self.zoom = item_flags.zoom;
+
         let Some(root_mc) = stage.root_clip().and_then(|c| c.as_movie_clip()) else {
             return;
         };
+        let playback = core_text(language, "context-menu-playback");
+        let quality = core_text(language, "context-menu-quality");
         if item_flags.play {
             let is_playing_root_movie = root_mc.playing();
             self.push(
@@ -77,10 +88,23 @@ impl<'gc> ContextMenuState<'gc> {
                     separator_before: true,
                     caption: core_text(language, "context-menu-play"),
                     checked: is_playing_root_movie,
+                    submenu: Some(playback.clone()),
                 },
                 ContextMenuCallback::Play,
             );
         }
+        if item_flags.loop_ {
+            self.push(
+                ContextMenuItem {
+                    enabled: true,
+                    separator_before: false,
+                    caption: core_text(language, "context-menu-loop"),
+                    checked: stage.loop_root_movie(),
+                    submenu: Some(playback.clone()),
+                },
+                ContextMenuCallback::Loop,
+            );
+        }
         if item_flags.rewind {
             let is_first_frame = root_mc.current_frame() <= 1;
             self.push(
@@ -89,18 +113,21 @@ impl<'gc> ContextMenuState<'gc> {
                     separator_before: true,
                     caption: core_text(language, "context-menu-rewind"),
                     checked: false,
+                    submenu: Some(playback.clone()),
                 },
                 ContextMenuCallback::Rewind,
             );
         }
         if item_flags.forward_and_back {
             let is_first_frame = root_mc.current_frame() <= 1;
+            let is_last_frame = root_mc.current_frame() >= root_mc.total_frames();
             self.push(
                 ContextMenuItem {
-                    enabled: true,
+                    enabled: !is_last_frame,
                     separator_before: false,
                     caption: core_text(language, "context-menu-forward"),
                     checked: false,
+                    submenu: Some(playback.clone()),
                 },
                 ContextMenuCallback::Forward,
             );
@@ -110,18 +137,19 @@ impl<'gc> ContextMenuState<'gc> {
                     separator_before: false,
                     caption: core_text(language, "context-menu-back"),
                     checked: false,
+                    submenu: Some(playback.clone()),
                 },
                 ContextMenuCallback::Back,
             );
         }
         if item_flags.quality {
-            // TODO: This should be a submenu, but at time of writing those aren't supported
             self.push(
                 ContextMenuItem {
                     enabled: stage.quality() != StageQuality::Low,
                     separator_before: true,
                     checked: stage.quality() == StageQuality::Low,
                     caption: core_text(language, "context-menu-quality-low"),
+                    submenu: Some(quality.clone()),
                 },
                 ContextMenuCallback::QualityLow,
             );
@@ -131,6 +159,7 @@ impl<'gc> ContextMenuState<'gc> {
                     separator_before: false,
                     checked: stage.quality() == StageQuality::Medium,
                     caption: core_text(language, "context-menu-quality-medium"),
+                    submenu: Some(quality.clone()),
                 },
                 ContextMenuCallback::QualityMedium,
             );
@@ -140,6 +169,7 @@ impl<'gc> ContextMenuState<'gc> {
                     separator_before: false,
                     checked: stage.quality() == StageQuality::High,
                     caption: core_text(language, "context-menu-quality-high"),
+                    submenu: Some(quality.clone()),
                 },
                 ContextMenuCallback::QualityHigh,
             );
@@ -154,6 +184,7 @@ impl<'gc> ContextMenuState<'gc> {
                 separator_before: true,
                 caption: core_text(language, "context-menu-cut"),
                 checked: false,
+                submenu: None,
             },
             ContextMenuCallback::TextControl {
                 code: TextControlCode::Cut,
@@ -166,6 +197,7 @@ impl<'gc> ContextMenuState<'gc> {
                 separator_before: false,
                 caption: core_text(language, "context-menu-copy"),
                 checked: false,
+                submenu: None,
             },
             ContextMenuCallback::TextControl {
                 code: TextControlCode::Copy,
@@ -178,6 +210,7 @@ impl<'gc> ContextMenuState<'gc> {
                 separator_before: false,
                 caption: core_text(language, "context-menu-paste"),
                 checked: false,
+                submenu: None,
             },
             ContextMenuCallback::TextControl {
                 code: TextControlCode::Paste,
@@ -190,6 +223,7 @@ impl<'gc> ContextMenuState<'gc> {
                 separator_before: false,
                 caption: core_text(language, "context-menu-delete"),
                 checked: false,
+                submenu: None,
             },
             ContextMenuCallback::TextControl {
                 code: TextControlCode::Delete,
@@ -202,6 +236,7 @@ impl<'gc> ContextMenuState<'gc> {
                 separator_before: true,
                 caption: core_text(language, "context-menu-select-all"),
                 checked: false,
+                submenu: None,
             },
             ContextMenuCallback::TextControl {
                 code: TextControlCode::SelectAll,
@@ -219,6 +254,12 @@ pub struct ContextMenuItem {
     pub separator_before: bool,
     pub checked: bool,
     pub caption: String,
+    /// The caption of the submenu this item is grouped under, if any.
+    ///
+    /// Consecutive items with the same submenu are shown together in it, by frontends that support submenus.
+    /// Others show them in the menu itself, like any other item.
+    #[cfg_attr(feature = "serde", serde(skip))]
+    pub submenu: Option<String>,
 }
 
 #[derive(Collect)]
@@ -2962,9 +2962,10 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
         event: ClipEvent,
     ) -> ClipEventResult {
         match event {
-            ClipEvent::Press { .. } | ClipEvent::MouseWheel { .. } | ClipEvent::MouseMove => {
-                ClipEventResult::Handled
-            }
+            ClipEvent::Press { .. }
+            | ClipEvent::MouseWheel { .. }
+            | ClipEvent::HorizontalMouseWheel { .. }
+            | ClipEvent::MouseMove => ClipEventResult::Handled,
             _ => ClipEventResult::NotHandled,
         }
     }
@@ -2997,6 +2998,26 @@ impl<'gc> TInteractiveObject<'gc> for EditText<'gc> {
             }
         }
 
+        if let ClipEvent::HorizontalMouseWheel { delta } = event {
+            if !self.is_mouse_wheel_enabled() {
+                return ClipEventResult::NotHandled;
+            }
+            let hscroll = self.hscroll();
+            let new_hscroll = (hscroll - delta.pixels()).clamp(0.0, self.maxhscroll());
+            if new_hscroll == hscroll {
+                return ClipEventResult::NotHandled;
+            }
+            self.set_hscroll(new_hscroll);
+
+            let mut activation = Avm1Activation::from_nothing(
+                context,
+                ActivationIdentifier::root("[On Scroller]"),
+                self.into(),
+            );
+            self.on_scroller(&mut activation);
+            return ClipEventResult::Handled;
+        }
+
         if let ClipEvent::Press { index } = event {
             // We can't hold self as any link may end up modifying this object, so pull the info out
             let mut link_to_open = None;
@@ -1154,6 +1154,16 @@ impl<'gc> MovieClip<'gc> {
         actions.into_iter()
     }
 
+    /// Whether the clip goes back to its first frame after its last one,
+    /// which only the root movie may stop doing.
+    fn loops(self, context: &UpdateContext<'gc>) -> bool {
+        context.stage.loop_root_movie()
+            || !context
+                .stage
+                .root_clip()
+                .is_some_and(|root| DisplayObject::ptr_eq(root, self.into()))
+    }
+
     /// Determine what the clip's next frame should be.
     fn determine_next_frame(self) -> NextFrame {
         if self.current_frame() < self.total_frames() {
@@ -1186,6 +1196,10 @@ impl<'gc> MovieClip<'gc> {
                     self.0.increment_current_frame();
                 }
             }
+            NextFrame::First if !self.loops(context) => {
+                self.stop(context);
+                return;
+            }
             NextFrame::First => return self.run_goto(context, 1, true),
             NextFrame::Same => self.stop(context),
         }
@@ -101,6 +101,9 @@ pub struct StageData<'gc> {
     /// Determines how player content is resized to fit the stage.
     letterbox: Cell<Letterbox>,
 
+    /// The color of the bars drawn around the movie when letterboxing.
+    letterbox_color: Cell<Color>,
+
     /// The quality settings of the stage.
     quality: Cell<StageQuality>,
 
@@ -145,6 +148,10 @@ pub struct StageData<'gc> {
 
     /// Whether to show default context menu items
     show_menu: Cell<bool>,
+
+    /// Whether the root movie goes back to its first frame after its last one,
+    /// which the user can turn off with the "Loop" item of the context menu.
+    loop_root_movie: Cell<bool>,
 }
 
 impl<'gc> Stage<'gc> {
@@ -156,6 +163,7 @@ impl<'gc> Stage<'gc> {
                 child: RefLock::new(ChildContainer::new(&movie)),
                 background_color: Cell::new(None),
                 letterbox: Cell::new(Letterbox::Fullscreen),
+                letterbox_color: Cell::new(Color::BLACK),
                 // This is updated when we set the root movie
                 movie_size: Cell::new((0, 0)),
                 quality: Default::default(),
@@ -176,6 +184,7 @@ impl<'gc> Stage<'gc> {
                 view_bounds: Default::default(),
                 window_mode: Default::default(),
                 show_menu: Cell::new(true),
+                loop_root_movie: Cell::new(true),
                 stage_focus_rect: Cell::new(true),
                 avm2_object: Lock::new(None),
                 loader_info: Lock::new(None),
@@ -218,6 +227,14 @@ impl<'gc> Stage<'gc> {
         self.0.letterbox.set(letterbox)
     }
 
+    pub fn letterbox_color(self) -> Color {
+        self.0.letterbox_color.get()
+    }
+
+    pub fn set_letterbox_color(self, color: Color) {
+        self.0.letterbox_color.set(color)
+    }
+
     /// Get the size of the SWF file.
     pub fn movie_size(self) -> (u32, u32) {
         self.0.movie_size.get()
@@ -394,6 +411,12 @@ impl<'gc> Stage<'gc> {
 
         if result.is_ok() {
             self.0.display_state.set(display_state);
+            // The mouse is only locked for as long as the stage is in interactive fullscreen.
+            if display_state != StageDisplayState::FullScreenInteractive
+                && context.ui.mouse_locked()
+            {
+                context.ui.set_mouse_lock(false);
+            }
             self.fire_fullscreen_event(context);
         }
     }
@@ -458,6 +481,14 @@ impl<'gc> Stage<'gc> {
         self.0.show_menu.set(show_menu);
     }
 
+    pub fn loop_root_movie(self) -> bool {
+        self.0.loop_root_movie.get()
+    }
+
+    pub fn set_loop_root_movie(self, loop_root_movie: bool) {
+        self.0.loop_root_movie.set(loop_root_movie);
+    }
+
     /// Determine if we should letterbox the stage content.
     fn should_letterbox(self) -> bool {
         // Only enable letterbox in the default `ShowAll` scale mode.
@@ -598,6 +629,7 @@ impl<'gc> Stage<'gc> {
         let viewport_height = viewport_height as f32;
 
         let view_matrix = self.0.viewport_matrix.get();
+        let letterbox_color = self.0.letterbox_color.get();
 
         let (movie_width, movie_height) = self.0.movie_size.get();
         let movie_width = movie_width as f32 * view_matrix.a;
@@ -613,7 +645,7 @@ impl<'gc> Stage<'gc> {
             // Top + bottom
             if margin_top > 0.0 {
                 context.commands.draw_rect(
-                    Color::BLACK,
+                    letterbox_color,
                     Matrix::create_box(
                         viewport_width,
                         margin_top,
@@ -624,7 +656,7 @@ impl<'gc> Stage<'gc> {
             }
             if margin_bottom > 0.0 {
                 context.commands.draw_rect(
-                    Color::BLACK,
+                    letterbox_color,
                     Matrix::create_box(
                         viewport_width,
                         margin_bottom,
@@ -637,7 +669,7 @@ impl<'gc> Stage<'gc> {
             // Left + right
             if margin_left > 0.0 {
                 context.commands.draw_rect(
-                    Color::BLACK,
+                    letterbox_color,
                     Matrix::create_box(
                         margin_left,
                         viewport_height,
@@ -648,7 +680,7 @@ impl<'gc> Stage<'gc> {
             }
             if margin_right > 0.0 {
                 context.commands.draw_rect(
-                    Color::BLACK,
+                    letterbox_color,
                     Matrix::create_box(
                         margin_right,
                         viewport_height,
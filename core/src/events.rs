@@ -32,6 +32,13 @@ pub enum PlayerEvent {
     MouseWheel {
         delta: MouseWheelDelta,
     },
+    /// Sideways scrolling, such as tilting the mouse wheel or swiping a trackpad horizontally.
+    /// Positive deltas scroll to the left.
+    ///
+    /// This only scrolls text fields, see [`ClipEvent::HorizontalMouseWheel`].
+    HorizontalMouseWheel {
+        delta: MouseWheelDelta,
+    },
     GamepadButtonDown {
         button: GamepadButton,
     },
@@ -67,6 +74,14 @@ impl MouseWheelDelta {
             Self::Pixels(delta) => delta / Self::MOUSE_WHEEL_SCALE,
         }
     }
+
+    /// Returns the number of pixels that this delta represents.
+    pub fn pixels(self) -> f64 {
+        match self {
+            Self::Lines(delta) => delta * Self::MOUSE_WHEEL_SCALE,
+            Self::Pixels(delta) => delta,
+        }
+    }
 }
 
 impl PartialEq for MouseWheelDelta {
@@ -312,6 +327,14 @@ pub enum ClipEvent<'gc> {
     MouseWheel {
         delta: MouseWheelDelta,
     },
+
+    /// Mouse wheel was scrolled sideways over a particular display object.
+    ///
+    /// Flash Player has no event for this, so it's never dispatched to
+    /// ActionScript; it only scrolls text fields horizontally.
+    HorizontalMouseWheel {
+        delta: MouseWheelDelta,
+    },
 }
 
 impl ClipEvent<'_> {
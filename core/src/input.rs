@@ -47,6 +47,9 @@ pub enum InputEvent {
     MouseWheel {
         delta: MouseWheelDelta,
     },
+    HorizontalMouseWheel {
+        delta: MouseWheelDelta,
+    },
     TextInput {
         codepoint: char,
     },
@@ -205,6 +208,9 @@ impl InputManager {
             },
             PlayerEvent::MouseLeave => InputEvent::MouseLeave,
             PlayerEvent::MouseWheel { delta } => InputEvent::MouseWheel { delta },
+            PlayerEvent::HorizontalMouseWheel { delta } => {
+                InputEvent::HorizontalMouseWheel { delta }
+            }
 
             PlayerEvent::TextInput { codepoint } => InputEvent::TextInput { codepoint },
             PlayerEvent::TextControl { code } => InputEvent::TextControl { code },
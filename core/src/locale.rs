@@ -1,4 +1,5 @@
-use chrono::{DateTime, FixedOffset, Local, Offset, TimeZone, Utc};
+use chrono::{DateTime, FixedOffset, Local, Offset, TimeDelta, TimeZone, Utc};
+use std::time::Duration;
 
 // For tests, we emulate being in Nepal with a local time of 2001-02-03 at 04:05:06.
 // Nepal has a timezone offset of +5:45, and has never used DST.
@@ -24,3 +25,36 @@ pub fn get_timezone() -> FixedOffset {
         Local::now().offset().fix()
     }
 }
+
+/// A stand-in for the system clock, which only moves when it's advanced,
+/// so that a movie sees the same times on every run.
+#[derive(Clone, Copy, Debug)]
+pub struct FakeTime {
+    start: DateTime<Utc>,
+    elapsed: Duration,
+}
+
+impl FakeTime {
+    pub fn new(start: DateTime<Utc>) -> Self {
+        Self {
+            start,
+            elapsed: Duration::ZERO,
+        }
+    }
+
+    pub fn advance(&mut self, dt: Duration) {
+        self.elapsed += dt;
+    }
+
+    /// How much the clock was advanced since it started.
+    pub fn elapsed(&self) -> Duration {
+        self.elapsed
+    }
+
+    pub fn date_time(&self) -> DateTime<Utc> {
+        TimeDelta::from_std(self.elapsed)
+            .ok()
+            .and_then(|elapsed| self.start.checked_add_signed(elapsed))
+            .unwrap_or(DateTime::<Utc>::MAX_UTC)
+    }
+}
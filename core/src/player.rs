@@ -38,7 +38,7 @@ use crate::library::Library;
 use crate::limits::ExecutionLimit;
 use crate::loader::{LoadBehavior, LoadManager};
 use crate::local_connection::LocalConnections;
-use crate::locale::get_current_date_time;
+use crate::locale::{get_current_date_time, FakeTime};
 use crate::net_connection::NetConnections;
 use crate::prelude::*;
 use crate::socket::Sockets;
@@ -51,6 +51,7 @@ use crate::timer::Timers;
 use crate::vminterface::Instantiator;
 use crate::DefaultFont;
 use async_channel::Sender;
+use chrono::{DateTime, Utc};
 use gc_arena::lock::GcRefLock;
 use gc_arena::{Collect, DynamicRootSet, Mutation, Rootable};
 use rand::{rngs::SmallRng, SeedableRng};
@@ -300,6 +301,12 @@ pub struct Player {
     run_state: RunState,
     needs_render: bool,
 
+    /// The background color and commands of the last rendered frame,
+    /// kept to tell whether the next one is any different, if that's enabled.
+    last_frame: Option<(Color, CommandList)>,
+    detect_unchanged_frames: bool,
+    last_frame_changed: bool,
+
     renderer: Box<dyn RenderBackend>,
     audio: Box<dyn AudioBackend>,
     navigator: Box<dyn NavigatorBackend>,
@@ -354,6 +361,9 @@ pub struct Player {
     /// The instant at which the SWF was launched.
     start_time: Instant,
 
+    /// The clock used instead of the system clock, if time is faked.
+    fake_time: Option<FakeTime>,
+
     /// The maximum amount of time that can be called before a `Error::ExecutionTimeout`
     /// is raised. This defaults to 15 seconds but can be changed.
     max_execution_duration: Duration,
@@ -598,6 +608,11 @@ impl Player {
         std::time::Duration::from_micros(dt as u64 * 1000)
     }
 
+    /// The root movie, or an empty one if it hasn't been loaded yet.
+    pub fn swf(&self) -> &Arc<SwfMovie> {
+        &self.swf
+    }
+
     pub fn is_playing(&self) -> bool {
         match self.run_state {
             RunState::Playing | RunState::Stepping => true,
@@ -695,6 +710,18 @@ impl Player {
         })
     }
 
+    /// Whether the movie allows the zoom commands of the context menu that was last prepared.
+    ///
+    /// The core doesn't list them, as zooming is done by the frontend.
+    pub fn context_menu_zoom_allowed(&self) -> bool {
+        self.enter_arena(|_, gc_root, _| {
+            gc_root
+                .current_context_menu
+                .as_ref()
+                .is_some_and(|menu| menu.zoom())
+        })
+    }
+
     pub fn clear_custom_menu_items(&mut self) {
         self.enter_arena_mut(|_, gc_root, _| {
             gc_root.current_context_menu = None;
@@ -710,6 +737,10 @@ impl Player {
                         Self::run_context_menu_custom_callback(*item, *callback, context)
                     }
                     ContextMenuCallback::Play => Self::toggle_play_root_movie(context),
+                    ContextMenuCallback::Loop => {
+                        let stage = context.stage;
+                        stage.set_loop_root_movie(!stage.loop_root_movie());
+                    }
                     ContextMenuCallback::Forward => Self::forward_root_movie(context),
                     ContextMenuCallback::Back => Self::back_root_movie(context),
                     ContextMenuCallback::Rewind => Self::rewind_root_movie(context),
@@ -904,6 +935,23 @@ impl Player {
         self.needs_render
     }
 
+    /// Sets whether each rendered frame is compared to the previous one, see [`Player::last_frame_changed`].
+    pub fn set_detect_unchanged_frames(&mut self, detect_unchanged_frames: bool) {
+        self.detect_unchanged_frames = detect_unchanged_frames;
+        if !detect_unchanged_frames {
+            self.last_frame = None;
+        }
+    }
+
+    /// Whether the last rendered frame may look different from the one before it.
+    ///
+    /// This is always true unless unchanged frames are detected. Frames are compared by their
+    /// render commands, so changes to the pixels of a bitmap aren't noticed here;
+    /// the render backend has to be asked about those.
+    pub fn last_frame_changed(&self) -> bool {
+        self.last_frame_changed
+    }
+
     pub fn background_color(&mut self) -> Option<Color> {
         self.mutate_with_update_context(|context| context.stage.background_color())
     }
@@ -920,6 +968,12 @@ impl Player {
         self.mutate_with_update_context(|context| context.stage.set_letterbox(letterbox))
     }
 
+    /// Sets the color of the bars drawn around the movie when letterboxing, black by default.
+    pub fn set_letterbox_color(&mut self, color: Color) {
+        self.mutate_with_update_context(|context| context.stage.set_letterbox_color(color));
+        self.needs_render = true;
+    }
+
     pub fn movie_width(&mut self) -> u32 {
         self.mutate_with_update_context(|context| context.stage.movie_size().0)
     }
@@ -993,6 +1047,31 @@ impl Player {
         })
     }
 
+    pub fn align(&mut self) -> StageAlign {
+        self.mutate_with_update_context(|context| context.stage.align())
+    }
+
+    /// Sets the alignment of the stage, even if movies are prevented from changing it.
+    pub fn set_align(&mut self, align: StageAlign) {
+        self.mutate_with_update_context(|context| {
+            let stage = context.stage;
+            let forced_align = stage.forced_align();
+            stage.set_forced_align(false);
+            stage.set_align(context, align);
+            stage.set_forced_align(forced_align);
+        })
+    }
+
+    pub fn forced_align(&mut self) -> bool {
+        self.mutate_with_update_context(|context| context.stage.forced_align())
+    }
+
+    pub fn set_forced_align(&mut self, force: bool) {
+        self.mutate_with_update_context(|context| {
+            context.stage.set_forced_align(force);
+        })
+    }
+
     /// Handle an event sent into the player from the external windowing system
     /// or an HTML element.
     pub fn handle_event(&mut self, event: PlayerEvent) -> bool {
@@ -1005,6 +1084,7 @@ impl Player {
             | PlayerEvent::MouseDown { .. }
             | PlayerEvent::MouseLeave
             | PlayerEvent::MouseWheel { .. }
+            | PlayerEvent::HorizontalMouseWheel { .. }
             | PlayerEvent::GamepadButtonDown { .. }
             | PlayerEvent::GamepadButtonUp { .. }
             | PlayerEvent::Ime { .. }
@@ -1383,7 +1463,14 @@ impl Player {
             }
         }
 
-        if let InputEvent::MouseWheel { delta } = &event {
+        let wheel_event = match &event {
+            InputEvent::MouseWheel { delta } => Some(ClipEvent::MouseWheel { delta: *delta }),
+            InputEvent::HorizontalMouseWheel { delta } => {
+                Some(ClipEvent::HorizontalMouseWheel { delta: *delta })
+            }
+            _ => None,
+        };
+        if let Some(event) = wheel_event {
             self.mutate_with_update_context(|context| {
                 let target = if let Some(over_object) = context.mouse_data.hovered {
                     if over_object.as_displayobject().movie().is_action_script_3()
@@ -1397,7 +1484,6 @@ impl Player {
                     context.stage.as_interactive()
                 };
                 if let Some(target) = target {
-                    let event = ClipEvent::MouseWheel { delta: *delta };
                     if target.event_dispatch_to_avm2(context, event) == ClipEventResult::Handled {
                         player_event_handled = true;
                     }
@@ -2018,6 +2104,20 @@ impl Player {
             (cache_draws, commands)
         });
 
+        if self.detect_unchanged_frames {
+            // Cached bitmaps that are redrawn change their pixels, but not their commands.
+            self.last_frame_changed = !cache_draws.is_empty()
+                || !self.last_frame.as_ref().is_some_and(
+                    |(last_background_color, last_commands)| {
+                        *last_background_color == background_color
+                            && last_commands.is_equivalent(&commands)
+                    },
+                );
+            self.last_frame = Some((background_color, commands.clone()));
+        } else {
+            self.last_frame_changed = true;
+        }
+
         self.renderer
             .submit_frame(background_color, commands, cache_draws);
 
@@ -2233,6 +2333,7 @@ impl Player {
                 avm2,
                 external_interface,
                 start_time: this.start_time,
+                fake_time: this.fake_time,
                 update_start: Instant::now(),
                 max_execution_duration: this.max_execution_duration,
                 focus_tracker: stage.focus_tracker(),
@@ -2351,6 +2452,15 @@ impl Player {
         });
     }
 
+    /// Moves the fake clock forward, as seen by `getTimer` and `Date`.
+    ///
+    /// Does nothing unless the player was built with a fake time, see [`PlayerBuilder::with_fake_time`].
+    pub fn advance_fake_time(&mut self, dt: Duration) {
+        if let Some(fake_time) = &mut self.fake_time {
+            fake_time.advance(dt);
+        }
+    }
+
     /// Update all AVM-based timers (such as created via setInterval).
     /// Returns the approximate amount of time until the next timer tick.
     pub fn update_timers(&mut self, dt: f64) {
@@ -2405,6 +2515,14 @@ impl Player {
         self.spoofed_url.as_deref()
     }
 
+    /// The memory allocated by the garbage collected heap of the VMs, in bytes.
+    ///
+    /// This isn't available while the heap is in use.
+    pub fn gc_heap_size(&self) -> Option<usize> {
+        let arena = self.gc_arena.try_borrow().ok()?;
+        Some(arena.metrics().total_allocation())
+    }
+
     pub fn compatibility_rules(&self) -> &CompatibilityRules {
         &self.compatibility_rules
     }
@@ -2469,6 +2587,7 @@ pub struct PlayerBuilder {
 
     // Misc. player configuration
     autoplay: bool,
+    loop_root_movie: bool,
     align: StageAlign,
     forced_align: bool,
     scale_mode: StageScaleMode,
@@ -2485,11 +2604,14 @@ pub struct PlayerBuilder {
     compatibility_rules: CompatibilityRules,
     gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     player_version: Option<u8>,
+    full_player_version: Option<String>,
     player_runtime: PlayerRuntime,
     player_mode: PlayerMode,
     quality: StageQuality,
     page_url: Option<String>,
     frame_rate: Option<f64>,
+    random_seed: Option<u64>,
+    fake_time_start: Option<DateTime<Utc>>,
     external_interface_provider: Option<Box<dyn ExternalInterfaceProvider>>,
     fs_command_provider: Box<dyn FsCommandProvider>,
     #[cfg(feature = "known_stubs")]
@@ -2518,6 +2640,7 @@ impl PlayerBuilder {
             notification_sender: None,
 
             autoplay: false,
+            loop_root_movie: true,
             align: StageAlign::default(),
             forced_align: false,
             scale_mode: StageScaleMode::default(),
@@ -2539,11 +2662,14 @@ impl PlayerBuilder {
             compatibility_rules: CompatibilityRules::default(),
             gamepad_button_mapping: HashMap::new(),
             player_version: None,
+            full_player_version: None,
             player_runtime: PlayerRuntime::default(),
             player_mode: PlayerMode::default(),
             quality: StageQuality::High,
             page_url: None,
             frame_rate: None,
+            random_seed: None,
+            fake_time_start: None,
             external_interface_provider: None,
             fs_command_provider: Box::new(NullFsCommandProvider),
             #[cfg(feature = "known_stubs")]
@@ -2644,6 +2770,14 @@ impl PlayerBuilder {
         self
     }
 
+    /// Sets whether the root movie goes back to its first frame after its last one,
+    /// like the `loop` parameter of Flash Player. The user may still change it from the context menu.
+    #[inline]
+    pub fn with_loop(mut self, loop_root_movie: bool) -> Self {
+        self.loop_root_movie = loop_root_movie;
+        self
+    }
+
     /// Sets the letterbox setting for the player.
     #[inline]
     pub fn with_letterbox(mut self, letterbox: Letterbox) -> Self {
@@ -2716,6 +2850,14 @@ impl PlayerBuilder {
         self
     }
 
+    /// Configures the full player version reported to movies, such as `32,0,0,465`.
+    ///
+    /// By default, it's made of the target player version alone.
+    pub fn with_full_player_version(mut self, version: Option<String>) -> Self {
+        self.full_player_version = version;
+        self
+    }
+
     /// Configures the player runtime (default is `PlayerRuntime::FlashPlayer`)
     pub fn with_player_runtime(mut self, runtime: PlayerRuntime) -> Self {
         self.player_runtime = runtime;
@@ -2740,6 +2882,22 @@ impl PlayerBuilder {
         self
     }
 
+    /// Seeds the random number generator used by scripts, such as `Math.random()`,
+    /// so that the same seed gives the same numbers every time.
+    /// If None is provided, the seed is derived from the current time.
+    pub fn with_random_seed(mut self, random_seed: Option<u64>) -> Self {
+        self.random_seed = random_seed;
+        self
+    }
+
+    /// Fakes the time seen by scripts, starting at the given date and time.
+    /// The time then stands still until it's moved with [`Player::advance_fake_time`].
+    /// If None is provided, the system clock is used.
+    pub fn with_fake_time(mut self, start: Option<DateTime<Utc>>) -> Self {
+        self.fake_time_start = start;
+        self
+    }
+
     /// Adds an External Interface provider for movies to communicate with
     pub fn with_external_interface(mut self, provider: Box<dyn ExternalInterfaceProvider>) -> Self {
         self.external_interface_provider = Some(provider);
@@ -2889,6 +3047,7 @@ impl PlayerBuilder {
                 frame_accumulator: 0.0,
                 recent_run_frame_timings: VecDeque::with_capacity(10),
                 start_time: Instant::now(),
+                fake_time: self.fake_time_start.map(FakeTime::new),
                 time_offset: 0,
                 time_til_next_timer: None,
                 max_execution_duration: self.max_execution_duration,
@@ -2902,8 +3061,15 @@ impl PlayerBuilder {
                 mouse_cursor_needs_check: false,
 
                 // Misc. state
-                rng: SmallRng::seed_from_u64(get_current_date_time().timestamp_millis() as u64),
-                system: SystemProperties::new(language),
+                rng: SmallRng::seed_from_u64(self.random_seed.unwrap_or_else(|| {
+                    self.fake_time_start
+                        .unwrap_or_else(get_current_date_time)
+                        .timestamp_millis() as u64
+                })),
+                system: SystemProperties {
+                    version: self.full_player_version.clone(),
+                    ..SystemProperties::new(language)
+                },
                 page_url: self.page_url.clone(),
                 transform_stack: TransformStack::new(),
                 instance_counter: 0,
@@ -2916,6 +3082,9 @@ impl PlayerBuilder {
                     RunState::Suspended
                 },
                 needs_render: true,
+                last_frame: None,
+                detect_unchanged_frames: false,
+                last_frame_changed: true,
                 self_reference: self_ref.clone(),
                 load_behavior: self.load_behavior,
                 spoofed_url: self.spoofed_url.clone(),
@@ -2994,6 +3163,7 @@ impl PlayerBuilder {
             stage.set_scale_mode(context, self.scale_mode, false);
             stage.set_forced_scale_mode(self.forced_scale_mode);
             stage.set_allow_fullscreen(self.allow_fullscreen);
+            stage.set_loop_root_movie(self.loop_root_movie);
             stage.post_instantiation(context, None, Instantiator::Movie, false);
             stage.build_matrices(context);
             #[cfg(feature = "known_stubs")]
@@ -266,6 +266,9 @@ pub struct SystemProperties {
     pub cpu_architecture: CpuArchitecture,
     /// The highest supported h264 decoder level
     pub idc_level: String,
+    /// The full version of the player, such as `32,0,0,465`,
+    /// instead of one made of only the player version
+    pub version: Option<String>,
 }
 
 impl SystemProperties {
@@ -288,14 +291,22 @@ impl SystemProperties {
             os: OperatingSystem::Linux,
             cpu_architecture: CpuArchitecture::X86,
             idc_level: "5.1".into(),
+            version: None,
         }
     }
 
+    /// The version of the player without the platform, such as `32,0,0,0`.
+    pub fn get_version_numbers(&self, player_version: u8) -> String {
+        self.version
+            .clone()
+            .unwrap_or_else(|| format!("{player_version},0,0,0"))
+    }
+
     pub fn get_version_string(&self, player_version: u8) -> String {
         format!(
-            "{} {},0,0,0",
+            "{} {}",
             self.manufacturer.get_platform_name(),
-            player_version
+            self.get_version_numbers(player_version)
         )
     }
 
@@ -264,6 +264,27 @@ impl SwfMovie {
         &self.data
     }
 
+    /// Get the XMP metadata of the SWF, from its `Metadata` tag if it has one.
+    ///
+    /// Only the first frame is searched, as that's where this tag is expected to be.
+    pub fn metadata(&self) -> Option<String> {
+        let mut reader = swf::read::Reader::new(&self.data, self.version());
+        loop {
+            let (tag_code, tag_len) = reader.read_tag_code_and_length().ok()?;
+            let input = reader.get_ref();
+            let tag_data = input.get(..tag_len)?;
+            *reader.get_mut() = &input[tag_len..];
+            match TagCode::from_u16(tag_code) {
+                Some(TagCode::Metadata) => {
+                    return swf::SwfStr::from_bytes_null_terminated(tag_data)
+                        .map(|metadata| metadata.to_string_lossy(self.encoding));
+                }
+                Some(TagCode::ShowFrame | TagCode::End) => return None,
+                _ => {}
+            }
+        }
+    }
+
     /// Returns the suggested string encoding for the given SWF version.
     /// For SWF version 6 and higher, this is always UTF-8.
     /// For SWF version 5 and lower, this is locale-dependent,
@@ -8,3 +8,15 @@ remove = Remove
 
 enable = Enable
 disable = Disable
+
+window-title-paused = (Paused)
+window-title-paused-at-frame = (Paused at frame { $frame })
+window-title-muted = (Muted)
+window-title-fps = { $fps } FPS
+window-title-loading = Ruffle (Loading...)
+
+tab-close = Close Tab
+
+drop-to-open = Drop to open the movie
+drop-multiple-files = Only the first of the dropped files was opened
+movie-reloaded = The movie was reloaded after its file changed
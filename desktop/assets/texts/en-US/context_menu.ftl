@@ -1 +1,4 @@
 context-menu-exit-fullscreen = Exit Full Screen
+context-menu-zoom-in = Zoom In
+context-menu-zoom-out = Zoom Out
+context-menu-show-all = Show All
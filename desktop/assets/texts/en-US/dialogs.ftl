@@ -1,2 +1,4 @@
 dialog-ok = OK
 dialog-cancel = Cancel
+
+fullscreen-exit-hint = Hold { $shortcut } to exit full screen
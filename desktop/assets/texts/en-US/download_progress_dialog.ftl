@@ -0,0 +1,4 @@
+download-progress-dialog = Downloading Movie
+download-progress-dialog-received = { $loaded } of { $total } ({ $speed }/s)
+download-progress-dialog-unknown-length = The server didn't tell the size of the movie.
+download-progress-dialog-received-unknown-length = { $loaded } ({ $speed }/s)
@@ -1,4 +1,5 @@
 file-picker-title-open-file = Open a single file
+file-picker-title-record = Choose a folder to record to
 file-picker-filter-supported = All Supported Files
 file-picker-filter-swf = SWF (*.swf)
 file-picker-filter-spl = FutureSplash Animator (*.spl)
@@ -8,26 +8,38 @@
 # This is because some languages may use different wording for one or the other, especially with things like capitalisation.
 
 file-menu = File
+file-menu-new-window = New Window
 file-menu-open-quick = Open...
 file-menu-open-advanced = Open Advanced...
+file-menu-open-url = Open URL...
 file-menu-close = Close
 file-menu-reload = Reload
+file-menu-auto-reload = Auto-reload on Change
 file-menu-recents = Recents
 file-menu-recents-empty = No recent entries
 file-menu-preferences = Preferences...
 file-menu-exit = Exit
 
+edit-menu = Edit
+edit-menu-open-url-from-clipboard = Open URL from Clipboard
+
 controls-menu = Controls
 controls-menu-suspend = Suspend
 controls-menu-resume = Resume
 controls-menu-step-once = Step Once
+controls-menu-step-frames = Step Frames
 controls-menu-volume = Volume controls
+controls-menu-copy-frame = Copy Frame to Clipboard
+controls-menu-frame-copied = Frame copied to clipboard
+controls-menu-start-recording = Record to Folder...
+controls-menu-stop-recording = Stop Recording
 
 help-menu = Help
 help-menu-join-discord = Join Discord
 help-menu-report-a-bug = Report a Bug...
 help-menu-sponsor-development = Sponsor Development...
 help-menu-translate-ruffle = Translate Ruffle...
+help-menu-movie-properties = Movie Properties
 help-menu-about = About Ruffle
 
 bookmarks-menu = Bookmarks
@@ -41,6 +53,23 @@ debug-menu-open-movie = View Movie
 debug-menu-open-movie-list = Show Known Movies
 debug-menu-open-domain-list = Show Domains
 debug-menu-search-display-objects = Search Display Objects...
+debug-menu-lock-mouse = Lock Mouse Pointer
 
 view-menu = View
+view-menu-window-size = Resize Window to Movie
+view-menu-window-size-50 = 50%
+view-menu-window-size-100 = 100%
+view-menu-window-size-200 = 200%
+view-menu-lock-aspect-ratio = Lock Aspect Ratio
+view-menu-reset-zoom = Reset Zoom
+view-menu-reset-zoom-tooltip = Zoom in with Ctrl+scroll, and drag with the middle mouse button to move around.
+view-zoom-indicator = { $percent }%
 view-menu-fullscreen = Full Screen
+view-menu-fullscreen-monitor = Full Screen Monitor
+view-menu-picture-in-picture = Picture in Picture
+view-menu-timeline = Timeline
+view-menu-timeline-tooltip = Shows a bar to seek through movies with more than one frame, such as animations.
+
+timeline-play = Play
+timeline-pause = Pause
+timeline-loading = Frames that haven't loaded yet can't be shown.
@@ -1,2 +1,12 @@
 message-dialog-root-movie-load-error-title = Movie Failed to Load
 message-dialog-root-movie-load-error-description = Failed to open or download this movie.
+message-dialog-missing-fonts-title = Missing Fonts
+message-dialog-missing-fonts-description =
+    No fonts supporting the selected language ({ $locale }) were found, so some text may not display correctly.
+    Installing one of the following fonts, such as { $suggestion }, should fix this: { $families }
+message-dialog-surface-error-title = Rendering Error
+message-dialog-surface-error-description =
+    Ruffle is repeatedly failing to draw to the window. This is usually caused by a graphics driver issue.
+    Try updating your graphics driver, or choosing another graphics backend in the preferences.
+message-dialog-graphics-error-title = Graphics Failed to Initialize
+message-dialog-graphics-error-description = Ruffle could not find a compatible graphics device. Updating your graphics drivers, or choosing a different graphics backend, may help.
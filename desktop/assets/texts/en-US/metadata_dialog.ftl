@@ -0,0 +1,17 @@
+metadata-dialog = Metadata
+
+metadata-title = Title
+metadata-creator = Creator
+metadata-description = Description
+metadata-subject = Subject
+metadata-publisher = Publisher
+metadata-contributor = Contributor
+metadata-date = Date
+metadata-type = Type
+metadata-format = Format
+metadata-identifier = Identifier
+metadata-source = Source
+metadata-language = Language
+metadata-relation = Relation
+metadata-coverage = Coverage
+metadata-rights = Rights
@@ -0,0 +1,27 @@
+movie-properties = Movie Properties
+movie-properties-no-movie = No movie is loaded.
+movie-properties-copy = Copy to Clipboard
+movie-properties-metadata = Metadata...
+movie-properties-no-metadata = This movie doesn't have any metadata.
+
+movie-properties-url = URL
+movie-properties-spoofed-url = Spoofed URL
+movie-properties-swf-version = SWF Version
+movie-properties-avm = ActionScript VM
+movie-properties-compression = Compression
+movie-properties-size = Size in Bytes (Compressed / Uncompressed)
+movie-properties-dimensions = Stage Size
+movie-properties-frame-rate = Frame Rate
+movie-properties-frames = Frames
+movie-properties-background-color = Background Color
+movie-properties-current-frame = Current Frame
+movie-properties-heap-size = Heap Memory
+movie-properties-quality = Quality
+movie-properties-scale-mode = Scale Mode
+
+movie-properties-avm1 = AVM1 (ActionScript 1/2)
+movie-properties-avm2 = AVM2 (ActionScript 3)
+movie-properties-frame-rate-value = { $fps } fps
+movie-properties-compression-none = None
+movie-properties-none = None
+movie-properties-unavailable = Unavailable
@@ -5,3 +5,6 @@ open-dialog-path = File or URL
 open-dialog-add-parameter = Add
 open-dialog-remove-parameter = Remove
 open-dialog-remove-parameters = Remove all
+open-dialog-parameter-name-tooltip = Paste a query string such as level=3&sound=off here to add all of its parameters
+open-dialog-reload-required = The player version and runtime only change once the movie is reloaded.
+open-dialog-reload-now = Reload Now
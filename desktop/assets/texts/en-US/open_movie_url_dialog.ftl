@@ -0,0 +1,4 @@
+open-movie-url-dialog = Open URL
+open-movie-url-dialog-url = URL
+open-movie-url-dialog-history = History
+open-movie-url-dialog-open = Open
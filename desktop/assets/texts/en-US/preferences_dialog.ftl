@@ -1,19 +1,42 @@
 preferences-dialog = Ruffle Preferences
+preferences-dialog-detach = Open in Separate Window
+preferences-dialog-detach-tooltip = Keeps the preferences open in a window of their own, next to the movie.
 
 preference-locked-by-cli = Read-Only (Set by CLI)
 
 graphics-backend = Graphics Backend
 graphics-backend-default = Default
+graphics-backend-unavailable = This backend isn't available on this computer.
 
 graphics-power = Power Preference
 graphics-power-low = Low (e.g. iGPU)
 graphics-power-high = High (e.g. GPU)
 
+graphics-frame-latency = Frame Latency
+graphics-frame-latency-tooltip =
+    The maximum number of frames that may be queued up for display.
+    Lower values reduce input lag, higher values may result in smoother playback on slow GPUs.
+graphics-ui-sample-count = Interface Antialiasing
+graphics-ui-sample-count-off = Off
+graphics-ui-sample-count-tooltip =
+    Smooths the edges of the menus and dialogs drawn on top of the movie.
+    This doesn't affect the movie, whose antialiasing depends on its quality.
+graphics-scale-factor-override = Scale Factor Override
+graphics-scale-factor-override-tooltip =
+    Renders the interface at this scale instead of the one set by the operating system.
+    This is useful for reproducing HiDPI issues on a monitor without scaling.
+
 language = Language
 
 audio-output-device = Audio Output Device
 audio-output-device-default = System Default
 
+unfocused-behavior = When Window Is Unfocused
+unfocused-behavior-continue = Keep Playing
+unfocused-behavior-pause = Pause
+unfocused-behavior-mute = Mute
+mute-shortcut = Mute Shortcut
+
 enable-openh264 = Enable OpenH264
 show-license = Show License
 openh264-license = OpenH264 License
@@ -28,12 +51,74 @@ storage-backend-memory = Memory
 
 recent-limit = Recent Limit
 recent-clear = Clear
+remember-window-geometry = Remember Window Size and Position
+remember-window-geometry-tooltip = The window is restored to the size and position it had when Ruffle was last closed, and when a movie is opened again, to the ones it had when that movie was last closed.
+show-fps-in-title = Show Frame Rate in Title
+idle-power-save = Save Power When Idle
+idle-power-save-tooltip =
+    While the movie looks the same, it's redrawn less often. Turn this off if some animations or videos stutter.
+touch-as-mouse = Use Touch as Mouse Only
+touch-as-mouse-tooltip =
+    Every touch acts as the mouse, and two fingers no longer zoom and pan the movie. Some older games need this.
+long-press-context-menu = Long Press for Context Menu
+long-press-context-menu-tooltip =
+    Holding a finger still on the movie opens the context menu, like a right click does.
+long-press-context-menu-milliseconds = { " " }ms
 
 theme = Theme
 theme-system = System Default
 theme-light = Light
 theme-dark = Dark
 
+fullscreen-toggle-shortcut = Toggle Full Screen
+fullscreen-exit-shortcut = Exit Full Screen
+fullscreen-hold-to-exit = Hold to Exit Full Screen
+fullscreen-hold-to-exit-tooltip =
+    The exit full screen key has to be held down to leave full screen.
+    Short presses are still sent to the movie, which is useful when it uses the same key.
+fullscreen-hide-cursor = Hide Cursor in Full Screen
+fullscreen-hide-cursor-tooltip =
+    Hides the mouse cursor after it wasn't moved for a while in full screen.
+    It's shown again as soon as the mouse is used.
+fullscreen-hide-cursor-seconds = { " " }s
+fullscreen-exclusive = Exclusive Full Screen
+fullscreen-exclusive-tooltip =
+    Full screen changes the display mode instead of covering the screen with a window.
+    This reduces latency in fast-paced games, but switching to and from full screen is slower.
+fullscreen-monitor = Full Screen Monitor
+fullscreen-monitor-current = Current Monitor
+fullscreen-monitor-unnamed = Monitor { $number }
+fullscreen-video-mode = Full Screen Display Mode
+fullscreen-video-mode-auto = Automatic
+shortcut-press-keys = Press a key...
+shortcut-conflict-fullscreen = The full screen shortcuts must be different
+shortcut-conflict-mute = The mute shortcut must be different from the full screen shortcuts
+shortcut-conflict-menu = A shortcut is already used by the menu
+
+key-remaps = Key Remapping
+key-remaps-tooltip =
+    Keys pressed on the keyboard are delivered to the movie as other keys.
+    Remaps for a single movie take priority over the ones for all movies, and the menu, mute and full screen shortcuts are not affected.
+key-remaps-all-movies = All Movies
+key-remaps-this-movie = This Movie
+key-remaps-add = Add Key...
+key-remaps-remove = Remove
+
+menu-visibility = Menu Bar
+menu-visibility-always = Always Visible
+menu-visibility-auto-hide = Auto-Hide
+menu-visibility-never = Hidden
+menu-visibility-never-tooltip =
+    The menu bar will not be shown at all.
+    Keyboard shortcuts keep working, and it can be restored by changing menu_visibility in preferences.toml.
+
+background-color = Background Color
+background-color-tooltip = The color shown around the movie when it doesn't fill the window.
+background-color-stage = Movie Background
+background-color-black = Black
+background-color-white = White
+background-color-custom = Custom
+
 # See for context https://github.com/FeralInteractive/gamemode
 gamemode = GameMode
 gamemode-tooltip =
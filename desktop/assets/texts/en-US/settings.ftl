@@ -5,6 +5,8 @@ movie-parameters = Movie Parameters
 custom-base-url = Custom Base URL
 
 spoof-swf-url = Spoof SWF URL
+spoof-swf-url-as-base = Use Spoofed URL as Base
+spoof-swf-url-as-base-tooltip = Resolve relative URLs against the spoofed SWF URL instead of the actual one, unless a custom base URL is given.
 referer-url = Referer URL
 cookie = Cookie
 
@@ -22,6 +24,11 @@ open-url-mode-allow = Allow
 open-url-mode-confirm = Ask
 open-url-mode-deny = Deny
 
+context-menu-mode = Context Menu
+context-menu-mode-shown = On Right-Click
+context-menu-mode-ctrl-click = On Ctrl+Right-Click
+context-menu-mode-disabled = Never
+
 load-behavior = Load Behavior
 load-behavior-streaming = Streaming
 load-behavior-delayed = Delayed
@@ -83,8 +90,10 @@ scale-mode-force-tooltip =
     Prevents the movie from changing the scale mode, locking it to the selected setting.
 
 player-version = Player Version
+player-version-custom = Custom
 
 player-runtime = Player Runtime
+player-runtime-default = Default
 player-runtime-flash = Flash Player
 player-runtime-air = Adobe AIR
 
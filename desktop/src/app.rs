@@ -1,54 +1,96 @@
-use crate::custom_event::RuffleEvent;
-use crate::gui::{GuiController, MENU_HEIGHT};
-use crate::player::{LaunchOptions, PlayerController};
+use crate::backends::DesktopUiBackend;
+use crate::cli::ContextMenuMode;
+use crate::custom_event::{RuffleEvent, RuffleEventProxy, WindowRuffleEvent};
+use crate::gui::dialogs::message_dialog::MessageDialogConfiguration;
+use crate::gui::{
+    fit_to_monitors, metadata_title, min_window_size, physical_key_to_egui, text, DialogDescriptor,
+    FilePicker, GraphicsContext, GuiController, LocalizableText,
+};
+use crate::player::{LaunchOptions, PlayerController, TabId};
 use crate::preferences::GlobalPreferences;
 use crate::util::{
-    get_screen_size, gilrs_button_to_gamepad_button, parse_url, plot_stats_in_tracy,
-    winit_input_to_ruffle_key_descriptor, winit_to_ruffle_text_control,
+    egui_key_to_ruffle_key_descriptor, get_screen_size, gilrs_button_to_gamepad_button, parse_url,
+    plot_stats_in_tracy, winit_input_to_ruffle_key_descriptor, winit_to_ruffle_text_control,
 };
 use anyhow::Error;
 use gilrs::{Event, EventType, Gilrs};
-use ruffle_core::events::{ImeEvent, ImeNotification, PlayerNotification};
+use ruffle_core::events::{
+    ImeEvent, ImeNotification, KeyDescriptor, LogicalKey, MouseWheelDelta, PlayerNotification,
+};
 use ruffle_core::swf::HeaderExt;
 use ruffle_core::PlayerEvent;
 use ruffle_render::backend::ViewportDimensions;
-use std::sync::Arc;
-use std::time::Instant;
+use std::any::Any;
+use std::collections::HashMap;
+use std::mem;
+use std::path::Path;
+use std::sync::{Arc, Weak};
+use std::time::{Duration, Instant};
 use url::Url;
 use winit::application::ApplicationHandler;
 use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize, Size};
-use winit::event::{ElementState, Ime, KeyEvent, Modifiers, StartCause, WindowEvent};
+use winit::event::{
+    DeviceEvent, DeviceId, ElementState, Ime, KeyEvent, Modifiers, MouseButton, MouseScrollDelta,
+    StartCause, Touch, TouchPhase, WindowEvent,
+};
 use winit::event_loop::{ActiveEventLoop, ControlFlow, EventLoop, EventLoopProxy};
-use winit::keyboard::{Key, NamedKey};
-use winit::window::{Fullscreen, Icon, WindowAttributes, WindowId};
+use winit::keyboard::{Key, ModifiersState, NamedKey, PhysicalKey};
+use winit::window::{Icon, Window, WindowAttributes, WindowId, WindowLevel};
 
 struct MainWindow {
     preferences: GlobalPreferences,
     gui: GuiController,
     player: PlayerController,
     minimized: bool,
+    /// Whether the IME is enabled, i.e. between `Ime::Enabled` and `Ime::Disabled`.
+    ime_active: bool,
     mouse_pos: PhysicalPosition<f64>,
+    /// Trackpad scrolling that didn't add up to a whole line yet, in lines.
+    wheel_remainder: f64,
+    /// The position of the mouse in the movie while it's locked, moved by the raw mouse movement.
+    mouse_lock_position: Option<(f64, f64)>,
+    /// The last position of the mouse in the picture-in-picture window.
+    picture_in_picture_mouse_pos: PhysicalPosition<f64>,
+    /// The ID of the touch that acts as the mouse, while a finger is down.
+    primary_touch: Option<u64>,
+    /// Whether the movie used the press of the primary touch, in which case
+    /// another finger doesn't turn it into a pinch of the movie view.
+    primary_touch_handled: bool,
+    /// The primary touch while it's held still, for it to open the context menu after a while.
+    long_press: Option<LongPress>,
+    /// The ID of the touch that opened the context menu with a long-press, while the menu is open.
+    context_menu_touch: Option<u64>,
+    /// The keys delivered to the movie for the remapped keys being held down,
+    /// so that releasing them releases the same key even if the remaps changed meanwhile.
+    remapped_keys: HashMap<PhysicalKey, KeyDescriptor>,
+    /// How many files were dropped onto the window since the event loop was last idle,
+    /// as dropping several files at once delivers an event for each of them.
+    dropped_files: usize,
+    /// Set while the movie is reloaded after its file changed,
+    /// so that the window isn't resized to fit the movie again once it's loaded.
+    keep_window_size: bool,
     modifiers: Modifiers,
-    min_window_size: LogicalSize<u32>,
     max_window_size: PhysicalSize<u32>,
-    no_gui: bool,
     preferred_width: Option<f64>,
     preferred_height: Option<f64>,
     start_fullscreen: bool,
+    /// The width of the movie's stage divided by its height, once it's known.
+    movie_aspect_ratio: Option<f64>,
     loaded: LoadingState,
     time: Instant,
     next_frame_time: Option<Instant>,
-    event_loop_proxy: EventLoopProxy<RuffleEvent>,
+    event_loop_proxy: RuffleEventProxy,
 }
 
 impl MainWindow {
-    pub fn window_event(&mut self, event_loop: &ActiveEventLoop, event: WindowEvent) {
+    pub fn window_event(&mut self, event: WindowEvent) {
         if matches!(event, WindowEvent::RedrawRequested) {
             // Don't render when minimized to avoid potential swap chain errors in `wgpu`.
             if !self.minimized {
                 if let Some(mut player) = self.player.get() {
                     // Even if the movie is paused, user interaction with debug tools can change the render output
                     player.render();
+                    self.gui.on_movie_rendered(&mut player);
                     self.gui.render(Some(player));
                 } else {
                     self.gui.render(None);
@@ -66,20 +108,20 @@ impl MainWindow {
             return;
         }
         match event {
-            WindowEvent::CloseRequested => {
-                event_loop.exit();
-            }
             WindowEvent::Resized(size) => {
                 // TODO: Change this when winit adds a `Window::minimized` or `WindowEvent::Minimize`.
                 self.minimized = size.width == 0 && size.height == 0;
-
-                if let Some(mut player) = self.player.get() {
-                    let viewport_scale_factor = self.gui.window().scale_factor();
-                    player.set_viewport_dimensions(ViewportDimensions {
-                        width: size.width,
-                        height: size.height.saturating_sub(self.gui.height_offset() as u32),
-                        scale_factor: viewport_scale_factor,
-                    });
+                // Until the window is resized to the snapped size, the movie keeps its layout,
+                // rather than being laid out at the wrong aspect ratio.
+                if let Some(size) = self.apply_aspect_ratio_lock(size) {
+                    if let Some(mut player) = self.player.get() {
+                        let viewport_scale_factor = self.gui.scale_factor();
+                        player.set_viewport_dimensions(ViewportDimensions {
+                            width: size.width,
+                            height: size.height.saturating_sub(self.gui.height_offset() as u32),
+                            scale_factor: viewport_scale_factor,
+                        });
+                    }
                 }
                 self.gui.window().request_redraw();
                 if matches!(self.loaded, LoadingState::WaitingForResize) {
@@ -92,25 +134,32 @@ impl MainWindow {
                 }
 
                 self.mouse_pos = position;
+                if self.mouse_lock_position.is_some() {
+                    // The movie follows the raw mouse movement instead, see `mouse_motion`.
+                    return;
+                }
                 let (x, y) = self.gui.window_to_movie_position(position);
                 let event = PlayerEvent::MouseMove { x, y };
                 self.player.handle_event(event);
                 self.check_redraw();
             }
+            WindowEvent::HoveredFile(_) => self.gui.set_files_hovered(true),
+            WindowEvent::HoveredFileCancelled => self.gui.set_files_hovered(false),
             WindowEvent::DroppedFile(file) => {
-                if let Ok(url) = parse_url(&file) {
-                    self.gui.create_movie(
-                        &mut self.player,
-                        LaunchOptions::from(&self.preferences),
-                        url,
-                    );
+                self.gui.set_files_hovered(false);
+                self.dropped_files += 1;
+                match self.dropped_files {
+                    1 => self.open_dropped_file(&file),
+                    2 => self.gui.show_notification("drop-multiple-files"),
+                    _ => {}
                 }
             }
-            WindowEvent::Focused(true) => {
-                self.player.handle_event(PlayerEvent::FocusGained);
-            }
-            WindowEvent::Focused(false) => {
-                self.player.handle_event(PlayerEvent::FocusLost);
+            WindowEvent::Focused(focused) => {
+                if !focused {
+                    self.set_mouse_lock(false);
+                }
+                self.player
+                    .set_window_focused(focused, self.preferences.unfocused_behavior());
             }
             WindowEvent::MouseInput { button, state, .. } => {
                 if self.gui.is_context_menu_visible() {
@@ -118,41 +167,35 @@ impl MainWindow {
                 }
 
                 use ruffle_core::events::MouseButton as RuffleMouseButton;
-                use winit::event::MouseButton;
-                let (x, y) = self.gui.window_to_movie_position(self.mouse_pos);
-                let button = match button {
-                    MouseButton::Left => RuffleMouseButton::Left,
-                    MouseButton::Right => RuffleMouseButton::Right,
-                    MouseButton::Middle => RuffleMouseButton::Middle,
-                    _ => RuffleMouseButton::Unknown,
-                };
-                let event = match state {
-                    // TODO We should get information about click index from the OS,
-                    //   but winit does not support that yet.
-                    ElementState::Pressed => PlayerEvent::MouseDown {
-                        x,
-                        y,
-                        button,
-                        index: None,
-                    },
-                    ElementState::Released => PlayerEvent::MouseUp { x, y, button },
-                };
+                let (x, y) = self.movie_mouse_position();
+                let button = winit_to_ruffle_mouse_button(button);
+                let context_menu_mode = self.context_menu_mode();
+                if context_menu_mode == ContextMenuMode::CtrlClick
+                    && button == RuffleMouseButton::Right
+                    && state == ElementState::Pressed
+                    && self.modifiers.state().control_key()
+                {
+                    // The movie never sees this click, so there's no press to finish once the menu closes.
+                    self.show_context_menu(None, PlayerEvent::MouseMove { x, y });
+                    self.check_redraw();
+                    return;
+                }
+
+                let event = mouse_button_event(x, y, button, state);
                 let handled = self.player.handle_event(event);
-                if !handled && state == ElementState::Pressed && button == RuffleMouseButton::Right
+                if !handled
+                    && state == ElementState::Pressed
+                    && button == RuffleMouseButton::Right
+                    && context_menu_mode == ContextMenuMode::Shown
                 {
-                    // Show context menu.
-                    if let Some(mut player) = self.player.get() {
-                        let context_menu = player.prepare_context_menu();
-
-                        // MouseUp event will be ignored when the context menu is shown,
-                        // but it has to be dispatched when the menu closes.
-                        let close_event = PlayerEvent::MouseUp {
-                            x,
-                            y,
-                            button: RuffleMouseButton::Right,
-                        };
-                        self.gui.show_context_menu(context_menu, close_event);
-                    }
+                    // MouseUp event will be ignored when the context menu is shown,
+                    // but it has to be dispatched when the menu closes.
+                    let close_event = PlayerEvent::MouseUp {
+                        x,
+                        y,
+                        button: RuffleMouseButton::Right,
+                    };
+                    self.show_context_menu(None, close_event);
                 }
                 self.check_redraw();
             }
@@ -161,14 +204,33 @@ impl MainWindow {
                     return;
                 }
 
-                use ruffle_core::events::MouseWheelDelta;
-                use winit::event::MouseScrollDelta;
-                let delta = match delta {
-                    MouseScrollDelta::LineDelta(_, dy) => MouseWheelDelta::Lines(dy.into()),
-                    MouseScrollDelta::PixelDelta(pos) => MouseWheelDelta::Pixels(pos.y),
-                };
-                let event = PlayerEvent::MouseWheel { delta };
-                self.player.handle_event(event);
+                let (horizontal, vertical) = self.wheel_deltas(delta);
+                if let Some(delta) = vertical {
+                    self.player.handle_event(PlayerEvent::MouseWheel { delta });
+                }
+                if let Some(delta) = horizontal {
+                    self.player
+                        .handle_event(PlayerEvent::HorizontalMouseWheel { delta });
+                }
+                self.check_redraw();
+            }
+            WindowEvent::Touch(touch) => {
+                if self.gui.is_context_menu_visible() {
+                    if self.context_menu_touch == Some(touch.id)
+                        && matches!(touch.phase, TouchPhase::Ended | TouchPhase::Cancelled)
+                    {
+                        // The finger that opened the menu was lifted outside of it,
+                        // as the menu would have consumed the touch otherwise.
+                        self.context_menu_touch = None;
+                        if let Some(mut player) = self.player.get() {
+                            self.gui.close_context_menu(&mut player);
+                        }
+                        self.check_redraw();
+                    }
+                    return;
+                }
+
+                self.handle_touch(touch);
                 self.check_redraw();
             }
             WindowEvent::CursorEntered { .. } => {
@@ -194,16 +256,32 @@ impl MainWindow {
                     return;
                 }
 
-                // Handle escaping from fullscreen.
-                if let KeyEvent {
-                    state: ElementState::Pressed,
-                    logical_key: Key::Named(NamedKey::Escape),
-                    ..
-                } = event
+                if self.mouse_lock_position.is_some()
+                    && event.state == ElementState::Pressed
+                    && event.logical_key == Key::Named(NamedKey::Escape)
                 {
-                    let _ = self
-                        .event_loop_proxy
-                        .send_event(RuffleEvent::ExitFullScreen);
+                    self.set_mouse_lock(false);
+                    return;
+                }
+
+                if self.handle_mute_shortcut(&event) {
+                    self.check_redraw();
+                    return;
+                }
+
+                if self.handle_fullscreen_shortcuts(&event) {
+                    self.check_redraw();
+                    return;
+                }
+
+                if self.handle_context_menu_key(&event) {
+                    self.check_redraw();
+                    return;
+                }
+
+                if self.handle_remapped_key(&event) {
+                    self.check_redraw();
+                    return;
                 }
 
                 let key = winit_input_to_ruffle_key_descriptor(&event);
@@ -229,7 +307,10 @@ impl MainWindow {
                 self.check_redraw();
             }
             WindowEvent::Ime(ime) => match ime {
-                Ime::Enabled => {}
+                Ime::Enabled => self.ime_active = true,
+                // Some platforms send stray events while the IME is disabled,
+                // which must not reach the movie.
+                Ime::Preedit(..) | Ime::Commit(_) if !self.ime_active => {}
                 Ime::Preedit(text, cursor) => {
                     self.player
                         .handle_event(PlayerEvent::Ime(ImeEvent::Preedit(text, cursor)));
@@ -238,22 +319,347 @@ impl MainWindow {
                     self.player
                         .handle_event(PlayerEvent::Ime(ImeEvent::Commit(text)));
                 }
-                Ime::Disabled => {}
+                Ime::Disabled => {
+                    if self.ime_active {
+                        // Clear any composition that was still in progress.
+                        self.player
+                            .handle_event(PlayerEvent::Ime(ImeEvent::Preedit(String::new(), None)));
+                    }
+                    self.ime_active = false;
+                }
             },
             _ => (),
         }
     }
 
-    fn on_metadata(&mut self, swf_header: HeaderExt) {
-        let height_offset = if self.gui.window().fullscreen().is_some() || self.no_gui {
-            0.0
-        } else {
-            MENU_HEIGHT as f64
+    /// Converts scrolling into the horizontal and vertical deltas for the movie, if there are any.
+    ///
+    /// Trackpad scrolling is given to the movie in fractions of lines, but only once it adds up
+    /// to at least a line, as movies mostly act on whole lines and would miss smaller amounts.
+    /// Either way, a single event never scrolls more than [`MAX_WHEEL_LINES`],
+    /// so that momentum scrolling doesn't fling the movie around.
+    ///
+    /// Flash Player has no horizontal mouse wheel event, so horizontal scrolling
+    /// only scrolls text fields, and never reaches `MouseEvent.MOUSE_WHEEL` listeners.
+    fn wheel_deltas(
+        &mut self,
+        delta: MouseScrollDelta,
+    ) -> (Option<MouseWheelDelta>, Option<MouseWheelDelta>) {
+        let (horizontal, vertical) = match delta {
+            MouseScrollDelta::LineDelta(dx, dy) => (
+                MouseWheelDelta::Lines(dx.into()),
+                MouseWheelDelta::Lines(dy.into()),
+            ),
+            MouseScrollDelta::PixelDelta(position) => {
+                let position = position.to_logical::<f64>(self.gui.scale_factor());
+                let lines = MouseWheelDelta::Pixels(position.y).lines();
+                // Scrolling back the other way starts over, rather than first undoing what's left.
+                if lines * self.wheel_remainder < 0.0 {
+                    self.wheel_remainder = 0.0;
+                }
+                let lines = self.wheel_remainder + lines;
+                let vertical = if lines.abs() >= 1.0 {
+                    self.wheel_remainder = 0.0;
+                    lines
+                } else {
+                    self.wheel_remainder = lines;
+                    0.0
+                };
+                (
+                    MouseWheelDelta::Pixels(position.x),
+                    MouseWheelDelta::Lines(vertical),
+                )
+            }
+        };
+        let max_pixels = MouseWheelDelta::Lines(MAX_WHEEL_LINES).pixels();
+        let horizontal = horizontal.pixels().clamp(-max_pixels, max_pixels);
+        let vertical = vertical.lines().clamp(-MAX_WHEEL_LINES, MAX_WHEEL_LINES);
+        (
+            (horizontal != 0.0).then_some(MouseWheelDelta::Pixels(horizontal)),
+            (vertical != 0.0).then_some(MouseWheelDelta::Lines(vertical)),
+        )
+    }
+
+    /// The position of the mouse in the movie, which only follows the cursor while it isn't locked.
+    fn movie_mouse_position(&self) -> (f64, f64) {
+        self.mouse_lock_position
+            .unwrap_or_else(|| self.gui.window_to_movie_position(self.mouse_pos))
+    }
+
+    /// Locks the mouse pointer in place, or releases it.
+    ///
+    /// While it's locked, the movie is moved by the raw mouse movement, see [`Self::mouse_motion`],
+    /// starting from wherever the cursor was.
+    /// The movie is told whether it worked, see [`DesktopUiBackend::set_mouse_locked`].
+    fn set_mouse_lock(&mut self, locked: bool) {
+        if locked != self.mouse_lock_position.is_some() {
+            if !locked {
+                self.mouse_lock_position = None;
+                self.gui.unlock_mouse();
+            } else if self.gui.window().has_focus() && self.gui.lock_mouse() {
+                self.mouse_lock_position = Some(self.gui.window_to_movie_position(self.mouse_pos));
+            }
+        }
+
+        if let Some(mut player) = self.player.get() {
+            if let Some(ui) = <dyn Any>::downcast_mut::<DesktopUiBackend>(player.ui_mut()) {
+                ui.set_mouse_locked(self.mouse_lock_position.is_some());
+            }
+        }
+    }
+
+    /// Moves the mouse in the movie by the raw movement of the mouse, while it's locked.
+    fn mouse_motion(&mut self, (dx, dy): (f64, f64)) {
+        let Some((x, y)) = &mut self.mouse_lock_position else {
+            return;
+        };
+        if self.gui.is_context_menu_visible() {
+            return;
+        }
+        *x += dx;
+        *y += dy;
+        let event = PlayerEvent::MouseMove { x: *x, y: *y };
+        self.player.handle_event(event);
+        self.check_redraw();
+    }
+
+    /// Mutes or unmutes the movie when the mute shortcut is pressed, instead of forwarding it.
+    ///
+    /// Returns true if the key was consumed and shouldn't reach the movie.
+    fn handle_mute_shortcut(&mut self, event: &KeyEvent) -> bool {
+        if !self
+            .preferences
+            .mute_shortcut()
+            .matches(&event.logical_key, self.modifiers.state())
+        {
+            return false;
+        }
+        if event.state == ElementState::Pressed && !event.repeat {
+            self.gui.toggle_mute(self.player.get().as_deref_mut());
+        }
+        true
+    }
+
+    /// Handles the fullscreen shortcuts before the key is forwarded to the movie.
+    ///
+    /// Returns true if the key was consumed and shouldn't reach the movie.
+    fn handle_fullscreen_shortcuts(&mut self, event: &KeyEvent) -> bool {
+        let modifiers = self.modifiers.state();
+        let is_fullscreen = self.gui.window().fullscreen().is_some();
+
+        if event.state == ElementState::Pressed
+            && self
+                .preferences
+                .fullscreen_toggle_shortcut()
+                .matches(&event.logical_key, modifiers)
+        {
+            if !event.repeat {
+                if let Some(mut player) = self.player.get() {
+                    player.set_fullscreen(!is_fullscreen);
+                }
+            }
+            return true;
+        }
+
+        if !is_fullscreen {
+            return false;
+        }
+
+        let exit_shortcut = self.preferences.fullscreen_exit_shortcut();
+        if self.preferences.fullscreen_hold_to_exit() {
+            // The movie still gets the key, so that it can be used in gameplay;
+            // only holding it down leaves fullscreen.
+            match event.state {
+                ElementState::Pressed if exit_shortcut.matches(&event.logical_key, modifiers) => {
+                    self.gui.begin_fullscreen_exit_hold();
+                }
+                ElementState::Released if exit_shortcut.matches_key(&event.logical_key) => {
+                    self.gui.end_fullscreen_exit_hold();
+                }
+                _ => {}
+            }
+        } else if event.state == ElementState::Pressed
+            && exit_shortcut.matches(&event.logical_key, modifiers)
+        {
+            let _ = self
+                .event_loop_proxy
+                .send_event(RuffleEvent::ExitFullScreen);
+        }
+        false
+    }
+
+    /// Opens the context menu when the Menu key or Shift+F10 is pressed, for using it without a mouse.
+    ///
+    /// The menu opens at the pointer while it's over the movie, and at the center of the movie otherwise.
+    /// Returns true if the key was consumed and shouldn't reach the movie.
+    fn handle_context_menu_key(&mut self, event: &KeyEvent) -> bool {
+        let modifiers = self.modifiers.state();
+        let is_menu_key = match event.logical_key {
+            Key::Named(NamedKey::ContextMenu) => modifiers.is_empty(),
+            Key::Named(NamedKey::F10) => modifiers == ModifiersState::SHIFT,
+            _ => false,
+        };
+        if !is_menu_key || self.context_menu_mode() == ContextMenuMode::Disabled {
+            return false;
+        }
+        if event.state == ElementState::Pressed && !event.repeat {
+            let mouse_in_stage = self
+                .player
+                .get()
+                .is_some_and(|player| player.mouse_in_stage());
+            let position = if mouse_in_stage {
+                self.mouse_pos
+            } else {
+                self.gui.movie_center_position()
+            };
+            // The movie doesn't see the key, so there's nothing to finish once the menu closes.
+            let (x, y) = self.movie_mouse_position();
+            self.show_context_menu(Some(position), PlayerEvent::MouseMove { x, y });
+        }
+        true
+    }
+
+    /// Opens the current movie again from its URL, which fetches it again if it's on the network.
+    ///
+    /// The window keeps its size, instead of fitting the movie again.
+    fn reload_movie(&mut self) {
+        if self.player.movie().is_none() {
+            return;
+        }
+        self.keep_window_size = true;
+        self.gui.reload_movie(&mut self.player);
+        self.update_movie_aspect_ratio();
+    }
+
+    /// Opens a file dropped onto the window, the same way as File → Open does.
+    fn open_dropped_file(&mut self, file: &Path) {
+        // Whatever the file is, it's up to the loader to tell, as bundles are directories
+        // and movies don't always have the usual extension.
+        match parse_url(file) {
+            Ok(url) => {
+                self.keep_window_size = false;
+                self.gui.create_movie(
+                    &mut self.player,
+                    LaunchOptions::from(&self.preferences),
+                    url,
+                );
+            }
+            Err(e) => {
+                tracing::warn!("Can't open dropped file {file:?}: {e}");
+                self.gui.open_dialog(DialogDescriptor::ShowMessage(
+                    MessageDialogConfiguration::new(
+                        LocalizableText::LocalizedText(
+                            "message-dialog-root-movie-load-error-title",
+                        ),
+                        LocalizableText::LocalizedText(
+                            "message-dialog-root-movie-load-error-description",
+                        ),
+                    ),
+                ));
+            }
+        }
+    }
+
+    /// Delivers a key to the movie as the key it's remapped to, if it is.
+    ///
+    /// This happens after the GUI and its shortcuts had the key, so that they aren't affected.
+    /// Returns true if the key was remapped.
+    fn handle_remapped_key(&mut self, event: &KeyEvent) -> bool {
+        match event.state {
+            ElementState::Pressed => {
+                let Some(to) = physical_key_to_egui(event.physical_key).and_then(|from| {
+                    let url = self.player.movie().map(|(url, _)| url);
+                    self.preferences.key_remap(url, from)
+                }) else {
+                    return false;
+                };
+                let key = egui_key_to_ruffle_key_descriptor(to);
+                self.remapped_keys.insert(event.physical_key, key);
+                self.player.handle_event(PlayerEvent::KeyDown { key });
+                // The text of the original key would be wrong, so only characters are typed.
+                if let LogicalKey::Character(codepoint) = key.logical_key {
+                    self.player
+                        .handle_event(PlayerEvent::TextInput { codepoint });
+                }
+            }
+            ElementState::Released => {
+                let Some(key) = self.remapped_keys.remove(&event.physical_key) else {
+                    return false;
+                };
+                self.player.handle_event(PlayerEvent::KeyUp { key });
+            }
+        }
+        true
+    }
+
+    /// Snaps the window to the aspect ratio of the movie, if it's locked.
+    ///
+    /// The window is resized to whichever of the new width or height changed the least,
+    /// keeping room for the menu bar.
+    /// Returns the size the window ends up with, or `None` if it's yet to be resized,
+    /// in which case another `Resized` event follows.
+    fn apply_aspect_ratio_lock(&mut self, size: PhysicalSize<u32>) -> Option<PhysicalSize<u32>> {
+        let window = self.gui.window();
+        let Some(aspect_ratio) = self.movie_aspect_ratio else {
+            return Some(size);
         };
+        if !self.preferences.lock_aspect_ratio()
+            || self.minimized
+            || window.fullscreen().is_some()
+            || window.is_maximized()
+        {
+            return Some(size);
+        }
+
+        let height_offset = self.gui.height_offset();
+        let movie_height = (size.height as f64 - height_offset).max(1.0);
+        let by_width = PhysicalSize::new(
+            size.width,
+            (size.width as f64 / aspect_ratio + height_offset).round() as u32,
+        );
+        let by_height =
+            PhysicalSize::new((movie_height * aspect_ratio).round() as u32, size.height);
+        let snapped =
+            if by_width.height.abs_diff(size.height) <= by_height.width.abs_diff(size.width) {
+                by_width
+            } else {
+                by_height
+            };
+
+        // Allow for rounding, so that we don't keep resizing back and forth.
+        if snapped.width.abs_diff(size.width) <= 1 && snapped.height.abs_diff(size.height) <= 1 {
+            return Some(size);
+        }
+        let new_size = window.request_inner_size(snapped)?;
+        self.gui.resize(new_size);
+        Some(new_size)
+    }
+
+    /// Takes the aspect ratio from the movie of the selected tab, after switching tabs.
+    fn update_movie_aspect_ratio(&mut self) {
+        self.movie_aspect_ratio = self
+            .player
+            .get()
+            .map(|mut player| (player.movie_width(), player.movie_height()))
+            .filter(|&(_, height)| height > 0)
+            .map(|(width, height)| width as f64 / height as f64);
+    }
+
+    fn on_metadata(&mut self, swf_header: HeaderExt) {
+        let height_offset = self.gui.height_offset() / self.gui.window().scale_factor();
+        let stage_size = swf_header.stage_size();
+        let stage_height = stage_size.height().to_pixels();
+        self.movie_aspect_ratio =
+            (stage_height > 0.0).then(|| stage_size.width().to_pixels() / stage_height);
 
         // To prevent issues like waiting on resize indefinitely (#11364) or desyncing the window state on Windows,
         // do not resize while window is maximized.
-        let should_resize = !self.gui.window().is_maximized();
+        // A window geometry restored for this movie takes precedence over the movie's size too.
+        // The same goes for a reloaded movie, whose window the user may have resized already.
+        let restored_geometry = self.gui.restored_window_geometry().cloned();
+        let keep_window_size = std::mem::take(&mut self.keep_window_size);
+        let should_resize =
+            !self.gui.window().is_maximized() && restored_geometry.is_none() && !keep_window_size;
 
         let (viewport_size, state) = if should_resize {
             let movie_width = swf_header.stage_size().width().to_pixels();
@@ -288,7 +694,7 @@ impl MainWindow {
 
             let window_size = Size::clamp(
                 window_size,
-                self.min_window_size.into(),
+                self.gui.min_window_size().into(),
                 self.max_window_size.into(),
                 self.gui.window().scale_factor(),
             );
@@ -323,25 +729,254 @@ impl MainWindow {
 
         self.loaded = state;
 
-        self.gui.window().set_fullscreen(if self.start_fullscreen {
-            Some(Fullscreen::Borderless(None))
-        } else {
-            None
-        });
+        let fullscreen =
+            self.start_fullscreen || restored_geometry.is_some_and(|geometry| geometry.fullscreen);
+        self.gui.set_fullscreen(fullscreen);
         self.gui.window().set_visible(true);
 
-        let viewport_scale_factor = self.gui.window().scale_factor();
+        let viewport_scale_factor = self.gui.scale_factor();
+        let mut movie_title = None;
         if let Some(mut player) = self.player.get() {
             player.set_viewport_dimensions(ViewportDimensions {
                 width: viewport_size.width,
                 height: viewport_size.height - height_offset as u32,
                 scale_factor: viewport_scale_factor,
             });
+            movie_title = player
+                .swf()
+                .metadata()
+                .and_then(|metadata| metadata_title(&metadata));
+        }
+        if let Some(movie_title) = movie_title {
+            self.gui.set_movie_title(&movie_title);
         }
     }
 
-    fn about_to_wait(&mut self, gilrs: Option<&mut Gilrs>) {
-        if let Some(Event { event, .. }) = gilrs.and_then(|gilrs| gilrs.next_event()) {
+    /// Handles an event sent on behalf of this window, see [`RuffleEventProxy`].
+    ///
+    /// Events sent by the movie of a tab that isn't selected are dropped,
+    /// except for those that don't act on the window or the selected movie.
+    fn user_event(&mut self, event: RuffleEvent, tab: Option<TabId>) {
+        if tab.is_some_and(|tab| !self.player.is_active_tab(tab))
+            && !matches!(
+                event,
+                RuffleEvent::TaskPoll
+                    | RuffleEvent::OpenDialog(DialogDescriptor::DownloadProgress(..))
+            )
+        {
+            return;
+        }
+
+        match event {
+            RuffleEvent::TaskPoll => self.player.poll(),
+
+            RuffleEvent::OnMetadata(swf_header) => self.on_metadata(swf_header),
+
+            RuffleEvent::ContextMenuItemClicked(index) => {
+                if let Some(mut player) = self.player.get() {
+                    player.run_context_menu_callback(index);
+                }
+            }
+
+            RuffleEvent::BrowseAndOpen(options) => {
+                let event_loop = self.event_loop_proxy.clone();
+                let picker = self.gui.file_picker();
+                tokio::spawn(async move {
+                    if let Some(url) = picker
+                        .pick_ruffle_file(None)
+                        .await
+                        .and_then(|p| Url::from_file_path(p).ok())
+                    {
+                        let _ = event_loop.send_event(RuffleEvent::Open(url, options));
+                    }
+                });
+            }
+
+            RuffleEvent::Open(url, options) => {
+                self.keep_window_size = false;
+                self.gui.create_movie(&mut self.player, *options, url);
+            }
+
+            RuffleEvent::MovieFileChanged(path) => {
+                let is_open_movie = self
+                    .player
+                    .movie()
+                    .and_then(|(url, _)| url.to_file_path().ok())
+                    .is_some_and(|movie_path| movie_path == path);
+                if is_open_movie {
+                    tracing::info!("Reloading {path:?} after it changed");
+                    self.reload_movie();
+                    self.gui.show_notification("movie-reloaded");
+                }
+            }
+
+            RuffleEvent::ReloadMovie => self.reload_movie(),
+
+            RuffleEvent::StepFrames(frames) => {
+                self.player.step_frames(frames);
+                self.gui.window().request_redraw();
+            }
+
+            RuffleEvent::SetAutoReload(enabled) => {
+                self.gui.set_auto_reload(enabled);
+            }
+
+            RuffleEvent::SetTimelineBar(enabled) => {
+                self.gui.set_timeline_bar_enabled(enabled);
+            }
+
+            RuffleEvent::OpenDialog(descriptor) => {
+                self.gui.open_dialog(descriptor);
+            }
+
+            RuffleEvent::MissingFonts(missing_fonts) => {
+                self.gui.show_missing_fonts_warning(missing_fonts);
+            }
+
+            RuffleEvent::CloseFile => {
+                self.gui.close_movie(&mut self.player);
+                self.update_movie_aspect_ratio();
+            }
+
+            RuffleEvent::CancelDownload(tab) => {
+                if let Some(index) = self.player.tab_position(tab) {
+                    self.gui.close_tab(&mut self.player, index);
+                    self.update_movie_aspect_ratio();
+                }
+            }
+
+            RuffleEvent::SelectTab(index) => {
+                self.gui.select_tab(&mut self.player, index);
+                self.update_movie_aspect_ratio();
+            }
+
+            RuffleEvent::CloseTab(index) => {
+                self.gui.close_tab(&mut self.player, index);
+                self.update_movie_aspect_ratio();
+            }
+
+            RuffleEvent::ResizeToMovie(zoom) => {
+                let movie_size = self
+                    .player
+                    .get()
+                    .map(|mut player| (player.movie_width(), player.movie_height()));
+                if let Some((width, height)) = movie_size {
+                    self.gui.resize_to_movie(width, height, zoom);
+                }
+            }
+
+            RuffleEvent::SetAspectRatioLock(lock_aspect_ratio) => {
+                if let Err(e) = self
+                    .preferences
+                    .write_preferences(|writer| writer.set_lock_aspect_ratio(lock_aspect_ratio))
+                {
+                    tracing::error!("Could not save preferences: {e}");
+                }
+                let size = self.gui.window().inner_size();
+                self.apply_aspect_ratio_lock(size);
+            }
+
+            RuffleEvent::BrowseAndRecord => {
+                let event_loop = self.event_loop_proxy.clone();
+                let picker = self.gui.file_picker();
+                tokio::spawn(async move {
+                    if let Some(path) = picker.pick_recording_directory().await {
+                        let _ = event_loop.send_event(RuffleEvent::StartRecording(path));
+                    }
+                });
+            }
+
+            RuffleEvent::StartRecording(path) => {
+                // Record at the movie's own frame rate, so that no frames are skipped.
+                let Some(fps) = self.player.get().map(|player| player.frame_rate()) else {
+                    return;
+                };
+                if let Err(e) = self.gui.start_recording(path.clone(), fps) {
+                    tracing::error!("Couldn't start recording to {path:?}: {e}");
+                }
+            }
+
+            RuffleEvent::ResetZoom => {
+                self.gui.reset_zoom();
+            }
+
+            RuffleEvent::ZoomView(factor) => {
+                self.gui.zoom_view(factor);
+            }
+
+            RuffleEvent::CopyFrameToClipboard => {
+                self.gui.copy_frame_to_clipboard();
+            }
+
+            RuffleEvent::OpenFromClipboard => {
+                self.gui.open_from_clipboard();
+            }
+
+            RuffleEvent::StopRecording => {
+                self.gui.stop_recording();
+            }
+
+            RuffleEvent::SetMouseLock(locked) => self.set_mouse_lock(locked),
+
+            RuffleEvent::SetFullscreen(fullscreen) => self.gui.set_fullscreen(fullscreen),
+
+            RuffleEvent::EnterFullScreen => {
+                if let Some(mut player) = self.player.get() {
+                    if player.is_playing() {
+                        player.set_fullscreen(true);
+                    }
+                }
+            }
+
+            RuffleEvent::ExitFullScreen => {
+                if let Some(mut player) = self.player.get() {
+                    if player.is_playing() {
+                        player.set_fullscreen(false);
+                    }
+                }
+            }
+
+            RuffleEvent::PlayerNotification(notification) => match notification {
+                PlayerNotification::ImeNotification(ImeNotification::ImeReady {
+                    purpose,
+                    cursor_area,
+                }) => {
+                    let ime_enabled = self.preferences.ime_enabled().unwrap_or(false);
+                    self.gui.set_ime_allowed(ime_enabled);
+                    self.gui.set_ime_purpose(purpose);
+                    self.gui.set_ime_cursor_area(cursor_area);
+                }
+                PlayerNotification::ImeNotification(ImeNotification::ImePurposeUpdated(
+                    purpose,
+                )) => {
+                    self.gui.set_ime_purpose(purpose);
+                }
+                PlayerNotification::ImeNotification(ImeNotification::ImeCursorAreaUpdated(
+                    cursor_area,
+                )) => {
+                    self.gui.set_ime_cursor_area(cursor_area);
+                }
+                PlayerNotification::ImeNotification(ImeNotification::ImeNotReady) => {
+                    self.gui.set_ime_allowed(false);
+                }
+            },
+
+            _ => {}
+        }
+    }
+
+    fn about_to_wait(&mut self, gamepad_events: Vec<EventType>) {
+        self.dropped_files = 0;
+
+        if self
+            .long_press
+            .as_ref()
+            .is_some_and(|long_press| Instant::now() >= long_press.deadline)
+        {
+            self.open_context_menu_by_long_press();
+        }
+
+        for event in gamepad_events {
             match event {
                 EventType::ButtonPressed(button, _) => {
                     if let Some(button) = gilrs_button_to_gamepad_button(button) {
@@ -375,31 +1010,341 @@ impl MainWindow {
                 } else {
                     self.next_frame_time = None;
                 }
-                self.check_redraw();
             }
         }
+
+        // The GUI may also be due for a repaint, even while there's no movie to tick.
+        self.check_redraw();
+    }
+
+    /// Translates a touch into mouse events for the movie, so that tapping acts as a left click
+    /// and dragging a finger acts as dragging the mouse.
+    ///
+    /// Only the first finger to touch the window is passed on to the movie as the mouse, until it's lifted.
+    /// Ruffle doesn't support the touch events of Flash, so other fingers never reach the movie.
+    /// Instead, a second finger turns the first one into a pinch, zooming and panning the movie view,
+    /// and holding the first one still opens the context menu, see [`LongPress`].
+    fn handle_touch(&mut self, touch: Touch) {
+        use ruffle_core::events::MouseButton as RuffleMouseButton;
+
+        if self.gui.handle_touch_gesture(&touch) {
+            return;
+        }
+
+        match touch.phase {
+            TouchPhase::Started if self.primary_touch.is_none() => {
+                self.primary_touch = Some(touch.id);
+                self.long_press =
+                    self.preferences
+                        .long_press_duration()
+                        .map(|duration| LongPress {
+                            deadline: Instant::now() + duration,
+                            position: touch.location,
+                        });
+            }
+            TouchPhase::Started
+                if !self.primary_touch_handled && !self.preferences.touch_as_mouse() =>
+            {
+                self.start_touch_gesture(&touch);
+                return;
+            }
+            _ if self.primary_touch != Some(touch.id) => return,
+            TouchPhase::Ended | TouchPhase::Cancelled => {
+                self.primary_touch = None;
+                self.long_press = None;
+            }
+            TouchPhase::Moved => {
+                if let Some(long_press) = &self.long_press {
+                    let distance = (touch.location.x - long_press.position.x)
+                        .hypot(touch.location.y - long_press.position.y);
+                    if distance / self.gui.scale_factor() > LONG_PRESS_TOLERANCE {
+                        self.long_press = None;
+                    }
+                }
+            }
+            TouchPhase::Started => {}
+        }
+
+        self.mouse_pos = touch.location;
+        let (x, y) = self.gui.window_to_movie_position(touch.location);
+        match touch.phase {
+            TouchPhase::Started => {
+                // There's no cursor hovering beforehand, so move it to the finger first
+                // for the movie to know what's being pressed.
+                if let Some(mut player) = self.player.get() {
+                    player.set_mouse_in_stage(true);
+                }
+                self.player.handle_event(PlayerEvent::MouseMove { x, y });
+                self.primary_touch_handled = self.player.handle_event(PlayerEvent::MouseDown {
+                    x,
+                    y,
+                    button: RuffleMouseButton::Left,
+                    index: None,
+                });
+                if self.primary_touch_handled {
+                    // The movie uses the press, such as for dragging, so holding it doesn't open the menu.
+                    self.long_press = None;
+                }
+            }
+            TouchPhase::Moved => {
+                self.player.handle_event(PlayerEvent::MouseMove { x, y });
+            }
+            TouchPhase::Ended => {
+                self.player.handle_event(PlayerEvent::MouseUp {
+                    x,
+                    y,
+                    button: RuffleMouseButton::Left,
+                });
+            }
+            TouchPhase::Cancelled => self.cancel_touch_press(x, y),
+        }
+    }
+
+    /// Lets go of the press of the primary touch, as a second finger turned it into a pinch.
+    fn start_touch_gesture(&mut self, touch: &Touch) {
+        let Some(primary_touch) = self.primary_touch.take() else {
+            return;
+        };
+        self.long_press = None;
+        let (x, y) = self.gui.window_to_movie_position(self.mouse_pos);
+        self.cancel_touch_press(x, y);
+        self.gui
+            .start_touch_gesture((primary_touch, self.mouse_pos), (touch.id, touch.location));
+    }
+
+    /// When right clicks open the context menu, for the movie that's playing.
+    fn context_menu_mode(&self) -> ContextMenuMode {
+        self.player
+            .movie()
+            .and_then(|(_, options)| options.context_menu_mode)
+            .unwrap_or_else(|| self.preferences.context_menu_mode())
+    }
+
+    /// Shows the context menu of the movie, dispatching `close_event` to it once the menu closes.
+    fn show_context_menu(
+        &mut self,
+        position: Option<PhysicalPosition<f64>>,
+        close_event: PlayerEvent,
+    ) {
+        if let Some(mut player) = self.player.get() {
+            let context_menu = player.prepare_context_menu();
+            let zoom = player.context_menu_zoom_allowed();
+            self.gui
+                .show_context_menu(context_menu, zoom, position, close_event);
+        }
+    }
+
+    /// Turns the press of the primary touch into a right click, once it was held still for long enough.
+    ///
+    /// Like with a right click, the context menu only opens if the movie doesn't use the press itself.
+    fn open_context_menu_by_long_press(&mut self) {
+        use ruffle_core::events::MouseButton as RuffleMouseButton;
+
+        self.long_press = None;
+        let Some(touch_id) = self.primary_touch.take() else {
+            return;
+        };
+        self.gui.show_long_press_cue(self.mouse_pos);
+
+        // The finger is done with the movie, even while it stays down.
+        let (x, y) = self.gui.window_to_movie_position(self.mouse_pos);
+        self.player.handle_event(PlayerEvent::MouseUp {
+            x,
+            y,
+            button: RuffleMouseButton::Left,
+        });
+        let handled = self.player.handle_event(PlayerEvent::MouseDown {
+            x,
+            y,
+            button: RuffleMouseButton::Right,
+            index: None,
+        });
+        let close_event = PlayerEvent::MouseUp {
+            x,
+            y,
+            button: RuffleMouseButton::Right,
+        };
+        if !handled && self.context_menu_mode() == ContextMenuMode::Shown {
+            self.show_context_menu(Some(self.mouse_pos), close_event.clone());
+        }
+        if self.gui.is_context_menu_visible() {
+            self.context_menu_touch = Some(touch_id);
+        } else {
+            // There's no menu to wait for, so the right click is over right away.
+            self.player.handle_event(close_event);
+        }
+        self.check_redraw();
+    }
+
+    /// When the primary touch will have been held long enough to open the context menu.
+    fn long_press_deadline(&self) -> Option<Instant> {
+        self.long_press
+            .as_ref()
+            .map(|long_press| long_press.deadline)
+    }
+
+    fn cancel_touch_press(&mut self, x: f64, y: f64) {
+        use ruffle_core::events::MouseButton as RuffleMouseButton;
+
+        // The press can't be aborted, so release it and move the pointer away,
+        // which is what happens when the mouse leaves the window while pressed.
+        self.player.handle_event(PlayerEvent::MouseUp {
+            x,
+            y,
+            button: RuffleMouseButton::Left,
+        });
+        if let Some(mut player) = self.player.get() {
+            player.set_mouse_in_stage(false);
+        }
+        self.player.handle_event(PlayerEvent::MouseLeave);
     }
 
     fn check_redraw(&self) {
         let player = self.player.get();
-        if player.map(|p| p.needs_render()).unwrap_or_default() || self.gui.needs_render() {
+        let movie_needs_render = player.is_some_and(|p| p.needs_render());
+        if (movie_needs_render && self.gui.should_render_movie()) || self.gui.needs_render() {
             self.gui.window().request_redraw();
         }
+        self.gui.request_child_viewport_redraws();
+        // While the main window is minimized, the picture-in-picture window renders the movie instead.
+        if movie_needs_render && self.minimized {
+            if let Some(window) = self.gui.picture_in_picture_window() {
+                window.request_redraw();
+            }
+        }
+    }
+
+    /// Handles an event of a window showing part of the GUI, such as a popped out dialog.
+    ///
+    /// None of its input reaches the movie, and focusing it doesn't count as the movie losing focus.
+    fn child_viewport_event(&mut self, window_id: WindowId, event: WindowEvent) {
+        if let WindowEvent::Focused(true) = event {
+            self.player
+                .set_window_focused(true, self.preferences.unfocused_behavior());
+        }
+        self.gui.handle_child_viewport_event(window_id, &event);
+    }
+
+    /// Handles an event of the picture-in-picture window,
+    /// which only shows the movie and passes the mouse on to it.
+    fn picture_in_picture_event(&mut self, event: WindowEvent) {
+        match event {
+            WindowEvent::RedrawRequested => {
+                let mut player = self.player.get();
+                // The main window renders the movie itself, unless it's minimized.
+                if self.minimized {
+                    if let Some(player) = player.as_deref_mut() {
+                        player.render();
+                        self.gui.on_movie_rendered(player);
+                    }
+                }
+                self.gui.render_picture_in_picture(player.as_deref_mut());
+            }
+            WindowEvent::Resized(size) => self.gui.resize_picture_in_picture(size),
+            WindowEvent::CloseRequested => self.close_picture_in_picture(),
+            WindowEvent::ModifiersChanged(new_modifiers) => self.modifiers = new_modifiers,
+            WindowEvent::CursorMoved { position, .. } => {
+                self.picture_in_picture_mouse_pos = position;
+                self.gui.hover_picture_in_picture(position);
+                if let Some((x, y)) = self.gui.picture_in_picture_to_movie_position(position) {
+                    self.player.handle_event(PlayerEvent::MouseMove { x, y });
+                }
+                self.check_redraw();
+            }
+            WindowEvent::MouseInput { button, state, .. } => {
+                if button == MouseButton::Left
+                    && state == ElementState::Pressed
+                    && self.gui.drag_picture_in_picture(
+                        self.picture_in_picture_mouse_pos,
+                        self.modifiers.state().alt_key(),
+                    )
+                {
+                    return;
+                }
+                if let Some((x, y)) = self
+                    .gui
+                    .picture_in_picture_to_movie_position(self.picture_in_picture_mouse_pos)
+                {
+                    let button = winit_to_ruffle_mouse_button(button);
+                    self.player
+                        .handle_event(mouse_button_event(x, y, button, state));
+                }
+                self.check_redraw();
+            }
+            WindowEvent::CursorLeft { .. } => {
+                self.player.handle_event(PlayerEvent::MouseLeave);
+                self.check_redraw();
+            }
+            _ => {}
+        }
+    }
+
+    /// Closes the picture-in-picture window and brings back the main window, if it was minimized.
+    ///
+    /// The movie keeps playing in the main window as if nothing happened.
+    fn close_picture_in_picture(&mut self) {
+        if self.gui.picture_in_picture_window().is_none() {
+            return;
+        }
+        self.gui.close_picture_in_picture();
+        let window = self.gui.window();
+        window.set_minimized(false);
+        window.focus_window();
+        window.request_redraw();
+    }
+}
+
+fn winit_to_ruffle_mouse_button(
+    button: winit::event::MouseButton,
+) -> ruffle_core::events::MouseButton {
+    use ruffle_core::events::MouseButton as RuffleMouseButton;
+    use winit::event::MouseButton;
+    match button {
+        MouseButton::Left => RuffleMouseButton::Left,
+        MouseButton::Right => RuffleMouseButton::Right,
+        MouseButton::Middle => RuffleMouseButton::Middle,
+        _ => RuffleMouseButton::Unknown,
+    }
+}
+
+fn mouse_button_event(
+    x: f64,
+    y: f64,
+    button: ruffle_core::events::MouseButton,
+    state: ElementState,
+) -> PlayerEvent {
+    match state {
+        // TODO We should get information about click index from the OS,
+        //   but winit does not support that yet.
+        ElementState::Pressed => PlayerEvent::MouseDown {
+            x,
+            y,
+            button,
+            index: None,
+        },
+        ElementState::Released => PlayerEvent::MouseUp { x, y, button },
     }
 }
 
 pub struct App {
-    main_window: Option<MainWindow>,
+    /// All open windows, each with a player of its own.
+    windows: HashMap<WindowId, MainWindow>,
+    /// The first window, waiting for the graphics device to be created.
+    ///
+    /// Any further windows share that device.
+    pending_window: Option<Arc<Window>>,
     gilrs: Option<Gilrs>,
-    event_loop_proxy: EventLoopProxy<RuffleEvent>,
+    event_loop_proxy: EventLoopProxy<WindowRuffleEvent>,
     preferences: GlobalPreferences,
     font_database: fontdb::Database,
+    /// The file picker shared by all windows, so that only one of them picks a file at a time.
+    file_picker: FilePicker,
 }
 
 impl App {
     pub async fn new(
         preferences: GlobalPreferences,
-    ) -> Result<(Self, EventLoop<RuffleEvent>), Error> {
+    ) -> Result<(Self, EventLoop<WindowRuffleEvent>), Error> {
         let event_loop = EventLoop::with_user_event().build()?;
 
         let mut font_database = fontdb::Database::default();
@@ -411,250 +1356,495 @@ impl App {
             })
             .ok();
         let event_loop_proxy = event_loop.create_proxy();
+        let file_picker = FilePicker::new(Weak::new(), preferences.clone());
 
         Ok((
             Self {
-                main_window: None,
+                windows: HashMap::new(),
+                pending_window: None,
                 gilrs,
                 event_loop_proxy,
                 font_database,
                 preferences,
+                file_picker,
             },
             event_loop,
         ))
     }
 }
 
-impl ApplicationHandler<RuffleEvent> for App {
-    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
-        if cause == StartCause::Init {
-            let movie_url = self.preferences.cli.movie_url.clone();
-            let icon_bytes = include_bytes!("../assets/favicon-32.rgba");
-            let icon =
-                Icon::from_rgba(icon_bytes.to_vec(), 32, 32).expect("App icon should be correct");
-
-            let no_gui = self.preferences.cli.no_gui;
-            let min_window_size = if no_gui {
-                (16, 16)
-            } else {
-                (350, MENU_HEIGHT + 16)
-            }
-            .into();
-            let preferred_width = self.preferences.cli.width;
-            let preferred_height = self.preferences.cli.height;
-            let start_fullscreen = self.preferences.cli.fullscreen;
+impl App {
+    /// Creates a window with its GUI and player, once its graphics are available.
+    ///
+    /// Only the first window is launched fullscreen, if requested.
+    fn create_main_window(
+        &mut self,
+        window: Arc<Window>,
+        graphics: GraphicsContext,
+        movie_url: Option<Url>,
+    ) {
+        let no_gui = self.preferences.cli.no_gui;
+        let max_window_size = get_screen_size(&window);
+        let preferred_width = self.preferences.cli.width;
+        let preferred_height = self.preferences.cli.height;
+        let start_fullscreen = self.preferences.cli.fullscreen && self.windows.is_empty();
 
-            #[cfg_attr(not(target_os = "linux"), allow(unused_mut))]
-            let mut window_attributes = WindowAttributes::default()
-                .with_visible(false)
-                .with_title("Ruffle")
-                .with_window_icon(Some(icon))
-                .with_min_inner_size(min_window_size);
+        let event_loop_proxy = RuffleEventProxy::new(self.event_loop_proxy.clone(), window.id());
+        let preferences = self.preferences.clone();
+        let font_database = self.font_database.clone();
 
-            #[cfg(target_os = "linux")]
-            {
-                use winit::platform::startup_notify::{
-                    self, EventLoopExtStartupNotify, WindowAttributesExtStartupNotify,
-                };
-                use winit::platform::wayland::WindowAttributesExtWayland;
-                window_attributes = window_attributes.with_name("rs.ruffle.Ruffle", "main");
-                if let Some(token) = event_loop.read_token_from_env() {
-                    startup_notify::reset_activation_token_env();
-                    window_attributes = window_attributes.with_activation_token(token);
-                }
-            }
+        let mut gui = GuiController::new(
+            window.clone(),
+            event_loop_proxy.clone(),
+            preferences.clone(),
+            &font_database,
+            movie_url.clone(),
+            no_gui,
+            graphics,
+            self.file_picker.with_parent(Arc::downgrade(&window)),
+        );
 
-            let event_loop_proxy = self.event_loop_proxy.clone();
-            let preferences = self.preferences.clone();
-            let window = event_loop
-                .create_window(window_attributes)
-                .expect("Window should be created");
-            let max_window_size = get_screen_size(&window);
-            window.set_max_inner_size(Some(max_window_size));
-            let window = Arc::new(window);
-            let font_database = self.font_database.clone();
-
-            let mut gui = GuiController::new(
-                window.clone(),
-                event_loop_proxy.clone(),
-                preferences.clone(),
-                &font_database,
-                movie_url.clone(),
-                no_gui,
-            )
-            .expect("GUI controller should be created");
+        let mut player = PlayerController::new(
+            event_loop_proxy.clone(),
+            window.clone(),
+            gui.descriptors().clone(),
+            font_database,
+            preferences.clone(),
+            gui.file_picker(),
+        );
 
-            let mut player = PlayerController::new(
-                event_loop_proxy.clone(),
-                window.clone(),
-                gui.descriptors().clone(),
-                font_database,
-                preferences.clone(),
-                gui.file_picker(),
+        if let Some(movie_url) = &movie_url {
+            gui.create_movie(
+                &mut player,
+                LaunchOptions::from(&preferences),
+                movie_url.clone(),
             );
+        } else {
+            gui.show_open_dialog();
+        }
 
-            if let Some(movie_url) = &movie_url {
-                gui.create_movie(
-                    &mut player,
-                    LaunchOptions::from(&preferences),
-                    movie_url.clone(),
-                );
-            } else {
-                gui.show_open_dialog();
-            }
-
-            let mut loaded = LoadingState::Loading;
+        let loaded = if movie_url.is_none() {
+            // The window is already visible, and there's no movie to wait for.
+            LoadingState::Loaded
+        } else {
+            LoadingState::Loading
+        };
 
-            if movie_url.is_none() {
-                // No SWF provided on command line; show window with dummy movie immediately.
-                window.set_visible(true);
-                loaded = LoadingState::Loaded;
-            }
+        window.set_title("Ruffle");
+        window.request_redraw();
 
-            self.main_window = Some(MainWindow {
+        self.windows.insert(
+            window.id(),
+            MainWindow {
                 preferences,
                 gui,
                 player,
-                min_window_size,
                 max_window_size,
-                no_gui,
                 preferred_width,
                 preferred_height,
                 start_fullscreen,
+                movie_aspect_ratio: None,
                 loaded,
                 minimized: false,
+                ime_active: false,
                 mouse_pos: PhysicalPosition::new(0.0, 0.0),
+                wheel_remainder: 0.0,
+                mouse_lock_position: None,
+                picture_in_picture_mouse_pos: PhysicalPosition::new(0.0, 0.0),
+                primary_touch: None,
+                primary_touch_handled: false,
+                long_press: None,
+                context_menu_touch: None,
+                remapped_keys: HashMap::new(),
+                dropped_files: 0,
+                keep_window_size: false,
                 modifiers: Modifiers::default(),
                 time: Instant::now(),
                 next_frame_time: None,
                 event_loop_proxy,
-            });
+            },
+        );
+    }
+
+    /// Opens another window, sharing the graphics device with the existing ones.
+    fn open_new_window(&mut self, event_loop: &ActiveEventLoop) {
+        let Some(descriptors) = self
+            .windows
+            .values()
+            .next()
+            .map(|main_window| main_window.gui.descriptors().clone())
+        else {
+            return;
+        };
+
+        // The geometry of the main window isn't restored, as it would be right on top of it.
+        let window = match event_loop.create_window(self.window_attributes(event_loop, false)) {
+            Ok(window) => window,
+            Err(e) => {
+                tracing::error!("Couldn't create a new window: {e}");
+                return;
+            }
+        };
+        window.set_max_inner_size(Some(get_screen_size(&window)));
+        window.set_visible(true);
+
+        let window = Arc::new(window);
+        match GuiController::share_graphics(&window, &descriptors) {
+            Ok(graphics) => self.create_main_window(window, graphics, None),
+            Err(e) => tracing::error!("Couldn't create graphics for the new window: {e}"),
         }
     }
 
-    fn resumed(&mut self, _event_loop: &ActiveEventLoop) {}
+    /// Closes a window, destroying its player while the other windows keep running.
+    ///
+    /// Ruffle exits once the last window is closed.
+    fn close_window(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId) {
+        let Some(mut main_window) = self.windows.remove(&window_id) else {
+            return;
+        };
+        if self.windows.is_empty() {
+            main_window.gui.save_window_geometry();
+            main_window.gui.save_main_window_geometry();
+            event_loop.exit();
+        } else {
+            main_window.gui.close_picture_in_picture();
+            main_window.gui.window().set_visible(false);
+            main_window.gui.close_movie(&mut main_window.player);
+        }
+    }
 
-    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: RuffleEvent) {
-        match (&mut self.main_window, event) {
-            (Some(main_window), RuffleEvent::TaskPoll) => main_window.player.poll(),
+    /// Closes the tab with the given ID, or its whole window if it's the last tab left.
+    fn close_tab(&mut self, event_loop: &ActiveEventLoop, window_id: WindowId, tab: TabId) {
+        let Some(main_window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        let Some(index) = main_window.player.tab_position(tab) else {
+            return;
+        };
+        if main_window.player.tab_count() > 1 {
+            main_window.gui.close_tab(&mut main_window.player, index);
+            main_window.update_movie_aspect_ratio();
+        } else {
+            self.close_window(event_loop, window_id);
+        }
+    }
 
-            (Some(main_window), RuffleEvent::OnMetadata(swf_header)) => {
-                main_window.on_metadata(swf_header)
-            }
+    /// Opens or closes a small frameless window showing the movie of a window,
+    /// which stays on top so that the main window can be minimized.
+    fn set_picture_in_picture(
+        &mut self,
+        event_loop: &ActiveEventLoop,
+        window_id: WindowId,
+        enabled: bool,
+    ) {
+        let Some(main_window) = self.windows.get_mut(&window_id) else {
+            return;
+        };
+        if !enabled {
+            main_window.close_picture_in_picture();
+            return;
+        }
+        if main_window.gui.picture_in_picture_window().is_some() {
+            return;
+        }
 
-            (Some(main_window), RuffleEvent::ContextMenuItemClicked(index)) => {
-                if let Some(mut player) = main_window.player.get() {
-                    player.run_context_menu_callback(index);
-                }
-            }
+        let window_attributes = WindowAttributes::default()
+            .with_title("Ruffle")
+            .with_window_icon(Some(window_icon()))
+            .with_inner_size(main_window.gui.picture_in_picture_size())
+            .with_decorations(false)
+            .with_window_level(WindowLevel::AlwaysOnTop);
+        match event_loop.create_window(window_attributes) {
+            Ok(window) => main_window.gui.open_picture_in_picture(window),
+            Err(e) => tracing::error!("Couldn't create the picture-in-picture window: {e}"),
+        }
+    }
 
-            (Some(main_window), RuffleEvent::BrowseAndOpen(options)) => {
-                let event_loop = main_window.event_loop_proxy.clone();
-                let picker = main_window.gui.file_picker();
-                tokio::spawn(async move {
-                    if let Some(url) = picker
-                        .pick_ruffle_file(None)
-                        .await
-                        .and_then(|p| Url::from_file_path(p).ok())
-                    {
-                        let _ = event_loop.send_event(RuffleEvent::Open(url, options));
+    /// The attributes of a new window, restoring the geometry of the main window
+    /// from the last session if `restore_geometry` is set.
+    fn window_attributes(
+        &self,
+        event_loop: &ActiveEventLoop,
+        restore_geometry: bool,
+    ) -> WindowAttributes {
+        let mut window_attributes = WindowAttributes::default()
+            .with_visible(false)
+            .with_title("Ruffle")
+            .with_window_icon(Some(window_icon()))
+            .with_min_inner_size(min_window_size(
+                self.preferences.cli.no_gui,
+                self.preferences.menu_visibility(),
+            ))
+            .with_transparent(self.preferences.cli.transparent);
+
+        // Launching fullscreen or without the GUI sizes the window for the movie instead,
+        // as does a movie loaded without its own saved geometry.
+        let cli = &self.preferences.cli;
+        if let Some(geometry) = self
+            .preferences
+            .main_window_geometry()
+            .filter(|_| restore_geometry && !cli.fullscreen && !cli.no_gui)
+        {
+            let (position, size) = fit_to_monitors(
+                event_loop.available_monitors(),
+                event_loop.primary_monitor(),
+                PhysicalPosition::new(geometry.x, geometry.y),
+                PhysicalSize::new(geometry.width, geometry.height),
+            );
+            window_attributes = window_attributes
+                .with_position(position)
+                .with_inner_size(size)
+                .with_maximized(geometry.maximized);
+        }
+
+        #[cfg(target_os = "linux")]
+        {
+            use winit::platform::wayland::WindowAttributesExtWayland;
+            window_attributes = window_attributes.with_name("rs.ruffle.Ruffle", "main");
+        }
+
+        window_attributes
+    }
+
+    fn show_graphics_error(&self, error: &str) {
+        tracing::error!("Couldn't initialize graphics: {error}");
+        let locale = self.preferences.language();
+        rfd::MessageDialog::new()
+            .set_level(rfd::MessageLevel::Error)
+            .set_title(text(&locale, "message-dialog-graphics-error-title"))
+            .set_description(format!(
+                "{}\n\n{error}",
+                text(&locale, "message-dialog-graphics-error-description")
+            ))
+            .set_buttons(rfd::MessageButtons::Ok)
+            .show();
+    }
+}
+
+impl ApplicationHandler<RuffleEvent> for App {
+    fn new_events(&mut self, event_loop: &ActiveEventLoop, cause: StartCause) {
+        if cause == StartCause::Init {
+            // Restore the window from the last session.
+            let window_attributes = self.window_attributes(event_loop, true);
+
+            #[cfg(target_os = "linux")]
+            let window_attributes = {
+                use winit::platform::startup_notify::{
+                    self, EventLoopExtStartupNotify, WindowAttributesExtStartupNotify,
+                };
+                match event_loop.read_token_from_env() {
+                    Some(token) => {
+                        startup_notify::reset_activation_token_env();
+                        window_attributes.with_activation_token(token)
                     }
-                });
-            }
+                    None => window_attributes,
+                }
+            };
 
-            (Some(main_window), RuffleEvent::Open(url, options)) => {
-                main_window
-                    .gui
-                    .create_movie(&mut main_window.player, *options, url);
-            }
+            let window = event_loop
+                .create_window(window_attributes)
+                .expect("Window should be created");
+            window.set_max_inner_size(Some(get_screen_size(&window)));
 
-            (Some(main_window), RuffleEvent::OpenDialog(descriptor)) => {
-                main_window.gui.open_dialog(descriptor);
+            if self.preferences.cli.movie_url.is_none() {
+                // No SWF provided on command line; show the window immediately,
+                // while the graphics device is still being created.
+                window.set_title(&text(&self.preferences.language(), "window-title-loading"));
+                window.set_visible(true);
             }
 
-            (Some(main_window), RuffleEvent::CloseFile) => {
-                main_window.gui.window().set_title("Ruffle"); // Reset title since file has been closed.
-                main_window.gui.close_movie(&mut main_window.player);
+            if let Err(e) = GuiController::request_graphics(
+                &window,
+                RuffleEventProxy::new(self.event_loop_proxy.clone(), window.id()),
+                &self.preferences,
+            ) {
+                self.show_graphics_error(&e.to_string());
+                event_loop.exit();
+                return;
             }
+            self.pending_window = Some(Arc::new(window));
+        }
+    }
 
-            (Some(main_window), RuffleEvent::EnterFullScreen) => {
-                if let Some(mut player) = main_window.player.get() {
-                    if player.is_playing() {
-                        player.set_fullscreen(true);
+    fn resumed(&mut self, _event_loop: &ActiveEventLoop) {}
+
+    fn user_event(&mut self, event_loop: &ActiveEventLoop, event: WindowRuffleEvent) {
+        let WindowRuffleEvent {
+            window_id,
+            tab,
+            event,
+        } = event;
+        match event {
+            RuffleEvent::GraphicsReady(result) => {
+                let Some(window) = self
+                    .pending_window
+                    .take_if(|window| window.id() == window_id)
+                else {
+                    return;
+                };
+                match result {
+                    Ok(graphics) => {
+                        let movie_url = self.preferences.cli.movie_url.clone();
+                        self.create_main_window(window, *graphics, movie_url);
+                    }
+                    Err(e) => {
+                        self.show_graphics_error(&e);
+                        event_loop.exit();
                     }
                 }
             }
 
-            (Some(main_window), RuffleEvent::ExitFullScreen) => {
-                if let Some(mut player) = main_window.player.get() {
-                    if player.is_playing() {
-                        player.set_fullscreen(false);
-                    }
-                }
+            RuffleEvent::NewWindow => self.open_new_window(event_loop),
+
+            RuffleEvent::SetPictureInPicture(enabled) => {
+                self.set_picture_in_picture(event_loop, window_id, enabled)
             }
 
-            (Some(main_window), RuffleEvent::PlayerNotification(notification)) => {
-                match notification {
-                    PlayerNotification::ImeNotification(ImeNotification::ImeReady {
-                        purpose,
-                        cursor_area,
-                    }) => {
-                        let ime_enabled = main_window.preferences.ime_enabled().unwrap_or(false);
-                        main_window.gui.set_ime_allowed(ime_enabled);
-                        main_window.gui.set_ime_purpose(purpose);
-                        main_window.gui.set_ime_cursor_area(cursor_area);
-                    }
-                    PlayerNotification::ImeNotification(ImeNotification::ImePurposeUpdated(
-                        purpose,
-                    )) => {
-                        main_window.gui.set_ime_purpose(purpose);
+            RuffleEvent::ExitRequested => match tab {
+                // A movie asking to quit only closes its own tab.
+                Some(tab) => self.close_tab(event_loop, window_id, tab),
+                None => {
+                    for main_window in self.windows.values_mut() {
+                        main_window.gui.save_window_geometry();
                     }
-                    PlayerNotification::ImeNotification(ImeNotification::ImeCursorAreaUpdated(
-                        cursor_area,
-                    )) => {
-                        main_window.gui.set_ime_cursor_area(cursor_area);
-                    }
-                    PlayerNotification::ImeNotification(ImeNotification::ImeNotReady) => {
-                        main_window.gui.set_ime_allowed(false);
+                    if let Some(main_window) = self.windows.get(&window_id) {
+                        main_window.gui.save_main_window_geometry();
                     }
+                    event_loop.exit();
                 }
-            }
+            },
 
-            (_, RuffleEvent::ExitRequested) => {
-                event_loop.exit();
+            event => {
+                if let Some(main_window) = self.windows.get_mut(&window_id) {
+                    main_window.user_event(event, tab);
+                }
             }
-
-            _ => {}
         }
     }
 
     fn window_event(
         &mut self,
         event_loop: &ActiveEventLoop,
-        _window_id: WindowId,
+        window_id: WindowId,
         event: WindowEvent,
     ) {
-        if let Some(main_window) = &mut self.main_window {
-            main_window.window_event(event_loop, event);
+        if let Some(main_window) = self
+            .windows
+            .values_mut()
+            .find(|main_window| main_window.gui.is_picture_in_picture_window(window_id))
+        {
+            main_window.picture_in_picture_event(event);
+            return;
+        }
+        if let Some(main_window) = self
+            .windows
+            .values_mut()
+            .find(|main_window| main_window.gui.is_child_viewport_window(window_id))
+        {
+            main_window.child_viewport_event(window_id, event);
+            return;
+        }
+        if matches!(event, WindowEvent::CloseRequested) {
+            self.close_window(event_loop, window_id);
+            return;
+        }
+        if let Some(main_window) = self.windows.get_mut(&window_id) {
+            main_window.window_event(event);
+        }
+    }
+
+    fn device_event(
+        &mut self,
+        _event_loop: &ActiveEventLoop,
+        _device_id: DeviceId,
+        event: DeviceEvent,
+    ) {
+        if let DeviceEvent::MouseMotion { delta } = event {
+            // Only a focused window can have the mouse locked, so at most one of them is interested.
+            for main_window in self.windows.values_mut() {
+                main_window.mouse_motion(delta);
+            }
         }
     }
 
     fn about_to_wait(&mut self, event_loop: &ActiveEventLoop) {
-        if let Some(main_window) = &mut self.main_window {
-            main_window.about_to_wait(self.gilrs.as_mut());
-
-            // The event loop is finished; let's find out how long we need to wait for.
-            // We don't need to worry about earlier update requests, as it's the
-            // only place where we're setting control flow, and events cancel wait.
-            // Note: the control flow might be set to `ControlFlow::WaitUntil` with a
-            // timestamp in the past! Take that into consideration when changing this code.
-            if let Some(next_frame_time) = main_window.next_frame_time {
-                event_loop.set_control_flow(ControlFlow::WaitUntil(next_frame_time));
+        // Gamepad events don't wake the event loop, so all that arrived since it last woke are handled.
+        let mut gamepad_events = Vec::new();
+        if let Some(gilrs) = &mut self.gilrs {
+            while let Some(Event { event, .. }) = gilrs.next_event() {
+                gamepad_events.push(event);
             }
         }
+
+        // The event loop is finished; let's find out how long we need to wait for.
+        // That's until the next frame of a movie or the next repaint of a GUI,
+        // whichever comes first, or until an event arrives if none of them wants to run.
+        // We don't need to worry about earlier update requests, as it's the
+        // only place where we're setting control flow, and events cancel wait.
+        // Note: the control flow might be set to `ControlFlow::WaitUntil` with a
+        // timestamp in the past! Take that into consideration when changing this code.
+        let mut deadline: Option<Instant> = None;
+        for main_window in self.windows.values_mut() {
+            // Gamepads aren't tied to a window, so their input goes to the focused one.
+            let window_gamepad_events = if main_window.gui.window().has_focus() {
+                mem::take(&mut gamepad_events)
+            } else {
+                Vec::new()
+            };
+            main_window.about_to_wait(window_gamepad_events);
+            main_window.gui.create_pending_viewports(event_loop);
+
+            let gamepad_poll_time = main_window
+                .next_frame_time
+                .and(self.gilrs.as_ref())
+                .filter(|gilrs| gilrs.gamepads().next().is_some())
+                // The movie's frames wake the event loop often enough for most movies;
+                // this only bounds the delay of gamepad input for those of a low frame rate.
+                .map(|_| Instant::now() + GAMEPAD_POLL_INTERVAL);
+            deadline = [
+                deadline,
+                main_window.next_frame_time,
+                // A minimized window isn't rendered, so waiting for its repaint would spin.
+                main_window
+                    .gui
+                    .next_repaint_time()
+                    .filter(|_| !main_window.minimized),
+                gamepad_poll_time,
+                main_window.long_press_deadline(),
+            ]
+            .into_iter()
+            .flatten()
+            .min();
+        }
+        event_loop.set_control_flow(match deadline {
+            Some(deadline) => ControlFlow::WaitUntil(deadline),
+            None => ControlFlow::Wait,
+        });
     }
 }
 
+fn window_icon() -> Icon {
+    let icon_bytes = include_bytes!("../assets/favicon-32.rgba");
+    Icon::from_rgba(icon_bytes.to_vec(), 32, 32).expect("App icon should be correct")
+}
+
+/// The most lines a single scroll event can scroll the movie by.
+const MAX_WHEEL_LINES: f64 = 3.0;
+
+/// The longest gamepad input may wait to be handled while a movie is playing, if one is connected.
+const GAMEPAD_POLL_INTERVAL: Duration = Duration::from_millis(50);
+
+/// How far a finger may move while it's held down for the context menu, in logical pixels.
+const LONG_PRESS_TOLERANCE: f64 = 10.0;
+
+/// A finger held down on the movie, which opens the context menu once it was held still for long enough,
+/// unless the movie used its press.
+struct LongPress {
+    deadline: Instant,
+    /// Where the finger first touched the window.
+    position: PhysicalPosition<f64>,
+}
+
 enum LoadingState {
     Loading,
     WaitingForResize,
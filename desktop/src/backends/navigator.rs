@@ -1,5 +1,5 @@
 use rfd::{MessageButtons, MessageDialog, MessageDialogResult, MessageLevel};
-use ruffle_frontend_utils::backends::navigator::NavigatorInterface;
+use ruffle_frontend_utils::backends::navigator::{DownloadProgress, NavigatorInterface};
 use std::fs::File;
 use std::io;
 use std::io::ErrorKind;
@@ -7,10 +7,9 @@ use std::path::{Path, PathBuf};
 use std::sync::{Arc, Mutex};
 use tokio::sync::oneshot;
 use url::Url;
-use winit::event_loop::EventLoopProxy;
 
 use crate::cli::{FilesystemAccessMode, OpenUrlMode};
-use crate::custom_event::RuffleEvent;
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
 use crate::gui::dialogs::filesystem_access_dialog::{
     FilesystemAccessDialogConfiguration, FilesystemAccessDialogResult,
 };
@@ -18,6 +17,7 @@ use crate::gui::dialogs::network_access_dialog::{
     NetworkAccessDialogConfiguration, NetworkAccessDialogResult,
 };
 use crate::gui::DialogDescriptor;
+use crate::player::TabId;
 use crate::preferences::GlobalPreferences;
 use crate::util::open_url;
 
@@ -78,7 +78,10 @@ pub struct DesktopNavigatorInterface {
     preferences: GlobalPreferences,
 
     // Arc + Mutex due to macOS
-    event_loop: Arc<Mutex<EventLoopProxy<RuffleEvent>>>,
+    event_loop: Arc<Mutex<RuffleEventProxy>>,
+
+    /// The tab of the movie, whose download progress is reported.
+    tab: TabId,
 
     filesystem_access_mode: FilesystemAccessMode,
 
@@ -88,13 +91,15 @@ pub struct DesktopNavigatorInterface {
 impl DesktopNavigatorInterface {
     pub fn new(
         preferences: GlobalPreferences,
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
+        tab: TabId,
         movie_path: Option<PathBuf>,
         filesystem_access_mode: FilesystemAccessMode,
     ) -> Self {
         Self {
             preferences,
             event_loop: Arc::new(Mutex::new(event_loop)),
+            tab,
             allow_list: PathAllowList::new(movie_path),
             filesystem_access_mode,
         }
@@ -191,4 +196,16 @@ impl NavigatorInterface for DesktopNavigatorInterface {
         let result = receiver.await;
         result == Ok(NetworkAccessDialogResult::Allow)
     }
+
+    fn root_movie_download_progress(&self, url: &Url, progress: DownloadProgress) {
+        let _ = self
+            .event_loop
+            .lock()
+            .expect("Non-poisoned event loop")
+            .send_event(RuffleEvent::OpenDialog(DialogDescriptor::DownloadProgress(
+                self.tab,
+                url.clone(),
+                progress,
+            )));
+    }
 }
@@ -1,5 +1,5 @@
 use crate::cli::OpenUrlMode;
-use crate::custom_event::RuffleEvent;
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
 use crate::gui::dialogs::message_dialog::MessageDialogConfiguration;
 use crate::gui::{DialogDescriptor, FilePicker, LocalizableText};
 use crate::preferences::GlobalPreferences;
@@ -21,9 +21,8 @@ use std::rc::Rc;
 use std::sync::Arc;
 use tokio::io::AsyncReadExt;
 use url::Url;
-use winit::event_loop::EventLoopProxy;
 use winit::raw_window_handle::HasDisplayHandle;
-use winit::window::{Fullscreen, Window};
+use winit::window::Window;
 
 pub struct DesktopFileDialogResult {
     handle: Option<FileHandle>,
@@ -145,9 +144,11 @@ impl FileDialogResult for DesktopFileDialogResult {
 }
 
 pub struct DesktopUiBackend {
-    window: Arc<Window>,
-    event_loop: EventLoopProxy<RuffleEvent>,
+    event_loop: RuffleEventProxy,
     cursor_visible: bool,
+    /// Whether the mouse is locked, as last requested by the movie or reported by the window,
+    /// see [`DesktopUiBackend::set_mouse_locked`].
+    mouse_locked: bool,
     clipboard: Clipboard,
     preferences: GlobalPreferences,
     preferred_cursor: MouseCursor,
@@ -158,24 +159,18 @@ pub struct DesktopUiBackend {
 impl DesktopUiBackend {
     pub fn new(
         window: Arc<Window>,
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
         font_database: Rc<fontdb::Database>,
         preferences: GlobalPreferences,
         file_picker: FilePicker,
     ) -> Result<Self, Error> {
         // The window handle is only relevant to linux/wayland
         // If it fails it'll fallback to x11 or wlr-data-control
-        let clipboard = Clipboard::new(
-            window
-                .clone()
-                .display_handle()
-                .ok()
-                .map(|handle| handle.as_raw()),
-        );
+        let clipboard = Clipboard::new(window.display_handle().ok().map(|handle| handle.as_raw()));
         Ok(Self {
-            window,
             event_loop,
             cursor_visible: true,
+            mouse_locked: false,
             clipboard,
             preferences,
             preferred_cursor: MouseCursor::Arrow,
@@ -184,6 +179,13 @@ impl DesktopUiBackend {
         })
     }
 
+    /// Updates whether the mouse is locked, after the window locked or released it.
+    ///
+    /// The lock may fail, or be released by the user at any time, which the movie should know about.
+    pub fn set_mouse_locked(&mut self, locked: bool) {
+        self.mouse_locked = locked;
+    }
+
     pub fn cursor(&self) -> egui::CursorIcon {
         if self.cursor_visible {
             match self.preferred_cursor {
@@ -207,6 +209,18 @@ impl UiBackend for DesktopUiBackend {
         self.cursor_visible = visible;
     }
 
+    fn set_mouse_lock(&mut self, locked: bool) {
+        // Assume the lock succeeds until the window says otherwise.
+        self.mouse_locked = locked;
+        let _ = self
+            .event_loop
+            .send_event(RuffleEvent::SetMouseLock(locked));
+    }
+
+    fn mouse_locked(&self) -> bool {
+        self.mouse_locked
+    }
+
     fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
         self.preferred_cursor = cursor;
     }
@@ -220,11 +234,10 @@ impl UiBackend for DesktopUiBackend {
     }
 
     fn set_fullscreen(&mut self, is_full: bool) -> Result<(), FullscreenError> {
-        self.window.set_fullscreen(if is_full {
-            Some(Fullscreen::Borderless(None))
-        } else {
-            None
-        });
+        // The GUI has to move the movie view along with the menu, so it changes the window.
+        let _ = self
+            .event_loop
+            .send_event(RuffleEvent::SetFullscreen(is_full));
         Ok(())
     }
 
@@ -6,6 +6,7 @@ use ruffle_core::backend::navigator::SocketMode;
 use ruffle_core::config::Letterbox;
 use ruffle_core::events::{GamepadButton, KeyCode};
 use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
+use ruffle_frontend_utils::player_options::PlayerVersion;
 use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use std::path::Path;
@@ -179,9 +180,10 @@ pub struct Opt {
     #[clap(long)]
     pub cookie: Option<String>,
 
-    /// The version of the player to emulate
-    #[clap(long)]
-    pub player_version: Option<u8>,
+    /// The version of the player to emulate, either a major version such as 32
+    /// or a full one such as 32,0,0,465
+    #[clap(long, value_parser(parse_player_version))]
+    pub player_version: Option<PlayerVersion>,
 
     /// The runtime to emulate (Flash Player or Adobe AIR)
     #[clap(long)]
@@ -195,6 +197,13 @@ pub struct Opt {
     #[clap(long)]
     pub open_url_mode: Option<OpenUrlMode>,
 
+    /// When right-clicking the movie opens the context menu,
+    /// for movies that use right clicks themselves.
+    ///
+    /// This option temporarily overrides any stored preference.
+    #[clap(long)]
+    pub context_menu: Option<ContextMenuMode>,
+
     /// How to handle non-interactive filesystem access.
     #[clap(long, default_value = "ask")]
     pub filesystem_access_mode: FilesystemAccessMode,
@@ -209,6 +218,14 @@ pub struct Opt {
     #[clap(long)]
     pub no_gui: bool,
 
+    /// Makes the window transparent where the movie has no content, like wmode=transparent in a browser.
+    /// The movie's background color is ignored, unless it's fullscreen.
+    ///
+    /// This requires a compositor that supports transparent windows;
+    /// without one, the window is opaque as usual.
+    #[clap(long)]
+    pub transparent: bool,
+
     /// Remaps a specific button on a gamepad to a keyboard key.
     /// This can be used to add new gamepad support to existing games, for example mapping
     /// the D-pad to the arrow keys with -B d-pad-up=up -B d-pad-down=down etc.
@@ -259,6 +276,11 @@ fn parse_align(value: &str) -> Result<StageAlign, Error> {
         .map_err(|_| anyhow::anyhow!("Invalid stage alignment"))
 }
 
+fn parse_player_version(value: &str) -> Result<PlayerVersion, Error> {
+    crate::preferences::parse_player_version(value)
+        .ok_or_else(|| anyhow!("Invalid player version, expected one such as 32 or 32,0,0,465"))
+}
+
 fn parse_gamepad_button(mapping: &str) -> Result<(GamepadButton, KeyCode), Error> {
     let pos = mapping.find('=').ok_or_else(|| {
         anyhow!("invalid <gamepad button>=<key name>: no `=` found in `{mapping}`")
@@ -368,6 +390,40 @@ impl FromStr for OpenUrlMode {
     }
 }
 
+/// When right-clicking the movie opens the context menu.
+#[derive(Default, Debug, Copy, Clone, PartialEq, Eq, clap::ValueEnum)]
+pub enum ContextMenuMode {
+    /// Right clicks that the movie doesn't use open the menu.
+    #[default]
+    Shown,
+    /// Right clicks always go to the movie, and only Ctrl+right-click opens the menu.
+    CtrlClick,
+    /// Right clicks always go to the movie, and the menu never opens.
+    Disabled,
+}
+
+impl ContextMenuMode {
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            ContextMenuMode::Shown => None,
+            ContextMenuMode::CtrlClick => Some("ctrl-click"),
+            ContextMenuMode::Disabled => Some("disabled"),
+        }
+    }
+}
+
+impl FromStr for ContextMenuMode {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "ctrl-click" => Ok(ContextMenuMode::CtrlClick),
+            "disabled" => Ok(ContextMenuMode::Disabled),
+            _ => Err(()),
+        }
+    }
+}
+
 // TODO The following enum exists in order to preserve
 //   the behavior of mapping gamepad buttons,
 //   We should probably do something smarter here.
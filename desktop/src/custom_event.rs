@@ -2,13 +2,21 @@
 
 use ruffle_core::events::PlayerNotification;
 
-use crate::{gui::DialogDescriptor, player::LaunchOptions};
+use crate::{
+    gui::{DialogDescriptor, GraphicsContext, MissingFonts},
+    player::{LaunchOptions, TabId},
+};
+use winit::event_loop::{EventLoopClosed, EventLoopProxy};
+use winit::window::WindowId;
 
 /// User-defined events.
 pub enum RuffleEvent {
     /// Indicates that one or more tasks are ready to poll on our executor.
     TaskPoll,
 
+    /// Indicates that the graphics device has been created, or that it failed to be.
+    GraphicsReady(Result<Box<GraphicsContext>, String>),
+
     /// Indicates that an asynchronous SWF metadata load has been completed.
     OnMetadata(ruffle_core::swf::HeaderExt),
 
@@ -18,16 +26,79 @@ pub enum RuffleEvent {
     /// The user requested to open a movie.
     Open(url::Url, Box<LaunchOptions>),
 
+    /// The user requested to restart the current movie, opening it again with the same options.
+    ReloadMovie,
+
+    /// The file of the open movie changed, while it's reloaded automatically.
+    MovieFileChanged(std::path::PathBuf),
+
+    /// The user toggled reloading the movie automatically when its file changes.
+    SetAutoReload(bool),
+
+    /// The user toggled the timeline bar for seeking through the movie.
+    SetTimelineBar(bool),
+
     /// The user requested to close the current SWF.
     CloseFile,
 
+    /// The user cancelled the download of the movie in the given tab, closing it.
+    CancelDownload(TabId),
+
+    /// The user selected the tab at the given index.
+    SelectTab(usize),
+
+    /// The user requested to close the tab at the given index.
+    CloseTab(usize),
+
+    /// The user requested to open another window, with a player of its own.
+    NewWindow,
+
+    /// The user requested to pause the movie and advance it by the given number of frames.
+    StepFrames(u32),
+
+    /// The user requested to resize the window, so that the movie is shown at the given zoom.
+    ResizeToMovie(f64),
+
+    /// The user toggled keeping the window at the aspect ratio of the movie.
+    SetAspectRatioLock(bool),
+
+    /// The user toggled showing the movie in a small window that stays on top of the others.
+    SetPictureInPicture(bool),
+
+    /// The movie or the user toggled locking the mouse pointer in place.
+    SetMouseLock(bool),
+
+    /// The movie entered or left full screen, or the window has to move to the monitor it's set to use.
+    SetFullscreen(bool),
+
     /// The user requested to enter full screen.
     EnterFullScreen,
 
     /// The user requested to exit full screen.
     ExitFullScreen,
 
-    /// The user requested to exit Ruffle.
+    /// The user requested to pick a folder and then record the movie to it.
+    BrowseAndRecord,
+
+    /// The user requested to record the movie to a folder.
+    StartRecording(std::path::PathBuf),
+
+    /// The user requested to stop recording the movie.
+    StopRecording,
+
+    /// The user requested to reset the zoom and pan of the movie view.
+    ResetZoom,
+
+    /// The user requested to zoom the movie view in or out by the given factor, around its center.
+    ZoomView(f64),
+
+    /// The user requested to copy the current frame of the movie to the clipboard.
+    CopyFrameToClipboard,
+
+    /// The user requested to open the movie at the URL on the clipboard.
+    OpenFromClipboard,
+
+    /// The user requested to exit Ruffle, or, when sent by a movie, to close its tab.
     ExitRequested,
 
     /// The user selected an item in the right-click context menu.
@@ -36,6 +107,55 @@ pub enum RuffleEvent {
     /// The movie wants to open a dialog.
     OpenDialog(DialogDescriptor),
 
+    /// The fonts needed by the GUI language couldn't be found.
+    MissingFonts(MissingFonts),
+
     /// Ruffle core has a notification to handle.
     PlayerNotification(PlayerNotification),
 }
+
+/// A [`RuffleEvent`] along with the window it's meant for,
+/// and the tab whose movie sent it, if it didn't come from the GUI.
+pub struct WindowRuffleEvent {
+    pub window_id: WindowId,
+    pub tab: Option<TabId>,
+    pub event: RuffleEvent,
+}
+
+/// Sends [`RuffleEvent`]s to the event loop on behalf of a single window,
+/// so that they're handled by that window's player and GUI.
+#[derive(Clone)]
+pub struct RuffleEventProxy {
+    proxy: EventLoopProxy<WindowRuffleEvent>,
+    window_id: WindowId,
+    tab: Option<TabId>,
+}
+
+impl RuffleEventProxy {
+    pub fn new(proxy: EventLoopProxy<WindowRuffleEvent>, window_id: WindowId) -> Self {
+        Self {
+            proxy,
+            window_id,
+            tab: None,
+        }
+    }
+
+    /// Returns a proxy for the movie in the given tab of the same window,
+    /// whose events are tagged with that tab.
+    pub fn for_tab(&self, tab: TabId) -> Self {
+        Self {
+            tab: Some(tab),
+            ..self.clone()
+        }
+    }
+
+    pub fn send_event(&self, event: RuffleEvent) -> Result<(), EventLoopClosed<RuffleEvent>> {
+        self.proxy
+            .send_event(WindowRuffleEvent {
+                window_id: self.window_id,
+                tab: self.tab,
+                event,
+            })
+            .map_err(|EventLoopClosed(event)| EventLoopClosed(event.event))
+    }
+}
@@ -1,50 +1,94 @@
 mod context_menu;
 mod controller;
 pub mod dialogs;
+mod drop_indicator;
+mod fullscreen;
+mod fullscreen_hint;
+mod key_remap;
 mod locale;
+mod long_press;
 mod menu_bar;
 mod movie;
+mod movie_watcher;
+mod notification;
 mod picker;
+mod picture_in_picture;
+mod recorder;
+mod shortcut;
+mod tab_strip;
 mod theme;
+mod timeline_bar;
+mod title;
+mod viewport;
 mod widgets;
 
-pub use controller::GuiController;
+pub use controller::{
+    available_backends, fit_to_monitors, min_window_size, GraphicsContext, GuiController,
+    MissingFonts,
+};
 pub use dialogs::DialogDescriptor;
+pub use fullscreen::{
+    available_video_modes, fullscreen_mode, FullscreenMonitor, VideoModePreference,
+};
+pub use key_remap::{physical_key_to_egui, KeyRemaps};
 pub use locale::available_languages;
 pub use locale::optional_text;
 pub use locale::text;
 pub use locale::text_with_args;
 pub use locale::LocalizableText;
-pub use movie::MovieView;
-pub use picker::FilePicker;
+pub use menu_bar::MenuVisibility;
+pub use movie::{BackgroundColor, MovieView};
+pub use picker::{FilePicker, MOVIE_EXTENSIONS};
+pub use shortcut::Shortcut;
 pub use theme::ThemePreference;
+pub use title::metadata_title;
 
-use crate::custom_event::RuffleEvent;
+use crate::custom_event::RuffleEventProxy;
 use crate::gui::context_menu::ContextMenu;
 use crate::player::LaunchOptions;
 use crate::preferences::GlobalPreferences;
 use dialogs::Dialogs;
+use drop_indicator::DropIndicator;
 use egui::*;
+use fluent_templates::fluent_bundle::FluentValue;
+use fullscreen_hint::FullscreenExitHint;
+use long_press::LongPressCue;
 use menu_bar::MenuBar;
+use notification::Notification;
 use rfd::AsyncFileDialog;
 use ruffle_core::debug_ui::Message as DebugMessage;
 use ruffle_core::{Player, PlayerEvent};
+use std::collections::HashMap;
 use std::sync::{MutexGuard, Weak};
 use std::{fs, mem};
+use tab_strip::TabStrip;
+use timeline_bar::TimelineBar;
+use unic_langid::LanguageIdentifier;
 use url::Url;
-use winit::event_loop::EventLoopProxy;
 
 /// Size of the top menu bar in pixels.
 /// This is the offset at which the movie will be shown,
 /// and added to the window size if trying to match a movie.
 pub const MENU_HEIGHT: u32 = 24;
 
+/// Size of the tab strip below the menu bar in pixels, while it's shown.
+pub const TAB_STRIP_HEIGHT: u32 = 24;
+
+/// Size of the timeline bar below the menu bar and tab strip in pixels, while it's shown.
+pub const TIMELINE_BAR_HEIGHT: u32 = 28;
+
 /// The main controller for the Ruffle GUI.
 pub struct RuffleGui {
-    event_loop: EventLoopProxy<RuffleEvent>,
+    event_loop: RuffleEventProxy,
     context_menu: Option<ContextMenu>,
     dialogs: Dialogs,
     menu_bar: MenuBar,
+    tab_strip: TabStrip,
+    timeline_bar: TimelineBar,
+    fullscreen_exit_hint: FullscreenExitHint,
+    long_press_cue: LongPressCue,
+    drop_indicator: DropIndicator,
+    notification: Notification,
 
     was_suspended_before_debug: bool,
     preferences: GlobalPreferences,
@@ -53,10 +97,11 @@ pub struct RuffleGui {
 impl RuffleGui {
     fn new(
         window: Weak<winit::window::Window>,
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
         default_path: Option<Url>,
         default_launch_options: LaunchOptions,
         preferences: GlobalPreferences,
+        file_picker: FilePicker,
     ) -> Self {
         Self {
             was_suspended_before_debug: false,
@@ -68,12 +113,20 @@ impl RuffleGui {
                 default_path,
                 window.clone(),
                 event_loop.clone(),
+                file_picker,
             ),
             menu_bar: MenuBar::new(
+                window,
                 event_loop.clone(),
                 default_launch_options,
                 preferences.clone(),
             ),
+            tab_strip: Default::default(),
+            timeline_bar: Default::default(),
+            fullscreen_exit_hint: Default::default(),
+            long_press_cue: Default::default(),
+            drop_indicator: Default::default(),
+            notification: Default::default(),
 
             event_loop,
             preferences,
@@ -85,20 +138,60 @@ impl RuffleGui {
         &mut self,
         egui_ctx: &egui::Context,
         show_menu: bool,
+        show_tab_strip: bool,
+        show_timeline_bar: bool,
         mut player: Option<&mut Player>,
         menu_height_offset: f64,
+        is_recording: bool,
+        zoom: f64,
     ) {
         let locale = self.preferences.language();
 
         self.menu_bar
             .consume_shortcuts(egui_ctx, &mut self.dialogs, player.as_deref_mut());
         if show_menu {
-            self.menu_bar
-                .show(&locale, egui_ctx, &mut self.dialogs, player.as_deref_mut());
+            self.menu_bar.show(
+                &locale,
+                egui_ctx,
+                &mut self.dialogs,
+                player.as_deref_mut(),
+                is_recording,
+                zoom,
+            );
+        }
+        if show_tab_strip {
+            self.tab_strip.show(&locale, egui_ctx, &self.event_loop);
+        }
+        if show_timeline_bar {
+            self.timeline_bar
+                .show(&locale, egui_ctx, player.as_deref_mut());
         }
 
         self.dialogs.show(&locale, egui_ctx, player.as_deref_mut());
 
+        let is_fullscreen = player
+            .as_deref()
+            .is_some_and(|player| player.is_fullscreen());
+        if self.fullscreen_exit_hint.update(is_fullscreen) {
+            if let Some(player) = player.as_deref_mut() {
+                player.set_fullscreen(false);
+            }
+        } else if is_fullscreen && self.preferences.fullscreen_hold_to_exit() {
+            let shortcut = self.preferences.fullscreen_exit_shortcut();
+            self.fullscreen_exit_hint.show(
+                &locale,
+                egui_ctx,
+                &egui_ctx.format_shortcut(&shortcut.0),
+            );
+        }
+
+        self.long_press_cue.show(egui_ctx);
+        self.drop_indicator.show(&locale, egui_ctx);
+        self.notification.show(&locale, egui_ctx);
+        if zoom != 1.0 {
+            show_zoom_indicator(&locale, egui_ctx, zoom);
+        }
+
         if let Some(player) = player {
             let was_suspended = player.debug_ui().should_suspend_player();
             player.show_debug_ui(egui_ctx, menu_height_offset);
@@ -138,13 +231,22 @@ impl RuffleGui {
         };
     }
 
+    pub fn toggle_mute(&mut self, player: Option<&mut Player>) {
+        self.dialogs
+            .volume_controls
+            .toggle_mute(player, &self.preferences);
+    }
+
+    /// Opens the context menu at the given position, or at the pointer if there's none.
     pub fn show_context_menu(
         &mut self,
         menu: Vec<ruffle_core::ContextMenuItem>,
+        zoom: bool,
+        position: Option<Pos2>,
         close_event: PlayerEvent,
     ) {
         if !menu.is_empty() {
-            self.context_menu = Some(ContextMenu::new(menu, close_event));
+            self.context_menu = Some(ContextMenu::new(menu, zoom, position, close_event));
         }
     }
 
@@ -158,6 +260,35 @@ impl RuffleGui {
         self.context_menu.is_some()
     }
 
+    /// Briefly shows the given message at the bottom of the window.
+    pub fn show_notification(&mut self, message: &'static str) {
+        self.notification.set(message);
+    }
+
+    /// Opens the movie at the URL in the given text, such as text pasted by the user.
+    ///
+    /// If it isn't the URL of a movie, the open dialog is shown with the text so it can be corrected.
+    pub fn open_url_text(&mut self, text: &str) {
+        self.menu_bar.open_url_text(text, &mut self.dialogs);
+    }
+
+    /// Shows that dropping the files being dragged over the window opens them, or stops showing it.
+    pub fn set_files_hovered(&mut self, hovered: bool) {
+        self.drop_indicator.set_visible(hovered);
+    }
+
+    pub fn begin_fullscreen_exit_hold(&mut self) {
+        self.fullscreen_exit_hint.begin_hold();
+    }
+
+    pub fn end_fullscreen_exit_hold(&mut self) {
+        self.fullscreen_exit_hint.end_hold();
+    }
+
+    pub fn show_long_press_cue(&mut self, position: Pos2) {
+        self.long_press_cue.begin(position);
+    }
+
     /// Notifies the GUI that the player has been destroyed.
     fn on_player_destroyed(&mut self) {
         self.dialogs.close_dialogs_with_notifiers();
@@ -179,3 +310,20 @@ impl RuffleGui {
         player.set_volume(self.dialogs.volume_controls.get_volume());
     }
 }
+
+/// Shows how much the movie view is zoomed in, in the bottom right corner.
+fn show_zoom_indicator(locale: &LanguageIdentifier, egui_ctx: &Context, zoom: f64) {
+    Area::new(Id::new("zoom_indicator"))
+        .order(Order::Foreground)
+        .anchor(Align2::RIGHT_BOTTOM, vec2(-8.0, -8.0))
+        .interactable(false)
+        .show(egui_ctx, |ui| {
+            Frame::popup(ui.style()).show(ui, |ui| {
+                ui.label(text_with_args(
+                    locale,
+                    "view-zoom-indicator",
+                    &HashMap::from([("percent".into(), FluentValue::from((zoom * 100.0).round()))]),
+                ));
+            });
+        });
+}
@@ -17,13 +17,22 @@ fn srgb_to_linear(srgb: vec4<f32>) -> vec4<f32> {
     return vec4<f32>(mix(a, b, c) * srgb.a, srgb.a);
 }
 
+/// The zoom and pan applied to the movie, see `ViewTransform`.
+struct Transform {
+    offset: vec2<f32>,
+    scale: f32,
+    _padding: f32,
+};
+
+@group(0) @binding(2) var<uniform> transform: Transform;
+
 @vertex
 fn vs_main(
     @location(0) a_pos: vec2<f32>,
     @location(1) a_tex_coord: vec2<f32>,
 ) -> VertexOutput {
     var out: VertexOutput;
-    out.tex_coord = a_tex_coord;
+    out.tex_coord = a_tex_coord / transform.scale + transform.offset;
     out.position = vec4<f32>(a_pos, 0.0, 1.0);
     return out;
 }
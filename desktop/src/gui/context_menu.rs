@@ -1,26 +1,80 @@
-use crate::custom_event::RuffleEvent;
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
 use egui::{
     vec2, Align, Area, Button, Checkbox, Color32, Frame, Id, Key, KeyboardShortcut, Layout,
-    Modifiers, Order, Pos2, Stroke, Style, Widget,
+    Modifiers, Order, Pos2, Response, Shape, Stroke, Style, Ui, Widget,
 };
 use ruffle_core::{ContextMenuItem, PlayerEvent};
 use unic_langid::LanguageIdentifier;
-use winit::event_loop::EventLoopProxy;
 
 use super::text;
 
+/// How much the movie view is zoomed in or out by the zoom items of the menu.
+const MENU_ZOOM_FACTOR: f64 = 2.0;
+
+/// An entry of the context menu itself.
+enum Entry {
+    /// The item with the given index.
+    Item(usize),
+    /// A submenu holding consecutive items grouped under the same caption.
+    Submenu { caption: String, items: Vec<usize> },
+}
+
+impl Entry {
+    fn is_enabled(&self, items: &[ContextMenuItem]) -> bool {
+        match self {
+            Entry::Item(index) => items[*index].enabled,
+            Entry::Submenu { items: indices, .. } => indices.iter().any(|i| items[*i].enabled),
+        }
+    }
+}
+
 pub struct ContextMenu {
     items: Vec<ContextMenuItem>,
+    entries: Vec<Entry>,
+    /// Whether the zoom items are shown after those of the movie.
+    zoom: bool,
     position: Option<Pos2>,
     close_event: PlayerEvent,
+    /// The entry whose submenu is open.
+    open_submenu: Option<usize>,
+    /// The entry highlighted by the pointer or the arrow keys.
+    highlighted: Option<usize>,
+    /// The position in the open submenu of the item highlighted by the pointer or the arrow keys.
+    ///
+    /// While it's set, the arrow keys move through the submenu rather than the menu itself.
+    submenu_highlighted: Option<usize>,
 }
 
 impl ContextMenu {
-    pub fn new(items: Vec<ContextMenuItem>, close_event: PlayerEvent) -> Self {
+    pub fn new(
+        items: Vec<ContextMenuItem>,
+        zoom: bool,
+        position: Option<Pos2>,
+        close_event: PlayerEvent,
+    ) -> Self {
+        let mut entries: Vec<Entry> = Vec::new();
+        for (i, item) in items.iter().enumerate() {
+            match (&item.submenu, entries.last_mut()) {
+                (Some(submenu), Some(Entry::Submenu { caption, items })) if caption == submenu => {
+                    items.push(i);
+                }
+                (Some(submenu), _) => entries.push(Entry::Submenu {
+                    caption: submenu.clone(),
+                    items: vec![i],
+                }),
+                (None, _) => entries.push(Entry::Item(i)),
+            }
+        }
+
         Self {
             items,
-            position: None,
+            entries,
+            zoom,
+            position,
             close_event,
+            open_submenu: None,
+            highlighted: None,
+            submenu_highlighted: None,
         }
     }
 
@@ -32,12 +86,41 @@ impl ContextMenu {
         &mut self,
         locale: &LanguageIdentifier,
         egui_ctx: &egui::Context,
-        event_loop: &EventLoopProxy<RuffleEvent>,
+        event_loop: &RuffleEventProxy,
         fullscreen: bool,
     ) -> bool {
-        let mut item_clicked = false;
+        let outcome = self.ui(locale, egui_ctx, fullscreen);
+        if let Some(index) = outcome.clicked_item {
+            let _ = event_loop.send_event(RuffleEvent::ContextMenuItemClicked(index));
+        }
+        if let Some(event) = outcome.desktop_event {
+            let _ = event_loop.send_event(event);
+        }
+        !outcome.close
+    }
+
+    /// Shows the menu for a frame, and returns what was done with it.
+    fn ui(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        fullscreen: bool,
+    ) -> MenuOutcome {
+        let mut clicked_item = None;
+        // An item handled by the desktop rather than the movie was chosen.
+        let mut desktop_event = None;
+        let mut close_menu = false;
         self.position = self.position.or(egui_ctx.pointer_latest_pos());
 
+        match self.handle_keyboard(egui_ctx) {
+            KeyboardAction::None => {}
+            KeyboardAction::Activate(index) => clicked_item = Some(index),
+            KeyboardAction::Close => close_menu = true,
+        }
+
+        let pointer_moved = egui_ctx.input(|input| input.pointer.is_moving());
+        let mut submenu_rect = None;
+
         let area = Area::new(Id::new("context_menu"))
             .order(Order::Foreground)
             .fixed_pos(self.position.unwrap_or_default())
@@ -48,22 +131,74 @@ impl ContextMenu {
                 Frame::menu(ui.style()).show(ui, |ui| {
                     ui.set_max_width(150.0);
                     ui.with_layout(Layout::top_down_justified(Align::Min), |ui| {
-                        for (i, item) in self.items.iter().enumerate() {
-                            if i != 0 && item.separator_before {
-                                ui.separator();
+                        for (entry_index, entry) in self.entries.iter().enumerate() {
+                            let highlighted = self.highlighted == Some(entry_index);
+                            match entry {
+                                Entry::Item(i) => {
+                                    let item = &self.items[*i];
+                                    if *i != 0 && item.separator_before {
+                                        ui.separator();
+                                    }
+                                    let response = item_ui(ui, item, highlighted);
+                                    match item_click(ui, item, *i, &response) {
+                                        Some(ItemClick::Chosen(index)) => {
+                                            clicked_item = Some(index)
+                                        }
+                                        Some(ItemClick::Dismissed) => close_menu = true,
+                                        None => {}
+                                    }
+                                    if response.hovered() && pointer_moved {
+                                        self.highlighted = Some(entry_index);
+                                        self.open_submenu = None;
+                                        self.submenu_highlighted = None;
+                                    }
+                                }
+                                Entry::Submenu { caption, items } => {
+                                    if items[0] != 0 && self.items[items[0]].separator_before {
+                                        ui.separator();
+                                    }
+                                    let is_open = self.open_submenu == Some(entry_index);
+                                    let button = Button::new(caption)
+                                        .shortcut_text("⏵")
+                                        .selected(is_open)
+                                        .wrap_mode(egui::TextWrapMode::Extend);
+                                    let response = highlight_ui(ui, highlighted, |ui| {
+                                        ui.add_enabled(entry.is_enabled(&self.items), button)
+                                    });
+                                    if (response.hovered() && pointer_moved) || response.clicked() {
+                                        self.highlighted = Some(entry_index);
+                                        if !is_open {
+                                            self.open_submenu = Some(entry_index);
+                                            self.submenu_highlighted = None;
+                                        }
+                                    }
+                                    if self.open_submenu == Some(entry_index) {
+                                        submenu_rect = Some(response.rect);
+                                    }
+                                }
                             }
-                            let clicked = if item.checked {
-                                Checkbox::new(&mut true, &item.caption).ui(ui).clicked()
-                            } else {
-                                let button = Button::new(&item.caption)
-                                    .wrap_mode(egui::TextWrapMode::Extend);
-
-                                ui.add_enabled(item.enabled, button).clicked()
-                            };
-                            if clicked {
-                                let _ =
-                                    event_loop.send_event(RuffleEvent::ContextMenuItemClicked(i));
-                                item_clicked = true;
+                        }
+
+                        if self.zoom {
+                            ui.separator();
+                            for (caption, event) in [
+                                (
+                                    "context-menu-zoom-in",
+                                    RuffleEvent::ZoomView(MENU_ZOOM_FACTOR),
+                                ),
+                                (
+                                    "context-menu-zoom-out",
+                                    RuffleEvent::ZoomView(1.0 / MENU_ZOOM_FACTOR),
+                                ),
+                                ("context-menu-show-all", RuffleEvent::ResetZoom),
+                            ] {
+                                if Button::new(text(locale, caption))
+                                    .wrap_mode(egui::TextWrapMode::Extend)
+                                    .ui(ui)
+                                    .clicked()
+                                {
+                                    desktop_event = Some(event);
+                                }
                             }
                         }
 
@@ -78,20 +213,225 @@ impl ContextMenu {
                                 .ui(ui)
                                 .clicked()
                             {
-                                let _ = event_loop.send_event(RuffleEvent::ExitFullScreen);
-                                item_clicked = true;
+                                desktop_event = Some(RuffleEvent::ExitFullScreen);
                             }
                         }
                     })
                 })
             });
 
-        let should_close = item_clicked
-            || area.response.clicked_elsewhere()
-            || egui_ctx.input_mut(|input| input.consume_key(Modifiers::NONE, Key::Escape));
+        let mut clicked_elsewhere = area.response.clicked_elsewhere();
+        if let (Some(rect), Some(Entry::Submenu { items, .. })) = (
+            submenu_rect,
+            self.open_submenu.and_then(|i| self.entries.get(i)),
+        ) {
+            let submenu_area = Area::new(Id::new("context_submenu"))
+                .order(Order::Foreground)
+                .fixed_pos(rect.right_top())
+                .constrain_to(egui_ctx.screen_rect())
+                .interactable(true)
+                .show(egui_ctx, |ui| {
+                    set_menu_style(ui.style_mut());
+                    Frame::menu(ui.style()).show(ui, |ui| {
+                        ui.set_max_width(150.0);
+                        ui.with_layout(Layout::top_down_justified(Align::Min), |ui| {
+                            for (position, i) in items.iter().enumerate() {
+                                let item = &self.items[*i];
+                                if position != 0 && item.separator_before {
+                                    ui.separator();
+                                }
+                                let highlighted = self.submenu_highlighted == Some(position);
+                                let response = item_ui(ui, item, highlighted);
+                                match item_click(ui, item, *i, &response) {
+                                    Some(ItemClick::Chosen(index)) => clicked_item = Some(index),
+                                    Some(ItemClick::Dismissed) => close_menu = true,
+                                    None => {}
+                                }
+                                if response.hovered() && pointer_moved {
+                                    self.submenu_highlighted = Some(position);
+                                }
+                            }
+                        })
+                    })
+                });
+            clicked_elsewhere &= submenu_area.response.clicked_elsewhere();
+        }
 
-        !should_close
+        let close =
+            clicked_item.is_some() || desktop_event.is_some() || clicked_elsewhere || close_menu;
+        MenuOutcome {
+            clicked_item,
+            desktop_event,
+            close,
+        }
     }
+
+    /// Moves the highlight with the arrow keys, opening and closing submenus with the right and left ones.
+    fn handle_keyboard(&mut self, egui_ctx: &egui::Context) -> KeyboardAction {
+        let [up, down, left, right, enter, escape] = egui_ctx.input_mut(|input| {
+            [
+                Key::ArrowUp,
+                Key::ArrowDown,
+                Key::ArrowLeft,
+                Key::ArrowRight,
+                Key::Enter,
+                Key::Escape,
+            ]
+            .map(|key| input.consume_key(Modifiers::NONE, key))
+        });
+
+        let submenu_items = match self.open_submenu.and_then(|i| self.entries.get(i)) {
+            Some(Entry::Submenu { items, .. }) => Some(items),
+            _ => None,
+        };
+
+        if let (Some(items), Some(position)) = (submenu_items, self.submenu_highlighted) {
+            if up || down {
+                self.submenu_highlighted = step(Some(position), items.len(), down, |position| {
+                    self.items[items[position]].enabled
+                });
+            } else if left || escape {
+                self.open_submenu = None;
+                self.submenu_highlighted = None;
+            } else if enter && self.items[items[position]].enabled {
+                return KeyboardAction::Activate(items[position]);
+            }
+            return KeyboardAction::None;
+        }
+
+        if escape {
+            // Escape closes an open submenu before closing the menu itself.
+            if self.open_submenu.take().is_some() {
+                return KeyboardAction::None;
+            }
+            return KeyboardAction::Close;
+        }
+        if up || down {
+            self.highlighted = step(self.highlighted, self.entries.len(), down, |i| {
+                self.entries[i].is_enabled(&self.items)
+            });
+            self.open_submenu = None;
+        } else if right || enter {
+            match self.highlighted.and_then(|i| self.entries.get(i)) {
+                Some(Entry::Submenu { items, .. }) => {
+                    self.open_submenu = self.highlighted;
+                    self.submenu_highlighted = step(None, items.len(), true, |position| {
+                        self.items[items[position]].enabled
+                    });
+                }
+                Some(Entry::Item(index)) if enter && self.items[*index].enabled => {
+                    return KeyboardAction::Activate(*index);
+                }
+                _ => {}
+            }
+        }
+        KeyboardAction::None
+    }
+}
+
+/// What was done with the menu in a frame.
+struct MenuOutcome {
+    /// The item of the movie that was chosen.
+    clicked_item: Option<usize>,
+    /// The event of an item handled by the desktop rather than the movie, if one was chosen.
+    desktop_event: Option<RuffleEvent>,
+    /// Whether the menu is closed, either by choosing an item or by dismissing it.
+    close: bool,
+}
+
+enum KeyboardAction {
+    None,
+    /// The item with the given index was chosen with the Enter key.
+    Activate(usize),
+    Close,
+}
+
+/// Moves a highlight to the next or previous enabled one of `count` entries, wrapping around.
+///
+/// If none of them is enabled, the highlight stays where it is.
+fn step(
+    current: Option<usize>,
+    count: usize,
+    forward: bool,
+    is_enabled: impl Fn(usize) -> bool,
+) -> Option<usize> {
+    let mut index = current;
+    for _ in 0..count {
+        let next = match (index, forward) {
+            (None, true) => 0,
+            (None, false) => count - 1,
+            (Some(i), true) => (i + 1) % count,
+            (Some(i), false) => (i + count - 1) % count,
+        };
+        if is_enabled(next) {
+            return Some(next);
+        }
+        index = Some(next);
+    }
+    current
+}
+
+/// What clicking on an item of the menu does.
+#[derive(Debug, PartialEq, Eq)]
+enum ItemClick {
+    /// The item with the given index is chosen.
+    Chosen(usize),
+    /// The item is disabled, so the click only dismisses the menu, like a click outside of it.
+    Dismissed,
+}
+
+impl ItemClick {
+    fn new(item: &ContextMenuItem, index: usize) -> Self {
+        if item.enabled {
+            Self::Chosen(index)
+        } else {
+            Self::Dismissed
+        }
+    }
+}
+
+/// Returns what the item was clicked for, if it was clicked.
+fn item_click(
+    ui: &Ui,
+    item: &ContextMenuItem,
+    index: usize,
+    response: &Response,
+) -> Option<ItemClick> {
+    // Disabled widgets don't report being clicked, so the pointer is checked directly.
+    let clicked = response.clicked()
+        || (ui.rect_contains_pointer(response.rect)
+            && ui.input(|input| input.pointer.primary_clicked()));
+    clicked.then(|| ItemClick::new(item, index))
+}
+
+/// Adds an item, with a checkmark if it's checked and grayed out if it's disabled.
+fn item_ui(ui: &mut Ui, item: &ContextMenuItem, highlighted: bool) -> Response {
+    highlight_ui(ui, highlighted, |ui| {
+        if item.checked {
+            ui.add_enabled(item.enabled, Checkbox::new(&mut true, &item.caption))
+        } else {
+            let button = Button::new(&item.caption).wrap_mode(egui::TextWrapMode::Extend);
+            ui.add_enabled(item.enabled, button)
+        }
+    })
+}
+
+/// Adds a menu entry, with the background of a hovered one if it's highlighted with the keyboard.
+fn highlight_ui(
+    ui: &mut Ui,
+    highlighted: bool,
+    add_contents: impl FnOnce(&mut Ui) -> Response,
+) -> Response {
+    let background = ui.painter().add(Shape::Noop);
+    let response = add_contents(ui);
+    if highlighted {
+        let visuals = &ui.visuals().widgets.hovered;
+        ui.painter().set(
+            background,
+            Shape::rect_filled(response.rect, visuals.corner_radius, visuals.weak_bg_fill),
+        );
+    }
+    response
 }
 
 // Shamelessly stolen from egui menu::set_menu_style, a private internal function
@@ -102,3 +442,163 @@ fn set_menu_style(style: &mut Style) {
     style.visuals.widgets.inactive.weak_bg_fill = Color32::TRANSPARENT;
     style.visuals.widgets.inactive.bg_stroke = Stroke::NONE;
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use ruffle_core::events::MouseButton;
+
+    fn item(caption: &str, enabled: bool, submenu: Option<&str>) -> ContextMenuItem {
+        ContextMenuItem {
+            enabled,
+            separator_before: false,
+            checked: false,
+            caption: caption.to_string(),
+            submenu: submenu.map(str::to_string),
+        }
+    }
+
+    fn close_event() -> PlayerEvent {
+        PlayerEvent::MouseUp {
+            x: 12.0,
+            y: 34.0,
+            button: MouseButton::Right,
+        }
+    }
+
+    #[test]
+    fn enabled_item_is_chosen() {
+        assert_eq!(
+            ItemClick::new(&item("Play", true, None), 3),
+            ItemClick::Chosen(3)
+        );
+    }
+
+    #[test]
+    fn disabled_item_dismisses() {
+        assert_eq!(
+            ItemClick::new(&item("Rewind", false, None), 3),
+            ItemClick::Dismissed
+        );
+    }
+
+    /// Runs a frame of the menu with the given input events.
+    fn run_frame(
+        egui_ctx: &egui::Context,
+        menu: &mut ContextMenu,
+        events: Vec<egui::Event>,
+    ) -> MenuOutcome {
+        let locale: LanguageIdentifier = "en-US".parse().unwrap();
+        let input = egui::RawInput {
+            screen_rect: Some(egui::Rect::from_min_size(Pos2::ZERO, vec2(800.0, 600.0))),
+            events,
+            ..Default::default()
+        };
+        let mut outcome = None;
+        let _ = egui_ctx.run(input, |egui_ctx| {
+            outcome = Some(menu.ui(&locale, egui_ctx, false));
+        });
+        outcome.expect("The menu is shown every frame")
+    }
+
+    /// Clicks on the item at `position` of the `count` items of the menu,
+    /// returning what was done with the menu in the frame the click ended in.
+    fn click_item(
+        egui_ctx: &egui::Context,
+        menu: &mut ContextMenu,
+        position: usize,
+        count: usize,
+    ) -> MenuOutcome {
+        // The menu is laid out first, so that it can be found and hovered.
+        // A new area only becomes visible in the frame after its sizing pass.
+        for _ in 0..2 {
+            assert!(!run_frame(egui_ctx, menu, vec![]).close);
+        }
+        let rect = egui_ctx
+            .memory(|memory| memory.area_rect(Id::new("context_menu")))
+            .expect("The menu is shown");
+        let pos = Pos2::new(
+            rect.center().x,
+            rect.top() + rect.height() * (2 * position + 1) as f32 / (2 * count) as f32,
+        );
+        let button = |pressed| egui::Event::PointerButton {
+            pos,
+            button: egui::PointerButton::Primary,
+            pressed,
+            modifiers: Modifiers::NONE,
+        };
+        assert!(!run_frame(egui_ctx, menu, vec![egui::Event::PointerMoved(pos)]).close);
+        assert!(!run_frame(egui_ctx, menu, vec![button(true)]).close);
+        run_frame(egui_ctx, menu, vec![button(false)])
+    }
+
+    #[test]
+    fn clicking_enabled_item_chooses_it() {
+        let egui_ctx = egui::Context::default();
+        let mut menu = ContextMenu::new(
+            vec![item("Play", true, None), item("Rewind", false, None)],
+            false,
+            Some(Pos2::new(10.0, 10.0)),
+            close_event(),
+        );
+        let outcome = click_item(&egui_ctx, &mut menu, 0, 2);
+        assert_eq!(outcome.clicked_item, Some(0));
+        assert!(outcome.close);
+    }
+
+    #[test]
+    fn close_event_after_disabled_item() {
+        let egui_ctx = egui::Context::default();
+        let mut menu = ContextMenu::new(
+            vec![item("Play", true, None), item("Rewind", false, None)],
+            false,
+            Some(Pos2::new(10.0, 10.0)),
+            close_event(),
+        );
+        let outcome = click_item(&egui_ctx, &mut menu, 1, 2);
+        assert_eq!(
+            outcome.clicked_item, None,
+            "The disabled item shouldn't be chosen"
+        );
+        assert!(outcome.desktop_event.is_none());
+        assert!(
+            outcome.close,
+            "Clicking the disabled item should close the menu"
+        );
+        // Once closed, the menu hands the event it was opened with back to the movie.
+        assert!(matches!(
+            menu.close_event(),
+            PlayerEvent::MouseUp {
+                button: MouseButton::Right,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn consecutive_items_grouped_into_submenus() {
+        let menu = ContextMenu::new(
+            vec![
+                item("Play", true, Some("Playback")),
+                item("Rewind", false, Some("Playback")),
+                item("Custom", true, None),
+                item("Low", true, Some("Quality")),
+                item("High", true, Some("Quality")),
+            ],
+            false,
+            None,
+            close_event(),
+        );
+        assert!(matches!(
+            menu.entries.as_slice(),
+            [
+                Entry::Submenu { caption: playback, items: playback_items },
+                Entry::Item(2),
+                Entry::Submenu { caption: quality, items: quality_items },
+            ] if playback == "Playback"
+                && playback_items == &[0, 1]
+                && quality == "Quality"
+                && quality_items == &[3, 4]
+        ));
+    }
+}
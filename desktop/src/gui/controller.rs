@@ -1,99 +1,303 @@
 use crate::backends::DesktopUiBackend;
-use crate::custom_event::RuffleEvent;
-use crate::gui::movie::{MovieView, MovieViewRenderer};
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
+use crate::gui::movie::{
+    movie_frame_changed, player_movie_view, render_movie_to_image, MovieView, MovieViewRenderer,
+    TouchGesture, ViewTransform,
+};
+use crate::gui::movie_watcher::MovieFileWatcher;
+use crate::gui::picture_in_picture::{PictureInPicture, PICTURE_IN_PICTURE_WIDTH};
+use crate::gui::recorder::MovieRecorder;
 use crate::gui::theme::ThemeController;
-use crate::gui::{RuffleGui, MENU_HEIGHT};
+use crate::gui::title::WindowTitle;
+use crate::gui::viewport::ChildViewport;
+use crate::gui::{
+    fullscreen_mode, BackgroundColor, MenuVisibility, RuffleGui, MENU_HEIGHT, TAB_STRIP_HEIGHT,
+    TIMELINE_BAR_HEIGHT,
+};
 use crate::player::{LaunchOptions, PlayerController};
-use crate::preferences::GlobalPreferences;
+use crate::preferences::{GlobalPreferences, MainWindowGeometry, WindowGeometry};
 use anyhow::anyhow;
-use egui::{Context, FontData, FontDefinitions, ViewportId};
+use egui::{
+    pos2, ColorImage, Context, CursorIcon, FontData, FontDefinitions, Pos2, ViewportId,
+    ViewportIdMap, ViewportOutput,
+};
+use fluent_templates::fluent_bundle::FluentValue;
 use fontdb::{Database, Family, Query, Source};
 use ruffle_core::events::{ImeCursorArea, ImePurpose};
-use ruffle_core::{Player, PlayerEvent};
-use ruffle_render_wgpu::backend::{request_adapter_and_device, WgpuRenderBackend};
+use ruffle_core::{Color, Player, PlayerEvent};
+use ruffle_render::backend::ViewportDimensions;
+use ruffle_render_wgpu::backend::request_adapter_and_device;
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_render_wgpu::utils::{format_list, get_backend_names};
 use std::any::Any;
-use std::sync::{Arc, MutexGuard};
+use std::collections::{HashMap, HashSet};
+use std::mem;
+use std::path::PathBuf;
+use std::sync::{Arc, LazyLock, Mutex, MutexGuard};
 use std::time::{Duration, Instant};
+use tokio::sync::broadcast::error::TryRecvError;
+use tokio::sync::broadcast::Receiver;
+use unic_langid::LanguageIdentifier;
 use url::Url;
 use wgpu::SurfaceError;
-use winit::dpi::{PhysicalPosition, PhysicalSize};
-use winit::event::WindowEvent;
-use winit::event_loop::EventLoopProxy;
-use winit::keyboard::{Key, NamedKey};
-use winit::window::{ImePurpose as WinitImePurpose, Theme, Window};
+use winit::dpi::{LogicalSize, PhysicalPosition, PhysicalSize};
+use winit::event::{ElementState, MouseButton, MouseScrollDelta, Touch, TouchPhase, WindowEvent};
+use winit::event_loop::ActiveEventLoop;
+use winit::keyboard::{Key, ModifiersState, NamedKey};
+use winit::monitor::MonitorHandle;
+use winit::window::{
+    CursorGrabMode, Fullscreen, ImePurpose as WinitImePurpose, Theme, Window, WindowId,
+};
 
-use super::{DialogDescriptor, FilePicker};
+use super::dialogs::message_dialog::MessageDialogConfiguration;
+use super::{text, text_with_args, DialogDescriptor, FilePicker, LocalizableText};
+
+/// How many frames in a row may fail with [`SurfaceError::Other`] before telling the user.
+const MAX_CONSECUTIVE_SURFACE_ERRORS: u32 = 10;
+
+/// How much the movie view is zoomed by each step of the mouse wheel.
+const VIEW_ZOOM_STEP: f64 = 1.25;
+
+/// How many pixels of smooth scrolling make up one step of the mouse wheel.
+const PIXELS_PER_SCROLL_STEP: f64 = 50.0;
+
+/// How many frames in a row have to look the same before the movie is redrawn less often.
+const IDLE_FRAMES_BEFORE_POWER_SAVE: u32 = 30;
+
+/// How often an idle movie is redrawn, to notice when it starts changing again.
+const IDLE_REDRAW_INTERVAL: Duration = Duration::from_millis(100);
+
+/// The width the menu bar needs to fit, in logical pixels.
+const MIN_MENU_WIDTH: u32 = 350;
+
+/// The smallest width and height left for the movie, in logical pixels.
+const MIN_MOVIE_SIZE: u32 = 16;
+
+/// The graphics device and window surface, created asynchronously
+/// by [`GuiController::request_graphics`].
+///
+/// Additional windows share the same device, see [`GuiController::share_graphics`].
+pub struct GraphicsContext {
+    descriptors: Arc<Descriptors>,
+    surface: wgpu::Surface<'static>,
+}
+
+/// The fonts needed to display the GUI in the current language, which couldn't be found.
+pub struct MissingFonts {
+    pub locale: LanguageIdentifier,
+    /// The names of all the font families that were tried.
+    pub families: Vec<String>,
+}
 
 /// Integration layer connecting wgpu+winit to egui.
 pub struct GuiController {
     descriptors: Arc<Descriptors>,
     egui_winit: egui_winit::State,
     egui_renderer: egui_wgpu::Renderer,
+    /// The windows of egui viewports shown by the GUI, such as popped out dialogs.
+    viewports: HashMap<ViewportId, ChildViewport>,
+    /// Viewports shown for the first time, waiting for [`GuiController::create_pending_viewports`].
+    pending_viewports: HashMap<ViewportId, ViewportOutput>,
+    /// Viewports whose window couldn't be created, so that it isn't attempted every frame.
+    failed_viewports: HashSet<ViewportId>,
     gui: RuffleGui,
     window: Arc<Window>,
     last_update: Instant,
     repaint_after: Duration,
     surface: wgpu::Surface<'static>,
     surface_format: wgpu::TextureFormat,
+    alpha_mode: wgpu::CompositeAlphaMode,
+    /// Whether the window is transparent where the movie has no content.
+    ///
+    /// This is only the case if it was requested and the surface supports it.
+    transparent: bool,
+    /// The number of MSAA samples used for the GUI render pass.
+    sample_count: u32,
+    /// The multisampled target that the GUI is rendered to and then resolved into the surface,
+    /// if MSAA is used.
+    msaa_view: Option<wgpu::TextureView>,
+    /// How many frames in a row failed to get a surface texture with a generic error.
+    consecutive_surface_errors: u32,
+    /// The `desired_maximum_frame_latency` the surface is configured with.
+    frame_latency: u32,
+    frame_latency_watcher: Receiver<u32>,
+    /// The scale factor used instead of the window's, for debugging HiDPI issues.
+    scale_factor_override: Option<f64>,
+    scale_factor_override_watcher: Receiver<Option<f64>>,
+    menu_visibility: MenuVisibility,
+    menu_visibility_watcher: Receiver<MenuVisibility>,
+    /// Whether the auto-hidden menu is currently shown on top of the movie.
+    menu_revealed: bool,
+    background_color: BackgroundColor,
+    background_color_watcher: Receiver<BackgroundColor>,
+    /// The letterbox color last given to the player, so that it's only updated when it changes.
+    letterbox_color: Option<[u8; 3]>,
+    title: WindowTitle,
+    show_fps_in_title_watcher: Receiver<bool>,
+    /// Whether an idle movie is redrawn less often, see [`IDLE_FRAMES_BEFORE_POWER_SAVE`].
+    idle_power_save: bool,
+    idle_power_save_watcher: Receiver<bool>,
+    /// How many rendered frames in a row looked the same as the one before.
+    unchanged_frames: u32,
+    last_movie_render: Instant,
     movie_view_renderer: Arc<MovieViewRenderer>,
+    /// The small window showing the movie as well, while picture in picture is on.
+    picture_in_picture: Option<PictureInPicture>,
+    /// The zoom and pan of the movie view, controlled with Ctrl+scroll and the middle mouse button.
+    view_transform: ViewTransform,
+    /// Whether the movie view is being dragged with the middle mouse button.
+    panning: bool,
+    /// The fingers zooming and panning the movie view, while they're down.
+    touch_gesture: Option<TouchGesture>,
+    cursor_position: PhysicalPosition<f64>,
+    /// When the mouse was last used, to hide the cursor in fullscreen after a while.
+    last_mouse_activity: Instant,
+    /// Whether the cursor was hidden for a lack of mouse activity, see [`GuiController::cursor_hide_time`].
+    cursor_auto_hidden: bool,
+    /// Whether the cursor is grabbed and hidden, so that the movie only gets relative mouse movement.
+    mouse_locked: bool,
+    modifiers: ModifiersState,
+    /// If this is set, the movie view is being recorded.
+    recorder: Option<MovieRecorder>,
+    /// Set when the next rendered frame of the movie should be copied to the clipboard.
+    copy_frame_requested: bool,
     // Note that `window.get_inner_size` can change at any point on x11, even between two lines of code.
     // Use this instead.
     size: PhysicalSize<u32>,
+    /// The last position and size of the window while it wasn't fullscreen.
+    windowed_geometry: Option<(PhysicalPosition<i32>, PhysicalSize<u32>)>,
+    /// Set after exclusive fullscreen, which doesn't always restore the window when it ends.
+    restore_windowed_geometry: bool,
+    /// The URL of the open movie, whose window geometry is saved when it's closed.
+    movie_url: Option<Url>,
+    /// The window geometry that was restored when the open movie was created, if any.
+    restored_window_geometry: Option<WindowGeometry>,
+    /// Whether the open movie is reloaded whenever its file changes.
+    auto_reload: bool,
+    /// Watches the file of the open movie while it's reloaded automatically.
+    movie_watcher: Option<MovieFileWatcher>,
     /// If this is set, we should not render the main menu.
     no_gui: bool,
     theme_controller: ThemeController,
 }
 
 impl GuiController {
+    /// Starts creating the graphics device for the given window.
+    ///
+    /// Requesting an adapter and device can take a long time on some drivers,
+    /// so it happens asynchronously to keep the event loop responsive.
+    /// [`RuffleEvent::GraphicsReady`] is sent when it's done, successfully or not.
+    pub fn request_graphics(
+        window: &Window,
+        event_loop: RuffleEventProxy,
+        preferences: &GlobalPreferences,
+    ) -> anyhow::Result<()> {
+        let (instance, backend) = create_wgpu_instance(preferences.graphics_backends().into())?;
+        let surface = unsafe {
+            instance.create_surface_unsafe(wgpu::SurfaceTargetUnsafe::from_window(window)?)
+        }?;
+        let power_preference = preferences.graphics_power_preference().into();
+        tokio::spawn(async move {
+            let result =
+                request_adapter_and_device(backend, &instance, Some(&surface), power_preference)
+                    .await
+                    .map(|(adapter, device, queue)| {
+                        let adapter_info = adapter.get_info();
+                        tracing::info!(
+                            "Using graphics API {} on {} (type: {:?})",
+                            adapter_info.backend.to_str(),
+                            adapter_info.name,
+                            adapter_info.device_type
+                        );
+                        let descriptors = Descriptors::new(instance, adapter, device, queue);
+                        Box::new(GraphicsContext {
+                            descriptors: Arc::new(descriptors),
+                            surface,
+                        })
+                    })
+                    .map_err(|e| e.to_string());
+            let _ = event_loop.send_event(RuffleEvent::GraphicsReady(result));
+        });
+        Ok(())
+    }
+
+    /// Creates a surface for another window, using the graphics device of an existing one.
+    ///
+    /// This is much faster than creating a new device with [`GuiController::request_graphics`].
+    pub fn share_graphics(
+        window: &Window,
+        descriptors: &Arc<Descriptors>,
+    ) -> anyhow::Result<GraphicsContext> {
+        let surface = unsafe {
+            descriptors
+                .wgpu_instance
+                .create_surface_unsafe(wgpu::SurfaceTargetUnsafe::from_window(window)?)
+        }?;
+        Ok(GraphicsContext {
+            descriptors: descriptors.clone(),
+            surface,
+        })
+    }
+
+    #[expect(clippy::too_many_arguments)]
     pub fn new(
         window: Arc<Window>,
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
         preferences: GlobalPreferences,
         font_database: &Database,
         initial_movie_url: Option<Url>,
         no_gui: bool,
-    ) -> anyhow::Result<Self> {
-        let (instance, backend) = create_wgpu_instance(preferences.graphics_backends().into())?;
-        let surface = unsafe {
-            instance.create_surface_unsafe(wgpu::SurfaceTargetUnsafe::from_window(window.as_ref())?)
-        }?;
-        let (adapter, device, queue) = futures::executor::block_on(request_adapter_and_device(
-            backend,
-            &instance,
-            Some(&surface),
-            preferences.graphics_power_preference().into(),
-        ))
-        .map_err(|e| anyhow!(e.to_string()))?;
-        let adapter_info = adapter.get_info();
-        tracing::info!(
-            "Using graphics API {} on {} (type: {:?})",
-            adapter_info.backend.to_str(),
-            adapter_info.name,
-            adapter_info.device_type
-        );
-        let surface_format = surface
-            .get_capabilities(&adapter)
+        graphics: GraphicsContext,
+        file_picker: FilePicker,
+    ) -> Self {
+        let GraphicsContext {
+            descriptors,
+            surface,
+        } = graphics;
+        let capabilities = surface.get_capabilities(&descriptors.adapter);
+        let surface_format = capabilities
             .formats
             .first()
             .cloned()
             .expect("At least one format should be supported");
-        let size = window.inner_size();
+        let transparent_alpha_mode = preferences
+            .cli
+            .transparent
+            .then(|| transparent_alpha_mode(&capabilities))
+            .flatten();
+        if preferences.cli.transparent && transparent_alpha_mode.is_none() {
+            tracing::warn!(
+                "Transparent windows aren't supported (alpha modes: {:?}), using an opaque one",
+                capabilities.alpha_modes
+            );
+        }
+        let transparent = transparent_alpha_mode.is_some();
+        let alpha_mode = transparent_alpha_mode.unwrap_or_default();
+        let menu_visibility = preferences.menu_visibility();
+        window.set_min_inner_size(Some(min_window_size(no_gui, menu_visibility)));
+        // On Wayland, the window may not have a size yet until it's first configured by the compositor,
+        // so start with the smallest one until the real size is known.
+        let size = match window.inner_size() {
+            PhysicalSize {
+                width: 0,
+                height: 0,
+            } => min_window_size(no_gui, menu_visibility).to_physical(window.scale_factor()),
+            size => clamp_surface_size(size, &descriptors),
+        };
+        let frame_latency = preferences.frame_latency();
         surface.configure(
-            &device,
+            &descriptors.device,
             &wgpu::SurfaceConfiguration {
                 usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
                 format: surface_format,
                 width: size.width,
                 height: size.height,
                 present_mode: Default::default(),
-                desired_maximum_frame_latency: 2,
-                alpha_mode: Default::default(),
+                desired_maximum_frame_latency: frame_latency,
+                alpha_mode,
                 view_formats: Default::default(),
             },
         );
-        let descriptors = Descriptors::new(instance, adapter, device, queue);
         let egui_ctx = Context::default();
 
         let theme_controller = futures::executor::block_on(ThemeController::new(
@@ -110,44 +314,116 @@ impl GuiController {
             None,
         );
         egui_winit.set_max_texture_side(descriptors.limits.max_texture_dimension_2d as usize);
+        // Show viewports in windows of their own, see `GuiController::create_pending_viewports`.
+        egui_winit.egui_ctx().set_embed_viewports(false);
 
+        let scale_factor_override = preferences.scale_factor_override();
+        let sample_count =
+            supported_sample_count(&descriptors, surface_format, preferences.ui_sample_count());
+        let msaa_view = create_msaa_view(&descriptors.device, surface_format, size, sample_count);
         let movie_view_renderer = Arc::new(MovieViewRenderer::new(
             &descriptors.device,
             surface_format,
-            window.fullscreen().is_none() && !no_gui,
+            sample_count,
+            menu_height_offset(
+                window.fullscreen().is_some(),
+                scale_factor_override.unwrap_or_else(|| window.scale_factor()),
+                no_gui,
+                menu_visibility,
+            ),
             size.height,
-            window.scale_factor(),
         ));
-        let egui_renderer =
-            egui_wgpu::Renderer::new(&descriptors.device, surface_format, None, 1, true);
-        let descriptors = Arc::new(descriptors);
+        let egui_renderer = egui_wgpu::Renderer::new(
+            &descriptors.device,
+            surface_format,
+            None,
+            sample_count,
+            true,
+        );
+        let frame_latency_watcher = preferences.frame_latency_watcher();
+        let menu_visibility_watcher = preferences.menu_visibility_watcher();
+        let scale_factor_override_watcher = preferences.scale_factor_override_watcher();
+        let background_color = preferences.background_color();
+        let background_color_watcher = preferences.background_color_watcher();
+        let title = WindowTitle::new(preferences.show_fps_in_title());
+        let show_fps_in_title_watcher = preferences.show_fps_in_title_watcher();
+        let idle_power_save = preferences.idle_power_save();
+        let idle_power_save_watcher = preferences.idle_power_save_watcher();
+        let (system_fonts, missing_fonts) =
+            load_system_fonts(font_database, preferences.language().to_owned());
+        egui_winit.egui_ctx().set_fonts(system_fonts);
+        if let Some(missing_fonts) = missing_fonts {
+            let _ = event_loop.send_event(RuffleEvent::MissingFonts(missing_fonts));
+        }
         let gui = RuffleGui::new(
             Arc::downgrade(&window),
             event_loop,
             initial_movie_url.clone(),
             LaunchOptions::from(&preferences),
             preferences.clone(),
+            file_picker,
         );
-        let system_fonts = load_system_fonts(font_database, preferences.language().to_owned());
-        egui_winit.egui_ctx().set_fonts(system_fonts);
 
         egui_extras::install_image_loaders(egui_winit.egui_ctx());
 
-        Ok(Self {
+        let mut this = Self {
             descriptors,
             egui_winit,
             egui_renderer,
+            viewports: HashMap::new(),
+            pending_viewports: HashMap::new(),
+            failed_viewports: HashSet::new(),
             gui,
             window,
             last_update: Instant::now(),
             repaint_after: Duration::ZERO,
             surface,
             surface_format,
+            alpha_mode,
+            transparent,
+            sample_count,
+            msaa_view,
+            consecutive_surface_errors: 0,
+            frame_latency,
+            frame_latency_watcher,
+            scale_factor_override,
+            scale_factor_override_watcher,
+            menu_visibility,
+            menu_visibility_watcher,
+            menu_revealed: false,
+            background_color,
+            background_color_watcher,
+            letterbox_color: None,
+            title,
+            show_fps_in_title_watcher,
+            idle_power_save,
+            idle_power_save_watcher,
+            unchanged_frames: 0,
+            last_movie_render: Instant::now(),
             movie_view_renderer,
+            picture_in_picture: None,
+            view_transform: Default::default(),
+            panning: false,
+            touch_gesture: None,
+            cursor_position: PhysicalPosition::new(0.0, 0.0),
+            last_mouse_activity: Instant::now(),
+            cursor_auto_hidden: false,
+            mouse_locked: false,
+            modifiers: ModifiersState::empty(),
+            recorder: None,
+            copy_frame_requested: false,
             size,
+            windowed_geometry: None,
+            restore_windowed_geometry: false,
+            movie_url: None,
+            auto_reload: false,
+            movie_watcher: None,
+            restored_window_geometry: None,
             no_gui,
             theme_controller,
-        })
+        };
+        this.apply_egui_zoom_factor();
+        this
     }
 
     pub fn set_theme(&self, theme: Theme) {
@@ -166,14 +442,46 @@ impl GuiController {
         &self.window
     }
 
+    /// The smallest the window may be made, so that the menu and some of the movie always fit.
+    pub fn min_window_size(&self) -> LogicalSize<u32> {
+        min_window_size(self.no_gui, self.menu_visibility)
+    }
+
     pub fn resize(&mut self, size: PhysicalSize<u32>) {
-        if size.width > 0 && size.height > 0 {
-            self.size = size;
-            self.reconfigure_surface();
+        // A minimized window is 0×0, as is a window on Wayland before it's first configured.
+        // Keep the last size until it's shown again.
+        if size.width == 0 && size.height == 0 {
+            return;
+        }
+        self.size = clamp_surface_size(size, &self.descriptors);
+        self.reconfigure_surface();
+    }
+
+    /// Remembers the windowed geometry, and restores it after leaving exclusive fullscreen.
+    fn track_windowed_geometry(&mut self) {
+        match self.window.fullscreen() {
+            Some(Fullscreen::Exclusive(_)) => self.restore_windowed_geometry = true,
+            Some(Fullscreen::Borderless(_)) => {}
+            None if self.restore_windowed_geometry => {
+                self.restore_windowed_geometry = false;
+                if let Some((position, size)) = self.windowed_geometry {
+                    let _ = self.window.request_inner_size(size);
+                    self.window.set_outer_position(position);
+                }
+            }
+            None => {
+                if self.window.is_minimized() == Some(true) {
+                    return;
+                }
+                if let Ok(position) = self.window.outer_position() {
+                    self.windowed_geometry = Some((position, self.window.inner_size()));
+                }
+            }
         }
     }
 
     pub fn reconfigure_surface(&mut self) {
+        self.size = clamp_surface_size(self.size, &self.descriptors);
         self.surface.configure(
             &self.descriptors.device,
             &wgpu::SurfaceConfiguration {
@@ -182,29 +490,252 @@ impl GuiController {
                 width: self.size.width,
                 height: self.size.height,
                 present_mode: Default::default(),
-                desired_maximum_frame_latency: 2,
-                alpha_mode: Default::default(),
+                desired_maximum_frame_latency: self.frame_latency,
+                alpha_mode: self.alpha_mode,
                 view_formats: Default::default(),
             },
         );
+        self.msaa_view = create_msaa_view(
+            &self.descriptors.device,
+            self.surface_format,
+            self.size,
+            self.sample_count,
+        );
         self.movie_view_renderer.update_resolution(
             &self.descriptors,
-            self.window.fullscreen().is_none() && !self.no_gui,
+            self.height_offset(),
             self.size.height,
-            self.window.scale_factor(),
         );
     }
 
+    /// Reconfigures the surface if the frame latency preference has changed,
+    /// so that the new value is applied without a restart.
+    fn apply_frame_latency_changes(&mut self) {
+        let Some(frame_latency) = latest_value(&mut self.frame_latency_watcher) else {
+            return;
+        };
+        if frame_latency != self.frame_latency {
+            tracing::info!("Frame latency changed to {frame_latency}, reconfiguring surface");
+            self.frame_latency = frame_latency;
+            self.reconfigure_surface();
+        }
+    }
+
+    /// The scale factor of the window, unless it's overridden by the preferences.
+    pub fn scale_factor(&self) -> f64 {
+        self.scale_factor_override
+            .unwrap_or_else(|| self.window.scale_factor())
+    }
+
+    /// Makes egui use the overridden scale factor as its pixels per point.
+    ///
+    /// egui multiplies the window's scale factor by its zoom factor,
+    /// so the zoom factor has to follow the window's scale factor too.
+    fn apply_egui_zoom_factor(&mut self) {
+        let zoom_factor = match self.scale_factor_override {
+            Some(scale_factor) => (scale_factor / self.window.scale_factor()) as f32,
+            None => 1.0,
+        };
+        self.egui_winit.egui_ctx().set_zoom_factor(zoom_factor);
+    }
+
+    /// Applies a changed scale factor override preference.
+    ///
+    /// Returns true if the space available to the movie has changed.
+    fn apply_scale_factor_override_changes(&mut self) -> bool {
+        let Some(scale_factor_override) = latest_value(&mut self.scale_factor_override_watcher)
+        else {
+            return false;
+        };
+        if scale_factor_override == self.scale_factor_override {
+            return false;
+        }
+        tracing::info!("Scale factor override changed to {scale_factor_override:?}");
+        self.scale_factor_override = scale_factor_override;
+        self.apply_egui_zoom_factor();
+        self.reconfigure_surface();
+        true
+    }
+
+    /// Applies a changed menu visibility preference.
+    ///
+    /// Returns true if the space available to the movie has changed.
+    fn apply_menu_visibility_changes(&mut self) -> bool {
+        let Some(menu_visibility) = latest_value(&mut self.menu_visibility_watcher) else {
+            return false;
+        };
+        if menu_visibility == self.menu_visibility {
+            return false;
+        }
+        let previous_height_offset = self.height_offset();
+        self.menu_visibility = menu_visibility;
+        self.menu_revealed = false;
+        self.window.set_min_inner_size(Some(self.min_window_size()));
+        if self.height_offset() != previous_height_offset {
+            self.reconfigure_surface();
+            true
+        } else {
+            false
+        }
+    }
+
+    /// Returns the color shown around the movie, and passes it on to the player for its letterbox.
+    ///
+    /// It may depend on the stage background color, so this is checked on every frame.
+    fn update_background_color(&mut self, player: Option<&mut Player>) -> [u8; 3] {
+        if let Some(background_color) = latest_value(&mut self.background_color_watcher) {
+            self.background_color = background_color;
+        }
+        let Some(player) = player else {
+            return self.background_color.resolve(None);
+        };
+        let color = self
+            .background_color
+            .resolve(player.background_color().map(|c| [c.r, c.g, c.b]));
+        if self.letterbox_color != Some(color) {
+            let [r, g, b] = color;
+            // In a transparent window, the letterbox lets the desktop show through instead.
+            let a = if self.transparent { 0 } else { 255 };
+            player.set_letterbox_color(Color { r, g, b, a });
+            self.letterbox_color = Some(color);
+            // The letterbox is drawn by the player, so the movie has to be rendered again.
+            self.window.request_redraw();
+        }
+        color
+    }
+
+    /// Shows or hides the auto-hidden menu depending on where the cursor is.
+    fn update_menu_revealed(&mut self, cursor_y: Option<f64>) {
+        if self.menu_visibility != MenuVisibility::AutoHide {
+            return;
+        }
+        let menu_height = MENU_HEIGHT as f64 * self.scale_factor();
+        let revealed = match cursor_y {
+            Some(y) if y < menu_height => true,
+            // Keep the menu around while it's being used, e.g. while a submenu is open below it.
+            Some(_) => self.menu_revealed && self.egui_winit.egui_ctx().is_pointer_over_area(),
+            None => false,
+        };
+        if revealed != self.menu_revealed {
+            self.menu_revealed = revealed;
+            self.window.request_redraw();
+        }
+    }
+
+    fn show_tab_strip(&self) -> bool {
+        self.gui.tab_strip.has_tabs() && self.window.fullscreen().is_none() && !self.no_gui
+    }
+
+    fn show_timeline_bar(&self) -> bool {
+        self.gui.timeline_bar.is_visible() && self.window.fullscreen().is_none() && !self.no_gui
+    }
+
+    /// Sets whether the timeline bar is shown for movies with more than one frame.
+    pub fn set_timeline_bar_enabled(&mut self, enabled: bool) {
+        self.gui.timeline_bar.set_enabled(enabled);
+        self.gui.menu_bar.timeline_bar = enabled;
+        self.window.request_redraw();
+    }
+
+    /// Follows the main timeline of the movie in the timeline bar.
+    ///
+    /// Returns true if the space available to the movie has changed, as the bar was shown or hidden.
+    fn update_timeline_bar(&mut self, player: Option<&mut Player>) -> bool {
+        let previous_height_offset = self.height_offset();
+        self.gui.timeline_bar.update(player);
+        if self.height_offset() != previous_height_offset {
+            self.reconfigure_surface();
+            true
+        } else {
+            false
+        }
+    }
+
+    fn show_menu(&self) -> bool {
+        if self.window.fullscreen().is_some() || self.no_gui {
+            return false;
+        }
+        match self.menu_visibility {
+            MenuVisibility::Always => true,
+            MenuVisibility::AutoHide => self.menu_revealed,
+            MenuVisibility::Never => false,
+        }
+    }
+
     #[must_use]
     pub fn handle_event(&mut self, event: &WindowEvent) -> bool {
         if let WindowEvent::Resized(size) = &event {
             self.resize(*size);
         }
 
+        if let WindowEvent::ScaleFactorChanged { .. } = &event {
+            self.apply_egui_zoom_factor();
+        }
+
         if let WindowEvent::ThemeChanged(theme) = &event {
             self.set_theme(*theme);
         }
 
+        match &event {
+            WindowEvent::CursorMoved { position, .. } => {
+                self.update_menu_revealed(Some(position.y))
+            }
+            WindowEvent::CursorLeft { .. } => self.update_menu_revealed(None),
+            WindowEvent::Resized(_) | WindowEvent::Moved(_) => self.track_windowed_geometry(),
+            WindowEvent::Focused(false)
+                if matches!(self.window.fullscreen(), Some(Fullscreen::Exclusive(_))) =>
+            {
+                // Keeping the display mode while another window is focused
+                // leaves a dead surface on top of it, so get out of the way.
+                self.window.set_minimized(true);
+            }
+            WindowEvent::Focused(true) => self.window.request_redraw(),
+            _ => {}
+        }
+
+        if matches!(
+            &event,
+            WindowEvent::CursorMoved { .. }
+                | WindowEvent::MouseInput { .. }
+                | WindowEvent::MouseWheel { .. }
+                | WindowEvent::Touch(_)
+        ) {
+            self.last_mouse_activity = Instant::now();
+            if self.cursor_auto_hidden {
+                // Show the cursor again right away.
+                self.window.request_redraw();
+            }
+        }
+
+        if matches!(
+            &event,
+            WindowEvent::CursorMoved { .. }
+                | WindowEvent::MouseInput { .. }
+                | WindowEvent::MouseWheel { .. }
+                | WindowEvent::KeyboardInput { .. }
+                | WindowEvent::Ime(_)
+                | WindowEvent::Touch(_)
+        ) {
+            // Input is likely to change the movie, so leave power saving right away.
+            self.unchanged_frames = 0;
+        }
+
+        if self.mouse_locked
+            && matches!(
+                &event,
+                WindowEvent::CursorMoved { .. }
+                    | WindowEvent::MouseInput { .. }
+                    | WindowEvent::MouseWheel { .. }
+            )
+        {
+            // The cursor position is meaningless while it's locked, so it mustn't click the menu.
+            return false;
+        }
+
+        if self.handle_view_transform_event(event) {
+            return true;
+        }
+
         if matches!(
             &event,
             WindowEvent::KeyboardInput {
@@ -226,18 +757,201 @@ impl GuiController {
         response.consumed
     }
 
+    /// Zooms the movie view with Ctrl+scroll, and pans it by dragging with the middle mouse button.
+    ///
+    /// Returns true if the event was used, and shouldn't reach the movie.
+    fn handle_view_transform_event(&mut self, event: &WindowEvent) -> bool {
+        match event {
+            WindowEvent::ModifiersChanged(modifiers) => {
+                self.modifiers = modifiers.state();
+                false
+            }
+            WindowEvent::CursorMoved { position, .. } => {
+                let previous = mem::replace(&mut self.cursor_position, *position);
+                if !self.panning {
+                    return false;
+                }
+                let (width, height) = self.movie_view_size();
+                self.view_transform.pan((
+                    (position.x - previous.x) / width,
+                    (position.y - previous.y) / height,
+                ));
+                self.update_view_transform();
+                true
+            }
+            WindowEvent::MouseWheel { delta, .. }
+                if command_held(self.modifiers)
+                    && !self.egui_winit.egui_ctx().is_pointer_over_area() =>
+            {
+                let steps = match delta {
+                    MouseScrollDelta::LineDelta(_, y) => *y as f64,
+                    MouseScrollDelta::PixelDelta(position) => position.y / PIXELS_PER_SCROLL_STEP,
+                };
+                let anchor = self.window_to_view_position(self.cursor_position);
+                self.view_transform
+                    .zoom_at(VIEW_ZOOM_STEP.powf(steps), anchor);
+                self.update_view_transform();
+                true
+            }
+            WindowEvent::MouseInput {
+                button: MouseButton::Middle,
+                state,
+                ..
+            } if self.panning || !self.view_transform.is_identity() => {
+                // When not zoomed in, there's nothing to pan, so the movie gets the button.
+                self.panning = *state == ElementState::Pressed;
+                true
+            }
+            _ => false,
+        }
+    }
+
+    /// Starts zooming and panning the movie view with two fingers, see [`TouchGesture`].
+    pub fn start_touch_gesture(
+        &mut self,
+        first: (u64, PhysicalPosition<f64>),
+        second: (u64, PhysicalPosition<f64>),
+    ) {
+        self.touch_gesture = Some(TouchGesture::new(first, second));
+    }
+
+    /// Zooms and pans the movie view while two fingers are pinching it.
+    ///
+    /// Returns true if the touch was used, which is any touch while the gesture goes on,
+    /// so that other fingers don't reach the movie either.
+    pub fn handle_touch_gesture(&mut self, touch: &Touch) -> bool {
+        let Some(gesture) = &mut self.touch_gesture else {
+            return false;
+        };
+        match touch.phase {
+            TouchPhase::Moved => {
+                if let Some((previous_center, center, factor)) =
+                    gesture.move_touch(touch.id, touch.location)
+                {
+                    let previous_center = self.window_to_view_position(previous_center);
+                    let center = self.window_to_view_position(center);
+                    self.view_transform
+                        .pan((center.0 - previous_center.0, center.1 - previous_center.1));
+                    self.view_transform.zoom_at(factor, center);
+                    self.update_view_transform();
+                }
+            }
+            // Lifting either finger ends the gesture, and the other one is ignored until it's lifted too.
+            TouchPhase::Ended if gesture.has_touch(touch.id) => self.touch_gesture = None,
+            TouchPhase::Cancelled => self.touch_gesture = None,
+            TouchPhase::Started | TouchPhase::Ended => {}
+        }
+        true
+    }
+
+    pub fn reset_zoom(&mut self) {
+        self.view_transform = Default::default();
+        self.panning = false;
+        self.touch_gesture = None;
+        self.update_view_transform();
+    }
+
+    /// Zooms the movie view by `factor`, keeping its center still.
+    pub fn zoom_view(&mut self, factor: f64) {
+        self.view_transform.zoom_at(factor, (0.5, 0.5));
+        self.update_view_transform();
+    }
+
+    fn update_view_transform(&mut self) {
+        self.movie_view_renderer
+            .update_transform(&self.descriptors, self.view_transform);
+        self.window.request_redraw();
+    }
+
+    /// Closes the selected tab, showing the next one if there are any left.
     pub fn close_movie(&mut self, player: &mut PlayerController) {
+        self.save_window_geometry();
+        self.restored_window_geometry = None;
+        self.stop_recording();
         player.destroy();
         self.gui.on_player_destroyed();
+        self.on_tabs_changed(player);
+    }
+
+    /// Closes the tab at the given index, which doesn't have to be the selected one.
+    pub fn close_tab(&mut self, player: &mut PlayerController, index: usize) {
+        if index == player.active_tab() {
+            self.close_movie(player);
+        } else {
+            player.close_tab(index);
+            self.on_tabs_changed(player);
+        }
+    }
+
+    /// Shows the tab at the given index, pausing the one shown before.
+    pub fn select_tab(&mut self, player: &mut PlayerController, index: usize) {
+        if index == player.active_tab() {
+            return;
+        }
+        self.save_window_geometry();
+        self.restored_window_geometry = None;
+        self.stop_recording();
+        self.reset_zoom();
+        player.select_tab(index);
+        self.on_tabs_changed(player);
+    }
+
+    /// Updates the tab strip, window title and movie view to show the selected tab.
+    fn on_tabs_changed(&mut self, player: &mut PlayerController) {
+        let previous_height_offset = self.height_offset();
+        self.gui
+            .tab_strip
+            .set_tabs(player.tab_names(), player.active_tab());
+        if self.height_offset() != previous_height_offset {
+            self.reconfigure_surface();
+        }
+
+        let movie = player
+            .movie()
+            .map(|(url, options)| (url.clone(), options.clone()));
+        self.movie_url = movie.as_ref().map(|(url, _)| url.clone());
+        self.gui.menu_bar.currently_opened = movie;
+        self.update_movie_watcher();
+        self.letterbox_color = None;
+        self.title.set_movie_name(player.movie_name());
+        self.title
+            .apply(&self.window, &self.gui.preferences.language());
+
+        // The movie may not have been shown since the window was last resized.
+        if let Some(mut player) = player.get() {
+            player.set_viewport_dimensions(ViewportDimensions {
+                width: self.size.width,
+                height: self.size.height.saturating_sub(self.height_offset() as u32),
+                scale_factor: self.scale_factor(),
+            });
+        }
+        self.window.request_redraw();
+    }
+
+    /// Shows the title from the movie's own metadata in the window title, instead of its file name.
+    pub fn set_movie_title(&mut self, title: &str) {
+        self.title.set_movie_name(Some(title));
+        self.title
+            .apply(&self.window, &self.gui.preferences.language());
     }
 
+    /// Opens a movie in a new tab, keeping any others open in the background.
     pub fn create_movie(
         &mut self,
         player: &mut PlayerController,
         opt: LaunchOptions,
         movie_url: Url,
     ) {
-        self.close_movie(player);
+        self.save_window_geometry();
+        self.stop_recording();
+        let opt = LaunchOptions {
+            transparent: opt.transparent && self.transparent,
+            ..opt
+        };
+        self.reset_zoom();
+        self.letterbox_color = None;
+        self.restore_window_geometry(&movie_url);
+        self.movie_url = Some(movie_url.clone());
         let movie_view = MovieView::new(
             self.movie_view_renderer.clone(),
             &self.descriptors.device,
@@ -245,6 +959,7 @@ impl GuiController {
             self.size.height,
         );
         player.create(&opt, &movie_url, movie_view);
+        self.on_tabs_changed(player);
         self.gui.on_player_created(
             opt,
             movie_url,
@@ -254,26 +969,370 @@ impl GuiController {
         );
     }
 
+    /// Reopens the movie in the selected tab, with the URL and options it was opened with,
+    /// leaving the tab where it is.
+    pub fn reload_movie(&mut self, player: &mut PlayerController) {
+        let Some((movie_url, opt)) = player
+            .movie()
+            .map(|(url, options)| (url.clone(), options.clone()))
+        else {
+            return;
+        };
+        self.save_window_geometry();
+        self.stop_recording();
+        self.gui.on_player_destroyed();
+        self.reset_zoom();
+        self.letterbox_color = None;
+        let movie_view = MovieView::new(
+            self.movie_view_renderer.clone(),
+            &self.descriptors.device,
+            self.size.width,
+            self.size.height,
+        );
+        player.reload(movie_view);
+        self.on_tabs_changed(player);
+        self.gui.on_player_created(
+            opt,
+            movie_url,
+            player
+                .get()
+                .expect("Player must exist after being reloaded."),
+        );
+    }
+
+    /// Mutes or unmutes the movie, which is shown in the menu bar and the window title.
+    pub fn toggle_mute(&mut self, player: Option<&mut Player>) {
+        self.gui.toggle_mute(player);
+        self.window.request_redraw();
+    }
+
+    /// Sets whether the open movie is reloaded whenever its file changes.
+    pub fn set_auto_reload(&mut self, enabled: bool) {
+        self.auto_reload = enabled;
+        self.gui.menu_bar.auto_reload = enabled;
+        self.update_movie_watcher();
+    }
+
+    /// Watches the file of the open movie if it's reloaded automatically and is a local file.
+    fn update_movie_watcher(&mut self) {
+        let path = self
+            .movie_url
+            .as_ref()
+            .filter(|url| self.auto_reload && url.scheme() == "file")
+            .and_then(|url| url.to_file_path().ok());
+        let watched_path = self.movie_watcher.as_ref().map(MovieFileWatcher::path);
+        if watched_path == path.as_deref() {
+            return;
+        }
+
+        self.movie_watcher = path.and_then(|path| {
+            MovieFileWatcher::start(path, self.gui.event_loop.clone())
+                .inspect_err(|e| tracing::error!("Couldn't watch the movie file: {e}"))
+                .ok()
+        });
+    }
+
+    /// Saves the window geometry for the open movie, so that it can be restored the next time it's opened.
+    pub fn save_window_geometry(&mut self) {
+        let Some(url) = self.movie_url.take() else {
+            return;
+        };
+        if !self.gui.preferences.remember_window_geometry() {
+            return;
+        }
+        let Some((position, size)) = self.current_windowed_geometry() else {
+            return;
+        };
+        let geometry = WindowGeometry {
+            url,
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+            maximized: self.window.is_maximized(),
+            fullscreen: self.window.fullscreen().is_some(),
+        };
+        if let Err(e) = self
+            .gui
+            .preferences
+            .write_preferences(|writer| writer.set_window_geometry(geometry))
+        {
+            tracing::warn!("Couldn't save window geometry: {e}");
+        }
+    }
+
+    /// Saves the window geometry, so that it can be restored the next time Ruffle is launched.
+    ///
+    /// A window without the GUI is sized for its movie, so it isn't worth remembering.
+    pub fn save_main_window_geometry(&self) {
+        if self.no_gui || !self.gui.preferences.remember_window_geometry() {
+            return;
+        }
+        let Some((position, size)) = self.current_windowed_geometry() else {
+            return;
+        };
+        let geometry = MainWindowGeometry {
+            x: position.x,
+            y: position.y,
+            width: size.width,
+            height: size.height,
+            maximized: self.window.is_maximized(),
+        };
+        if let Err(e) = self
+            .gui
+            .preferences
+            .write_preferences(|writer| writer.set_main_window_geometry(geometry))
+        {
+            tracing::warn!("Couldn't save window geometry: {e}");
+        }
+    }
+
+    /// The outer position and inner size of the window, or the ones to go back to when fullscreen.
+    fn current_windowed_geometry(&self) -> Option<(PhysicalPosition<i32>, PhysicalSize<u32>)> {
+        if let Some(geometry) = self.windowed_geometry {
+            return Some(geometry);
+        }
+        match self.window.outer_position() {
+            Ok(position) if self.window.is_minimized() != Some(true) => {
+                Some((position, self.window.inner_size()))
+            }
+            _ => None,
+        }
+    }
+
+    /// Restores the window geometry saved for the given movie, if any.
+    ///
+    /// Fullscreen isn't restored here, as it's only entered once the movie is loaded.
+    fn restore_window_geometry(&mut self, url: &Url) {
+        self.restored_window_geometry = self.gui.preferences.window_geometry(url);
+        let Some(geometry) = &self.restored_window_geometry else {
+            return;
+        };
+        if self.window.fullscreen().is_some() {
+            return;
+        }
+
+        let (position, size) = fit_to_monitors(
+            self.window.available_monitors(),
+            self.window
+                .primary_monitor()
+                .or_else(|| self.window.current_monitor()),
+            PhysicalPosition::new(geometry.x, geometry.y),
+            PhysicalSize::new(geometry.width, geometry.height),
+        );
+        self.window.set_maximized(false);
+        if let Some(new_size) = self.window.request_inner_size(size) {
+            self.resize(new_size);
+        }
+        self.window.set_outer_position(position);
+        if geometry.maximized {
+            self.window.set_maximized(true);
+        }
+    }
+
+    /// The window geometry that was restored for the open movie, if any.
+    pub fn restored_window_geometry(&self) -> Option<&WindowGeometry> {
+        self.restored_window_geometry.as_ref()
+    }
+
+    /// Starts recording the movie view to a sequence of PNG images in `path`,
+    /// capturing at most `fps` frames per second.
+    ///
+    /// Only the movie is recorded, at its native resolution, without any of the GUI.
+    pub fn start_recording(&mut self, path: PathBuf, fps: f64) -> std::io::Result<()> {
+        self.stop_recording();
+        let recorder = MovieRecorder::start(path, fps)?;
+        tracing::info!("Recording movie to {:?}", recorder.directory());
+        self.recorder = Some(recorder);
+        Ok(())
+    }
+
+    pub fn stop_recording(&mut self) {
+        if let Some(recorder) = self.recorder.take() {
+            recorder.stop();
+        }
+    }
+
+    pub fn is_recording(&self) -> bool {
+        self.recorder.is_some()
+    }
+
+    /// Copies the current frame of the movie to the clipboard, on the next render.
+    ///
+    /// Like recording, only the movie is captured, at the size it's currently rendered at.
+    pub fn copy_frame_to_clipboard(&mut self) {
+        self.copy_frame_requested = true;
+        self.window.request_redraw();
+    }
+
+    /// Resizes the window so that a movie of the given stage size is shown at `zoom` times its size.
+    ///
+    /// If that doesn't fit on the monitor, the window is made as large as possible
+    /// while keeping the movie's aspect ratio.
+    pub fn resize_to_movie(&mut self, movie_width: u32, movie_height: u32, zoom: f64) {
+        if self.window.fullscreen().is_some() || movie_width == 0 || movie_height == 0 {
+            return;
+        }
+        self.window.set_maximized(false);
+
+        let scale_factor = self.scale_factor();
+        let height_offset = self.height_offset();
+        let mut width = movie_width as f64 * zoom * scale_factor;
+        let mut height = movie_height as f64 * zoom * scale_factor;
+
+        // winit doesn't expose the work area, so leave room for the window decorations
+        // and settle for the whole monitor.
+        if let Some(monitor) = self.window.current_monitor() {
+            let outer_size = self.window.outer_size();
+            let inner_size = self.window.inner_size();
+            let max_width = monitor
+                .size()
+                .width
+                .saturating_sub(outer_size.width.saturating_sub(inner_size.width));
+            let max_height = monitor
+                .size()
+                .height
+                .saturating_sub(outer_size.height.saturating_sub(inner_size.height))
+                as f64
+                - height_offset;
+            let fit = (max_width as f64 / width).min(max_height / height);
+            if fit < 1.0 {
+                tracing::warn!(
+                    "The movie doesn't fit on the monitor at {}%, shrinking the window",
+                    zoom * 100.0
+                );
+                width *= fit;
+                height *= fit;
+            }
+        }
+
+        let size = PhysicalSize::new(
+            width.round().max(1.0) as u32,
+            (height + height_offset).round().max(1.0) as u32,
+        );
+        if let Some(new_size) = self.window.request_inner_size(size) {
+            self.resize(new_size);
+        }
+    }
+
+    /// The amount of physical pixels at the top of the window taken up by the menu bar, tab strip and timeline bar.
+    ///
+    /// The menu bar doesn't count when it's auto-hidden, as it then overlays the movie instead.
     pub fn height_offset(&self) -> f64 {
-        if self.window.fullscreen().is_some() || self.no_gui {
+        self.height_offset_when(self.window.fullscreen().is_some())
+    }
+
+    /// The [`GuiController::height_offset`] the window has, or will have, in or out of fullscreen.
+    ///
+    /// Fullscreen changes don't always apply right away, so this doesn't ask the window.
+    fn height_offset_when(&self, fullscreen: bool) -> f64 {
+        if fullscreen || self.no_gui {
+            return 0.0;
+        }
+        let tab_strip_height = if self.gui.tab_strip.has_tabs() {
+            TAB_STRIP_HEIGHT as f64 * self.scale_factor()
+        } else {
             0.0
+        };
+        let timeline_bar_height = if self.gui.timeline_bar.is_visible() {
+            TIMELINE_BAR_HEIGHT as f64 * self.scale_factor()
         } else {
-            MENU_HEIGHT as f64 * self.window.scale_factor()
-        }
+            0.0
+        };
+        menu_height_offset(
+            fullscreen,
+            self.scale_factor(),
+            self.no_gui,
+            self.menu_visibility,
+        ) + tab_strip_height
+            + timeline_bar_height
+    }
+
+    /// Enters or leaves fullscreen, in the mode chosen in the preferences.
+    ///
+    /// The movie view is moved to where it'll be right away, so that it isn't drawn
+    /// below a menu that's no longer there, or under one that's back, until the window is resized.
+    /// The surface itself is only reconfigured once the new size is known.
+    pub fn set_fullscreen(&mut self, fullscreen: bool) {
+        self.window.set_fullscreen(
+            fullscreen.then(|| fullscreen_mode(&self.window, &self.gui.preferences)),
+        );
+        self.movie_view_renderer.update_resolution(
+            &self.descriptors,
+            self.height_offset_when(fullscreen),
+            self.size.height,
+        );
+        self.window.request_redraw();
+    }
+
+    /// The size of the area of the window showing the movie, in physical pixels.
+    fn movie_view_size(&self) -> (f64, f64) {
+        (
+            (self.size.width as f64).max(1.0),
+            (self.size.height as f64 - self.height_offset()).max(1.0),
+        )
+    }
+
+    /// Converts a position in the window to a position relative to the movie view, see [`ViewTransform`].
+    fn window_to_view_position(&self, position: PhysicalPosition<f64>) -> (f64, f64) {
+        let (width, height) = self.movie_view_size();
+        (
+            position.x / width,
+            (position.y - self.height_offset()) / height,
+        )
     }
 
     pub fn window_to_movie_position(&self, position: PhysicalPosition<f64>) -> (f64, f64) {
-        let x = position.x;
-        let y = position.y - self.height_offset();
-        (x, y)
+        let (width, height) = self.movie_view_size();
+        let (x, y) = self
+            .view_transform
+            .view_to_movie(self.window_to_view_position(position));
+        (x * width, y * height)
     }
 
     pub fn movie_to_window_position(&self, x: f64, y: f64) -> PhysicalPosition<f64> {
-        let y = y + self.height_offset();
-        PhysicalPosition::new(x, y)
+        let (width, height) = self.movie_view_size();
+        let (x, y) = self.view_transform.movie_to_view((x / width, y / height));
+        PhysicalPosition::new(x * width, y * height + self.height_offset())
+    }
+
+    /// The position in the window of the center of the movie view, following its zoom.
+    pub fn movie_center_position(&self) -> PhysicalPosition<f64> {
+        let (width, height) = self.movie_view_size();
+        self.movie_to_window_position(width / 2.0, height / 2.0)
     }
 
     pub fn render(&mut self, mut player: Option<MutexGuard<Player>>) {
+        self.apply_frame_latency_changes();
+        let menu_visibility_changed = self.apply_menu_visibility_changes();
+        let scale_factor_changed = self.apply_scale_factor_override_changes();
+        let timeline_bar_changed = self.update_timeline_bar(player.as_deref_mut());
+        if menu_visibility_changed || scale_factor_changed || timeline_bar_changed {
+            if let Some(player) = player.as_deref_mut() {
+                player.set_viewport_dimensions(ViewportDimensions {
+                    width: self.size.width,
+                    height: self.size.height.saturating_sub(self.height_offset() as u32),
+                    scale_factor: self.scale_factor(),
+                });
+            }
+        }
+
+        let background_color = self.update_background_color(player.as_deref_mut());
+
+        if let Some(show_fps_in_title) = latest_value(&mut self.show_fps_in_title_watcher) {
+            self.title.set_show_fps(show_fps_in_title);
+        }
+        let paused = player.as_deref().is_some_and(|player| !player.is_playing());
+        let frame = player.as_deref().and_then(Player::current_frame);
+        let muted = self.gui.dialogs.volume_controls.is_muted();
+        self.title.update(
+            &self.window,
+            &self.gui.preferences.language(),
+            paused,
+            frame,
+            muted,
+        );
+
         let surface_texture = match self.surface.get_current_texture() {
             Ok(surface_texture) => surface_texture,
             Err(e @ (SurfaceError::Lost | SurfaceError::Outdated)) => {
@@ -301,30 +1360,74 @@ impl GuiController {
                 panic!("wgpu: Out of memory: no more memory left to allocate a new frame");
             }
             Err(SurfaceError::Other) => {
-                // Generic error, not much we can do.
-                panic!("wgpu: Acquiring a texture failed with a generic error");
+                // Generic error, which some drivers return for transient issues.
+                // Try again with a fresh surface, and only give up if that keeps failing.
+                self.consecutive_surface_errors += 1;
+                tracing::warn!(
+                    "Surface became unavailable with a generic error ({} in a row), reconfiguring",
+                    self.consecutive_surface_errors
+                );
+                if self.consecutive_surface_errors == MAX_CONSECUTIVE_SURFACE_ERRORS {
+                    // A dialog drawn by egui would end up on the very surface that keeps failing.
+                    // It's shown without blocking the event loop, which keeps trying to render.
+                    let locale = &self.gui.locale;
+                    let dialog = rfd::AsyncMessageDialog::new()
+                        .set_level(rfd::MessageLevel::Error)
+                        .set_title(text(locale, "message-dialog-surface-error-title"))
+                        .set_description(text(locale, "message-dialog-surface-error-description"))
+                        .set_buttons(rfd::MessageButtons::Ok)
+                        .set_parent(&self.window)
+                        .show();
+                    tokio::spawn(dialog);
+                }
+                self.reconfigure_surface();
+                return;
             }
         };
+        self.consecutive_surface_errors = 0;
+
+        let mut copied_frame = None;
+        if mem::take(&mut self.copy_frame_requested) {
+            if let Some(player) = player.as_deref_mut() {
+                let image = self.render_movie_at_viewport_size(player);
+                copied_frame = Some(ColorImage::from_rgba_unmultiplied(
+                    [image.width() as usize, image.height() as usize],
+                    image.as_raw(),
+                ));
+                self.gui.show_notification("controls-menu-frame-copied");
+            }
+        }
 
         let raw_input = self.egui_winit.take_egui_input(&self.window);
-        let show_menu = self.window.fullscreen().is_none() && !self.no_gui;
+        let show_menu = self.show_menu();
+        let show_tab_strip = self.show_tab_strip();
+        let show_timeline_bar = self.show_timeline_bar();
+        let height_offset = self.height_offset();
+        let is_recording = self.is_recording();
+        let zoom = self.view_transform.scale();
         let mut full_output = self.egui_winit.egui_ctx().run(raw_input, |context| {
+            if let Some(image) = copied_frame.take() {
+                // egui passes this on to the clipboard with the rest of the platform output.
+                context.copy_image(image);
+            }
             self.gui.update(
                 context,
                 show_menu,
+                show_tab_strip,
+                show_timeline_bar,
                 player.as_deref_mut(),
-                if show_menu {
-                    MENU_HEIGHT as f64 * self.window.scale_factor()
-                } else {
-                    0.0
-                },
+                height_offset,
+                is_recording,
+                zoom,
             );
         });
-        self.repaint_after = full_output
-            .viewport_output
-            .get(&ViewportId::ROOT)
+        let mut viewport_output = mem::take(&mut full_output.viewport_output);
+        self.repaint_after = viewport_output
+            .remove(&ViewportId::ROOT)
             .expect("Root viewport must exist")
             .repaint_delay;
+        self.last_update = Instant::now();
+        self.update_child_viewports(viewport_output);
 
         // If we're not in a UI, tell egui which cursor we prefer to use instead
         if !self.egui_winit.egui_ctx().wants_pointer_input() {
@@ -335,6 +1438,14 @@ impl GuiController {
                         .cursor();
             }
         }
+        // After a while without mouse activity in fullscreen, get the cursor out of the way.
+        // This only ever hides it, so a movie that hid the cursor itself keeps it hidden.
+        self.cursor_auto_hidden = self
+            .cursor_hide_time()
+            .is_some_and(|time| Instant::now() >= time);
+        if self.cursor_auto_hidden || self.mouse_locked {
+            full_output.platform_output.cursor_icon = CursorIcon::None;
+        }
         self.egui_winit
             .handle_platform_output(&self.window, full_output.platform_output);
 
@@ -343,10 +1454,9 @@ impl GuiController {
             .egui_ctx()
             .tessellate(full_output.shapes, full_output.pixels_per_point);
 
-        let scale_factor = self.window.scale_factor() as f32;
         let screen_descriptor = egui_wgpu::ScreenDescriptor {
             size_in_pixels: [self.size.width, self.size.height],
-            pixels_per_point: scale_factor,
+            pixels_per_point: self.scale_factor() as f32,
         };
 
         let mut encoder =
@@ -373,26 +1483,28 @@ impl GuiController {
             &screen_descriptor,
         );
 
-        let movie_view = if let Some(player) = player.as_deref_mut() {
-            let renderer =
-                <dyn Any>::downcast_ref::<WgpuRenderBackend<MovieView>>(player.renderer_mut())
-                    .expect("Renderer must be correct type");
-            Some(renderer.target())
-        } else {
-            None
-        };
+        let movie_view = player.as_deref_mut().map(player_movie_view);
 
         {
             let surface_view = surface_texture.texture.create_view(&Default::default());
 
+            // With MSAA, render to the multisampled target and resolve it into the surface.
+            let (view, resolve_target, store) = match &self.msaa_view {
+                Some(msaa_view) => (msaa_view, Some(&surface_view), wgpu::StoreOp::Discard),
+                None => (&surface_view, None, wgpu::StoreOp::Store),
+            };
             let mut render_pass = encoder
                 .begin_render_pass(&wgpu::RenderPassDescriptor {
                     color_attachments: &[Some(wgpu::RenderPassColorAttachment {
-                        view: &surface_view,
-                        resolve_target: None,
+                        view,
+                        resolve_target,
                         ops: wgpu::Operations {
-                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
-                            store: wgpu::StoreOp::Store,
+                            load: wgpu::LoadOp::Clear(if self.transparent {
+                                wgpu::Color::TRANSPARENT
+                            } else {
+                                clear_color(background_color, self.surface_format.is_srgb())
+                            }),
+                            store,
                         },
                     })],
                     label: Some("egui_render"),
@@ -416,28 +1528,405 @@ impl GuiController {
         self.descriptors.queue.submit(command_buffers);
         self.window.pre_present_notify();
         surface_texture.present();
+
+        if let Some(picture_in_picture) = &self.picture_in_picture {
+            picture_in_picture.window().request_redraw();
+        }
+
+        if let Some(player) = player.as_deref_mut() {
+            if self
+                .recorder
+                .as_ref()
+                .is_some_and(|recorder| recorder.wants_frame())
+            {
+                let image = self.render_movie_at_viewport_size(player);
+                if let Some(recorder) = &mut self.recorder {
+                    recorder.push_frame(image);
+                }
+            }
+        }
+    }
+
+    /// Grabs and hides the cursor, so that the mouse can be moved without ever leaving the window.
+    ///
+    /// Returns false if the platform doesn't support grabbing the cursor.
+    pub fn lock_mouse(&mut self) -> bool {
+        // Not every platform can keep the cursor in place, but confining it
+        // to the window still keeps the raw mouse movement coming.
+        let result = self
+            .window
+            .set_cursor_grab(CursorGrabMode::Locked)
+            .or_else(|_| self.window.set_cursor_grab(CursorGrabMode::Confined));
+        if let Err(e) = result {
+            tracing::warn!("Couldn't lock the mouse pointer: {e}");
+            return false;
+        }
+        self.mouse_locked = true;
+        self.gui.menu_bar.mouse_locked = true;
+        self.window.request_redraw();
+        true
+    }
+
+    /// Releases the cursor, which makes the menu usable again.
+    pub fn unlock_mouse(&mut self) {
+        if let Err(e) = self.window.set_cursor_grab(CursorGrabMode::None) {
+            tracing::warn!("Couldn't unlock the mouse pointer: {e}");
+        }
+        self.mouse_locked = false;
+        self.gui.menu_bar.mouse_locked = false;
+        self.window.request_redraw();
+    }
+
+    /// The size the picture-in-picture window opens at, keeping the aspect ratio of the movie view.
+    pub fn picture_in_picture_size(&self) -> LogicalSize<f64> {
+        let (width, height) = self.movie_view_size();
+        LogicalSize::new(
+            PICTURE_IN_PICTURE_WIDTH,
+            PICTURE_IN_PICTURE_WIDTH * height / width,
+        )
+    }
+
+    /// Starts showing the movie in `window` as well, see [`PictureInPicture`].
+    pub fn open_picture_in_picture(&mut self, window: Window) {
+        match PictureInPicture::new(
+            Arc::new(window),
+            &self.descriptors,
+            &self.movie_view_renderer,
+        ) {
+            Ok(picture_in_picture) => {
+                picture_in_picture.window().request_redraw();
+                self.picture_in_picture = Some(picture_in_picture);
+                self.gui.menu_bar.picture_in_picture = true;
+            }
+            Err(e) => tracing::error!("Couldn't create the picture-in-picture window: {e}"),
+        }
+    }
+
+    /// Closes the picture-in-picture window, if it's open.
+    pub fn close_picture_in_picture(&mut self) {
+        self.picture_in_picture = None;
+        self.gui.menu_bar.picture_in_picture = false;
+    }
+
+    pub fn picture_in_picture_window(&self) -> Option<&Arc<Window>> {
+        self.picture_in_picture
+            .as_ref()
+            .map(|picture_in_picture| picture_in_picture.window())
+    }
+
+    pub fn is_picture_in_picture_window(&self, window_id: WindowId) -> bool {
+        self.picture_in_picture_window()
+            .is_some_and(|window| window.id() == window_id)
+    }
+
+    pub fn resize_picture_in_picture(&mut self, size: PhysicalSize<u32>) {
+        let (width, height) = self.movie_view_size();
+        if let Some(picture_in_picture) = &mut self.picture_in_picture {
+            picture_in_picture.resize(&self.descriptors, size, width / height);
+        }
+    }
+
+    pub fn hover_picture_in_picture(&self, position: PhysicalPosition<f64>) {
+        if let Some(picture_in_picture) = &self.picture_in_picture {
+            picture_in_picture.hover(position);
+        }
+    }
+
+    /// Starts resizing or moving the picture-in-picture window by a press at `position`,
+    /// see [`PictureInPicture::drag`].
+    pub fn drag_picture_in_picture(
+        &self,
+        position: PhysicalPosition<f64>,
+        move_window: bool,
+    ) -> bool {
+        self.picture_in_picture
+            .as_ref()
+            .is_some_and(|picture_in_picture| picture_in_picture.drag(position, move_window))
+    }
+
+    /// Converts a position in the picture-in-picture window to a position in the movie.
+    ///
+    /// The window shows the whole movie view of the main window scaled down,
+    /// so this goes through the same position in the main window.
+    pub fn picture_in_picture_to_movie_position(
+        &self,
+        position: PhysicalPosition<f64>,
+    ) -> Option<(f64, f64)> {
+        let (x, y) = self
+            .picture_in_picture
+            .as_ref()?
+            .window_to_view_position(position);
+        let (width, height) = self.movie_view_size();
+        Some(self.window_to_movie_position(PhysicalPosition::new(
+            x * width,
+            y * height + self.height_offset(),
+        )))
+    }
+
+    pub fn render_picture_in_picture(&mut self, player: Option<&mut Player>) {
+        if let Some(picture_in_picture) = &mut self.picture_in_picture {
+            let movie_view = player.map(player_movie_view);
+            picture_in_picture.render(&self.descriptors, movie_view);
+        }
+    }
+
+    /// Keeps the windows of child viewports in sync with the viewports egui showed this frame,
+    /// closing those that are gone.
+    ///
+    /// Only viewports shown directly by the main window are supported, not those nested in another one.
+    fn update_child_viewports(&mut self, mut viewport_output: ViewportIdMap<ViewportOutput>) {
+        self.viewports
+            .retain(|id, _| viewport_output.contains_key(id));
+        self.failed_viewports
+            .retain(|id| viewport_output.contains_key(id));
+        self.pending_viewports.clear();
+        for (id, output) in viewport_output.drain() {
+            if let Some(viewport) = self.viewports.get_mut(&id) {
+                viewport.update(output);
+            } else if !self.failed_viewports.contains(&id) {
+                self.pending_viewports.insert(id, output);
+            }
+        }
+    }
+
+    /// Creates windows for the viewports egui started showing since the last call.
+    ///
+    /// This can't happen while rendering, as creating windows needs the event loop.
+    pub fn create_pending_viewports(&mut self, event_loop: &ActiveEventLoop) {
+        for (id, output) in mem::take(&mut self.pending_viewports) {
+            match ChildViewport::new(
+                event_loop,
+                self.egui_winit.egui_ctx(),
+                id,
+                &output.builder,
+                output.viewport_ui_cb,
+                &self.descriptors,
+                self.surface_format,
+                self.sample_count,
+            ) {
+                Ok(viewport) => {
+                    self.viewports.insert(id, viewport);
+                }
+                Err(e) => {
+                    tracing::error!("Couldn't create a window for viewport {id:?}: {e}");
+                    self.failed_viewports.insert(id);
+                }
+            }
+        }
+    }
+
+    pub fn is_child_viewport_window(&self, window_id: WindowId) -> bool {
+        self.viewports
+            .values()
+            .any(|viewport| viewport.window().id() == window_id)
+    }
+
+    /// Handles an event of the window of a child viewport, which only ever goes to egui.
+    pub fn handle_child_viewport_event(&mut self, window_id: WindowId, event: &WindowEvent) {
+        let Some(viewport) = self
+            .viewports
+            .values_mut()
+            .find(|viewport| viewport.window().id() == window_id)
+        else {
+            return;
+        };
+        if matches!(event, WindowEvent::RedrawRequested) {
+            // The main window may need to react, e.g. to close a viewport that's no longer shown.
+            if viewport.render(&self.descriptors, &mut self.egui_renderer) {
+                self.window.request_redraw();
+            }
+        } else {
+            viewport.handle_event(&self.descriptors, event);
+        }
+    }
+
+    /// Requests a redraw of the child viewports that want to be repainted by now.
+    pub fn request_child_viewport_redraws(&self) {
+        for viewport in self.viewports.values() {
+            if viewport.needs_render() {
+                viewport.window().request_redraw();
+            }
+        }
     }
 
+    /// Renders the movie alone to an image of the given size, without the GUI or the window's surface.
+    ///
+    /// See [`render_movie_to_image`] for how the player is rendered at a size other than the window's.
+    pub fn render_movie_to_image(
+        &self,
+        width: u32,
+        height: u32,
+        player: &mut Player,
+    ) -> image::RgbaImage {
+        render_movie_to_image(&self.descriptors, player, width, height)
+    }
+
+    /// Reads back the movie as it's shown in the window, which is usually already rendered.
+    fn render_movie_at_viewport_size(&self, player: &mut Player) -> image::RgbaImage {
+        let ViewportDimensions { width, height, .. } = player.viewport_dimensions();
+        self.render_movie_to_image(width, height, player)
+    }
+
+    /// Opens the context menu at the given position in the window, or at the pointer if there's none.
     pub fn show_context_menu(
         &mut self,
         menu: Vec<ruffle_core::ContextMenuItem>,
+        zoom: bool,
+        position: Option<PhysicalPosition<f64>>,
         close_event: PlayerEvent,
     ) {
-        self.gui.show_context_menu(menu, close_event);
+        let position = position.map(|position| self.window_to_egui_position(position));
+        self.gui
+            .show_context_menu(menu, zoom, position, close_event);
+    }
+
+    pub fn close_context_menu(&mut self, player: &mut Player) {
+        self.gui.close_context_menu(player);
     }
 
     pub fn is_context_menu_visible(&self) -> bool {
         self.gui.is_context_menu_visible()
     }
 
+    /// Shows or hides the indicator that files dragged over the window can be dropped to open them.
+    pub fn set_files_hovered(&mut self, hovered: bool) {
+        self.gui.set_files_hovered(hovered);
+        self.window.request_redraw();
+    }
+
+    /// Briefly shows the given message at the bottom of the window.
+    pub fn show_notification(&mut self, message: &'static str) {
+        self.gui.show_notification(message);
+        self.window.request_redraw();
+    }
+
+    /// Starts tracking how long the "exit fullscreen" key is held for.
+    pub fn begin_fullscreen_exit_hold(&mut self) {
+        self.gui.begin_fullscreen_exit_hold();
+        self.window.request_redraw();
+    }
+
+    pub fn end_fullscreen_exit_hold(&mut self) {
+        self.gui.end_fullscreen_exit_hold();
+    }
+
+    /// Briefly highlights where a finger was held down for long enough to open the context menu.
+    pub fn show_long_press_cue(&mut self, position: PhysicalPosition<f64>) {
+        let position = self.window_to_egui_position(position);
+        self.gui.show_long_press_cue(position);
+        self.window.request_redraw();
+    }
+
+    fn window_to_egui_position(&self, position: PhysicalPosition<f64>) -> Pos2 {
+        let position = position.to_logical::<f32>(self.scale_factor());
+        pos2(position.x, position.y)
+    }
+
     pub fn needs_render(&self) -> bool {
         Instant::now().duration_since(self.last_update) >= self.repaint_after
+            || self
+                .pending_cursor_hide_time()
+                .is_some_and(|time| Instant::now() >= time)
+    }
+
+    /// When the cursor is hidden for a lack of mouse activity, or `None` if it isn't,
+    /// as the window isn't fullscreen or hiding the cursor is turned off.
+    fn cursor_hide_time(&self) -> Option<Instant> {
+        self.window.fullscreen()?;
+        let delay = self.gui.preferences.fullscreen_hide_cursor_after()?;
+        self.last_mouse_activity.checked_add(delay)
+    }
+
+    /// When the cursor is about to be hidden, i.e. the GUI has to be rendered again.
+    fn pending_cursor_hide_time(&self) -> Option<Instant> {
+        self.cursor_hide_time().filter(|_| !self.cursor_auto_hidden)
+    }
+
+    /// When the GUI wants to be rendered next, or `None` if it's idle until something happens.
+    pub fn next_repaint_time(&self) -> Option<Instant> {
+        self.viewports
+            .values()
+            .map(ChildViewport::next_repaint_time)
+            .chain([
+                self.last_update.checked_add(self.repaint_after),
+                self.pending_cursor_hide_time(),
+            ])
+            .flatten()
+            .min()
+    }
+
+    /// Returns true if a new frame of the movie should be rendered now.
+    ///
+    /// While the movie has looked the same for a while, it's only redrawn every [`IDLE_REDRAW_INTERVAL`].
+    /// The movie keeps running as usual in the meantime, so this doesn't affect its audio.
+    pub fn should_render_movie(&self) -> bool {
+        !self.idle_power_save
+            || self.unchanged_frames < IDLE_FRAMES_BEFORE_POWER_SAVE
+            || self.last_movie_render.elapsed() >= IDLE_REDRAW_INTERVAL
+    }
+
+    /// Records whether a newly rendered frame of the movie looked any different from the last one.
+    pub fn on_movie_rendered(&mut self, player: &mut Player) {
+        if let Some(idle_power_save) = latest_value(&mut self.idle_power_save_watcher) {
+            self.idle_power_save = idle_power_save;
+            self.unchanged_frames = 0;
+        }
+        player.set_detect_unchanged_frames(self.idle_power_save);
+        self.last_movie_render = Instant::now();
+        if !self.idle_power_save || movie_frame_changed(player) {
+            self.unchanged_frames = 0;
+        } else {
+            self.unchanged_frames = self.unchanged_frames.saturating_add(1);
+        }
     }
 
     pub fn show_open_dialog(&mut self) {
         self.gui.dialogs.open_file_advanced()
     }
 
+    /// Opens the movie at the URL on the clipboard.
+    pub fn open_from_clipboard(&mut self) {
+        let text = self.egui_winit.clipboard_text().unwrap_or_default();
+        self.gui.open_url_text(&text);
+        self.window.request_redraw();
+    }
+
+    /// Warns the user that text in the GUI may not display correctly.
+    pub fn show_missing_fonts_warning(&mut self, missing_fonts: MissingFonts) {
+        let locale = &missing_fonts.locale;
+        let description = text_with_args(
+            locale,
+            "message-dialog-missing-fonts-description",
+            &HashMap::from([
+                (
+                    "locale".into(),
+                    FluentValue::String(locale.to_string().into()),
+                ),
+                (
+                    "families".into(),
+                    FluentValue::String(missing_fonts.families.join(", ").into()),
+                ),
+                (
+                    "suggestion".into(),
+                    FluentValue::String(
+                        missing_fonts
+                            .families
+                            .first()
+                            .cloned()
+                            .unwrap_or_default()
+                            .into(),
+                    ),
+                ),
+            ]),
+        );
+        self.open_dialog(DialogDescriptor::ShowMessage(
+            MessageDialogConfiguration::new(
+                LocalizableText::LocalizedText("message-dialog-missing-fonts-title"),
+                LocalizableText::NonLocalizedText(description),
+            ),
+        ));
+    }
+
     pub fn open_dialog(&mut self, dialog_event: DialogDescriptor) {
         self.gui.dialogs.open_dialog(dialog_event);
     }
@@ -461,6 +1950,197 @@ impl GuiController {
     }
 }
 
+/// Returns the preferred sample count for the GUI if the surface format supports it, or 1 otherwise.
+fn supported_sample_count(
+    descriptors: &Descriptors,
+    format: wgpu::TextureFormat,
+    preferred: u32,
+) -> u32 {
+    if preferred <= 1 {
+        return 1;
+    }
+    let features = if descriptors
+        .device
+        .features()
+        .contains(wgpu::Features::TEXTURE_ADAPTER_SPECIFIC_FORMAT_FEATURES)
+    {
+        descriptors.adapter.get_texture_format_features(format)
+    } else {
+        format.guaranteed_format_features(descriptors.device.features())
+    };
+    if features.flags.sample_count_supported(preferred)
+        && features
+            .flags
+            .contains(wgpu::TextureFormatFeatureFlags::MULTISAMPLE_RESOLVE)
+    {
+        preferred
+    } else {
+        tracing::warn!(
+            "{preferred}x MSAA isn't supported for {format:?}, disabling it for the GUI"
+        );
+        1
+    }
+}
+
+pub(super) fn create_msaa_view(
+    device: &wgpu::Device,
+    format: wgpu::TextureFormat,
+    size: PhysicalSize<u32>,
+    sample_count: u32,
+) -> Option<wgpu::TextureView> {
+    if sample_count <= 1 {
+        return None;
+    }
+    let texture = device.create_texture(&wgpu::TextureDescriptor {
+        label: Some("GUI multisampled framebuffer"),
+        size: wgpu::Extent3d {
+            width: size.width.max(1),
+            height: size.height.max(1),
+            depth_or_array_layers: 1,
+        },
+        mip_level_count: 1,
+        sample_count,
+        dimension: wgpu::TextureDimension::D2,
+        format,
+        usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+        view_formats: &[],
+    });
+    Some(texture.create_view(&Default::default()))
+}
+
+/// Whether the key used for shortcuts is held, i.e. Cmd on macOS and Ctrl elsewhere.
+fn command_held(modifiers: ModifiersState) -> bool {
+    if cfg!(target_os = "macos") {
+        modifiers.super_key()
+    } else {
+        modifiers.control_key()
+    }
+}
+
+/// Moves and shrinks a saved window geometry so that it fits on a monitor.
+///
+/// The monitor it overlaps the most is used, or `fallback` if it's entirely off-screen,
+/// which happens when the monitor it was on has been disconnected or rearranged.
+pub fn fit_to_monitors(
+    monitors: impl Iterator<Item = MonitorHandle>,
+    fallback: Option<MonitorHandle>,
+    position: PhysicalPosition<i32>,
+    size: PhysicalSize<u32>,
+) -> (PhysicalPosition<i32>, PhysicalSize<u32>) {
+    let overlap = |monitor: &MonitorHandle| {
+        let monitor_position = monitor.position();
+        let monitor_size = monitor.size();
+        let width = (position.x as i64 + size.width as i64)
+            .min(monitor_position.x as i64 + monitor_size.width as i64)
+            - (position.x as i64).max(monitor_position.x as i64);
+        let height = (position.y as i64 + size.height as i64)
+            .min(monitor_position.y as i64 + monitor_size.height as i64)
+            - (position.y as i64).max(monitor_position.y as i64);
+        width.max(0) * height.max(0)
+    };
+    let monitor = monitors
+        .map(|monitor| (overlap(&monitor), monitor))
+        .filter(|(overlap, _)| *overlap > 0)
+        .max_by_key(|(overlap, _)| *overlap)
+        .map(|(_, monitor)| monitor);
+    let Some(monitor) = monitor.or_else(|| {
+        tracing::info!("The saved window position is off-screen, moving it to the primary monitor");
+        fallback
+    }) else {
+        return (position, size);
+    };
+
+    let monitor_position = monitor.position();
+    let monitor_size = monitor.size();
+    let size = PhysicalSize::new(
+        size.width.min(monitor_size.width),
+        size.height.min(monitor_size.height),
+    );
+    let position = PhysicalPosition::new(
+        position.x.clamp(
+            monitor_position.x,
+            monitor_position.x + (monitor_size.width - size.width) as i32,
+        ),
+        position.y.clamp(
+            monitor_position.y,
+            monitor_position.y + (monitor_size.height - size.height) as i32,
+        ),
+    );
+    (position, size)
+}
+
+/// Returns the smallest the window may be made, so that the menu and some of the movie always fit.
+///
+/// Without the GUI, or with a menu that's never shown, only the movie needs to fit.
+pub fn min_window_size(no_gui: bool, menu_visibility: MenuVisibility) -> LogicalSize<u32> {
+    if no_gui || menu_visibility == MenuVisibility::Never {
+        (MIN_MOVIE_SIZE, MIN_MOVIE_SIZE)
+    } else {
+        (MIN_MENU_WIDTH, MENU_HEIGHT + MIN_MOVIE_SIZE)
+    }
+    .into()
+}
+
+/// Clamps a size to one the surface can be configured with, which can't be empty or larger than a texture.
+fn clamp_surface_size(size: PhysicalSize<u32>, descriptors: &Descriptors) -> PhysicalSize<u32> {
+    let max_size = descriptors.limits.max_texture_dimension_2d;
+    PhysicalSize::new(
+        size.width.clamp(1, max_size),
+        size.height.clamp(1, max_size),
+    )
+}
+
+fn menu_height_offset(
+    fullscreen: bool,
+    scale_factor: f64,
+    no_gui: bool,
+    menu_visibility: MenuVisibility,
+) -> f64 {
+    if fullscreen || no_gui || menu_visibility != MenuVisibility::Always {
+        0.0
+    } else {
+        MENU_HEIGHT as f64 * scale_factor
+    }
+}
+
+/// Returns the alpha mode to use for a transparent window, if the surface supports one.
+///
+/// The movie and egui are both rendered with premultiplied alpha, which is preferred.
+fn transparent_alpha_mode(
+    capabilities: &wgpu::SurfaceCapabilities,
+) -> Option<wgpu::CompositeAlphaMode> {
+    [
+        wgpu::CompositeAlphaMode::PreMultiplied,
+        wgpu::CompositeAlphaMode::PostMultiplied,
+    ]
+    .into_iter()
+    .find(|mode| capabilities.alpha_modes.contains(mode))
+}
+
+/// Converts an sRGB color to a clear color, which is in linear space for sRGB formats.
+fn clear_color(color: [u8; 3], srgb_format: bool) -> wgpu::Color {
+    let [r, g, b] = color.map(|c| {
+        if srgb_format {
+            egui::ecolor::linear_f32_from_gamma_u8(c) as f64
+        } else {
+            c as f64 / 255.0
+        }
+    });
+    wgpu::Color { r, g, b, a: 1.0 }
+}
+
+/// Returns the most recent value sent to a preference watcher, if any.
+fn latest_value<T: Clone>(watcher: &mut Receiver<T>) -> Option<T> {
+    let mut latest = None;
+    loop {
+        match watcher.try_recv() {
+            Ok(value) => latest = Some(value),
+            Err(TryRecvError::Lagged(_)) => continue,
+            Err(TryRecvError::Empty | TryRecvError::Closed) => return latest,
+        }
+    }
+}
+
 fn create_wgpu_instance(
     preferred_backends: wgpu::Backends,
 ) -> anyhow::Result<(wgpu::Instance, wgpu::Backends)> {
@@ -494,6 +2174,16 @@ fn create_wgpu_instance(
     ))
 }
 
+/// Lists the graphics backends that have at least one adapter on this machine.
+///
+/// Backends that were built in but can't be used, such as Vulkan without a driver, are left out.
+pub fn available_backends() -> Vec<wgpu::Backends> {
+    wgpu::Backends::all()
+        .iter()
+        .filter(|&backend| try_wgpu_backend(backend).is_some())
+        .collect()
+}
+
 fn try_wgpu_backend(backend: wgpu::Backends) -> Option<wgpu::Instance> {
     let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
         backends: backend,
@@ -507,11 +2197,14 @@ fn try_wgpu_backend(backend: wgpu::Backends) -> Option<wgpu::Instance> {
     }
 }
 
-// Load fallback fonts
+/// Loads the fonts used by the GUI.
+///
+/// Also returns which fonts are missing, if none of the fonts needed
+/// by the script of `locale` (e.g. CJK) could be registered.
 fn load_system_fonts(
     font_database: &Database,
-    locale: unic_langid::LanguageIdentifier,
-) -> egui::FontDefinitions {
+    locale: LanguageIdentifier,
+) -> (egui::FontDefinitions, Option<MissingFonts>) {
     let mut fd: FontDefinitions = egui::FontDefinitions::default();
 
     let lang = locale.language.as_str();
@@ -520,11 +2213,13 @@ fn load_system_fonts(
     let is_zh = lang == "zh";
     let is_sc = is_zh && locale.to_string().as_str() == "zh-CN";
     let is_tc = is_zh && !is_sc;
+    let is_he = lang == "he";
+    let is_ar = lang == "ar";
 
     let mut queries: PrioritizedQueries = Vec::new();
 
     // The main font
-    queries.push((1, vec![Family::SansSerif]));
+    queries.push((1, vec![Family::SansSerif], false));
 
     // Pan-CJK fonts
     queries.push((
@@ -535,6 +2230,7 @@ fn load_system_fonts(
             Family::Name("WenQuanYi Zen Hei"), // Open font
             Family::Name("Arial Unicode MS"),  // MacOS
         ],
+        is_ja || is_ko || is_zh,
     ));
 
     // Korean
@@ -544,6 +2240,7 @@ fn load_system_fonts(
             Family::Name("Noto Sans CJK KR"), // Open font
             Family::Name("Malgun Gothic"),    // Windows
         ],
+        is_ko,
     ));
 
     // Japanese
@@ -553,6 +2250,7 @@ fn load_system_fonts(
             Family::Name("Noto Sans CJK JP"), // Open font
             Family::Name("MS UI Gothic"),     // Windows
         ],
+        is_ja,
     ));
 
     // Chinese Simplified
@@ -562,6 +2260,7 @@ fn load_system_fonts(
             Family::Name("Noto Sans CJK SC"), // Open font
             Family::Name("Microsoft YaHei"),  // Windows
         ],
+        is_sc,
     ));
 
     // Chinese Traditional
@@ -571,6 +2270,7 @@ fn load_system_fonts(
             Family::Name("Noto Sans CJK TC"),   // Open font
             Family::Name("Microsoft JhengHei"), // Windows
         ],
+        is_tc,
     ));
 
     // Hebrew
@@ -579,6 +2279,7 @@ fn load_system_fonts(
         vec![
             Family::Name("Noto Sans Hebrew"), // Open font
         ],
+        is_he,
     ));
 
     // Arabic
@@ -587,31 +2288,54 @@ fn load_system_fonts(
         vec![
             Family::Name("Noto Sans Arabic"), // Open font
         ],
+        is_ar,
     ));
 
-    register_family(
+    let locale_families: Vec<String> = queries
+        .iter()
+        .filter(|(_, _, for_locale)| *for_locale)
+        .flat_map(|(_, query, _)| query.iter())
+        .map(|family| font_database.family_name(family).to_string())
+        .collect();
+
+    let registered_for_locale = register_family(
         font_database,
         &mut fd,
         egui::FontFamily::Proportional,
         queries,
     );
 
-    fd
+    let missing_fonts = if !locale_families.is_empty() && !registered_for_locale {
+        tracing::warn!("No fonts found for the {locale} locale, tried {locale_families:?}");
+        Some(MissingFonts {
+            locale,
+            families: locale_families,
+        })
+    } else {
+        None
+    };
+
+    (fd, missing_fonts)
 }
 
 type FamilyQuery<'a> = Vec<Family<'a>>;
-type PrioritizedQueries<'a> = Vec<(usize, FamilyQuery<'a>)>;
+/// Font queries with their priority, and whether they're needed by the current locale.
+type PrioritizedQueries<'a> = Vec<(usize, FamilyQuery<'a>, bool)>;
 
+/// Returns true if a font was registered for any of the queries needed by the current locale.
 fn register_family(
     font_database: &Database,
     fd: &mut FontDefinitions,
     family: egui::FontFamily,
     mut queries: PrioritizedQueries<'_>,
-) {
-    queries.sort_by_key(|(priority, _)| *priority);
-    for (_, query) in queries {
-        register_family_font(font_database, fd, family.clone(), &query);
+) -> bool {
+    queries.sort_by_key(|(priority, _, _)| *priority);
+    let mut registered_for_locale = false;
+    for (_, query, for_locale) in queries {
+        let registered = register_family_font(font_database, fd, family.clone(), &query);
+        registered_for_locale |= registered && for_locale;
     }
+    registered_for_locale
 }
 
 fn register_family_font(
@@ -619,25 +2343,48 @@ fn register_family_font(
     fd: &mut FontDefinitions,
     family: egui::FontFamily,
     query: &FamilyQuery<'_>,
-) {
+) -> bool {
     let (name, fontdata) = match load_system_font(font_database, query) {
         Ok((name, fontdata)) => (name, fontdata),
         Err(e) => {
             tracing::warn!("Failed to register {query:?} as {family}: {e}");
-            return;
+            return false;
         }
     };
 
     tracing::info!("Registering font {name} as {family}");
 
-    fd.font_data.insert(name.clone(), fontdata.into());
+    fd.font_data.insert(name.clone(), fontdata);
     fd.families.entry(family).or_default().push(name);
+    true
+}
+
+/// A system font file that was already read, with the face it was read for.
+struct CachedFont {
+    path: PathBuf,
+    index: u32,
+    data: Arc<FontData>,
+}
+
+/// Font files read by [`load_system_font`], so that setting up fonts again
+/// (e.g. after changing the language) doesn't re-read large CJK fonts from disk.
+///
+/// The face ids of a font database aren't stable across databases,
+/// so the fonts are cached by the normalized family names they were queried with.
+static FONT_CACHE: LazyLock<Mutex<HashMap<Vec<String>, CachedFont>>> =
+    LazyLock::new(Default::default);
+
+fn font_cache_key(font_database: &Database, families: &[Family<'_>]) -> Vec<String> {
+    families
+        .iter()
+        .map(|family| font_database.family_name(family).to_lowercase())
+        .collect()
 }
 
 fn load_system_font(
     font_database: &Database,
     families: &Vec<Family<'_>>,
-) -> anyhow::Result<(String, FontData)> {
+) -> anyhow::Result<(String, Arc<FontData>)> {
     let system_unicode_fonts = Query {
         families,
         ..Query::default()
@@ -651,17 +2398,68 @@ fn load_system_font(
         .map(|f| (f.post_script_name.clone(), f.source.clone(), f.index))
         .expect("id not found in font database");
 
-    let mut fontdata = match src {
+    let fontdata = match src {
         Source::File(path) => {
-            let data = std::fs::read(path)?;
-            egui::FontData::from_owned(data)
+            let mut cache = FONT_CACHE.lock().expect("Non-poisoned font cache");
+            let key = font_cache_key(font_database, families);
+            match cache.get(&key) {
+                Some(cached) if cached.path == path && cached.index == index => cached.data.clone(),
+                _ => {
+                    let mut fontdata = egui::FontData::from_owned(std::fs::read(&path)?);
+                    fontdata.index = index;
+                    let data = Arc::new(fontdata);
+                    cache.insert(
+                        key,
+                        CachedFont {
+                            path,
+                            index,
+                            data: data.clone(),
+                        },
+                    );
+                    data
+                }
+            }
         }
         Source::Binary(bin) | Source::SharedFile(_, bin) => {
             let data = bin.as_ref().as_ref().to_vec();
-            egui::FontData::from_owned(data)
+            let mut fontdata = egui::FontData::from_owned(data);
+            fontdata.index = index;
+            Arc::new(fontdata)
         }
     };
-    fontdata.index = index;
 
     Ok((name, fontdata))
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn test_font_database() -> Database {
+        let mut font_database = Database::new();
+        font_database
+            .load_font_file(concat!(
+                env!("CARGO_MANIFEST_DIR"),
+                "/../tests/tests/swfs/visual/edittext/edittext_gutter/TestFont.ttf"
+            ))
+            .expect("Test font is readable");
+        font_database
+    }
+
+    #[test]
+    fn system_font_is_cached_across_databases() {
+        let first_database = test_font_database();
+        let family = first_database.faces().next().unwrap().families[0].0.clone();
+
+        let (first_name, first_data) =
+            load_system_font(&first_database, &vec![Family::Name(&family)]).unwrap();
+
+        // Setting up fonts again uses a new database, with face ids of its own.
+        let second_database = test_font_database();
+        let (second_name, second_data) =
+            load_system_font(&second_database, &vec![Family::Name(&family)]).unwrap();
+
+        assert_eq!(first_name, second_name);
+        assert!(Arc::ptr_eq(&first_data, &second_data));
+    }
+}
@@ -1,41 +1,60 @@
 mod about_dialog;
 mod bookmarks_dialog;
+mod download_progress_dialog;
 pub mod filesystem_access_dialog;
 pub mod message_dialog;
+mod metadata_dialog;
+mod movie_properties_dialog;
 pub mod network_access_dialog;
 mod open_dialog;
+mod open_movie_url_dialog;
 mod open_url_dialog;
 mod preferences_dialog;
 mod volume_controls;
 
-use crate::custom_event::RuffleEvent;
-use crate::player::LaunchOptions;
+use crate::custom_event::RuffleEventProxy;
+use crate::player::{LaunchOptions, TabId};
 use crate::preferences::GlobalPreferences;
 use bookmarks_dialog::{BookmarkAddDialog, BookmarksDialog};
+use download_progress_dialog::DownloadProgressDialog;
+use egui::{ViewportBuilder, ViewportCommand, ViewportId};
 use filesystem_access_dialog::{FilesystemAccessDialog, FilesystemAccessDialogConfiguration};
 use message_dialog::{MessageDialog, MessageDialogConfiguration};
+pub use metadata_dialog::dublin_core_title;
+use metadata_dialog::MetadataDialog;
 use network_access_dialog::{NetworkAccessDialog, NetworkAccessDialogConfiguration};
 use open_dialog::OpenDialog;
+use open_movie_url_dialog::OpenMovieUrlDialog;
 use open_url_dialog::OpenUrlDialog;
 use preferences_dialog::PreferencesDialog;
 use ruffle_core::Player;
-use std::{collections::VecDeque, sync::Weak};
+use ruffle_frontend_utils::backends::navigator::DownloadProgress;
+use std::collections::VecDeque;
+use std::mem;
+use std::sync::{Arc, Mutex, Weak};
 use unic_langid::LanguageIdentifier;
 use url::Url;
 use volume_controls::VolumeControls;
-use winit::event_loop::EventLoopProxy;
 
-use super::FilePicker;
+use super::{text, FilePicker};
 
 pub struct Dialogs {
-    event_loop: EventLoopProxy<RuffleEvent>,
+    event_loop: RuffleEventProxy,
 
     picker: FilePicker,
     preferences_dialog: Option<PreferencesDialog>,
+    /// The preferences dialog while it's popped out into a window of its own.
+    ///
+    /// It's shared with the UI callback of that window, which empties it once the dialog closes.
+    detached_preferences_dialog: Option<Arc<Mutex<Option<PreferencesDialog>>>>,
+    /// Whether the popped out preferences dialog should be brought to the front.
+    focus_detached_preferences: bool,
     bookmarks_dialog: Option<BookmarksDialog>,
     bookmark_add_dialog: Option<BookmarkAddDialog>,
     open_url_dialog: Option<OpenUrlDialog>,
+    open_movie_url_dialog: Option<OpenMovieUrlDialog>,
     message_dialog: Option<MessageDialog>,
+    download_progress_dialog: Option<DownloadProgressDialog>,
 
     // Use a queue for the following dialogs in order to:
     //  1. support handling multiple instances of them,
@@ -53,7 +72,11 @@ pub struct Dialogs {
 
     is_about_visible: bool,
 
+    is_movie_properties_visible: bool,
+    metadata_dialog: Option<MetadataDialog>,
+
     preferences: GlobalPreferences,
+    window: Weak<winit::window::Window>,
 }
 
 pub enum DialogDescriptor {
@@ -61,6 +84,9 @@ pub enum DialogDescriptor {
     ShowMessage(MessageDialogConfiguration),
     NetworkAccess(NetworkAccessDialogConfiguration),
     FilesystemAccess(FilesystemAccessDialogConfiguration),
+    /// Shows, updates or closes the progress of downloading the movie at the URL,
+    /// which is opened in the given tab.
+    DownloadProgress(TabId, Url, DownloadProgress),
 }
 
 impl Dialogs {
@@ -69,15 +95,19 @@ impl Dialogs {
         player_options: LaunchOptions,
         default_path: Option<Url>,
         window: Weak<winit::window::Window>,
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
+        picker: FilePicker,
     ) -> Self {
-        let picker = FilePicker::new(window, preferences.clone());
         Self {
             preferences_dialog: None,
+            detached_preferences_dialog: None,
+            focus_detached_preferences: false,
             bookmarks_dialog: None,
             bookmark_add_dialog: None,
             open_url_dialog: None,
+            open_movie_url_dialog: None,
             message_dialog: None,
+            download_progress_dialog: None,
 
             network_access_dialog_queue: VecDeque::new(),
             filesystem_access_dialog: None,
@@ -96,9 +126,13 @@ impl Dialogs {
 
             is_about_visible: false,
 
+            is_movie_properties_visible: false,
+            metadata_dialog: None,
+
             event_loop,
             picker,
             preferences,
+            window,
         }
     }
 
@@ -120,7 +154,7 @@ impl Dialogs {
         &mut self,
         opt: LaunchOptions,
         url: Option<Url>,
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
     ) {
         self.is_open_dialog_visible = false;
         self.open_dialog = OpenDialog::new(opt, url, self.picker.clone(), event_loop);
@@ -130,8 +164,31 @@ impl Dialogs {
         self.is_open_dialog_visible = true;
     }
 
-    pub fn open_preferences(&mut self) {
-        self.preferences_dialog = Some(PreferencesDialog::new(self.preferences.clone()));
+    /// Shows the open dialog with its path or URL replaced by the given text.
+    pub fn open_file_advanced_with_path(&mut self, path: &str) {
+        self.open_dialog.set_path(path);
+        self.is_open_dialog_visible = true;
+    }
+
+    /// Shows the dialog asking for the URL of a movie, which is opened with the given options.
+    pub fn open_movie_url(&mut self, options: LaunchOptions) {
+        self.open_movie_url_dialog = Some(OpenMovieUrlDialog::new(
+            &self.preferences,
+            options,
+            self.event_loop.clone(),
+        ));
+    }
+
+    pub fn open_preferences(&mut self, movie_url: Option<url::Url>) {
+        if self.detached_preferences_dialog.is_some() {
+            self.focus_detached_preferences = true;
+            return;
+        }
+        self.preferences_dialog = Some(PreferencesDialog::new(
+            self.preferences.clone(),
+            self.window.upgrade().as_deref(),
+            movie_url,
+        ));
     }
 
     pub fn open_bookmarks(&mut self) {
@@ -158,6 +215,10 @@ impl Dialogs {
         self.is_about_visible = true;
     }
 
+    pub fn open_movie_properties(&mut self) {
+        self.is_movie_properties_visible = true;
+    }
+
     pub fn open_dialog(&mut self, event: DialogDescriptor) {
         match event {
             DialogDescriptor::OpenUrl(url) => {
@@ -172,6 +233,31 @@ impl Dialogs {
             DialogDescriptor::FilesystemAccess(config) => {
                 self.filesystem_access_dialog_queue.push_back(config)
             }
+            DialogDescriptor::DownloadProgress(tab, url, progress) => {
+                let is_same_download = self
+                    .download_progress_dialog
+                    .as_ref()
+                    .is_some_and(|dialog| dialog.tab() == tab);
+                match progress {
+                    DownloadProgress::Receiving { loaded, total } => {
+                        if !is_same_download {
+                            self.download_progress_dialog = Some(DownloadProgressDialog::new(
+                                tab,
+                                url,
+                                self.event_loop.clone(),
+                            ));
+                        }
+                        if let Some(dialog) = &mut self.download_progress_dialog {
+                            dialog.set_progress(loaded, total);
+                        }
+                    }
+                    DownloadProgress::Finished => {
+                        if is_same_download {
+                            self.download_progress_dialog = None;
+                        }
+                    }
+                }
+            }
         }
     }
 
@@ -179,23 +265,38 @@ impl Dialogs {
         &mut self,
         locale: &LanguageIdentifier,
         egui_ctx: &egui::Context,
-        player: Option<&mut Player>,
+        mut player: Option<&mut Player>,
     ) {
-        self.show_open_dialog(locale, egui_ctx);
+        self.show_open_dialog(locale, egui_ctx, player.as_deref_mut());
         self.show_preferences_dialog(locale, egui_ctx);
         self.show_bookmarks_dialog(locale, egui_ctx);
         self.show_bookmark_add_dialog(locale, egui_ctx);
+        self.show_movie_properties_dialog(locale, egui_ctx, player.as_deref_mut());
+        self.show_metadata_dialog(locale, egui_ctx);
         self.show_volume_controls(locale, egui_ctx, player);
         self.show_about_dialog(locale, egui_ctx);
         self.show_open_url_dialog(locale, egui_ctx);
+        self.show_open_movie_url_dialog(locale, egui_ctx);
         self.show_message_dialog(locale, egui_ctx);
+        self.show_download_progress_dialog(locale, egui_ctx);
         self.show_network_access_dialog(locale, egui_ctx);
         self.show_filesystem_access_dialog(locale, egui_ctx);
     }
 
-    fn show_open_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+    fn show_open_dialog(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        player: Option<&mut Player>,
+    ) {
         if self.is_open_dialog_visible {
-            let keep_open = self.open_dialog.show(locale, egui_ctx);
+            let keep_open = self.open_dialog.show(
+                locale,
+                egui_ctx,
+                &mut self.volume_controls,
+                player,
+                &self.preferences,
+            );
             self.is_open_dialog_visible = keep_open;
         }
     }
@@ -208,6 +309,51 @@ impl Dialogs {
         };
         if !keep_open {
             self.preferences_dialog = None;
+        } else if let Some(dialog) = self
+            .preferences_dialog
+            .take_if(|dialog| dialog.take_detach_request())
+        {
+            self.detached_preferences_dialog = Some(Arc::new(Mutex::new(Some(dialog))));
+        }
+
+        if self
+            .detached_preferences_dialog
+            .as_ref()
+            .is_some_and(|dialog| {
+                dialog
+                    .lock()
+                    .expect("Preferences dialog lock should not be poisoned")
+                    .is_none()
+            })
+        {
+            self.detached_preferences_dialog = None;
+        }
+        if let Some(dialog) = &self.detached_preferences_dialog {
+            let viewport_id = ViewportId::from_hash_of("preferences-dialog");
+            if mem::take(&mut self.focus_detached_preferences) {
+                egui_ctx.send_viewport_cmd_to(viewport_id, ViewportCommand::Focus);
+            }
+            let dialog = dialog.clone();
+            let locale = locale.clone();
+            egui_ctx.show_viewport_deferred(
+                viewport_id,
+                ViewportBuilder::default()
+                    .with_title(text(&locale, "preferences-dialog"))
+                    .with_inner_size([480.0, 640.0]),
+                move |egui_ctx, _class| {
+                    let mut dialog = dialog
+                        .lock()
+                        .expect("Preferences dialog lock should not be poisoned");
+                    let keep_open = dialog
+                        .as_mut()
+                        .is_some_and(|dialog| dialog.show_detached(&locale, egui_ctx));
+                    if !keep_open {
+                        *dialog = None;
+                        // The main window stops showing the viewport, which closes its window.
+                        egui_ctx.request_repaint_of(ViewportId::ROOT);
+                    }
+                },
+            );
         }
     }
 
@@ -254,6 +400,34 @@ impl Dialogs {
         }
     }
 
+    fn show_movie_properties_dialog(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        player: Option<&mut Player>,
+    ) {
+        if self.is_movie_properties_visible {
+            let keep_open = movie_properties_dialog::show_movie_properties_dialog(
+                locale,
+                egui_ctx,
+                player,
+                &mut self.metadata_dialog,
+            );
+            self.is_movie_properties_visible = keep_open;
+        }
+    }
+
+    fn show_metadata_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        let keep_open = if let Some(dialog) = &mut self.metadata_dialog {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.metadata_dialog = None;
+        }
+    }
+
     fn show_open_url_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
         let keep_open = if let Some(dialog) = &mut self.open_url_dialog {
             dialog.show(locale, egui_ctx)
@@ -265,6 +439,21 @@ impl Dialogs {
         }
     }
 
+    fn show_open_movie_url_dialog(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+    ) {
+        let keep_open = if let Some(dialog) = &mut self.open_movie_url_dialog {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.open_movie_url_dialog = None;
+        }
+    }
+
     fn show_message_dialog(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
         let keep_open = if let Some(dialog) = &mut self.message_dialog {
             dialog.show(locale, egui_ctx)
@@ -276,6 +465,21 @@ impl Dialogs {
         }
     }
 
+    fn show_download_progress_dialog(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+    ) {
+        let keep_open = if let Some(dialog) = &mut self.download_progress_dialog {
+            dialog.show(locale, egui_ctx)
+        } else {
+            true
+        };
+        if !keep_open {
+            self.download_progress_dialog = None;
+        }
+    }
+
     fn show_network_access_dialog(
         &mut self,
         locale: &LanguageIdentifier,
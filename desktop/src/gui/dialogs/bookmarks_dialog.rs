@@ -1,13 +1,15 @@
 use crate::gui::widgets::PathOrUrlField;
 use crate::gui::{text, FilePicker};
 use crate::preferences::GlobalPreferences;
-use crate::{custom_event::RuffleEvent, player::LaunchOptions};
+use crate::{
+    custom_event::{RuffleEvent, RuffleEventProxy},
+    player::LaunchOptions,
+};
 use egui::{Align2, Button, Grid, Label, Layout, Sense, Ui, Widget, Window};
 use egui_extras::{Column, TableBuilder};
 use ruffle_frontend_utils::bookmarks::Bookmark;
 use unic_langid::LanguageIdentifier;
 use url::Url;
-use winit::event_loop::EventLoopProxy;
 
 pub struct BookmarkAddDialog {
     preferences: GlobalPreferences,
@@ -99,7 +101,7 @@ struct SelectedBookmark {
 }
 
 pub struct BookmarksDialog {
-    event_loop: EventLoopProxy<RuffleEvent>,
+    event_loop: RuffleEventProxy,
     picker: FilePicker,
     preferences: GlobalPreferences,
     selected_bookmark: Option<SelectedBookmark>,
@@ -109,7 +111,7 @@ impl BookmarksDialog {
     pub fn new(
         preferences: GlobalPreferences,
         picker: FilePicker,
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
     ) -> Self {
         Self {
             picker,
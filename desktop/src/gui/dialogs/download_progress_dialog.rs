@@ -0,0 +1,187 @@
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
+use crate::gui::{text, text_with_args};
+use crate::player::TabId;
+use egui::{Align2, Layout, ProgressBar, Ui, Window};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use unic_langid::LanguageIdentifier;
+use url::Url;
+
+/// How often the speed of the download is updated, when no progress is reported in the meantime.
+const SPEED_REFRESH_INTERVAL: Duration = Duration::from_millis(500);
+
+/// Shows how far along the download of a movie opened from the network is,
+/// offering to cancel it.
+pub struct DownloadProgressDialog {
+    event_loop: RuffleEventProxy,
+    /// The tab the movie is opened in, which is closed if the download is cancelled.
+    tab: TabId,
+    url: Url,
+    loaded: u64,
+    /// The length of the movie, if the server sent it.
+    total: Option<u64>,
+    /// When the download started, to tell its speed.
+    started: Instant,
+}
+
+impl DownloadProgressDialog {
+    pub fn new(tab: TabId, url: Url, event_loop: RuffleEventProxy) -> Self {
+        Self {
+            event_loop,
+            tab,
+            url,
+            loaded: 0,
+            total: None,
+            started: Instant::now(),
+        }
+    }
+
+    pub fn tab(&self) -> TabId {
+        self.tab
+    }
+
+    pub fn set_progress(&mut self, loaded: u64, total: Option<u64>) {
+        self.loaded = loaded;
+        self.total = total;
+    }
+
+    /// Shows the dialog, which can't be closed other than by cancelling the download,
+    /// as it would be opened again by the next progress update.
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut should_close = false;
+
+        Window::new(text(locale, "download-progress-dialog"))
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                should_close = self.render_window_contents(locale, ui);
+            });
+
+        !should_close
+    }
+
+    fn render_window_contents(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) -> bool {
+        let mut should_close = false;
+
+        ui.monospace(self.url.as_str());
+        ui.add_space(4.0);
+
+        let elapsed = self.started.elapsed().as_secs_f64();
+        let speed = if elapsed > 0.0 {
+            (self.loaded as f64 / elapsed) as u64
+        } else {
+            0
+        };
+        let received = match self.total {
+            Some(total) if total > 0 => {
+                ui.add(
+                    ProgressBar::new(self.loaded as f32 / total as f32)
+                        .show_percentage()
+                        .desired_width(360.0),
+                );
+                text_with_args(
+                    locale,
+                    "download-progress-dialog-received",
+                    &HashMap::from([
+                        (
+                            "loaded".into(),
+                            FluentValue::String(format_size(self.loaded).into()),
+                        ),
+                        (
+                            "total".into(),
+                            FluentValue::String(format_size(total).into()),
+                        ),
+                        (
+                            "speed".into(),
+                            FluentValue::String(format_size(speed).into()),
+                        ),
+                    ]),
+                )
+            }
+            // Without the length of the movie, there's no telling how much is left.
+            _ => {
+                ui.horizontal(|ui| {
+                    ui.spinner();
+                    ui.label(text(locale, "download-progress-dialog-unknown-length"));
+                });
+                text_with_args(
+                    locale,
+                    "download-progress-dialog-received-unknown-length",
+                    &HashMap::from([
+                        (
+                            "loaded".into(),
+                            FluentValue::String(format_size(self.loaded).into()),
+                        ),
+                        (
+                            "speed".into(),
+                            FluentValue::String(format_size(speed).into()),
+                        ),
+                    ]),
+                )
+            }
+        };
+        ui.label(received);
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(text(locale, "dialog-cancel")).clicked() {
+                    let _ = self
+                        .event_loop
+                        .send_event(RuffleEvent::CancelDownload(self.tab));
+                    should_close = true;
+                }
+            });
+        });
+
+        // The speed keeps changing even while no progress is reported.
+        ui.ctx().request_repaint_after(SPEED_REFRESH_INTERVAL);
+
+        should_close
+    }
+}
+
+/// Formats a number of bytes in the largest unit that keeps it above 1.
+fn format_size(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["KiB", "MiB", "GiB", "TiB"];
+    if bytes < 1024 {
+        return format!("{bytes} B");
+    }
+    let mut size = bytes as f64 / 1024.0;
+    let mut unit = UNITS[0];
+    for next_unit in &UNITS[1..] {
+        if size < 1024.0 {
+            break;
+        }
+        size /= 1024.0;
+        unit = next_unit;
+    }
+    format!("{size:.1} {unit}")
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn size_in_bytes() {
+        assert_eq!(format_size(0), "0 B");
+        assert_eq!(format_size(1023), "1023 B");
+    }
+
+    #[test]
+    fn size_in_largest_unit() {
+        assert_eq!(format_size(1024), "1.0 KiB");
+        assert_eq!(format_size(1536), "1.5 KiB");
+        assert_eq!(format_size(5 * 1024 * 1024), "5.0 MiB");
+        assert_eq!(format_size(3 * 1024 * 1024 * 1024), "3.0 GiB");
+        assert_eq!(format_size(2 * 1024 * 1024 * 1024 * 1024), "2.0 TiB");
+    }
+
+    #[test]
+    fn size_beyond_largest_unit() {
+        assert_eq!(format_size(2048 * 1024 * 1024 * 1024 * 1024), "2048.0 TiB");
+    }
+}
@@ -0,0 +1,285 @@
+use crate::gui::text;
+use egui::{Align2, Grid, Id, ScrollArea, TextEdit, Window};
+use quick_xml::events::{BytesStart, Event};
+use quick_xml::name::QName;
+use quick_xml::{Reader, Writer};
+use unic_langid::LanguageIdentifier;
+
+/// The elements of the Dublin Core set, in the order they're shown, with the IDs of their labels.
+const DUBLIN_CORE_FIELDS: [(&str, &str); 15] = [
+    ("title", "metadata-title"),
+    ("creator", "metadata-creator"),
+    ("description", "metadata-description"),
+    ("subject", "metadata-subject"),
+    ("publisher", "metadata-publisher"),
+    ("contributor", "metadata-contributor"),
+    ("date", "metadata-date"),
+    ("type", "metadata-type"),
+    ("format", "metadata-format"),
+    ("identifier", "metadata-identifier"),
+    ("source", "metadata-source"),
+    ("language", "metadata-language"),
+    ("relation", "metadata-relation"),
+    ("coverage", "metadata-coverage"),
+    ("rights", "metadata-rights"),
+];
+
+/// Shows the XMP metadata that a movie embeds in its `Metadata` tag.
+///
+/// The common Dublin Core fields are listed on their own, above the whole XML.
+pub struct MetadataDialog {
+    /// The Dublin Core fields found, as the IDs of their labels and their values.
+    fields: Vec<(&'static str, String)>,
+    /// The XML, indented if it's well-formed.
+    xml: String,
+}
+
+impl MetadataDialog {
+    pub fn new(metadata: &str) -> Self {
+        Self {
+            fields: dublin_core_fields(metadata).unwrap_or_default(),
+            xml: pretty_print(metadata).unwrap_or_else(|| metadata.to_string()),
+        }
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+
+        let title = title_field(&self.fields)
+            .map(str::to_owned)
+            .unwrap_or_else(|| text(locale, "metadata-dialog").to_string());
+
+        Window::new(title)
+            .id(Id::new("metadata_dialog"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .default_width(480.0)
+            .show(egui_ctx, |ui| {
+                if !self.fields.is_empty() {
+                    Grid::new("metadata_fields").striped(true).show(ui, |ui| {
+                        for (id, value) in &self.fields {
+                            ui.label(text(locale, id));
+                            ui.label(value);
+                            ui.end_row();
+                        }
+                    });
+                    ui.separator();
+                }
+
+                ScrollArea::both().max_height(320.0).show(ui, |ui| {
+                    ui.add(
+                        TextEdit::multiline(&mut self.xml.as_str())
+                            .code_editor()
+                            .desired_width(f32::INFINITY),
+                    );
+                });
+            });
+
+        keep_open
+    }
+}
+
+/// Returns the title of a movie from its XMP metadata, the same one the dialog shows.
+pub fn dublin_core_title(metadata: &str) -> Option<String> {
+    title_field(&dublin_core_fields(metadata)?).map(str::to_owned)
+}
+
+/// The value of the title among the Dublin Core fields, if it's one of them.
+fn title_field(fields: &[(&'static str, String)]) -> Option<&str> {
+    fields
+        .iter()
+        .find(|(id, _)| *id == "metadata-title")
+        .map(|(_, title)| title.as_str())
+}
+
+/// Extracts the Dublin Core fields of RDF metadata, in the order of [`DUBLIN_CORE_FIELDS`].
+///
+/// Fields are recognized by the usual `dc` prefix, either as elements or as attributes.
+/// A field given several times, such as the subjects in an `rdf:Bag`, has its values joined.
+/// Of the alternatives in an `rdf:Alt`, such as a title in several languages,
+/// only the `x-default` one is taken, or the first one without it.
+/// Returns `None` if the metadata isn't well-formed XML.
+fn dublin_core_fields(metadata: &str) -> Option<Vec<(&'static str, String)>> {
+    let mut values = vec![Vec::new(); DUBLIN_CORE_FIELDS.len()];
+    let mut reader = Reader::from_str(metadata);
+    reader.config_mut().trim_text(true);
+
+    // The field whose element is open, and how many elements deeper the reader is inside of it.
+    let mut current_field: Option<(usize, usize)> = None;
+    // The alternatives of the `rdf:Alt` open in the field, as whether each one is the default and its value.
+    let mut alternatives: Option<Vec<(bool, String)>> = None;
+    // Whether the open alternative is the default one.
+    let mut default_alternative = false;
+    let mut open_elements = 0usize;
+    loop {
+        match reader.read_event().ok()? {
+            Event::Start(element) => {
+                open_elements += 1;
+                match &mut current_field {
+                    Some((_, depth)) => {
+                        *depth += 1;
+                        match element.name().as_ref() {
+                            b"rdf:Alt" if alternatives.is_none() => alternatives = Some(vec![]),
+                            b"rdf:li" => default_alternative = is_default_alternative(&element),
+                            _ => {}
+                        }
+                    }
+                    None => current_field = dublin_core_field(element.name()).map(|i| (i, 0)),
+                }
+                read_attribute_fields(&element, &mut values)?;
+            }
+            Event::Empty(element) => read_attribute_fields(&element, &mut values)?,
+            Event::End(element) => {
+                open_elements = open_elements.checked_sub(1)?;
+                if let (Some((field, _)), b"rdf:Alt") = (current_field, element.name().as_ref()) {
+                    let alternatives = alternatives.take().unwrap_or_default();
+                    let chosen = alternatives
+                        .iter()
+                        .find(|(default, _)| *default)
+                        .or(alternatives.first());
+                    if let Some((_, value)) = chosen {
+                        values[field].push(value.clone());
+                    }
+                }
+                current_field = match current_field {
+                    Some((field, depth)) if depth > 0 => Some((field, depth - 1)),
+                    _ => None,
+                };
+            }
+            Event::Text(value) => {
+                if let Some((field, _)) = current_field {
+                    let value = value.unescape().ok()?.into_owned();
+                    match &mut alternatives {
+                        Some(alternatives) => alternatives.push((default_alternative, value)),
+                        None => values[field].push(value),
+                    }
+                }
+            }
+            Event::CData(value) => {
+                if let Some((field, _)) = current_field {
+                    let value = String::from_utf8_lossy(&value).into_owned();
+                    match &mut alternatives {
+                        Some(alternatives) => alternatives.push((default_alternative, value)),
+                        None => values[field].push(value),
+                    }
+                }
+            }
+            // Elements left open are just as malformed as mismatched ones.
+            Event::Eof if open_elements > 0 => return None,
+            Event::Eof => break,
+            _ => {}
+        }
+    }
+
+    Some(
+        DUBLIN_CORE_FIELDS
+            .iter()
+            .zip(values)
+            .filter(|(_, values)| !values.is_empty())
+            .map(|((_, id), values)| (*id, values.join(", ")))
+            .collect(),
+    )
+}
+
+/// Adds the values of the Dublin Core fields given as attributes of the element.
+fn read_attribute_fields(element: &BytesStart, values: &mut [Vec<String>]) -> Option<()> {
+    for attribute in element.attributes() {
+        let attribute = attribute.ok()?;
+        if let Some(field) = dublin_core_field(attribute.key) {
+            values[field].push(attribute.unescape_value().ok()?.into_owned());
+        }
+    }
+    Some(())
+}
+
+/// Whether the `rdf:li` element is the alternative for the default language, `xml:lang="x-default"`.
+fn is_default_alternative(element: &BytesStart) -> bool {
+    element.attributes().flatten().any(|attribute| {
+        attribute.key.as_ref() == b"xml:lang" && attribute.value.as_ref() == b"x-default"
+    })
+}
+
+/// The index in [`DUBLIN_CORE_FIELDS`] of the field with the given name, if it's one of them.
+fn dublin_core_field(name: QName) -> Option<usize> {
+    if name.prefix()?.as_ref() != b"dc" {
+        return None;
+    }
+    DUBLIN_CORE_FIELDS
+        .iter()
+        .position(|(field, _)| field.as_bytes() == name.local_name().as_ref())
+}
+
+/// Indents the metadata XML, or returns `None` if it isn't well-formed.
+fn pretty_print(metadata: &str) -> Option<String> {
+    let mut reader = Reader::from_str(metadata);
+    reader.config_mut().trim_text(true);
+    let mut writer = Writer::new_with_indent(Vec::new(), b' ', 2);
+    loop {
+        match reader.read_event().ok()? {
+            Event::Eof => break,
+            event => writer.write_event(event).ok()?,
+        }
+    }
+    String::from_utf8(writer.into_inner()).ok()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn dublin_core_elements() {
+        let metadata = r#"<rdf:RDF xmlns:rdf="http://www.w3.org/1999/02/22-rdf-syntax-ns#"><rdf:Description rdf:about="" xmlns:dc="http://purl.org/dc/elements/1.1/"><dc:title><rdf:Alt><rdf:li xml:lang="x-default">Game &amp; Watch</rdf:li></rdf:Alt></dc:title><dc:subject><rdf:Bag><rdf:li>arcade</rdf:li><rdf:li>retro</rdf:li></rdf:Bag></dc:subject><dc:creator>Someone</dc:creator></rdf:Description></rdf:RDF>"#;
+        assert_eq!(
+            dublin_core_fields(metadata),
+            Some(vec![
+                ("metadata-title", "Game & Watch".to_string()),
+                ("metadata-creator", "Someone".to_string()),
+                ("metadata-subject", "arcade, retro".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn default_alternative() {
+        let metadata = r#"<rdf:RDF><rdf:Description><dc:title><rdf:Alt><rdf:li xml:lang="fr">Jeu</rdf:li><rdf:li xml:lang="x-default">Game</rdf:li><rdf:li xml:lang="de">Spiel</rdf:li></rdf:Alt></dc:title><dc:rights><rdf:Alt><rdf:li xml:lang="en">Mine</rdf:li><rdf:li xml:lang="fr">À moi</rdf:li></rdf:Alt></dc:rights></rdf:Description></rdf:RDF>"#;
+        assert_eq!(
+            dublin_core_fields(metadata),
+            Some(vec![
+                ("metadata-title", "Game".to_string()),
+                ("metadata-rights", "Mine".to_string()),
+            ])
+        );
+        assert_eq!(dublin_core_title(metadata).as_deref(), Some("Game"));
+    }
+
+    #[test]
+    fn dublin_core_attributes() {
+        let metadata =
+            r#"<rdf:RDF><rdf:Description dc:title="Attributed" dc:date="2008"/></rdf:RDF>"#;
+        assert_eq!(
+            dublin_core_fields(metadata),
+            Some(vec![
+                ("metadata-title", "Attributed".to_string()),
+                ("metadata-date", "2008".to_string()),
+            ])
+        );
+    }
+
+    #[test]
+    fn malformed_metadata() {
+        let metadata = "<rdf:RDF><dc:title>Unclosed</rdf:RDF>";
+        assert_eq!(dublin_core_fields(metadata), None);
+        assert_eq!(dublin_core_fields("<dc:title>Unclosed"), None);
+        assert_eq!(pretty_print(metadata), None);
+    }
+
+    #[test]
+    fn pretty_printed() {
+        assert_eq!(
+            pretty_print("<a>\n<b>text</b>  <c/></a>").as_deref(),
+            Some("<a>\n  <b>text</b>\n  <c/>\n</a>")
+        );
+    }
+}
@@ -0,0 +1,173 @@
+use crate::gui::dialogs::MetadataDialog;
+use crate::gui::{text, text_with_args};
+use egui::{Align2, Button, Grid, Layout};
+use fluent_templates::fluent_bundle::FluentValue;
+use ruffle_core::swf::Compression;
+use ruffle_core::Player;
+use std::collections::HashMap;
+use std::time::Duration;
+use unic_langid::LanguageIdentifier;
+
+/// How often the runtime properties are refreshed while the dialog is open.
+const REFRESH_INTERVAL: Duration = Duration::from_millis(250);
+
+/// Renders the dialog listing the header of the loaded movie and how it's running.
+///
+/// The properties are read from the player every time, so they stay up to date.
+/// The metadata of the movie can be opened from here, in a dialog of its own.
+pub fn show_movie_properties_dialog(
+    locale: &LanguageIdentifier,
+    egui_ctx: &egui::Context,
+    player: Option<&mut Player>,
+    metadata_dialog: &mut Option<MetadataDialog>,
+) -> bool {
+    let mut keep_open = true;
+
+    egui::Window::new(text(locale, "movie-properties"))
+        .collapsible(false)
+        .resizable(false)
+        .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+        .open(&mut keep_open)
+        .show(egui_ctx, |ui| {
+            let Some(player) = player else {
+                ui.label(text(locale, "movie-properties-no-movie"));
+                return;
+            };
+            let properties = movie_properties(locale, player);
+
+            Grid::new("movie_properties").striped(true).show(ui, |ui| {
+                for (name, value) in &properties {
+                    ui.label(name);
+                    ui.label(value);
+                    ui.end_row();
+                }
+            });
+
+            ui.separator();
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui.button(text(locale, "movie-properties-copy")).clicked() {
+                    let report = properties
+                        .iter()
+                        .map(|(name, value)| format!("{name}: {value}"))
+                        .collect::<Vec<_>>()
+                        .join("\n");
+                    ui.ctx().copy_text(report);
+                }
+
+                let metadata = player.swf().metadata();
+                if ui
+                    .add_enabled(
+                        metadata.is_some(),
+                        Button::new(text(locale, "movie-properties-metadata")),
+                    )
+                    .on_disabled_hover_text(text(locale, "movie-properties-no-metadata"))
+                    .clicked()
+                {
+                    if let Some(metadata) = metadata {
+                        *metadata_dialog = Some(MetadataDialog::new(&metadata));
+                    }
+                }
+            });
+
+            egui_ctx.request_repaint_after(REFRESH_INTERVAL);
+        });
+
+    keep_open
+}
+
+/// Lists the names and values of the properties of the movie, in the order they're shown.
+fn movie_properties(locale: &LanguageIdentifier, player: &mut Player) -> Vec<(String, String)> {
+    let swf = player.swf().clone();
+    let header = swf.header();
+
+    let compression = match header.compression() {
+        Compression::None => text(locale, "movie-properties-compression-none").to_string(),
+        Compression::Zlib => "zlib".to_string(),
+        Compression::Lzma => "LZMA".to_string(),
+    };
+    let avm = if swf.is_action_script_3() {
+        text(locale, "movie-properties-avm2")
+    } else {
+        text(locale, "movie-properties-avm1")
+    };
+    let background_color = match header.background_color() {
+        Some(color) => format!("#{:02X}{:02X}{:02X}", color.r, color.g, color.b),
+        None => text(locale, "movie-properties-none").to_string(),
+    };
+    let spoofed_url = player
+        .spoofed_url()
+        .map(str::to_string)
+        .unwrap_or_else(|| text(locale, "movie-properties-none").to_string());
+    let current_frame = match player.current_frame() {
+        Some(frame) => format!("{frame} / {}", swf.num_frames()),
+        None => text(locale, "movie-properties-unavailable").to_string(),
+    };
+    let heap_size = match player.gc_heap_size() {
+        Some(size) => format!("{:.1} MiB", size as f64 / (1024.0 * 1024.0)),
+        None => text(locale, "movie-properties-unavailable").to_string(),
+    };
+
+    vec![
+        (
+            text(locale, "movie-properties-url").to_string(),
+            swf.url().to_string(),
+        ),
+        (
+            text(locale, "movie-properties-spoofed-url").to_string(),
+            spoofed_url,
+        ),
+        (
+            text(locale, "movie-properties-swf-version").to_string(),
+            swf.version().to_string(),
+        ),
+        (
+            text(locale, "movie-properties-avm").to_string(),
+            avm.to_string(),
+        ),
+        (
+            text(locale, "movie-properties-compression").to_string(),
+            compression,
+        ),
+        (
+            text(locale, "movie-properties-size").to_string(),
+            format!("{} / {}", swf.compressed_len(), swf.uncompressed_len()),
+        ),
+        (
+            text(locale, "movie-properties-dimensions").to_string(),
+            format!("{} x {}", swf.width().to_pixels(), swf.height().to_pixels()),
+        ),
+        (
+            text(locale, "movie-properties-frame-rate").to_string(),
+            text_with_args(
+                locale,
+                "movie-properties-frame-rate-value",
+                &HashMap::from([("fps".into(), FluentValue::from(swf.frame_rate().to_f64()))]),
+            )
+            .to_string(),
+        ),
+        (
+            text(locale, "movie-properties-frames").to_string(),
+            swf.num_frames().to_string(),
+        ),
+        (
+            text(locale, "movie-properties-background-color").to_string(),
+            background_color,
+        ),
+        (
+            text(locale, "movie-properties-current-frame").to_string(),
+            current_frame,
+        ),
+        (
+            text(locale, "movie-properties-heap-size").to_string(),
+            heap_size,
+        ),
+        (
+            text(locale, "movie-properties-quality").to_string(),
+            player.quality().to_string(),
+        ),
+        (
+            text(locale, "movie-properties-scale-mode").to_string(),
+            player.scale_mode().to_string(),
+        ),
+    ]
+}
@@ -1,24 +1,29 @@
-use crate::custom_event::RuffleEvent;
+use crate::cli::ContextMenuMode;
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
+use crate::gui::dialogs::preferences_dialog::context_menu_mode_name;
+use crate::gui::dialogs::volume_controls::VolumeControls;
 use crate::gui::widgets::PathOrUrlField;
 use crate::gui::{text, FilePicker, LocalizableText};
 use crate::player::LaunchOptions;
+use crate::preferences::{parse_player_version, GlobalPreferences, MovieOptions};
 use egui::{
-    emath, Align2, Button, Checkbox, ComboBox, Grid, Layout, Slider, TextEdit, Ui, Widget, Window,
+    Align2, Button, Checkbox, ComboBox, Grid, Layout, Slider, TextEdit, Ui, Widget, Window,
 };
 use ruffle_core::backend::navigator::SocketMode;
 use ruffle_core::config::Letterbox;
-use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
+use ruffle_core::{LoadBehavior, Player, PlayerRuntime, StageAlign, StageScaleMode};
+use ruffle_frontend_utils::player_options::PlayerVersion;
 use ruffle_render::quality::StageQuality;
 use std::borrow::Cow;
+use std::mem;
 use std::ops::RangeInclusive;
 use std::time::Duration;
 use unic_langid::LanguageIdentifier;
-use url::Url;
-use winit::event_loop::EventLoopProxy;
+use url::{form_urlencoded, Url};
 
 pub struct OpenDialog {
     options: LaunchOptions,
-    event_loop: EventLoopProxy<RuffleEvent>,
+    event_loop: RuffleEventProxy,
 
     // These are outside of PlayerOptions as it can be an invalid value (ie URL) during typing,
     // and we don't want to clear the value if the user, ie, toggles the checkbox.
@@ -28,6 +33,17 @@ pub struct OpenDialog {
     base_url: OptionalField<UrlField>,
     proxy_url: OptionalField<UrlField>,
     path: PathOrUrlField,
+    /// The URL whose saved options were last looked up, to restore them when another movie is chosen.
+    restored_url: Option<Url>,
+    /// The parameters the dialog was opened with, which a movie without saved parameters starts from.
+    default_parameters: Vec<(String, String)>,
+    /// The spoofed URL the dialog was opened with, which a movie without a saved one starts from.
+    default_spoof_url: Option<Url>,
+    /// Whether relative URLs are resolved against the spoofed URL rather than the actual one.
+    spoof_url_as_base: bool,
+    /// The open movie and the runtime and version it was opened with,
+    /// to offer reloading it when they're changed.
+    open_movie: Option<(Url, Option<PlayerRuntime>, Option<PlayerVersion>)>,
 
     framerate: f64,
     framerate_enabled: bool,
@@ -39,10 +55,11 @@ pub struct OpenDialog {
     scale_mode: OptionalField<FieldWithCheckbox<EnumDropdownField<StageScaleMode>>>,
     load_behavior: OptionalField<EnumDropdownField<LoadBehavior>>,
     letterbox: OptionalField<EnumDropdownField<Letterbox>>,
-    player_version: OptionalField<NumberField<u8>>,
+    player_version: OptionalField<PlayerVersionField>,
     player_runtime: OptionalField<EnumDropdownField<PlayerRuntime>>,
     dummy_external_interface: OptionalField<BooleanDropdownField>,
     upgrade_to_https: OptionalField<BooleanDropdownField>,
+    context_menu_mode: OptionalField<EnumDropdownField<ContextMenuMode>>,
 }
 
 impl OpenDialog {
@@ -50,7 +67,7 @@ impl OpenDialog {
         defaults: LaunchOptions,
         default_url: Option<Url>,
         picker: FilePicker,
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
     ) -> Self {
         let spoof_url = OptionalField::new(
             defaults.player.spoof_url.as_ref().map(Url::to_string),
@@ -72,6 +89,13 @@ impl OpenDialog {
             defaults.proxy.as_ref().map(Url::to_string),
             UrlField::new("socks5://localhost:8080"),
         );
+        let open_movie = default_url.clone().map(|url| {
+            (
+                url,
+                defaults.player.player_runtime,
+                defaults.player.player_version,
+            )
+        });
         let path = PathOrUrlField::new(default_url, "path/to/movie.swf", picker);
         let script_timeout = OptionalField::new(
             defaults
@@ -222,8 +246,13 @@ impl OpenDialog {
                 }),
             ),
         );
-        let player_version =
-            OptionalField::new(defaults.player.player_version, NumberField::new(1..=32, 32));
+        let player_version = OptionalField::new(
+            defaults
+                .player
+                .player_version
+                .map(|version| version.to_string()),
+            PlayerVersionField::new("32,0,0,465"),
+        );
         let player_runtime = OptionalField::new(
             defaults.player.player_runtime,
             EnumDropdownField::new(
@@ -255,7 +284,21 @@ impl OpenDialog {
                 }),
             ),
         );
+        let context_menu_mode = OptionalField::new(
+            defaults.context_menu_mode,
+            EnumDropdownField::new(
+                ContextMenuMode::Shown,
+                vec![
+                    ContextMenuMode::Shown,
+                    ContextMenuMode::CtrlClick,
+                    ContextMenuMode::Disabled,
+                ],
+                Box::new(|value, locale| context_menu_mode_name(locale, value)),
+            ),
+        );
 
+        let default_parameters = defaults.player.parameters.clone();
+        let default_spoof_url = defaults.player.spoof_url.clone();
         Self {
             options: defaults,
             event_loop,
@@ -265,6 +308,11 @@ impl OpenDialog {
             base_url,
             proxy_url,
             path,
+            restored_url: None,
+            default_parameters,
+            default_spoof_url,
+            spoof_url_as_base: false,
+            open_movie,
             framerate: 30.0,
             framerate_enabled: false,
             script_timeout,
@@ -278,22 +326,49 @@ impl OpenDialog {
             player_runtime,
             dummy_external_interface,
             upgrade_to_https,
+            context_menu_mode,
         }
     }
 
-    fn start(&mut self) -> bool {
+    /// Replaces the path or URL of the movie to open.
+    pub fn set_path(&mut self, path: &str) {
+        self.path.set_value(path);
+    }
+
+    fn start(&mut self, preferences: &GlobalPreferences) -> bool {
         if self.framerate_enabled {
             self.options.player.frame_rate = Some(self.framerate);
         } else {
             self.options.player.frame_rate = None;
         }
+        self.options
+            .player
+            .parameters
+            .retain(|(name, _)| !name.is_empty());
         if let Some(url) = self.path.result() {
+            let movie_options = MovieOptions {
+                url: url.clone(),
+                parameters: self.options.player.parameters.clone(),
+                spoof_url: self.options.player.spoof_url.clone(),
+                spoof_url_as_base: self.spoof_url_as_base,
+                // The display settings are chosen from the View menu rather than here.
+                ..preferences
+                    .movie_options(url)
+                    .unwrap_or_else(|| MovieOptions::new(url.clone()))
+            };
+            if let Err(e) =
+                preferences.write_preferences(|writer| writer.set_movie_options(movie_options))
+            {
+                tracing::warn!("Couldn't save the movie options: {e}");
+            }
+
+            let mut options = self.options.clone();
+            if self.spoof_url_as_base && options.player.base.is_none() {
+                options.player.base = options.player.spoof_url.clone();
+            }
             if self
                 .event_loop
-                .send_event(RuffleEvent::Open(
-                    url.clone(),
-                    Box::new(self.options.clone()),
-                ))
+                .send_event(RuffleEvent::Open(url.clone(), Box::new(options)))
                 .is_ok()
             {
                 return true;
@@ -303,10 +378,19 @@ impl OpenDialog {
         false
     }
 
-    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+    /// Shows the dialog, along with the volume controls shared with the rest of the GUI.
+    pub fn show(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        volume_controls: &mut VolumeControls,
+        player: Option<&mut Player>,
+        preferences: &GlobalPreferences,
+    ) -> bool {
         let mut keep_open = true;
         let mut should_close = false;
         let mut is_valid = true;
+        let is_movie_open = player.is_some();
 
         Window::new(text(locale, "open-dialog"))
             .open(&mut keep_open)
@@ -324,6 +408,7 @@ impl OpenDialog {
                             ui.end_row();
                         });
                 });
+                self.restore_movie_options(preferences);
 
                 ui.collapsing(text(locale, "network-settings"), |ui| {
                     is_valid &= self.network_settings(locale, ui);
@@ -331,19 +416,39 @@ impl OpenDialog {
 
                 ui.collapsing(text(locale, "player-settings"), |ui| {
                     self.player_settings(locale, ui);
+                    ui.separator();
+                    volume_controls.ui(locale, ui, player, preferences);
                 });
 
                 ui.collapsing(text(locale, "movie-parameters"), |ui| {
                     self.movie_parameters(locale, ui);
                 });
 
+                if is_movie_open && self.is_reload_required() {
+                    ui.horizontal(|ui| {
+                        ui.colored_label(
+                            ui.style().visuals.warn_fg_color,
+                            text(locale, "open-dialog-reload-required"),
+                        );
+                        if ui
+                            .add_enabled(
+                                is_valid,
+                                Button::new(text(locale, "open-dialog-reload-now")),
+                            )
+                            .clicked()
+                        {
+                            should_close = self.start(preferences);
+                        }
+                    });
+                }
+
                 ui.horizontal(|ui| {
                     ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
                         if ui
                             .add_enabled(is_valid, Button::new(text(locale, "start")))
                             .clicked()
                         {
-                            should_close = self.start();
+                            should_close = self.start(preferences);
                         }
                     })
                 });
@@ -374,6 +479,14 @@ impl OpenDialog {
                     .is_valid();
                 ui.end_row();
 
+                ui.label(text(locale, "spoof-swf-url-as-base"));
+                ui.add_enabled(
+                    self.options.player.spoof_url.is_some(),
+                    Checkbox::without_text(&mut self.spoof_url_as_base),
+                )
+                .on_hover_text(text(locale, "spoof-swf-url-as-base-tooltip"));
+                ui.end_row();
+
                 ui.label(text(locale, "referer-url"));
                 is_valid &= self
                     .referer
@@ -473,6 +586,11 @@ impl OpenDialog {
                 }
                 ui.end_row();
 
+                ui.label(text(locale, "context-menu-mode"));
+                self.context_menu_mode
+                    .ui(ui, &mut self.options.context_menu_mode, locale);
+                ui.end_row();
+
                 ui.label(text(locale, "dummy-external-interface"));
                 self.dummy_external_interface.ui(
                     ui,
@@ -507,6 +625,52 @@ impl OpenDialog {
             });
     }
 
+    /// Whether the open movie is chosen with another runtime or version than it's running with,
+    /// which only applies once it's loaded again.
+    fn is_reload_required(&self) -> bool {
+        self.open_movie
+            .as_ref()
+            .is_some_and(|(url, player_runtime, player_version)| {
+                self.path.result() == Some(url)
+                    && (
+                        self.options.player.player_runtime,
+                        self.options.player.player_version,
+                    ) != (*player_runtime, *player_version)
+            })
+    }
+
+    /// Brings back the parameters and spoofed URL the chosen movie was last opened with, if it had any.
+    fn restore_movie_options(&mut self, preferences: &GlobalPreferences) {
+        let url = self.path.result();
+        if url == self.restored_url.as_ref() {
+            return;
+        }
+        self.restored_url = url.cloned();
+        let Some(url) = url else {
+            return;
+        };
+        let movie_options = preferences
+            .movie_options(url)
+            .unwrap_or_else(|| MovieOptions::new(url.clone()));
+
+        // What the movie chosen before had saved doesn't carry over to one without saved options.
+        self.options.player.parameters = if movie_options.parameters.is_empty() {
+            self.default_parameters.clone()
+        } else {
+            movie_options.parameters
+        };
+        if let Some(spoof_url) = movie_options.spoof_url {
+            self.spoof_url.set(Some(spoof_url.to_string()));
+            self.options.player.spoof_url = Some(spoof_url);
+            self.spoof_url_as_base = movie_options.spoof_url_as_base;
+        } else {
+            self.spoof_url
+                .set(self.default_spoof_url.as_ref().map(Url::to_string));
+            self.options.player.spoof_url = self.default_spoof_url.clone();
+            self.spoof_url_as_base = false;
+        }
+    }
+
     fn movie_parameters(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
         ui.horizontal(|ui| {
             if ui
@@ -530,15 +694,25 @@ impl OpenDialog {
             }
         });
 
+        let mut pasted_query = None;
         Grid::new("open-file-params")
             .num_columns(2)
             .spacing([5.0, 4.0])
             .min_col_width(100.0)
             .striped(true)
             .show(ui, |ui| {
+                let mut index = 0;
                 self.options.player.parameters.retain_mut(|(key, value)| {
                     let mut keep = true;
-                    ui.text_edit_singleline(key);
+                    if ui
+                        .text_edit_singleline(key)
+                        .on_hover_text(text(locale, "open-dialog-parameter-name-tooltip"))
+                        .changed()
+                        && key.contains('=')
+                    {
+                        // A query string such as `a=1&b=2` was pasted, which is split into its parameters.
+                        pasted_query = Some((index, mem::take(key)));
+                    }
                     ui.horizontal(|ui| {
                         ui.text_edit_singleline(value);
                         if ui
@@ -550,9 +724,24 @@ impl OpenDialog {
                         }
                     });
                     ui.end_row();
+                    if keep {
+                        index += 1;
+                    }
                     keep
                 });
             });
+
+        if let Some((index, query)) = pasted_query {
+            let pasted: Vec<_> = form_urlencoded::parse(query.trim_start_matches('?').as_bytes())
+                .map(|(name, value)| (name.into_owned(), value.into_owned()))
+                .collect();
+            let parameters = &mut self.options.player.parameters;
+            if index < parameters.len() {
+                parameters.splice(index..=index, pasted);
+            } else {
+                parameters.extend(pasted);
+            }
+        }
     }
 }
 
@@ -672,31 +861,37 @@ impl InnerField for DurationField {
     }
 }
 
-struct NumberField<T: emath::Numeric> {
-    range: RangeInclusive<T>,
-    default: T,
+struct PlayerVersionField {
+    hint: &'static str,
 }
 
-impl<T: emath::Numeric> NumberField<T> {
-    pub fn new(range: RangeInclusive<T>, default: T) -> Self {
-        Self { range, default }
+impl PlayerVersionField {
+    pub fn new(hint: &'static str) -> Self {
+        Self { hint }
     }
 }
 
-impl<T: emath::Numeric> InnerField for NumberField<T> {
-    type Value = T;
-    type Result = T;
+impl InnerField for PlayerVersionField {
+    type Value = String;
+    type Result = PlayerVersion;
 
     fn value_if_missing(&self) -> Self::Value {
-        self.default
+        String::new()
     }
 
-    fn ui(&self, ui: &mut Ui, value: &mut Self::Value, _error: bool, _locale: &LanguageIdentifier) {
-        Slider::new(value, self.range.clone()).ui(ui);
+    fn ui(&self, ui: &mut Ui, value: &mut Self::Value, error: bool, _locale: &LanguageIdentifier) {
+        TextEdit::singleline(value)
+            .hint_text(self.hint)
+            .text_color_opt(if error {
+                Some(ui.style().visuals.error_fg_color)
+            } else {
+                None
+            })
+            .ui(ui);
     }
 
     fn value_to_result(&self, value: &Self::Value) -> Result<Self::Result, ()> {
-        Ok(*value)
+        parse_player_version(value).ok_or(())
     }
 }
 
@@ -901,6 +1096,15 @@ impl<Inner: InnerField> OptionalField<Inner> {
         self
     }
 
+    /// Replaces the value of the field, turning it off without one.
+    pub fn set(&mut self, value: Option<Inner::Value>) {
+        self.enabled = value.is_some();
+        if let Some(value) = value {
+            self.value = value;
+        }
+        self.error = false;
+    }
+
     pub fn is_valid(&self) -> bool {
         !self.error
     }
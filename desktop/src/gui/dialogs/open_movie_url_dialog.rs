@@ -0,0 +1,127 @@
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
+use crate::gui::text;
+use crate::player::LaunchOptions;
+use crate::preferences::GlobalPreferences;
+use egui::{Align2, Button, Color32, ComboBox, Key, Layout, TextEdit, Ui, Window};
+use unic_langid::LanguageIdentifier;
+use url::Url;
+
+/// Asks for the URL of a movie to open, offering the URLs of recently opened movies.
+///
+/// The history is the part of the recents that was opened from the network,
+/// so it shares their limit, and opening a movie from here adds it to both.
+pub struct OpenMovieUrlDialog {
+    event_loop: RuffleEventProxy,
+    options: LaunchOptions,
+    url: String,
+    /// The URLs of the movies recently opened from the network, the latest first.
+    history: Vec<Url>,
+    /// Whether the text field still has to be focused, as the dialog was just opened.
+    focus_field: bool,
+}
+
+impl OpenMovieUrlDialog {
+    pub fn new(
+        preferences: &GlobalPreferences,
+        options: LaunchOptions,
+        event_loop: RuffleEventProxy,
+    ) -> Self {
+        let history = preferences.recents(|recents| {
+            recents
+                .iter()
+                .rev()
+                .filter(|recent| !recent.is_invalid() && recent.url.scheme() != "file")
+                .map(|recent| recent.url.clone())
+                .collect()
+        });
+        Self {
+            event_loop,
+            options,
+            url: String::new(),
+            history,
+            focus_field: true,
+        }
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut keep_open = true;
+        let mut should_close = false;
+
+        Window::new(text(locale, "open-movie-url-dialog"))
+            .open(&mut keep_open)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .collapsible(false)
+            .resizable(false)
+            .show(egui_ctx, |ui| {
+                should_close = self.render_window_contents(locale, ui);
+            });
+
+        keep_open && !should_close
+    }
+
+    fn render_window_contents(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) -> bool {
+        let mut submitted = false;
+        let mut cancelled = false;
+
+        ui.horizontal(|ui| {
+            ui.label(text(locale, "open-movie-url-dialog-url"));
+            let response = ui.add(
+                TextEdit::singleline(&mut self.url)
+                    .hint_text("https://example.org/game.swf")
+                    .desired_width(360.0),
+            );
+            if std::mem::take(&mut self.focus_field) {
+                response.request_focus();
+            }
+            submitted = response.lost_focus() && ui.input(|input| input.key_pressed(Key::Enter));
+
+            ui.add_enabled_ui(!self.history.is_empty(), |ui| {
+                ComboBox::from_id_salt("open-movie-url-history")
+                    .selected_text(text(locale, "open-movie-url-dialog-history"))
+                    .show_ui(ui, |ui| {
+                        for url in &self.history {
+                            if ui.selectable_label(false, url.as_str()).clicked() {
+                                self.url = url.to_string();
+                            }
+                        }
+                    });
+            });
+        });
+
+        let url = Url::parse(self.url.trim());
+        if let Err(e) = &url {
+            if !self.url.trim().is_empty() {
+                ui.colored_label(Color32::LIGHT_RED, e.to_string());
+            }
+        }
+
+        ui.separator();
+        ui.horizontal(|ui| {
+            ui.with_layout(Layout::right_to_left(egui::Align::Center), |ui| {
+                if ui
+                    .add_enabled(
+                        url.is_ok(),
+                        Button::new(text(locale, "open-movie-url-dialog-open")),
+                    )
+                    .clicked()
+                {
+                    submitted = true;
+                }
+                if ui.button(text(locale, "dialog-cancel")).clicked() {
+                    cancelled = true;
+                }
+            });
+        });
+
+        match url {
+            Ok(url) if submitted => {
+                // Any error fetching the movie is reported by the message dialog once it's loading.
+                let _ = self
+                    .event_loop
+                    .send_event(RuffleEvent::Open(url, Box::new(self.options.clone())));
+                true
+            }
+            _ => cancelled,
+        }
+    }
+}
@@ -1,12 +1,39 @@
-use crate::cli::{GameModePreference, OpenUrlMode};
-use crate::gui::{available_languages, optional_text, text, ThemePreference};
+use crate::cli::{ContextMenuMode, GameModePreference, OpenUrlMode};
+use crate::gui::menu_bar::MenuBar;
+use crate::gui::{
+    available_backends, available_languages, available_video_modes, optional_text, text,
+    BackgroundColor, FullscreenMonitor, KeyRemaps, MenuVisibility, Shortcut, ThemePreference,
+    VideoModePreference,
+};
 use crate::log::FilenamePattern;
-use crate::preferences::{storage::StorageBackend, GlobalPreferences};
+use crate::player::UnfocusedBehavior;
+use crate::preferences::{
+    parse_player_version, storage::StorageBackend, GlobalPreferences, FRAME_LATENCY_RANGE,
+    PLAYER_VERSION_RANGE, SCALE_FACTOR_OVERRIDE_RANGE, UI_SAMPLE_COUNTS,
+};
 use cpal::traits::{DeviceTrait, HostTrait};
-use egui::{Align2, Button, Checkbox, ComboBox, DragValue, Grid, Ui, Widget, Window};
+use egui::{
+    Align2, Button, CentralPanel, Checkbox, ComboBox, DragValue, Event, Grid, Key, ScrollArea,
+    TextEdit, Ui, Widget, Window,
+};
+use ruffle_core::PlayerRuntime;
+use ruffle_frontend_utils::player_options::PlayerVersion;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
 use std::borrow::Cow;
+use std::mem;
 use unic_langid::LanguageIdentifier;
+use url::Url;
+use winit::monitor::MonitorHandle;
+
+/// The color first picked when switching to a custom background color.
+const DEFAULT_CUSTOM_BACKGROUND_COLOR: [u8; 3] = [0x40, 0x40, 0x40];
+
+#[derive(Clone, Copy, PartialEq, Eq)]
+enum ShortcutField {
+    Toggle,
+    Exit,
+    Mute,
+}
 
 pub struct PreferencesDialog {
     available_backends: wgpu::Backends,
@@ -20,6 +47,15 @@ pub struct PreferencesDialog {
     power_preference_readonly: bool,
     power_preference_changed: bool,
 
+    frame_latency: u32,
+    frame_latency_changed: bool,
+
+    ui_sample_count: u32,
+    ui_sample_count_changed: bool,
+
+    scale_factor_override: Option<f64>,
+    scale_factor_override_changed: bool,
+
     gamemode_preference: GameModePreference,
     gamemode_preference_readonly: bool,
     gamemode_preference_changed: bool,
@@ -31,6 +67,12 @@ pub struct PreferencesDialog {
     available_output_devices: Vec<String>,
     output_device_changed: bool,
 
+    unfocused_behavior: UnfocusedBehavior,
+    unfocused_behavior_changed: bool,
+
+    mute_shortcut: Shortcut,
+    mute_shortcut_changed: bool,
+
     enable_openh264: bool,
     enable_openh264_changed: bool,
     openh264_license_visible: bool,
@@ -38,6 +80,20 @@ pub struct PreferencesDialog {
     recent_limit: usize,
     recent_limit_changed: bool,
 
+    remember_window_geometry: bool,
+    remember_window_geometry_changed: bool,
+
+    show_fps_in_title: bool,
+    show_fps_in_title_changed: bool,
+
+    idle_power_save: bool,
+    idle_power_save_changed: bool,
+
+    touch_as_mouse: bool,
+    touch_as_mouse_changed: bool,
+    long_press_duration: u32,
+    long_press_duration_changed: bool,
+
     log_filename_pattern: FilenamePattern,
     log_filename_pattern_changed: bool,
 
@@ -48,17 +104,83 @@ pub struct PreferencesDialog {
     theme_preference: ThemePreference,
     theme_preference_changed: bool,
 
+    menu_visibility: MenuVisibility,
+    menu_visibility_changed: bool,
+
+    background_color: BackgroundColor,
+    background_color_changed: bool,
+
+    fullscreen_toggle_shortcut: Shortcut,
+    fullscreen_toggle_shortcut_changed: bool,
+
+    fullscreen_exit_shortcut: Shortcut,
+    fullscreen_exit_shortcut_changed: bool,
+
+    fullscreen_hold_to_exit: bool,
+    fullscreen_hold_to_exit_changed: bool,
+
+    fullscreen_hide_cursor_after: u32,
+    fullscreen_hide_cursor_after_changed: bool,
+
+    fullscreen_exclusive: bool,
+    fullscreen_exclusive_changed: bool,
+
+    fullscreen_monitor: FullscreenMonitor,
+    available_monitors: Vec<MonitorHandle>,
+    /// The monitor the window is on, used when no monitor is chosen.
+    current_monitor: Option<MonitorHandle>,
+    fullscreen_monitor_changed: bool,
+
+    fullscreen_video_mode: Option<VideoModePreference>,
+    fullscreen_video_mode_changed: bool,
+
+    /// The shortcut currently waiting for the user to press a key, if any.
+    capturing_shortcut: Option<ShortcutField>,
+
+    key_remaps: KeyRemaps,
+    key_remaps_changed: bool,
+
+    /// The open movie and the keys remapped only for it, if a movie is open.
+    movie_key_remaps: Option<(Url, KeyRemaps)>,
+    movie_key_remaps_changed: bool,
+
+    /// Whether the remaps shown are the ones for the open movie, rather than for all movies.
+    editing_movie_key_remaps: bool,
+    /// Whether the next key pressed is added as a remapped key.
+    capturing_key_remap: bool,
+
     open_url_mode: OpenUrlMode,
     open_url_mode_readonly: bool,
     open_url_mode_changed: bool,
 
+    context_menu_mode: ContextMenuMode,
+    context_menu_mode_readonly: bool,
+    context_menu_mode_changed: bool,
+
+    player_runtime: Option<PlayerRuntime>,
+    player_runtime_readonly: bool,
+    player_runtime_changed: bool,
+
+    player_version: Option<PlayerVersion>,
+    /// The custom player version as it's being typed, which may not be valid yet.
+    player_version_text: String,
+    player_version_readonly: bool,
+    player_version_changed: bool,
+
     ime_enabled: Option<bool>,
     ime_enabled_changed: bool,
+
+    /// Whether the user asked to pop the dialog out into a window of its own.
+    detach_requested: bool,
 }
 
 impl PreferencesDialog {
-    pub fn new(preferences: GlobalPreferences) -> Self {
-        let available_backends = find_available_graphics_backends();
+    pub fn new(
+        preferences: GlobalPreferences,
+        window: Option<&winit::window::Window>,
+        movie_url: Option<Url>,
+    ) -> Self {
+        let available_backends = available_backends().into_iter().collect();
 
         let audio_host = cpal::default_host();
         let mut available_output_devices = Vec::new();
@@ -70,6 +192,11 @@ impl PreferencesDialog {
             }
         }
 
+        let available_monitors = window
+            .map(|window| window.available_monitors().collect())
+            .unwrap_or_default();
+        let current_monitor = window.and_then(|window| window.current_monitor());
+
         Self {
             available_backends,
             graphics_backend: preferences.graphics_backends(),
@@ -80,6 +207,15 @@ impl PreferencesDialog {
             power_preference_readonly: preferences.cli.power.is_some(),
             power_preference_changed: false,
 
+            frame_latency: preferences.frame_latency(),
+            frame_latency_changed: false,
+
+            ui_sample_count: preferences.ui_sample_count(),
+            ui_sample_count_changed: false,
+
+            scale_factor_override: preferences.scale_factor_override(),
+            scale_factor_override_changed: false,
+
             gamemode_preference: preferences.gamemode_preference(),
             gamemode_preference_readonly: preferences.cli.gamemode.is_some(),
             gamemode_preference_changed: false,
@@ -91,6 +227,12 @@ impl PreferencesDialog {
             available_output_devices,
             output_device_changed: false,
 
+            unfocused_behavior: preferences.unfocused_behavior(),
+            unfocused_behavior_changed: false,
+
+            mute_shortcut: preferences.mute_shortcut(),
+            mute_shortcut_changed: false,
+
             enable_openh264: preferences.openh264_enabled(),
             enable_openh264_changed: false,
             openh264_license_visible: false,
@@ -98,6 +240,21 @@ impl PreferencesDialog {
             recent_limit: preferences.recent_limit(),
             recent_limit_changed: false,
 
+            remember_window_geometry: preferences.remember_window_geometry(),
+            remember_window_geometry_changed: false,
+
+            show_fps_in_title: preferences.show_fps_in_title(),
+            show_fps_in_title_changed: false,
+
+            idle_power_save: preferences.idle_power_save(),
+            idle_power_save_changed: false,
+            touch_as_mouse: preferences.touch_as_mouse(),
+            touch_as_mouse_changed: false,
+            long_press_duration: preferences
+                .long_press_duration()
+                .map_or(0, |duration| duration.as_millis() as u32),
+            long_press_duration_changed: false,
+
             log_filename_pattern: preferences.log_filename_pattern(),
             log_filename_pattern_changed: false,
 
@@ -108,13 +265,75 @@ impl PreferencesDialog {
             theme_preference: preferences.theme_preference(),
             theme_preference_changed: false,
 
+            menu_visibility: preferences.menu_visibility(),
+            menu_visibility_changed: false,
+
+            background_color: preferences.background_color(),
+            background_color_changed: false,
+
+            fullscreen_toggle_shortcut: preferences.fullscreen_toggle_shortcut(),
+            fullscreen_toggle_shortcut_changed: false,
+
+            fullscreen_exit_shortcut: preferences.fullscreen_exit_shortcut(),
+            fullscreen_exit_shortcut_changed: false,
+
+            fullscreen_hold_to_exit: preferences.fullscreen_hold_to_exit(),
+            fullscreen_hold_to_exit_changed: false,
+
+            fullscreen_hide_cursor_after: preferences
+                .fullscreen_hide_cursor_after()
+                .map_or(0, |duration| duration.as_secs() as u32),
+            fullscreen_hide_cursor_after_changed: false,
+
+            fullscreen_exclusive: preferences.fullscreen_exclusive(),
+            fullscreen_exclusive_changed: false,
+
+            fullscreen_monitor: preferences.fullscreen_monitor(),
+            available_monitors,
+            current_monitor,
+            fullscreen_monitor_changed: false,
+
+            fullscreen_video_mode: preferences.fullscreen_video_mode(),
+            fullscreen_video_mode_changed: false,
+
+            capturing_shortcut: None,
+
+            key_remaps: preferences.key_remaps(),
+            key_remaps_changed: false,
+
+            movie_key_remaps: movie_url.map(|url| {
+                let remaps = preferences.movie_key_remaps(&url);
+                (url, remaps)
+            }),
+            movie_key_remaps_changed: false,
+
+            editing_movie_key_remaps: false,
+            capturing_key_remap: false,
+
             open_url_mode: preferences.open_url_mode(),
             open_url_mode_readonly: preferences.cli.open_url_mode.is_some(),
             open_url_mode_changed: false,
+            context_menu_mode: preferences.context_menu_mode(),
+            context_menu_mode_readonly: preferences.cli.context_menu.is_some(),
+            context_menu_mode_changed: false,
+
+            player_runtime: preferences.player_runtime(),
+            player_runtime_readonly: preferences.cli.player_runtime.is_some(),
+            player_runtime_changed: false,
+
+            player_version: preferences.player_version(),
+            player_version_text: preferences
+                .player_version()
+                .unwrap_or(PlayerVersion::new(*PLAYER_VERSION_RANGE.end()))
+                .to_string(),
+            player_version_readonly: preferences.cli.player_version.is_some(),
+            player_version_changed: false,
 
             ime_enabled: preferences.ime_enabled(),
             ime_enabled_changed: false,
 
+            detach_requested: false,
+
             preferences,
         }
     }
@@ -122,7 +341,6 @@ impl PreferencesDialog {
     pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
         let mut keep_open = true;
         let mut should_close = false;
-        let locked_text = text(locale, "preference-locked-by-cli");
 
         Window::new(text(locale, "preferences-dialog"))
             .open(&mut keep_open)
@@ -130,60 +348,126 @@ impl PreferencesDialog {
             .collapsible(false)
             .resizable(false)
             .show(egui_ctx, |ui| {
-                ui.vertical_centered_justified(|ui| {
-                    Grid::new("preferences-dialog-graphics")
-                        .num_columns(2)
-                        .striped(true)
-                        .show(ui, |ui| {
-                            self.show_graphics_preferences(locale, &locked_text, ui);
-
-                            if cfg!(target_os = "linux") {
-                                self.show_gamemode_preferences(locale, &locked_text, ui);
-                            }
+                should_close = self.show_contents(locale, egui_ctx, ui, false);
+            });
 
-                            self.show_open_url_mode_preferences(locale, &locked_text, ui);
+        keep_open && !should_close
+    }
 
-                            self.show_ime_preferences(locale, ui);
+    /// Shows the dialog filling a window of its own, after it was popped out of the main window.
+    ///
+    /// Returns false once it should be closed, either by saving or by closing its window.
+    pub fn show_detached(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) -> bool {
+        let mut should_close = egui_ctx.input(|input| input.viewport().close_requested());
+        CentralPanel::default().show(egui_ctx, |ui| {
+            ScrollArea::vertical().show(ui, |ui| {
+                should_close |= self.show_contents(locale, egui_ctx, ui, true);
+            });
+        });
+        !should_close
+    }
 
-                            self.show_language_preferences(locale, ui);
+    /// Whether the user asked to pop the dialog out into a window of its own, since the last call.
+    pub fn take_detach_request(&mut self) -> bool {
+        mem::take(&mut self.detach_requested)
+    }
 
-                            self.show_theme_preferences(locale, ui);
+    /// Shows the preferences and the buttons below them, returning true once they were saved.
+    fn show_contents(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        ui: &mut Ui,
+        detached: bool,
+    ) -> bool {
+        let mut should_close = false;
+        let locked_text = text(locale, "preference-locked-by-cli");
 
-                            self.show_audio_preferences(locale, ui);
+        ui.vertical_centered_justified(|ui| {
+            Grid::new("preferences-dialog-graphics")
+                .num_columns(2)
+                .striped(true)
+                .show(ui, |ui| {
+                    self.show_graphics_preferences(locale, &locked_text, ui);
 
-                            self.show_video_preferences(egui_ctx, locale, ui);
+                    if cfg!(target_os = "linux") {
+                        self.show_gamemode_preferences(locale, &locked_text, ui);
+                    }
 
-                            self.show_log_preferences(locale, ui);
+                    self.show_open_url_mode_preferences(locale, &locked_text, ui);
 
-                            self.show_storage_preferences(locale, &locked_text, ui);
+                    self.show_context_menu_mode_preferences(locale, &locked_text, ui);
 
-                            self.show_misc_preferences(locale, ui);
-                        });
+                    self.show_player_emulation_preferences(locale, &locked_text, ui);
 
-                    if self.restart_required() {
-                        ui.colored_label(
-                            ui.style().visuals.error_fg_color,
-                            "A restart is required to apply the selected changes",
-                        );
-                    }
+                    self.show_ime_preferences(locale, ui);
 
-                    ui.horizontal(|ui| {
-                        ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
-                            if Button::new(text(locale, "save")).ui(ui).clicked() {
-                                self.save();
-                                should_close = true;
-                            }
-                        })
-                    });
+                    self.show_language_preferences(locale, ui);
+
+                    self.show_theme_preferences(locale, ui);
+
+                    self.show_menu_visibility_preferences(locale, ui);
+
+                    self.show_background_color_preferences(locale, ui);
+
+                    self.show_fullscreen_preferences(locale, ui);
+
+                    self.show_key_remap_preferences(locale, ui);
+
+                    self.show_audio_preferences(locale, ui);
+
+                    self.show_video_preferences(egui_ctx, locale, ui);
+
+                    self.show_log_preferences(locale, ui);
+
+                    self.show_storage_preferences(locale, &locked_text, ui);
+
+                    self.show_misc_preferences(locale, ui);
                 });
+
+            if self.restart_required() {
+                ui.colored_label(
+                    ui.style().visuals.error_fg_color,
+                    "A restart is required to apply the selected changes",
+                );
+            }
+
+            if let Some(conflict) = self.shortcut_conflict() {
+                ui.colored_label(ui.style().visuals.error_fg_color, text(locale, conflict));
+            }
+
+            ui.horizontal(|ui| {
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    let save_enabled = self.shortcut_conflict().is_none();
+                    if ui
+                        .add_enabled(save_enabled, Button::new(text(locale, "save")))
+                        .clicked()
+                    {
+                        self.save();
+                        should_close = true;
+                    }
+                    if !detached
+                        && ui
+                            .button(text(locale, "preferences-dialog-detach"))
+                            .on_hover_text_at_pointer(text(
+                                locale,
+                                "preferences-dialog-detach-tooltip",
+                            ))
+                            .clicked()
+                    {
+                        self.detach_requested = true;
+                    }
+                })
             });
+        });
 
-        keep_open && !should_close
+        should_close
     }
 
     fn restart_required(&self) -> bool {
         self.graphics_backend != self.preferences.graphics_backends()
             || self.power_preference != self.preferences.graphics_power_preference()
+            || self.ui_sample_count != self.preferences.ui_sample_count()
             || self.output_device != self.preferences.output_device_name()
             || self.enable_openh264 != self.preferences.openh264_enabled()
             || self.log_filename_pattern != self.preferences.log_filename_pattern()
@@ -210,33 +494,22 @@ impl PreferencesDialog {
                         GraphicsBackend::Default,
                         text(locale, "graphics-backend-default"),
                     );
-                    if self.available_backends.contains(wgpu::Backends::VULKAN) {
-                        ui.selectable_value(
-                            &mut self.graphics_backend,
-                            GraphicsBackend::Vulkan,
-                            "Vulkan",
-                        );
-                    }
-                    if self.available_backends.contains(wgpu::Backends::METAL) {
-                        ui.selectable_value(
-                            &mut self.graphics_backend,
-                            GraphicsBackend::Metal,
-                            "Metal",
-                        );
-                    }
-                    if self.available_backends.contains(wgpu::Backends::DX12) {
-                        ui.selectable_value(
-                            &mut self.graphics_backend,
-                            GraphicsBackend::Dx12,
-                            "DirectX 12",
-                        );
-                    }
-                    if self.available_backends.contains(wgpu::Backends::GL) {
-                        ui.selectable_value(
-                            &mut self.graphics_backend,
-                            GraphicsBackend::Gl,
-                            "OpenGL",
-                        );
+                    // Unavailable backends are still listed, to tell why they can't be chosen.
+                    for backend in [
+                        GraphicsBackend::Vulkan,
+                        GraphicsBackend::Metal,
+                        GraphicsBackend::Dx12,
+                        GraphicsBackend::Gl,
+                    ] {
+                        let available = self.available_backends.contains(backend.into());
+                        ui.add_enabled_ui(available, |ui| {
+                            ui.selectable_value(
+                                &mut self.graphics_backend,
+                                backend,
+                                graphics_backend_name(locale, backend),
+                            )
+                            .on_disabled_hover_text(text(locale, "graphics-backend-unavailable"));
+                        });
                     }
                 });
             if self.graphics_backend != previous {
@@ -270,6 +543,61 @@ impl PreferencesDialog {
             }
         }
         ui.end_row();
+
+        ui.label(text(locale, "graphics-frame-latency"))
+            .on_hover_text_at_pointer(text(locale, "graphics-frame-latency-tooltip"));
+        let previous = self.frame_latency;
+        ComboBox::from_id_salt("graphics-frame-latency")
+            .selected_text(self.frame_latency.to_string())
+            .show_ui(ui, |ui| {
+                for value in FRAME_LATENCY_RANGE {
+                    ui.selectable_value(&mut self.frame_latency, value, value.to_string());
+                }
+            });
+        if self.frame_latency != previous {
+            self.frame_latency_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "graphics-ui-sample-count"))
+            .on_hover_text_at_pointer(text(locale, "graphics-ui-sample-count-tooltip"));
+        let previous = self.ui_sample_count;
+        ComboBox::from_id_salt("graphics-ui-sample-count")
+            .selected_text(ui_sample_count_name(locale, self.ui_sample_count))
+            .show_ui(ui, |ui| {
+                for value in UI_SAMPLE_COUNTS {
+                    ui.selectable_value(
+                        &mut self.ui_sample_count,
+                        value,
+                        ui_sample_count_name(locale, value),
+                    );
+                }
+            });
+        if self.ui_sample_count != previous {
+            self.ui_sample_count_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "graphics-scale-factor-override"))
+            .on_hover_text_at_pointer(text(locale, "graphics-scale-factor-override-tooltip"));
+        let previous = self.scale_factor_override;
+        ui.horizontal(|ui| {
+            let mut enabled = self.scale_factor_override.is_some();
+            ui.add(Checkbox::without_text(&mut enabled));
+            let mut scale_factor = self.scale_factor_override.unwrap_or(1.0);
+            ui.add_enabled(
+                enabled,
+                DragValue::new(&mut scale_factor)
+                    .range(SCALE_FACTOR_OVERRIDE_RANGE)
+                    .speed(0.05)
+                    .fixed_decimals(2),
+            );
+            self.scale_factor_override = enabled.then_some(scale_factor);
+        });
+        if self.scale_factor_override != previous {
+            self.scale_factor_override_changed = true;
+        }
+        ui.end_row();
     }
 
     fn show_language_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
@@ -320,6 +648,385 @@ impl PreferencesDialog {
         ui.end_row();
     }
 
+    fn show_menu_visibility_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "menu-visibility"));
+        let previous = self.menu_visibility;
+        ComboBox::from_id_salt("menu-visibility")
+            .selected_text(menu_visibility_name(locale, self.menu_visibility))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut self.menu_visibility,
+                    MenuVisibility::Always,
+                    menu_visibility_name(locale, MenuVisibility::Always),
+                );
+                ui.selectable_value(
+                    &mut self.menu_visibility,
+                    MenuVisibility::AutoHide,
+                    menu_visibility_name(locale, MenuVisibility::AutoHide),
+                );
+                ui.selectable_value(
+                    &mut self.menu_visibility,
+                    MenuVisibility::Never,
+                    menu_visibility_name(locale, MenuVisibility::Never),
+                )
+                .on_hover_text(text(locale, "menu-visibility-never-tooltip"));
+            });
+        if self.menu_visibility != previous {
+            self.menu_visibility_changed = true;
+        }
+        ui.end_row();
+    }
+
+    fn show_background_color_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "background-color"));
+        let previous = self.background_color;
+        let custom_color = match self.background_color {
+            BackgroundColor::Custom(color) => color,
+            _ => DEFAULT_CUSTOM_BACKGROUND_COLOR,
+        };
+        ui.horizontal(|ui| {
+            ComboBox::from_id_salt("background-color")
+                .selected_text(background_color_name(locale, self.background_color))
+                .show_ui(ui, |ui| {
+                    for background_color in [
+                        BackgroundColor::Stage,
+                        BackgroundColor::Black,
+                        BackgroundColor::White,
+                        BackgroundColor::Custom(custom_color),
+                    ] {
+                        ui.selectable_value(
+                            &mut self.background_color,
+                            background_color,
+                            background_color_name(locale, background_color),
+                        );
+                    }
+                })
+                .response
+                .on_hover_text(text(locale, "background-color-tooltip"));
+            if let BackgroundColor::Custom(color) = &mut self.background_color {
+                ui.color_edit_button_srgb(color);
+            }
+        });
+        if self.background_color != previous {
+            self.background_color_changed = true;
+        }
+        ui.end_row();
+    }
+
+    fn show_fullscreen_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "fullscreen-toggle-shortcut"));
+        if self.show_shortcut_button(locale, ui, ShortcutField::Toggle) {
+            self.fullscreen_toggle_shortcut_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "fullscreen-exit-shortcut"));
+        if self.show_shortcut_button(locale, ui, ShortcutField::Exit) {
+            self.fullscreen_exit_shortcut_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "fullscreen-hold-to-exit"))
+            .on_hover_text_at_pointer(text(locale, "fullscreen-hold-to-exit-tooltip"));
+        let previous = self.fullscreen_hold_to_exit;
+        ui.add(Checkbox::without_text(&mut self.fullscreen_hold_to_exit));
+        if self.fullscreen_hold_to_exit != previous {
+            self.fullscreen_hold_to_exit_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "fullscreen-hide-cursor"))
+            .on_hover_text_at_pointer(text(locale, "fullscreen-hide-cursor-tooltip"));
+        let previous = self.fullscreen_hide_cursor_after;
+        ui.horizontal(|ui| {
+            let mut enabled = self.fullscreen_hide_cursor_after > 0;
+            ui.add(Checkbox::without_text(&mut enabled));
+            let mut seconds = self.fullscreen_hide_cursor_after.max(1);
+            ui.add_enabled(
+                enabled,
+                DragValue::new(&mut seconds)
+                    .range(1..=60)
+                    .suffix(text(locale, "fullscreen-hide-cursor-seconds")),
+            );
+            self.fullscreen_hide_cursor_after = if enabled { seconds } else { 0 };
+        });
+        if self.fullscreen_hide_cursor_after != previous {
+            self.fullscreen_hide_cursor_after_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "fullscreen-monitor"));
+        let monitors: Vec<FullscreenMonitor> = self
+            .available_monitors
+            .iter()
+            .enumerate()
+            .map(|(index, monitor)| FullscreenMonitor::of(index, monitor))
+            .collect();
+        let previous = self.fullscreen_monitor.clone();
+        ComboBox::from_id_salt("fullscreen-monitor")
+            .selected_text(self.fullscreen_monitor.label(locale).into_owned())
+            .show_ui(ui, |ui| {
+                for monitor in std::iter::once(FullscreenMonitor::Current).chain(monitors) {
+                    let label = monitor.label(locale).into_owned();
+                    ui.selectable_value(&mut self.fullscreen_monitor, monitor, label);
+                }
+            });
+        if self.fullscreen_monitor != previous {
+            self.fullscreen_monitor_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "fullscreen-exclusive"))
+            .on_hover_text_at_pointer(text(locale, "fullscreen-exclusive-tooltip"));
+        let previous = self.fullscreen_exclusive;
+        ui.add(Checkbox::without_text(&mut self.fullscreen_exclusive));
+        if self.fullscreen_exclusive != previous {
+            self.fullscreen_exclusive_changed = true;
+        }
+        ui.end_row();
+
+        if !self.fullscreen_exclusive {
+            return;
+        }
+
+        ui.label(text(locale, "fullscreen-video-mode"));
+        let video_modes = self
+            .selected_monitor()
+            .map(available_video_modes)
+            .unwrap_or_default();
+        if !video_modes.is_empty()
+            && self
+                .fullscreen_video_mode
+                .is_some_and(|video_mode| !video_modes.contains(&video_mode))
+        {
+            // The chosen monitor doesn't support the previous mode.
+            self.fullscreen_video_mode = None;
+            self.fullscreen_video_mode_changed = true;
+        }
+        let previous = self.fullscreen_video_mode;
+        ComboBox::from_id_salt("fullscreen-video-mode")
+            .selected_text(video_mode_name(locale, self.fullscreen_video_mode))
+            .show_ui(ui, |ui| {
+                ui.selectable_value(
+                    &mut self.fullscreen_video_mode,
+                    None,
+                    video_mode_name(locale, None),
+                );
+                for video_mode in video_modes {
+                    ui.selectable_value(
+                        &mut self.fullscreen_video_mode,
+                        Some(video_mode),
+                        video_mode_name(locale, Some(video_mode)),
+                    );
+                }
+            });
+        if self.fullscreen_video_mode != previous {
+            self.fullscreen_video_mode_changed = true;
+        }
+        ui.end_row();
+    }
+
+    /// The monitor whose video modes can be chosen for exclusive fullscreen.
+    fn selected_monitor(&self) -> Option<&MonitorHandle> {
+        self.available_monitors
+            .iter()
+            .enumerate()
+            .find(|(index, monitor)| {
+                self.fullscreen_monitor == FullscreenMonitor::of(*index, monitor)
+            })
+            .map(|(_, monitor)| monitor)
+            .or(self.current_monitor.as_ref())
+    }
+
+    /// Shows a button which records the next key pressed as the shortcut.
+    ///
+    /// Returns true if the shortcut has changed.
+    fn show_shortcut_button(
+        &mut self,
+        locale: &LanguageIdentifier,
+        ui: &mut Ui,
+        field: ShortcutField,
+    ) -> bool {
+        let capturing = self.capturing_shortcut == Some(field);
+        let label = if capturing {
+            text(locale, "shortcut-press-keys")
+        } else {
+            ui.ctx().format_shortcut(&self.shortcut(field).0).into()
+        };
+        if ui.selectable_label(capturing, label).clicked() {
+            self.capturing_shortcut = if capturing { None } else { Some(field) };
+        }
+        if !capturing {
+            return false;
+        }
+
+        let pressed = ui.input_mut(|input| {
+            let index = input.events.iter().position(|event| {
+                matches!(
+                    event,
+                    Event::Key {
+                        pressed: true,
+                        repeat: false,
+                        ..
+                    }
+                )
+            })?;
+            match input.events.remove(index) {
+                Event::Key { key, modifiers, .. } => Some(Shortcut::from_egui(key, modifiers)),
+                _ => None,
+            }
+        });
+        let Some(shortcut) = pressed else {
+            return false;
+        };
+        self.capturing_shortcut = None;
+        let changed = shortcut != self.shortcut(field);
+        match field {
+            ShortcutField::Toggle => self.fullscreen_toggle_shortcut = shortcut,
+            ShortcutField::Exit => self.fullscreen_exit_shortcut = shortcut,
+            ShortcutField::Mute => self.mute_shortcut = shortcut,
+        }
+        changed
+    }
+
+    fn shortcut(&self, field: ShortcutField) -> Shortcut {
+        match field {
+            ShortcutField::Toggle => self.fullscreen_toggle_shortcut,
+            ShortcutField::Exit => self.fullscreen_exit_shortcut,
+            ShortcutField::Mute => self.mute_shortcut,
+        }
+    }
+
+    /// Returns the text id describing why the chosen shortcuts can't be used, if they can't.
+    fn shortcut_conflict(&self) -> Option<&'static str> {
+        if self.fullscreen_toggle_shortcut == self.fullscreen_exit_shortcut {
+            return Some("shortcut-conflict-fullscreen");
+        }
+        if self.mute_shortcut == self.fullscreen_toggle_shortcut
+            || self.mute_shortcut == self.fullscreen_exit_shortcut
+        {
+            return Some("shortcut-conflict-mute");
+        }
+        let reserved = MenuBar::RESERVED_SHORTCUTS;
+        if reserved.contains(&self.fullscreen_toggle_shortcut.0)
+            || reserved.contains(&self.fullscreen_exit_shortcut.0)
+            || reserved.contains(&self.mute_shortcut.0)
+        {
+            return Some("shortcut-conflict-menu");
+        }
+        None
+    }
+
+    fn show_key_remap_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
+        ui.label(text(locale, "key-remaps"))
+            .on_hover_text_at_pointer(text(locale, "key-remaps-tooltip"));
+        ui.vertical(|ui| {
+            if self.movie_key_remaps.is_some() {
+                ComboBox::from_id_salt("key-remaps-scope")
+                    .selected_text(if self.editing_movie_key_remaps {
+                        text(locale, "key-remaps-this-movie")
+                    } else {
+                        text(locale, "key-remaps-all-movies")
+                    })
+                    .show_ui(ui, |ui| {
+                        ui.selectable_value(
+                            &mut self.editing_movie_key_remaps,
+                            false,
+                            text(locale, "key-remaps-all-movies"),
+                        );
+                        ui.selectable_value(
+                            &mut self.editing_movie_key_remaps,
+                            true,
+                            text(locale, "key-remaps-this-movie"),
+                        );
+                    });
+            }
+
+            let mut changed = false;
+            let remaps = match &mut self.movie_key_remaps {
+                Some((_, remaps)) if self.editing_movie_key_remaps => remaps,
+                _ => &mut self.key_remaps,
+            };
+            for (from, to) in remaps.iter().collect::<Vec<_>>() {
+                ui.horizontal(|ui| {
+                    ui.label(from.name());
+                    ui.label("→");
+                    let mut selected = to;
+                    ComboBox::from_id_salt(("key-remap", from))
+                        .selected_text(selected.name())
+                        .show_ui(ui, |ui| {
+                            for key in Key::ALL {
+                                ui.selectable_value(&mut selected, *key, key.name());
+                            }
+                        });
+                    if selected != to {
+                        remaps.set(from, selected);
+                        changed = true;
+                    }
+                    if ui
+                        .button("✖")
+                        .on_hover_text_at_pointer(text(locale, "key-remaps-remove"))
+                        .clicked()
+                    {
+                        remaps.remove(from);
+                        changed = true;
+                    }
+                });
+            }
+
+            let label = if self.capturing_key_remap {
+                text(locale, "shortcut-press-keys")
+            } else {
+                text(locale, "key-remaps-add")
+            };
+            if ui
+                .selectable_label(self.capturing_key_remap, label)
+                .clicked()
+            {
+                self.capturing_key_remap = !self.capturing_key_remap;
+                self.capturing_shortcut = None;
+            }
+            if self.capturing_key_remap {
+                // Remaps are matched by the position of the key, not by what it types.
+                let pressed = ui.input_mut(|input| {
+                    let index = input.events.iter().position(|event| {
+                        matches!(
+                            event,
+                            Event::Key {
+                                pressed: true,
+                                repeat: false,
+                                ..
+                            }
+                        )
+                    })?;
+                    match input.events.remove(index) {
+                        Event::Key {
+                            key, physical_key, ..
+                        } => Some(physical_key.unwrap_or(key)),
+                        _ => None,
+                    }
+                });
+                if let Some(from) = pressed {
+                    self.capturing_key_remap = false;
+                    // It's delivered as itself until another key is chosen.
+                    if remaps.get(from).is_none() {
+                        remaps.set(from, from);
+                        changed = true;
+                    }
+                }
+            }
+
+            if changed {
+                if self.editing_movie_key_remaps {
+                    self.movie_key_remaps_changed = true;
+                } else {
+                    self.key_remaps_changed = true;
+                }
+            }
+        });
+        ui.end_row();
+    }
+
     fn show_gamemode_preferences(
         &mut self,
         locale: &LanguageIdentifier,
@@ -398,6 +1105,112 @@ impl PreferencesDialog {
         ui.end_row();
     }
 
+    fn show_context_menu_mode_preferences(
+        &mut self,
+        locale: &LanguageIdentifier,
+        locked_text: &str,
+        ui: &mut Ui,
+    ) {
+        ui.label(text(locale, "context-menu-mode"));
+        if self.context_menu_mode_readonly {
+            ui.label(context_menu_mode_name(locale, self.context_menu_mode))
+                .on_hover_text(locked_text);
+        } else {
+            let previous = self.context_menu_mode;
+            ComboBox::from_id_salt("context-menu-mode")
+                .selected_text(context_menu_mode_name(locale, self.context_menu_mode))
+                .show_ui(ui, |ui| {
+                    for mode in [
+                        ContextMenuMode::Shown,
+                        ContextMenuMode::CtrlClick,
+                        ContextMenuMode::Disabled,
+                    ] {
+                        ui.selectable_value(
+                            &mut self.context_menu_mode,
+                            mode,
+                            context_menu_mode_name(locale, mode),
+                        );
+                    }
+                });
+            if self.context_menu_mode != previous {
+                self.context_menu_mode_changed = true;
+            }
+        }
+        ui.end_row();
+    }
+
+    fn show_player_emulation_preferences(
+        &mut self,
+        locale: &LanguageIdentifier,
+        locked_text: &str,
+        ui: &mut Ui,
+    ) {
+        ui.label(text(locale, "player-runtime"));
+        if self.player_runtime_readonly {
+            ui.label(player_runtime_name(locale, self.player_runtime))
+                .on_hover_text(locked_text);
+        } else {
+            let previous = self.player_runtime;
+            ComboBox::from_id_salt("player-runtime")
+                .selected_text(player_runtime_name(locale, self.player_runtime))
+                .show_ui(ui, |ui| {
+                    for runtime in [
+                        None,
+                        Some(PlayerRuntime::FlashPlayer),
+                        Some(PlayerRuntime::AIR),
+                    ] {
+                        ui.selectable_value(
+                            &mut self.player_runtime,
+                            runtime,
+                            player_runtime_name(locale, runtime),
+                        );
+                    }
+                });
+            if self.player_runtime != previous {
+                self.player_runtime_changed = true;
+            }
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "player-version"));
+        if self.player_version_readonly {
+            ui.label(
+                self.player_version
+                    .map(|version| version.to_string())
+                    .unwrap_or_default(),
+            )
+            .on_hover_text(locked_text);
+        } else {
+            ui.horizontal(|ui| {
+                let mut enabled = self.player_version.is_some();
+                ui.checkbox(&mut enabled, text(locale, "player-version-custom"));
+                let is_valid = parse_player_version(&self.player_version_text).is_some();
+                ui.add_enabled(
+                    enabled,
+                    TextEdit::singleline(&mut self.player_version_text)
+                        .hint_text("32,0,0,465")
+                        .text_color_opt(if is_valid {
+                            None
+                        } else {
+                            Some(ui.style().visuals.error_fg_color)
+                        }),
+                );
+                let version = parse_player_version(&self.player_version_text);
+                // An invalid version keeps the last valid one until it's fixed.
+                let player_version = if enabled {
+                    version.or(self.player_version)
+                } else {
+                    None
+                };
+                if player_version != self.player_version {
+                    self.player_version = player_version;
+                    self.player_version_changed = true;
+                }
+            });
+        }
+        ui.end_row();
+    }
+
     fn show_ime_preferences(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) {
         ui.label(format!(
             "{} {}",
@@ -441,6 +1254,34 @@ impl PreferencesDialog {
             self.output_device_changed = true;
         }
         ui.end_row();
+
+        ui.label(text(locale, "unfocused-behavior"));
+        let previous = self.unfocused_behavior;
+        ComboBox::from_id_salt("unfocused-behavior")
+            .selected_text(unfocused_behavior_name(locale, self.unfocused_behavior))
+            .show_ui(ui, |ui| {
+                for unfocused_behavior in [
+                    UnfocusedBehavior::Continue,
+                    UnfocusedBehavior::Pause,
+                    UnfocusedBehavior::Mute,
+                ] {
+                    ui.selectable_value(
+                        &mut self.unfocused_behavior,
+                        unfocused_behavior,
+                        unfocused_behavior_name(locale, unfocused_behavior),
+                    );
+                }
+            });
+        if self.unfocused_behavior != previous {
+            self.unfocused_behavior_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "mute-shortcut"));
+        if self.show_shortcut_button(locale, ui, ShortcutField::Mute) {
+            self.mute_shortcut_changed = true;
+        }
+        ui.end_row();
     }
 
     fn show_video_preferences(
@@ -561,6 +1402,62 @@ impl PreferencesDialog {
             }
         });
 
+        ui.end_row();
+
+        ui.label(text(locale, "remember-window-geometry"))
+            .on_hover_text_at_pointer(text(locale, "remember-window-geometry-tooltip"));
+        let previous = self.remember_window_geometry;
+        ui.add(Checkbox::without_text(&mut self.remember_window_geometry));
+        if self.remember_window_geometry != previous {
+            self.remember_window_geometry_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "show-fps-in-title"));
+        let previous = self.show_fps_in_title;
+        ui.add(Checkbox::without_text(&mut self.show_fps_in_title));
+        if self.show_fps_in_title != previous {
+            self.show_fps_in_title_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "idle-power-save"))
+            .on_hover_text_at_pointer(text(locale, "idle-power-save-tooltip"));
+        let previous = self.idle_power_save;
+        ui.add(Checkbox::without_text(&mut self.idle_power_save));
+        if self.idle_power_save != previous {
+            self.idle_power_save_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "touch-as-mouse"))
+            .on_hover_text_at_pointer(text(locale, "touch-as-mouse-tooltip"));
+        let previous = self.touch_as_mouse;
+        ui.add(Checkbox::without_text(&mut self.touch_as_mouse));
+        if self.touch_as_mouse != previous {
+            self.touch_as_mouse_changed = true;
+        }
+        ui.end_row();
+
+        ui.label(text(locale, "long-press-context-menu"))
+            .on_hover_text_at_pointer(text(locale, "long-press-context-menu-tooltip"));
+        let previous = self.long_press_duration;
+        ui.horizontal(|ui| {
+            let mut enabled = self.long_press_duration > 0;
+            ui.add(Checkbox::without_text(&mut enabled));
+            let mut milliseconds = self.long_press_duration.max(200);
+            ui.add_enabled(
+                enabled,
+                DragValue::new(&mut milliseconds)
+                    .range(200..=2000)
+                    .speed(10)
+                    .suffix(text(locale, "long-press-context-menu-milliseconds")),
+            );
+            self.long_press_duration = if enabled { milliseconds } else { 0 };
+        });
+        if self.long_press_duration != previous {
+            self.long_press_duration_changed = true;
+        }
         ui.end_row()
     }
 
@@ -572,6 +1469,15 @@ impl PreferencesDialog {
             if self.power_preference_changed {
                 preferences.set_graphics_power_preference(self.power_preference);
             }
+            if self.frame_latency_changed {
+                preferences.set_frame_latency(self.frame_latency);
+            }
+            if self.ui_sample_count_changed {
+                preferences.set_ui_sample_count(self.ui_sample_count);
+            }
+            if self.scale_factor_override_changed {
+                preferences.set_scale_factor_override(self.scale_factor_override);
+            }
             if self.language_changed {
                 preferences.set_language(self.language.clone());
             }
@@ -579,6 +1485,12 @@ impl PreferencesDialog {
                 preferences.set_output_device(self.output_device.clone());
                 // [NA] TODO: Inform the running player that the device changed
             }
+            if self.unfocused_behavior_changed {
+                preferences.set_unfocused_behavior(self.unfocused_behavior);
+            }
+            if self.mute_shortcut_changed {
+                preferences.set_mute_shortcut(self.mute_shortcut);
+            }
             if self.enable_openh264_changed {
                 preferences.set_enable_openh264(self.enable_openh264);
             }
@@ -591,15 +1503,74 @@ impl PreferencesDialog {
             if self.recent_limit_changed {
                 preferences.set_recent_limit(self.recent_limit);
             }
+            if self.remember_window_geometry_changed {
+                preferences.set_remember_window_geometry(self.remember_window_geometry);
+            }
+            if self.show_fps_in_title_changed {
+                preferences.set_show_fps_in_title(self.show_fps_in_title);
+            }
+            if self.idle_power_save_changed {
+                preferences.set_idle_power_save(self.idle_power_save);
+            }
+            if self.touch_as_mouse_changed {
+                preferences.set_touch_as_mouse(self.touch_as_mouse);
+            }
+            if self.long_press_duration_changed {
+                preferences.set_long_press_duration(self.long_press_duration);
+            }
             if self.theme_preference_changed {
                 preferences.set_theme_preference(self.theme_preference);
             }
+            if self.menu_visibility_changed {
+                preferences.set_menu_visibility(self.menu_visibility);
+            }
+            if self.background_color_changed {
+                preferences.set_background_color(self.background_color);
+            }
+            if self.fullscreen_toggle_shortcut_changed {
+                preferences.set_fullscreen_toggle_shortcut(self.fullscreen_toggle_shortcut);
+            }
+            if self.fullscreen_exit_shortcut_changed {
+                preferences.set_fullscreen_exit_shortcut(self.fullscreen_exit_shortcut);
+            }
+            if self.fullscreen_hold_to_exit_changed {
+                preferences.set_fullscreen_hold_to_exit(self.fullscreen_hold_to_exit);
+            }
+            if self.fullscreen_hide_cursor_after_changed {
+                preferences.set_fullscreen_hide_cursor_after(self.fullscreen_hide_cursor_after);
+            }
+            if self.fullscreen_exclusive_changed {
+                preferences.set_fullscreen_exclusive(self.fullscreen_exclusive);
+            }
+            if self.fullscreen_monitor_changed {
+                preferences.set_fullscreen_monitor(self.fullscreen_monitor.clone());
+            }
+            if self.fullscreen_video_mode_changed {
+                preferences.set_fullscreen_video_mode(self.fullscreen_video_mode);
+            }
+            if self.key_remaps_changed {
+                preferences.set_key_remaps(self.key_remaps.clone());
+            }
+            if self.movie_key_remaps_changed {
+                if let Some((url, remaps)) = &self.movie_key_remaps {
+                    preferences.set_movie_key_remaps(url, remaps.clone());
+                }
+            }
             if self.gamemode_preference_changed {
                 preferences.set_gamemode_preference(self.gamemode_preference);
             }
             if self.open_url_mode_changed {
                 preferences.set_open_url_mode(self.open_url_mode);
             }
+            if self.context_menu_mode_changed {
+                preferences.set_context_menu_mode(self.context_menu_mode);
+            }
+            if self.player_runtime_changed {
+                preferences.set_player_runtime(self.player_runtime);
+            }
+            if self.player_version_changed {
+                preferences.set_player_version(self.player_version);
+            }
             if self.ime_enabled_changed {
                 preferences.set_ime_enabled(self.ime_enabled);
             }
@@ -620,6 +1591,14 @@ fn graphics_backend_name(locale: &LanguageIdentifier, backend: GraphicsBackend)
     }
 }
 
+fn ui_sample_count_name(locale: &LanguageIdentifier, sample_count: u32) -> Cow<'_, str> {
+    if sample_count == 1 {
+        text(locale, "graphics-ui-sample-count-off")
+    } else {
+        Cow::Owned(format!("{sample_count}x"))
+    }
+}
+
 fn graphics_power_name(
     locale: &LanguageIdentifier,
     power_preference: PowerPreference,
@@ -647,6 +1626,55 @@ fn theme_preference_name(
     }
 }
 
+fn unfocused_behavior_name(
+    locale: &LanguageIdentifier,
+    unfocused_behavior: UnfocusedBehavior,
+) -> Cow<'_, str> {
+    match unfocused_behavior {
+        UnfocusedBehavior::Continue => text(locale, "unfocused-behavior-continue"),
+        UnfocusedBehavior::Pause => text(locale, "unfocused-behavior-pause"),
+        UnfocusedBehavior::Mute => text(locale, "unfocused-behavior-mute"),
+    }
+}
+
+fn menu_visibility_name(
+    locale: &LanguageIdentifier,
+    menu_visibility: MenuVisibility,
+) -> Cow<'_, str> {
+    match menu_visibility {
+        MenuVisibility::Always => text(locale, "menu-visibility-always"),
+        MenuVisibility::AutoHide => text(locale, "menu-visibility-auto-hide"),
+        MenuVisibility::Never => text(locale, "menu-visibility-never"),
+    }
+}
+
+fn background_color_name(
+    locale: &LanguageIdentifier,
+    background_color: BackgroundColor,
+) -> Cow<'_, str> {
+    match background_color {
+        BackgroundColor::Stage => text(locale, "background-color-stage"),
+        BackgroundColor::Black => text(locale, "background-color-black"),
+        BackgroundColor::White => text(locale, "background-color-white"),
+        BackgroundColor::Custom(_) => text(locale, "background-color-custom"),
+    }
+}
+
+fn video_mode_name(
+    locale: &LanguageIdentifier,
+    video_mode: Option<VideoModePreference>,
+) -> Cow<'_, str> {
+    match video_mode {
+        Some(video_mode) => Cow::Owned(format!(
+            "{} × {} @ {:.2} Hz",
+            video_mode.width,
+            video_mode.height,
+            video_mode.refresh_rate_millihertz as f64 / 1000.0
+        )),
+        None => text(locale, "fullscreen-video-mode-auto"),
+    }
+}
+
 fn gamemode_preference_name(
     locale: &LanguageIdentifier,
     gamemode_preference: GameModePreference,
@@ -679,6 +1707,17 @@ fn open_url_mode_preference_name(
     }
 }
 
+pub fn context_menu_mode_name(
+    locale: &LanguageIdentifier,
+    context_menu_mode: ContextMenuMode,
+) -> Cow<'_, str> {
+    match context_menu_mode {
+        ContextMenuMode::Shown => text(locale, "context-menu-mode-shown"),
+        ContextMenuMode::CtrlClick => text(locale, "context-menu-mode-ctrl-click"),
+        ContextMenuMode::Disabled => text(locale, "context-menu-mode-disabled"),
+    }
+}
+
 fn filename_pattern_name(locale: &LanguageIdentifier, pattern: FilenamePattern) -> Cow<'_, str> {
     match pattern {
         FilenamePattern::SingleFile => text(locale, "log-filename-pattern-single-file"),
@@ -693,6 +1732,17 @@ fn storage_backend_name(locale: &LanguageIdentifier, backend: StorageBackend) ->
     }
 }
 
+fn player_runtime_name(
+    locale: &LanguageIdentifier,
+    player_runtime: Option<PlayerRuntime>,
+) -> Cow<'_, str> {
+    match player_runtime {
+        None => text(locale, "player-runtime-default"),
+        Some(PlayerRuntime::FlashPlayer) => text(locale, "player-runtime-flash"),
+        Some(PlayerRuntime::AIR) => text(locale, "player-runtime-air"),
+    }
+}
+
 fn ime_enabled_name(locale: &LanguageIdentifier, ime_enabled: Option<bool>) -> Cow<'_, str> {
     match ime_enabled {
         None => text(locale, "ime-enabled-default"),
@@ -700,30 +1750,3 @@ fn ime_enabled_name(locale: &LanguageIdentifier, ime_enabled: Option<bool>) -> C
         Some(false) => text(locale, "disable"),
     }
 }
-
-fn backend_availability(instance: &wgpu::Instance, backend: wgpu::Backends) -> wgpu::Backends {
-    if instance.enumerate_adapters(backend).is_empty() {
-        wgpu::Backends::empty()
-    } else {
-        backend
-    }
-}
-
-fn find_available_graphics_backends() -> wgpu::Backends {
-    let mut available_backends = wgpu::Backends::empty();
-
-    // We have to make a new instance here, as the one created for the entire application may not have
-    // all backends enabled
-    let instance = wgpu::Instance::new(&wgpu::InstanceDescriptor {
-        backends: wgpu::Backends::all(),
-        flags: wgpu::InstanceFlags::default().with_env(),
-        ..Default::default()
-    });
-
-    available_backends |= backend_availability(&instance, wgpu::Backends::VULKAN);
-    available_backends |= backend_availability(&instance, wgpu::Backends::GL);
-    available_backends |= backend_availability(&instance, wgpu::Backends::METAL);
-    available_backends |= backend_availability(&instance, wgpu::Backends::DX12);
-
-    available_backends
-}
@@ -1,10 +1,16 @@
 use crate::gui::text;
 use crate::preferences::GlobalPreferences;
-use egui::{Align2, Slider};
+use egui::{Align2, Button, Slider, Ui};
 use ruffle_core::Player;
 use unic_langid::LanguageIdentifier;
 
+/// The width of the volume slider in the menu bar, in points.
+const COMPACT_SLIDER_WIDTH: f32 = 80.0;
+
 /// The volume controls of the Ruffle GUI.
+///
+/// They're shown in a window of their own, in the menu bar and in the open dialog,
+/// which all change the same volume.
 pub struct VolumeControls {
     is_muted: bool,
     volume: f32,
@@ -33,41 +39,109 @@ impl VolumeControls {
             .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
             .open(&mut keep_open)
             .show(egui_ctx, |ui| {
-                let mut changed_slider = false;
+                self.ui(locale, ui, player, preferences);
+            });
 
-                let changed_checkbox = ui
-                    .checkbox(&mut self.is_muted, text(locale, "volume-controls-mute"))
-                    .changed();
+        keep_open
+    }
 
-                ui.add_enabled_ui(!self.is_muted, |ui| {
-                    ui.horizontal(|ui| {
-                        ui.label(text(locale, "volume-controls-volume"));
-                        changed_slider =
-                            ui.add(Slider::new(&mut self.volume, 0.0..=100.0)).changed();
-                    });
-                });
-
-                if changed_checkbox || changed_slider {
-                    if let Some(player) = player {
-                        player.set_volume(self.get_volume());
-                    }
-                    // Don't update persisted volume if the CLI set it
-                    if preferences.cli.volume.is_none() {
-                        if let Err(e) = preferences.write_preferences(|writer| {
-                            if changed_checkbox {
-                                writer.set_mute(self.is_muted);
-                            }
-                            if changed_slider {
-                                writer.set_volume(self.volume / 100.0);
-                            }
-                        }) {
-                            tracing::warn!("Couldn't update volume preferences: {e}");
-                        }
-                    }
-                }
+    /// Shows the mute checkbox and the labelled volume slider.
+    pub fn ui(
+        &mut self,
+        locale: &LanguageIdentifier,
+        ui: &mut Ui,
+        player: Option<&mut Player>,
+        preferences: &GlobalPreferences,
+    ) {
+        let mut changed_slider = false;
+
+        let changed_checkbox = ui
+            .checkbox(&mut self.is_muted, text(locale, "volume-controls-mute"))
+            .changed();
+
+        ui.add_enabled_ui(!self.is_muted, |ui| {
+            ui.horizontal(|ui| {
+                ui.label(text(locale, "volume-controls-volume"));
+                changed_slider = ui
+                    .add(Slider::new(&mut self.volume, 0.0..=100.0).suffix("%"))
+                    .changed();
             });
+        });
 
-        keep_open
+        self.apply(changed_checkbox, changed_slider, player, preferences);
+    }
+
+    /// Shows a mute toggle and a short volume slider, for the menu bar.
+    pub fn compact_ui(
+        &mut self,
+        locale: &LanguageIdentifier,
+        ui: &mut Ui,
+        player: Option<&mut Player>,
+        preferences: &GlobalPreferences,
+    ) {
+        let mut changed_slider = false;
+
+        let icon = if self.is_muted || self.volume == 0.0 {
+            "🔇"
+        } else {
+            "🔊"
+        };
+        let changed_checkbox = ui
+            .add(Button::new(icon).frame(false).selected(self.is_muted))
+            .on_hover_text(text(locale, "volume-controls-mute"))
+            .clicked();
+        if changed_checkbox {
+            self.is_muted = !self.is_muted;
+        }
+
+        ui.add_enabled_ui(!self.is_muted, |ui| {
+            ui.spacing_mut().slider_width = COMPACT_SLIDER_WIDTH;
+            changed_slider = ui
+                .add(Slider::new(&mut self.volume, 0.0..=100.0).show_value(false))
+                .on_hover_text(format!("{}%", self.volume.round()))
+                .changed();
+        });
+
+        self.apply(changed_checkbox, changed_slider, player, preferences);
+    }
+
+    /// Mutes or unmutes the movie, keeping its volume for when it's unmuted.
+    pub fn toggle_mute(&mut self, player: Option<&mut Player>, preferences: &GlobalPreferences) {
+        self.is_muted = !self.is_muted;
+        self.apply(true, false, player, preferences);
+    }
+
+    pub fn is_muted(&self) -> bool {
+        self.is_muted
+    }
+
+    /// Applies a changed mute or volume to the player right away, and persists it.
+    fn apply(
+        &self,
+        changed_checkbox: bool,
+        changed_slider: bool,
+        player: Option<&mut Player>,
+        preferences: &GlobalPreferences,
+    ) {
+        if !changed_checkbox && !changed_slider {
+            return;
+        }
+        if let Some(player) = player {
+            player.set_volume(self.get_volume());
+        }
+        // Don't update persisted volume if the CLI set it
+        if preferences.cli.volume.is_none() {
+            if let Err(e) = preferences.write_preferences(|writer| {
+                if changed_checkbox {
+                    writer.set_mute(self.is_muted);
+                }
+                if changed_slider {
+                    writer.set_volume(self.volume / 100.0);
+                }
+            }) {
+                tracing::warn!("Couldn't update volume preferences: {e}");
+            }
+        }
     }
 
     /// Returns the volume between 0 and 1 (calculated out of the
@@ -0,0 +1,46 @@
+use crate::gui::text;
+use egui::{Align2, Area, Frame, Id, LayerId, Order, StrokeKind};
+use unic_langid::LanguageIdentifier;
+
+/// The width of the outline drawn around the window, in points.
+const OUTLINE_WIDTH: f32 = 4.0;
+
+/// An outline around the window while files are dragged over it,
+/// to show that dropping them opens a movie.
+#[derive(Default)]
+pub struct DropIndicator {
+    visible: bool,
+}
+
+impl DropIndicator {
+    pub fn set_visible(&mut self, visible: bool) {
+        self.visible = visible;
+    }
+
+    pub fn show(&self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        if !self.visible {
+            return;
+        }
+
+        let visuals = &egui_ctx.style().visuals;
+        let rect = egui_ctx.screen_rect().shrink(OUTLINE_WIDTH / 2.0);
+        let painter = egui_ctx.layer_painter(LayerId::new(Order::Foreground, Id::new("drop")));
+        painter.rect_filled(rect, 0.0, visuals.selection.bg_fill.gamma_multiply(0.2));
+        painter.rect_stroke(
+            rect,
+            0.0,
+            (OUTLINE_WIDTH, visuals.selection.bg_fill),
+            StrokeKind::Middle,
+        );
+
+        Area::new(Id::new("drop_indicator"))
+            .order(Order::Foreground)
+            .anchor(Align2::CENTER_CENTER, egui::Vec2::ZERO)
+            .interactable(false)
+            .show(egui_ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(text(locale, "drop-to-open"));
+                });
+            });
+    }
+}
@@ -0,0 +1,195 @@
+use crate::gui::{text, text_with_args};
+use crate::preferences::GlobalPreferences;
+use fluent_templates::fluent_bundle::FluentValue;
+use std::borrow::Cow;
+use std::collections::HashMap;
+use std::fmt;
+use std::str::FromStr;
+use unic_langid::LanguageIdentifier;
+use winit::monitor::{MonitorHandle, VideoModeHandle};
+use winit::window::{Fullscreen, Window};
+
+/// A display mode used for exclusive fullscreen, such as `1920x1080@59.940Hz`.
+///
+/// Video mode handles are tied to a monitor and can't be saved,
+/// so the preference only records the resolution and refresh rate.
+#[derive(Clone, Copy, PartialEq, Eq, PartialOrd, Ord, Debug)]
+pub struct VideoModePreference {
+    pub width: u32,
+    pub height: u32,
+    pub refresh_rate_millihertz: u32,
+}
+
+impl VideoModePreference {
+    pub fn of(mode: &VideoModeHandle) -> Self {
+        Self {
+            width: mode.size().width,
+            height: mode.size().height,
+            refresh_rate_millihertz: mode.refresh_rate_millihertz(),
+        }
+    }
+}
+
+impl fmt::Display for VideoModePreference {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{}@{}.{:03}Hz",
+            self.width,
+            self.height,
+            self.refresh_rate_millihertz / 1000,
+            self.refresh_rate_millihertz % 1000
+        )
+    }
+}
+
+impl FromStr for VideoModePreference {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (size, refresh_rate) = s.split_once('@').ok_or(())?;
+        let (width, height) = size.split_once('x').ok_or(())?;
+        let refresh_rate: f64 = refresh_rate
+            .trim_end_matches("Hz")
+            .parse()
+            .map_err(|_| ())?;
+        if !refresh_rate.is_finite() || refresh_rate <= 0.0 {
+            return Err(());
+        }
+        Ok(Self {
+            width: width.parse().map_err(|_| ())?,
+            height: height.parse().map_err(|_| ())?,
+            refresh_rate_millihertz: (refresh_rate * 1000.0).round() as u32,
+        })
+    }
+}
+
+/// The monitor used for fullscreen.
+#[derive(Clone, PartialEq, Eq, Debug, Default)]
+pub enum FullscreenMonitor {
+    /// The monitor the window is on.
+    #[default]
+    Current,
+
+    /// A monitor identified by its name.
+    Name(String),
+
+    /// A monitor without a name, identified by its position in the list of monitors.
+    Index(usize),
+}
+
+impl FullscreenMonitor {
+    pub fn of(index: usize, monitor: &MonitorHandle) -> Self {
+        monitor.name().map_or(Self::Index(index), Self::Name)
+    }
+
+    /// Returns the value stored in the preferences, or `None` for the default.
+    pub fn as_preference(&self) -> Option<String> {
+        match self {
+            FullscreenMonitor::Current => None,
+            FullscreenMonitor::Name(name) => Some(name.clone()),
+            FullscreenMonitor::Index(index) => Some(format!("#{index}")),
+        }
+    }
+
+    pub fn label(&self, locale: &LanguageIdentifier) -> Cow<'_, str> {
+        match self {
+            FullscreenMonitor::Current => text(locale, "fullscreen-monitor-current"),
+            FullscreenMonitor::Name(name) => Cow::Borrowed(name),
+            FullscreenMonitor::Index(index) => text_with_args(
+                locale,
+                "fullscreen-monitor-unnamed",
+                &HashMap::from([("number".into(), FluentValue::from(index + 1))]),
+            ),
+        }
+    }
+
+    /// Finds the chosen monitor, or returns `None` to use the current one.
+    fn find(&self, window: &Window) -> Option<MonitorHandle> {
+        let monitor = match self {
+            FullscreenMonitor::Current => return None,
+            FullscreenMonitor::Name(name) => window
+                .available_monitors()
+                .find(|monitor| monitor.name().as_deref() == Some(name.as_str())),
+            FullscreenMonitor::Index(index) => window.available_monitors().nth(*index),
+        };
+        if monitor.is_none() {
+            tracing::warn!("Fullscreen monitor {self:?} isn't connected, using the current one");
+        }
+        monitor
+    }
+}
+
+impl FromStr for FullscreenMonitor {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        if s.is_empty() {
+            return Err(());
+        }
+        match s.strip_prefix('#') {
+            Some(index) => index.parse().map(Self::Index).map_err(|_| ()),
+            None => Ok(Self::Name(s.to_string())),
+        }
+    }
+}
+
+/// Returns how the window should be made fullscreen, according to the preferences.
+///
+/// Exclusive fullscreen falls back to borderless fullscreen
+/// if no suitable video mode can be found.
+pub fn fullscreen_mode(window: &Window, preferences: &GlobalPreferences) -> Fullscreen {
+    let monitor = preferences.fullscreen_monitor().find(window);
+    if preferences.fullscreen_exclusive() {
+        match monitor
+            .clone()
+            .or_else(|| window.current_monitor())
+            .and_then(|monitor| select_video_mode(&monitor, preferences.fullscreen_video_mode()))
+        {
+            Some(video_mode) => return Fullscreen::Exclusive(video_mode),
+            None => tracing::warn!(
+                "No video mode available for exclusive fullscreen, using borderless fullscreen"
+            ),
+        }
+    }
+    Fullscreen::Borderless(monitor)
+}
+
+/// Returns the video modes of the monitor, without duplicates and from best to worst.
+pub fn available_video_modes(monitor: &MonitorHandle) -> Vec<VideoModePreference> {
+    let mut modes: Vec<_> = monitor
+        .video_modes()
+        .map(|mode| VideoModePreference::of(&mode))
+        .collect();
+    modes.sort_unstable();
+    modes.dedup();
+    modes.reverse();
+    modes
+}
+
+fn select_video_mode(
+    monitor: &MonitorHandle,
+    preferred: Option<VideoModePreference>,
+) -> Option<VideoModeHandle> {
+    if let Some(preferred) = preferred {
+        let video_mode = monitor
+            .video_modes()
+            .filter(|mode| VideoModePreference::of(mode) == preferred)
+            .max_by_key(|mode| mode.bit_depth());
+        if video_mode.is_some() {
+            return video_mode;
+        }
+        tracing::warn!(
+            "Video mode {preferred} isn't supported by monitor {:?}, using the best one",
+            monitor.name()
+        );
+    }
+    // The highest resolution is usually the native one.
+    monitor.video_modes().max_by_key(|mode| {
+        (
+            mode.size().width as u64 * mode.size().height as u64,
+            mode.refresh_rate_millihertz(),
+            mode.bit_depth(),
+        )
+    })
+}
@@ -0,0 +1,87 @@
+use crate::gui::text_with_args;
+use egui::{Align2, Area, Frame, Id, Order};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use unic_langid::LanguageIdentifier;
+
+/// How long the exit key must be held to leave fullscreen, when holding is required.
+const HOLD_DURATION: Duration = Duration::from_secs(1);
+
+/// How long the hint is shown for after entering fullscreen.
+const HINT_DURATION: Duration = Duration::from_secs(3);
+
+/// Tracks the "hold to exit fullscreen" key, and shows a hint about it.
+///
+/// This is used when the movie itself may want to use the exit key
+/// (e.g. Escape for a pause menu), so that a short press goes to the movie
+/// and only holding it leaves fullscreen, like in browsers.
+#[derive(Default)]
+pub struct FullscreenExitHint {
+    entered_at: Option<Instant>,
+    held_since: Option<Instant>,
+}
+
+impl FullscreenExitHint {
+    pub fn begin_hold(&mut self) {
+        if self.held_since.is_none() {
+            self.held_since = Some(Instant::now());
+        }
+    }
+
+    pub fn end_hold(&mut self) {
+        self.held_since = None;
+    }
+
+    /// Updates the hint and returns true if the exit key has been held long enough.
+    pub fn update(&mut self, fullscreen: bool) -> bool {
+        if !fullscreen {
+            self.entered_at = None;
+            self.held_since = None;
+            return false;
+        }
+        if self.entered_at.is_none() {
+            self.entered_at = Some(Instant::now());
+        }
+        if self
+            .held_since
+            .is_some_and(|held_since| held_since.elapsed() >= HOLD_DURATION)
+        {
+            self.held_since = None;
+            return true;
+        }
+        false
+    }
+
+    pub fn show(&self, locale: &LanguageIdentifier, egui_ctx: &egui::Context, shortcut: &str) {
+        let Some(entered_at) = self.entered_at else {
+            return;
+        };
+        let shown_for = entered_at.elapsed();
+        if self.held_since.is_some() {
+            // Keep rendering so that we notice when the key has been held long enough.
+            egui_ctx.request_repaint();
+        } else if shown_for < HINT_DURATION {
+            egui_ctx.request_repaint_after(HINT_DURATION - shown_for);
+        } else {
+            return;
+        }
+
+        Area::new(Id::new("fullscreen_exit_hint"))
+            .order(Order::Foreground)
+            .anchor(Align2::CENTER_TOP, egui::vec2(0.0, 16.0))
+            .interactable(false)
+            .show(egui_ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(text_with_args(
+                        locale,
+                        "fullscreen-exit-hint",
+                        &HashMap::from([(
+                            "shortcut".into(),
+                            FluentValue::String(shortcut.to_string().into()),
+                        )]),
+                    ));
+                });
+            });
+    }
+}
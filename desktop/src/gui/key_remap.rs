@@ -0,0 +1,161 @@
+use egui::Key;
+use winit::keyboard::{KeyCode, PhysicalKey};
+
+/// Keys of the keyboard that are delivered to the movie as other keys,
+/// for movies that expect keys the keyboard doesn't have or that are awkward to reach.
+///
+/// The keys being replaced are matched by their position on the keyboard, named after
+/// the key at that position on a US keyboard, so that they don't depend on the layout.
+/// They're stored in the preferences as a table of key names, such as `Q = "A"`.
+#[derive(Clone, Default, PartialEq, Eq, Debug)]
+pub struct KeyRemaps(Vec<(Key, Key)>);
+
+impl KeyRemaps {
+    /// The key that the movie gets when `from` is pressed, if it's remapped.
+    pub fn get(&self, from: Key) -> Option<Key> {
+        self.0
+            .iter()
+            .find(|(remapped, _)| *remapped == from)
+            .map(|(_, to)| *to)
+    }
+
+    /// Remaps `from` to `to`, replacing any previous remap of `from`.
+    pub fn set(&mut self, from: Key, to: Key) {
+        match self.0.iter_mut().find(|(remapped, _)| *remapped == from) {
+            Some(remap) => remap.1 = to,
+            None => self.0.push((from, to)),
+        }
+    }
+
+    pub fn remove(&mut self, from: Key) {
+        self.0.retain(|(remapped, _)| *remapped != from);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = (Key, Key)> + '_ {
+        self.0.iter().copied()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
+impl FromIterator<(Key, Key)> for KeyRemaps {
+    fn from_iter<T: IntoIterator<Item = (Key, Key)>>(iter: T) -> Self {
+        let mut remaps = Self::default();
+        for (from, to) in iter {
+            remaps.set(from, to);
+        }
+        remaps
+    }
+}
+
+/// Returns the key at the position of `key` on a US keyboard, which remaps are matched against.
+pub fn physical_key_to_egui(key: PhysicalKey) -> Option<Key> {
+    let PhysicalKey::Code(code) = key else {
+        return None;
+    };
+    Some(match code {
+        KeyCode::Backquote => Key::Backtick,
+        KeyCode::Backslash => Key::Backslash,
+        KeyCode::BracketLeft => Key::OpenBracket,
+        KeyCode::BracketRight => Key::CloseBracket,
+        KeyCode::Comma => Key::Comma,
+        KeyCode::Digit0 => Key::Num0,
+        KeyCode::Digit1 => Key::Num1,
+        KeyCode::Digit2 => Key::Num2,
+        KeyCode::Digit3 => Key::Num3,
+        KeyCode::Digit4 => Key::Num4,
+        KeyCode::Digit5 => Key::Num5,
+        KeyCode::Digit6 => Key::Num6,
+        KeyCode::Digit7 => Key::Num7,
+        KeyCode::Digit8 => Key::Num8,
+        KeyCode::Digit9 => Key::Num9,
+        KeyCode::Equal => Key::Equals,
+        KeyCode::KeyA => Key::A,
+        KeyCode::KeyB => Key::B,
+        KeyCode::KeyC => Key::C,
+        KeyCode::KeyD => Key::D,
+        KeyCode::KeyE => Key::E,
+        KeyCode::KeyF => Key::F,
+        KeyCode::KeyG => Key::G,
+        KeyCode::KeyH => Key::H,
+        KeyCode::KeyI => Key::I,
+        KeyCode::KeyJ => Key::J,
+        KeyCode::KeyK => Key::K,
+        KeyCode::KeyL => Key::L,
+        KeyCode::KeyM => Key::M,
+        KeyCode::KeyN => Key::N,
+        KeyCode::KeyO => Key::O,
+        KeyCode::KeyP => Key::P,
+        KeyCode::KeyQ => Key::Q,
+        KeyCode::KeyR => Key::R,
+        KeyCode::KeyS => Key::S,
+        KeyCode::KeyT => Key::T,
+        KeyCode::KeyU => Key::U,
+        KeyCode::KeyV => Key::V,
+        KeyCode::KeyW => Key::W,
+        KeyCode::KeyX => Key::X,
+        KeyCode::KeyY => Key::Y,
+        KeyCode::KeyZ => Key::Z,
+        KeyCode::Minus => Key::Minus,
+        KeyCode::Period => Key::Period,
+        KeyCode::Quote => Key::Quote,
+        KeyCode::Semicolon => Key::Semicolon,
+        KeyCode::Slash => Key::Slash,
+        KeyCode::Backspace => Key::Backspace,
+        KeyCode::Enter => Key::Enter,
+        KeyCode::Space => Key::Space,
+        KeyCode::Tab => Key::Tab,
+        KeyCode::Delete => Key::Delete,
+        KeyCode::End => Key::End,
+        KeyCode::Home => Key::Home,
+        KeyCode::Insert => Key::Insert,
+        KeyCode::PageDown => Key::PageDown,
+        KeyCode::PageUp => Key::PageUp,
+        KeyCode::ArrowDown => Key::ArrowDown,
+        KeyCode::ArrowLeft => Key::ArrowLeft,
+        KeyCode::ArrowRight => Key::ArrowRight,
+        KeyCode::ArrowUp => Key::ArrowUp,
+        KeyCode::Escape => Key::Escape,
+        KeyCode::Copy => Key::Copy,
+        KeyCode::Cut => Key::Cut,
+        KeyCode::Paste => Key::Paste,
+        KeyCode::F1 => Key::F1,
+        KeyCode::F2 => Key::F2,
+        KeyCode::F3 => Key::F3,
+        KeyCode::F4 => Key::F4,
+        KeyCode::F5 => Key::F5,
+        KeyCode::F6 => Key::F6,
+        KeyCode::F7 => Key::F7,
+        KeyCode::F8 => Key::F8,
+        KeyCode::F9 => Key::F9,
+        KeyCode::F10 => Key::F10,
+        KeyCode::F11 => Key::F11,
+        KeyCode::F12 => Key::F12,
+        KeyCode::F13 => Key::F13,
+        KeyCode::F14 => Key::F14,
+        KeyCode::F15 => Key::F15,
+        KeyCode::F16 => Key::F16,
+        KeyCode::F17 => Key::F17,
+        KeyCode::F18 => Key::F18,
+        KeyCode::F19 => Key::F19,
+        KeyCode::F20 => Key::F20,
+        KeyCode::F21 => Key::F21,
+        KeyCode::F22 => Key::F22,
+        KeyCode::F23 => Key::F23,
+        KeyCode::F24 => Key::F24,
+        KeyCode::F25 => Key::F25,
+        KeyCode::F26 => Key::F26,
+        KeyCode::F27 => Key::F27,
+        KeyCode::F28 => Key::F28,
+        KeyCode::F29 => Key::F29,
+        KeyCode::F30 => Key::F30,
+        KeyCode::F31 => Key::F31,
+        KeyCode::F32 => Key::F32,
+        KeyCode::F33 => Key::F33,
+        KeyCode::F34 => Key::F34,
+        KeyCode::F35 => Key::F35,
+        _ => return None,
+    })
+}
@@ -0,0 +1,45 @@
+use egui::{Id, LayerId, Order, Pos2};
+use std::time::{Duration, Instant};
+
+/// How long the cue is shown for.
+const CUE_DURATION: Duration = Duration::from_millis(300);
+
+/// The radius of the cue once it has fully grown, in points.
+const CUE_RADIUS: f32 = 32.0;
+
+/// A circle briefly shown under a finger that was held long enough to open the context menu,
+/// as there's no cursor to show that the press registered.
+#[derive(Default)]
+pub struct LongPressCue {
+    shown_at: Option<(Pos2, Instant)>,
+}
+
+impl LongPressCue {
+    pub fn begin(&mut self, position: Pos2) {
+        self.shown_at = Some((position, Instant::now()));
+    }
+
+    pub fn show(&mut self, egui_ctx: &egui::Context) {
+        let Some((position, shown_at)) = self.shown_at else {
+            return;
+        };
+        let progress = shown_at.elapsed().as_secs_f32() / CUE_DURATION.as_secs_f32();
+        if progress >= 1.0 {
+            self.shown_at = None;
+            return;
+        }
+        // Keep rendering until the cue has faded out.
+        egui_ctx.request_repaint();
+
+        // Grow the circle while fading it out, below the context menu that's opening.
+        let color = egui_ctx
+            .style()
+            .visuals
+            .selection
+            .bg_fill
+            .gamma_multiply(1.0 - progress);
+        egui_ctx
+            .layer_painter(LayerId::new(Order::Middle, Id::new("long_press_cue")))
+            .circle_filled(position, CUE_RADIUS * (0.5 + progress / 2.0), color);
+    }
+}
@@ -1,48 +1,79 @@
-use crate::custom_event::RuffleEvent;
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
 use crate::gui::dialogs::Dialogs;
-use crate::gui::{text, DebugMessage};
+use crate::gui::{text, DebugMessage, FullscreenMonitor, MOVIE_EXTENSIONS};
 use crate::player::LaunchOptions;
-use crate::preferences::GlobalPreferences;
-use egui::{containers::menu, Button, Key, KeyboardShortcut, Modifiers, Widget};
+use crate::preferences::{GlobalPreferences, MovieOptions};
+use egui::{containers::menu, Button, DragValue, Key, KeyboardShortcut, Modifiers, Widget};
 use ruffle_core::config::Letterbox;
 use ruffle_core::focus_tracker::DisplayObject;
-use ruffle_core::{Player, StageScaleMode};
+use ruffle_core::{Player, StageAlign, StageScaleMode};
 use ruffle_frontend_utils::recents::Recent;
 use ruffle_render::quality::StageQuality;
+use std::path::Path;
+use std::str::FromStr;
+use std::sync::Weak;
 use unic_langid::LanguageIdentifier;
 use url::Url;
-use winit::event_loop::EventLoopProxy;
+use winit::window::Window;
 
 pub struct MenuBar {
-    event_loop: EventLoopProxy<RuffleEvent>,
+    window: Weak<Window>,
+    event_loop: RuffleEventProxy,
     default_launch_options: LaunchOptions,
     preferences: GlobalPreferences,
 
     cached_recents: Option<Vec<Recent>>,
     pub currently_opened: Option<(Url, LaunchOptions)>,
+    pub picture_in_picture: bool,
+    pub mouse_locked: bool,
+    pub auto_reload: bool,
+    pub timeline_bar: bool,
+    /// How many frames "Step Frames" advances the movie by.
+    step_count: u32,
 }
 
 impl MenuBar {
-    const SHORTCUT_FULLSCREEN: KeyboardShortcut = KeyboardShortcut::new(Modifiers::NONE, Key::F11);
-    const SHORTCUT_FULLSCREEN_WINDOWS: KeyboardShortcut =
-        KeyboardShortcut::new(Modifiers::ALT, Key::Enter);
     const SHORTCUT_OPEN: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::O);
     const SHORTCUT_OPEN_ADVANCED: KeyboardShortcut =
         KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::O);
+    const SHORTCUT_RELOAD: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::R);
     const SHORTCUT_PAUSE: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::P);
     const SHORTCUT_STEP: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Space);
+    const SHORTCUT_STEP_MANY: KeyboardShortcut =
+        KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::Space);
     const SHORTCUT_QUIT: KeyboardShortcut = KeyboardShortcut::new(Modifiers::COMMAND, Key::Q);
+    const SHORTCUT_COPY_FRAME: KeyboardShortcut =
+        KeyboardShortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::C);
+
+    /// The shortcuts used by the menu, which can't be assigned to anything else.
+    pub const RESERVED_SHORTCUTS: [KeyboardShortcut; 8] = [
+        Self::SHORTCUT_OPEN,
+        Self::SHORTCUT_OPEN_ADVANCED,
+        Self::SHORTCUT_RELOAD,
+        Self::SHORTCUT_PAUSE,
+        Self::SHORTCUT_STEP,
+        Self::SHORTCUT_STEP_MANY,
+        Self::SHORTCUT_QUIT,
+        Self::SHORTCUT_COPY_FRAME,
+    ];
 
     pub fn new(
-        event_loop: EventLoopProxy<RuffleEvent>,
+        window: Weak<Window>,
+        event_loop: RuffleEventProxy,
         default_launch_options: LaunchOptions,
         preferences: GlobalPreferences,
     ) -> Self {
         Self {
+            window,
             event_loop,
             default_launch_options,
             cached_recents: None,
             currently_opened: None,
+            picture_in_picture: false,
+            mouse_locked: false,
+            auto_reload: false,
+            timeline_bar: false,
+            step_count: 10,
             preferences,
         }
     }
@@ -63,29 +94,38 @@ impl MenuBar {
         if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_QUIT)) {
             self.request_exit();
         }
+        // Without a movie to receive it, pasting a URL opens it.
+        if player.is_none() && !egui_ctx.wants_keyboard_input() {
+            let pasted = egui_ctx.input(|input| {
+                input.events.iter().find_map(|event| match event {
+                    egui::Event::Paste(text) => Some(text.clone()),
+                    _ => None,
+                })
+            });
+            if let Some(pasted) = pasted {
+                self.open_url_text(&pasted, dialogs);
+            }
+        }
 
         if let Some(player) = &mut player {
+            if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_RELOAD)) {
+                self.reload_movie();
+            }
             let playing = player.is_playing();
             if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_PAUSE)) {
                 player.set_is_playing(!playing);
             }
+            if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_STEP_MANY)) {
+                self.step_frames(self.step_count);
+            }
             if !playing && egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_STEP))
             {
-                player.suspend_after_next_frame();
+                self.step_frames(1);
             }
-        }
-
-        let mut fullscreen_pressed =
-            egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_FULLSCREEN));
-        if cfg!(windows) && !fullscreen_pressed {
-            // TODO We can remove this shortcut when we add some kind of preferences.
-            fullscreen_pressed = egui_ctx
-                .input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_FULLSCREEN_WINDOWS));
-        }
-        if fullscreen_pressed {
-            if let Some(player) = &mut player {
-                let is_fullscreen = player.is_fullscreen();
-                player.set_fullscreen(!is_fullscreen);
+            if egui_ctx.input_mut(|input| input.consume_shortcut(&Self::SHORTCUT_COPY_FRAME)) {
+                let _ = self
+                    .event_loop
+                    .send_event(RuffleEvent::CopyFrameToClipboard);
             }
         }
     }
@@ -96,12 +136,15 @@ impl MenuBar {
         egui_ctx: &egui::Context,
         dialogs: &mut Dialogs,
         mut player: Option<&mut Player>,
+        is_recording: bool,
+        zoom: f64,
     ) {
         egui::TopBottomPanel::top("menu_bar").show(egui_ctx, |ui| {
              menu::Bar::new().ui(ui, |ui| {
                 self.file_menu(locale, ui, dialogs, player.is_some());
-                self.view_menu(locale, ui, &mut player);
-                self.controls_menu(locale, ui, dialogs, &mut player);
+                self.edit_menu(locale, ui);
+                self.view_menu(locale, ui, &mut player, zoom);
+                self.controls_menu(locale, ui, dialogs, &mut player, is_recording);
                 ui.menu_button( text(locale, "bookmarks-menu"), |ui| {
                     if Button::new(text(locale, "bookmarks-menu-add")).ui(ui).clicked() {
                         ui.close();
@@ -173,6 +216,12 @@ impl MenuBar {
                                 player.debug_ui().queue_message(DebugMessage::SearchForDisplayObject);
                             }
                         }
+                        ui.separator();
+                        let mut mouse_locked = self.mouse_locked;
+                        if ui.checkbox(&mut mouse_locked, text(locale, "debug-menu-lock-mouse")).clicked() {
+                            ui.close();
+                            let _ = self.event_loop.send_event(RuffleEvent::SetMouseLock(mouse_locked));
+                        }
                     });
                 });
                 ui.menu_button(text(locale, "help-menu"), |ui| {
@@ -189,11 +238,18 @@ impl MenuBar {
                         self.launch_website(ui, "https://crowdin.com/project/ruffle");
                     }
                     ui.separator();
+                    if ui.add_enabled(player.is_some(), Button::new(text(locale, "help-menu-movie-properties"))).clicked() {
+                        dialogs.open_movie_properties();
+                        ui.close();
+                    }
                     if ui.button(text(locale, "help-menu-about")).clicked() {
                         dialogs.open_about_screen();
                         ui.close();
                     }
                 });
+                ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
+                    dialogs.volume_controls.compact_ui(locale, ui, player.as_deref_mut(), &self.preferences);
+                });
             });
         });
     }
@@ -206,6 +262,15 @@ impl MenuBar {
         player_exists: bool,
     ) {
         ui.menu_button(text(locale, "file-menu"), |ui| {
+            if Button::new(text(locale, "file-menu-new-window"))
+                .ui(ui)
+                .clicked()
+            {
+                ui.close();
+                let _ = self.event_loop.send_event(RuffleEvent::NewWindow);
+            }
+            ui.separator();
+
             if Button::new(text(locale, "file-menu-open-quick"))
                 .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_OPEN))
                 .ui(ui)
@@ -224,11 +289,35 @@ impl MenuBar {
                 dialogs.open_file_advanced();
             }
 
+            if Button::new(text(locale, "file-menu-open-url"))
+                .ui(ui)
+                .clicked()
+            {
+                ui.close();
+                dialogs.open_movie_url(self.default_launch_options.clone());
+            }
+
             if ui
-                .add_enabled(player_exists, Button::new(text(locale, "file-menu-reload")))
+                .add_enabled(
+                    player_exists,
+                    Button::new(text(locale, "file-menu-reload"))
+                        .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_RELOAD)),
+                )
                 .clicked()
             {
-                self.reload_movie(ui);
+                ui.close();
+                self.reload_movie();
+            }
+
+            let mut auto_reload = self.auto_reload;
+            if ui
+                .checkbox(&mut auto_reload, text(locale, "file-menu-auto-reload"))
+                .clicked()
+            {
+                ui.close();
+                let _ = self
+                    .event_loop
+                    .send_event(RuffleEvent::SetAutoReload(auto_reload));
             }
 
             if ui
@@ -287,7 +376,8 @@ impl MenuBar {
                 .clicked()
             {
                 ui.close();
-                dialogs.open_preferences();
+                let movie_url = self.currently_opened.as_ref().map(|(url, _)| url.clone());
+                dialogs.open_preferences(movie_url);
             }
             ui.separator();
 
@@ -302,11 +392,24 @@ impl MenuBar {
         });
     }
 
+    fn edit_menu(&mut self, locale: &LanguageIdentifier, ui: &mut egui::Ui) {
+        ui.menu_button(text(locale, "edit-menu"), |ui| {
+            if Button::new(text(locale, "edit-menu-open-url-from-clipboard"))
+                .ui(ui)
+                .clicked()
+            {
+                ui.close();
+                let _ = self.event_loop.send_event(RuffleEvent::OpenFromClipboard);
+            }
+        });
+    }
+
     fn view_menu(
         &mut self,
         locale: &LanguageIdentifier,
         ui: &mut egui::Ui,
         player: &mut Option<&mut Player>,
+        zoom: f64,
     ) {
         ui.menu_button(text(locale, "view-menu"), |ui| {
             ui.add_enabled_ui(player.is_some(), |ui| {
@@ -346,6 +449,9 @@ impl MenuBar {
                             if let Some(player) = player {
                                 player.set_scale_mode(scale_mode);
                             }
+                            self.update_movie_options(|options| {
+                                options.scale_mode = Some(scale_mode)
+                            });
                         }
                     }
                     ui.separator();
@@ -364,6 +470,48 @@ impl MenuBar {
                     }
                 });
 
+                ui.menu_button(text(locale, "align"), |ui| {
+                    let items = [
+                        ("align-center", StageAlign::empty()),
+                        ("align-top", StageAlign::TOP),
+                        ("align-bottom", StageAlign::BOTTOM),
+                        ("align-left", StageAlign::LEFT),
+                        ("align-right", StageAlign::RIGHT),
+                        ("align-top-left", StageAlign::TOP | StageAlign::LEFT),
+                        ("align-top-right", StageAlign::TOP | StageAlign::RIGHT),
+                        ("align-bottom-left", StageAlign::BOTTOM | StageAlign::LEFT),
+                        ("align-bottom-right", StageAlign::BOTTOM | StageAlign::RIGHT),
+                    ];
+                    let current_align = player.as_mut().map(|player| player.align());
+                    for (id, align) in items {
+                        let clicked = if Some(align) == current_align {
+                            ui.checkbox(&mut true, text(locale, id)).clicked()
+                        } else {
+                            ui.button(text(locale, id)).clicked()
+                        };
+                        if clicked {
+                            ui.close();
+                            if let Some(player) = player {
+                                player.set_align(align);
+                            }
+                            self.update_movie_options(|options| options.align = Some(align));
+                        }
+                    }
+                    ui.separator();
+
+                    let original_forced_align = player
+                        .as_mut()
+                        .map(|player| player.forced_align())
+                        .unwrap_or_default();
+                    let mut forced_align = original_forced_align;
+                    ui.checkbox(&mut forced_align, text(locale, "align-force"));
+                    if forced_align != original_forced_align {
+                        if let Some(player) = player {
+                            player.set_forced_align(forced_align);
+                        }
+                    }
+                });
+
                 let original_letterbox = if let Some(player) = player {
                     player.letterbox() == Letterbox::On
                 } else {
@@ -380,10 +528,51 @@ impl MenuBar {
                         });
                     }
                 }
+
+                ui.menu_button(text(locale, "view-menu-window-size"), |ui| {
+                    let items = [
+                        ("view-menu-window-size-50", 0.5),
+                        ("view-menu-window-size-100", 1.0),
+                        ("view-menu-window-size-200", 2.0),
+                    ];
+                    for (id, zoom) in items {
+                        if ui.button(text(locale, id)).clicked() {
+                            ui.close();
+                            let _ = self.event_loop.send_event(RuffleEvent::ResizeToMovie(zoom));
+                        }
+                    }
+                });
+
+                let mut lock_aspect_ratio = self.preferences.lock_aspect_ratio();
+                if ui
+                    .checkbox(
+                        &mut lock_aspect_ratio,
+                        text(locale, "view-menu-lock-aspect-ratio"),
+                    )
+                    .clicked()
+                {
+                    let _ = self
+                        .event_loop
+                        .send_event(RuffleEvent::SetAspectRatioLock(lock_aspect_ratio));
+                }
+                if ui
+                    .add_enabled(
+                        zoom != 1.0,
+                        Button::new(text(locale, "view-menu-reset-zoom")),
+                    )
+                    .on_hover_text_at_pointer(text(locale, "view-menu-reset-zoom-tooltip"))
+                    .clicked()
+                {
+                    ui.close();
+                    let _ = self.event_loop.send_event(RuffleEvent::ResetZoom);
+                }
                 ui.separator();
 
                 if Button::new(text(locale, "view-menu-fullscreen"))
-                    .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_FULLSCREEN))
+                    .shortcut_text(
+                        ui.ctx()
+                            .format_shortcut(&self.preferences.fullscreen_toggle_shortcut().0),
+                    )
                     .ui(ui)
                     .clicked()
                 {
@@ -392,6 +581,31 @@ impl MenuBar {
                         player.set_fullscreen(true);
                     }
                 }
+                self.fullscreen_monitor_menu(locale, ui);
+                let mut picture_in_picture = self.picture_in_picture;
+                if ui
+                    .checkbox(
+                        &mut picture_in_picture,
+                        text(locale, "view-menu-picture-in-picture"),
+                    )
+                    .clicked()
+                {
+                    ui.close();
+                    let _ = self
+                        .event_loop
+                        .send_event(RuffleEvent::SetPictureInPicture(picture_in_picture));
+                }
+                let mut timeline_bar = self.timeline_bar;
+                if ui
+                    .checkbox(&mut timeline_bar, text(locale, "view-menu-timeline"))
+                    .on_hover_text_at_pointer(text(locale, "view-menu-timeline-tooltip"))
+                    .clicked()
+                {
+                    ui.close();
+                    let _ = self
+                        .event_loop
+                        .send_event(RuffleEvent::SetTimelineBar(timeline_bar));
+                }
                 ui.separator();
 
                 ui.menu_button(text(locale, "quality"), |ui| {
@@ -417,6 +631,7 @@ impl MenuBar {
                             if let Some(player) = player {
                                 player.set_quality(quality);
                             }
+                            self.update_movie_options(|options| options.quality = Some(quality));
                         }
                     }
                 });
@@ -424,12 +639,69 @@ impl MenuBar {
         });
     }
 
+    /// Remembers a display setting chosen for the currently opened movie.
+    fn update_movie_options(&self, update: impl FnOnce(&mut MovieOptions)) {
+        if let Some((url, _)) = &self.currently_opened {
+            if let Err(e) = self
+                .preferences
+                .write_preferences(|writer| writer.update_movie_options(url, update))
+            {
+                tracing::warn!("Couldn't save the options of the movie: {e}");
+            }
+        }
+    }
+
+    fn fullscreen_monitor_menu(&mut self, locale: &LanguageIdentifier, ui: &mut egui::Ui) {
+        ui.menu_button(text(locale, "view-menu-fullscreen-monitor"), |ui| {
+            let current_monitor = self.preferences.fullscreen_monitor();
+            let monitors: Vec<FullscreenMonitor> = self
+                .window
+                .upgrade()
+                .map(|window| {
+                    window
+                        .available_monitors()
+                        .enumerate()
+                        .map(|(index, monitor)| FullscreenMonitor::of(index, &monitor))
+                        .collect()
+                })
+                .unwrap_or_default();
+            for monitor in std::iter::once(FullscreenMonitor::Current).chain(monitors) {
+                let clicked = if monitor == current_monitor {
+                    ui.checkbox(&mut true, monitor.label(locale)).clicked()
+                } else {
+                    ui.button(monitor.label(locale)).clicked()
+                };
+                if clicked {
+                    ui.close();
+                    self.set_fullscreen_monitor(monitor);
+                }
+            }
+        });
+    }
+
+    fn set_fullscreen_monitor(&mut self, monitor: FullscreenMonitor) {
+        if let Err(e) = self
+            .preferences
+            .write_preferences(|writer| writer.set_fullscreen_monitor(monitor))
+        {
+            tracing::error!("Could not save preferences: {e}");
+        }
+
+        // Move the window over right away if it's already fullscreen.
+        if let Some(window) = self.window.upgrade() {
+            if window.fullscreen().is_some() {
+                let _ = self.event_loop.send_event(RuffleEvent::SetFullscreen(true));
+            }
+        }
+    }
+
     fn controls_menu(
         &mut self,
         locale: &LanguageIdentifier,
         ui: &mut egui::Ui,
         dialogs: &mut Dialogs,
         player: &mut Option<&mut Player>,
+        is_recording: bool,
     ) {
         ui.menu_button(text(locale, "controls-menu"), |ui| {
             ui.add_enabled_ui(player.is_some(), |ui| {
@@ -457,11 +729,20 @@ impl MenuBar {
                         .clicked()
                     {
                         ui.close();
-                        if let Some(player) = player {
-                            player.suspend_after_next_frame();
-                        }
+                        self.step_frames(1);
                     }
                 });
+                ui.horizontal(|ui| {
+                    if Button::new(text(locale, "controls-menu-step-frames"))
+                        .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_STEP_MANY))
+                        .ui(ui)
+                        .clicked()
+                    {
+                        ui.close();
+                        self.step_frames(self.step_count);
+                    }
+                    ui.add(DragValue::new(&mut self.step_count).range(1..=1000));
+                });
             });
             if Button::new(text(locale, "controls-menu-volume"))
                 .ui(ui)
@@ -470,9 +751,45 @@ impl MenuBar {
                 dialogs.open_volume_controls();
                 ui.close();
             }
+            ui.separator();
+            if ui
+                .add_enabled(
+                    player.is_some(),
+                    Button::new(text(locale, "controls-menu-copy-frame"))
+                        .shortcut_text(ui.ctx().format_shortcut(&Self::SHORTCUT_COPY_FRAME)),
+                )
+                .clicked()
+            {
+                ui.close();
+                let _ = self
+                    .event_loop
+                    .send_event(RuffleEvent::CopyFrameToClipboard);
+            }
+            if is_recording {
+                if Button::new(text(locale, "controls-menu-stop-recording"))
+                    .ui(ui)
+                    .clicked()
+                {
+                    ui.close();
+                    let _ = self.event_loop.send_event(RuffleEvent::StopRecording);
+                }
+            } else if ui
+                .add_enabled(
+                    player.is_some(),
+                    Button::new(text(locale, "controls-menu-start-recording")),
+                )
+                .clicked()
+            {
+                ui.close();
+                let _ = self.event_loop.send_event(RuffleEvent::BrowseAndRecord);
+            }
         });
     }
 
+    fn step_frames(&mut self, frames: u32) {
+        let _ = self.event_loop.send_event(RuffleEvent::StepFrames(frames));
+    }
+
     fn open_file(&mut self) {
         let _ = self
             .event_loop
@@ -481,20 +798,40 @@ impl MenuBar {
             )));
     }
 
+    /// Opens the movie at the URL in the given text,
+    /// or shows the open dialog with the text if it isn't the URL of a movie.
+    pub fn open_url_text(&mut self, text: &str, dialogs: &mut Dialogs) {
+        let text = text.trim();
+        let url = Url::parse(text).ok().filter(|url| {
+            let is_movie = Path::new(url.path()).extension().is_some_and(|extension| {
+                MOVIE_EXTENSIONS
+                    .iter()
+                    .any(|movie| extension.eq_ignore_ascii_case(movie))
+            });
+            matches!(url.scheme(), "http" | "https") && is_movie
+        });
+        match url {
+            Some(url) => {
+                let _ = self.event_loop.send_event(RuffleEvent::Open(
+                    url,
+                    Box::new(self.default_launch_options.clone()),
+                ));
+            }
+            None => {
+                tracing::warn!("Can't open {text:?} as the URL of a movie");
+                dialogs.open_file_advanced_with_path(text);
+            }
+        }
+    }
+
     fn close_movie(&mut self, ui: &mut egui::Ui) {
         let _ = self.event_loop.send_event(RuffleEvent::CloseFile);
         self.currently_opened = None;
         ui.close();
     }
 
-    fn reload_movie(&mut self, ui: &mut egui::Ui) {
-        let _ = self.event_loop.send_event(RuffleEvent::CloseFile);
-        if let Some((movie_url, opts)) = self.currently_opened.take() {
-            let _ = self
-                .event_loop
-                .send_event(RuffleEvent::Open(movie_url, opts.into()));
-        }
-        ui.close();
+    fn reload_movie(&mut self) {
+        let _ = self.event_loop.send_event(RuffleEvent::ReloadMovie);
     }
 
     fn request_exit(&mut self) {
@@ -506,3 +843,40 @@ impl MenuBar {
         ui.close();
     }
 }
+
+/// When the menu bar is shown while in windowed mode.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum MenuVisibility {
+    /// The menu bar is always shown above the movie.
+    #[default]
+    Always,
+
+    /// The menu bar is hidden, and temporarily overlays the movie
+    /// when the cursor is moved to the top edge of the window.
+    AutoHide,
+
+    /// The menu bar is never shown, like in a Flash projector.
+    Never,
+}
+
+impl MenuVisibility {
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            MenuVisibility::Always => None,
+            MenuVisibility::AutoHide => Some("auto-hide"),
+            MenuVisibility::Never => Some("never"),
+        }
+    }
+}
+
+impl FromStr for MenuVisibility {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "auto-hide" => Ok(MenuVisibility::AutoHide),
+            "never" => Ok(MenuVisibility::Never),
+            _ => Err(()),
+        }
+    }
+}
@@ -1,9 +1,215 @@
-use crate::gui::MENU_HEIGHT;
+use ruffle_core::Player;
+use ruffle_render::backend::ViewportDimensions;
+use ruffle_render_wgpu::backend::WgpuRenderBackend;
 use ruffle_render_wgpu::descriptors::Descriptors;
 use ruffle_render_wgpu::target::{RenderTarget, RenderTargetFrame};
+use ruffle_render_wgpu::utils::{buffer_to_image, BufferDimensions};
+use std::any::Any;
 use std::borrow::Cow;
+use std::ops::RangeInclusive;
+use std::str::FromStr;
 use std::sync::Arc;
 use wgpu::util::DeviceExt;
+use winit::dpi::PhysicalPosition;
+
+/// The color shown around the movie, where it doesn't fill the window.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum BackgroundColor {
+    /// The background color of the movie's stage, or black when there's no movie.
+    #[default]
+    Stage,
+    Black,
+    White,
+    /// An RGB color, stored as `#rrggbb`.
+    Custom([u8; 3]),
+}
+
+impl BackgroundColor {
+    /// Returns the value stored in the preferences, or `None` for the default.
+    pub fn as_preference(&self) -> Option<String> {
+        match self {
+            BackgroundColor::Stage => None,
+            BackgroundColor::Black => Some("black".to_string()),
+            BackgroundColor::White => Some("white".to_string()),
+            BackgroundColor::Custom([r, g, b]) => Some(format!("#{r:02x}{g:02x}{b:02x}")),
+        }
+    }
+
+    /// Returns the RGB color to use, given the stage background color of the current movie.
+    pub fn resolve(&self, stage_color: Option<[u8; 3]>) -> [u8; 3] {
+        match self {
+            BackgroundColor::Stage => stage_color.unwrap_or([0, 0, 0]),
+            BackgroundColor::Black => [0, 0, 0],
+            BackgroundColor::White => [255, 255, 255],
+            BackgroundColor::Custom(color) => *color,
+        }
+    }
+}
+
+impl FromStr for BackgroundColor {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "stage" => Ok(BackgroundColor::Stage),
+            "black" => Ok(BackgroundColor::Black),
+            "white" => Ok(BackgroundColor::White),
+            _ => {
+                let hex = s.strip_prefix('#').ok_or(())?;
+                if hex.len() != 6 {
+                    return Err(());
+                }
+                let rgb = u32::from_str_radix(hex, 16).map_err(|_| ())?;
+                Ok(BackgroundColor::Custom([
+                    (rgb >> 16) as u8,
+                    (rgb >> 8) as u8,
+                    rgb as u8,
+                ]))
+            }
+        }
+    }
+}
+
+/// The smallest and largest zoom of the movie view.
+pub const VIEW_ZOOM_RANGE: RangeInclusive<f64> = 1.0..=16.0;
+
+/// The zoom and pan of the movie view.
+///
+/// This only changes how the rendered movie is shown in the window,
+/// the movie keeps being rendered at the size of the window.
+/// Positions are relative to the area of the window showing the movie,
+/// from (0, 0) at its top left to (1, 1) at its bottom right.
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub struct ViewTransform {
+    scale: f64,
+    /// The position of the movie shown at the top left of the view.
+    offset: (f64, f64),
+}
+
+impl Default for ViewTransform {
+    fn default() -> Self {
+        Self {
+            scale: 1.0,
+            offset: (0.0, 0.0),
+        }
+    }
+}
+
+impl ViewTransform {
+    pub fn scale(&self) -> f64 {
+        self.scale
+    }
+
+    pub fn is_identity(&self) -> bool {
+        self.scale == 1.0
+    }
+
+    /// Returns the position of the movie shown at the given position of the view.
+    pub fn view_to_movie(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            x / self.scale + self.offset.0,
+            y / self.scale + self.offset.1,
+        )
+    }
+
+    /// Returns the position of the view where the given position of the movie is shown.
+    pub fn movie_to_view(&self, (x, y): (f64, f64)) -> (f64, f64) {
+        (
+            (x - self.offset.0) * self.scale,
+            (y - self.offset.1) * self.scale,
+        )
+    }
+
+    /// Multiplies the zoom by `factor`, keeping the movie still under `anchor`.
+    pub fn zoom_at(&mut self, factor: f64, anchor: (f64, f64)) {
+        let fixed = self.view_to_movie(anchor);
+        self.scale = (self.scale * factor).clamp(*VIEW_ZOOM_RANGE.start(), *VIEW_ZOOM_RANGE.end());
+        self.offset = (
+            fixed.0 - anchor.0 / self.scale,
+            fixed.1 - anchor.1 / self.scale,
+        );
+        self.clamp_offset();
+    }
+
+    /// Moves the movie by the given distance in the view.
+    pub fn pan(&mut self, (dx, dy): (f64, f64)) {
+        self.offset = (
+            self.offset.0 - dx / self.scale,
+            self.offset.1 - dy / self.scale,
+        );
+        self.clamp_offset();
+    }
+
+    /// Keeps the view within the movie, so that nothing outside of it is shown.
+    fn clamp_offset(&mut self) {
+        let max = 1.0 - 1.0 / self.scale;
+        self.offset = (self.offset.0.clamp(0.0, max), self.offset.1.clamp(0.0, max));
+    }
+
+    fn to_uniform(self) -> [f32; 4] {
+        [
+            self.offset.0 as f32,
+            self.offset.1 as f32,
+            self.scale as f32,
+            0.0,
+        ]
+    }
+}
+
+/// Two fingers pinching the movie view, to zoom and pan it like a photo.
+#[derive(Clone, Copy, Debug)]
+pub struct TouchGesture {
+    /// The IDs of the fingers and their positions in the window.
+    touches: [(u64, PhysicalPosition<f64>); 2],
+}
+
+impl TouchGesture {
+    pub fn new(first: (u64, PhysicalPosition<f64>), second: (u64, PhysicalPosition<f64>)) -> Self {
+        Self {
+            touches: [first, second],
+        }
+    }
+
+    pub fn has_touch(&self, id: u64) -> bool {
+        self.touches.iter().any(|(touch_id, _)| *touch_id == id)
+    }
+
+    /// The point between the fingers.
+    fn center(&self) -> PhysicalPosition<f64> {
+        let [(_, a), (_, b)] = self.touches;
+        PhysicalPosition::new((a.x + b.x) / 2.0, (a.y + b.y) / 2.0)
+    }
+
+    fn distance(&self) -> f64 {
+        let [(_, a), (_, b)] = self.touches;
+        (a.x - b.x).hypot(a.y - b.y)
+    }
+
+    /// Moves one of the fingers, if it's part of the gesture.
+    ///
+    /// Returns the previous and the new center of the fingers,
+    /// and by how much the distance between them was multiplied.
+    pub fn move_touch(
+        &mut self,
+        id: u64,
+        position: PhysicalPosition<f64>,
+    ) -> Option<(PhysicalPosition<f64>, PhysicalPosition<f64>, f64)> {
+        let previous_center = self.center();
+        let previous_distance = self.distance();
+        let (_, touch_position) = self
+            .touches
+            .iter_mut()
+            .find(|(touch_id, _)| *touch_id == id)?;
+        *touch_position = position;
+        // Fingers on top of each other can't zoom in any meaningful way.
+        let factor = if previous_distance > 0.0 {
+            self.distance() / previous_distance
+        } else {
+            1.0
+        };
+        Some((previous_center, self.center(), factor))
+    }
+}
 
 #[derive(Debug)]
 pub struct MovieViewRenderer {
@@ -11,15 +217,13 @@ pub struct MovieViewRenderer {
     pipeline: wgpu::RenderPipeline,
     sampler: wgpu::Sampler,
     vertices: wgpu::Buffer,
+    transform: wgpu::Buffer,
 }
 
-fn get_vertices(has_menu: bool, height: u32, scale_factor: f64) -> [[f32; 4]; 6] {
-    let top = if has_menu {
-        let menu_height = MENU_HEIGHT as f64 * scale_factor;
-        1.0 - ((menu_height / height as f64) * 2.0) as f32
-    } else {
-        1.0
-    };
+/// `height_offset` is the amount of physical pixels at the top of the window
+/// reserved for the menu bar, which the movie must not be drawn under.
+fn get_vertices(height_offset: f64, height: u32) -> [[f32; 4]; 6] {
+    let top = 1.0 - ((height_offset / height as f64) * 2.0) as f32;
     // x y u v
     [
         [-1.0, top, 0.0, 0.0],  // tl
@@ -35,14 +239,10 @@ impl MovieViewRenderer {
     pub fn new(
         device: &wgpu::Device,
         surface_format: wgpu::TextureFormat,
-        has_menu: bool,
+        sample_count: u32,
+        height_offset: f64,
         height: u32,
-        scale_factor: f64,
     ) -> Self {
-        let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
-            label: None,
-            source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("blit.wgsl"))),
-        });
         let bind_group_layout = device.create_bind_group_layout(&wgpu::BindGroupLayoutDescriptor {
             label: None,
             entries: &[
@@ -62,6 +262,16 @@ impl MovieViewRenderer {
                     ty: wgpu::BindingType::Sampler(wgpu::SamplerBindingType::Filtering),
                     count: None,
                 },
+                wgpu::BindGroupLayoutEntry {
+                    binding: 2,
+                    visibility: wgpu::ShaderStages::VERTEX,
+                    ty: wgpu::BindingType::Buffer {
+                        ty: wgpu::BufferBindingType::Uniform,
+                        has_dynamic_offset: false,
+                        min_binding_size: None,
+                    },
+                    count: None,
+                },
             ],
         });
         let sampler = device.create_sampler(&wgpu::SamplerDescriptor {
@@ -69,86 +279,129 @@ impl MovieViewRenderer {
             min_filter: wgpu::FilterMode::Linear,
             ..Default::default()
         });
-        let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
-            label: None,
-            bind_group_layouts: &[&bind_group_layout],
-            push_constant_ranges: &[],
-        });
-        let pipeline = device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
-            label: None,
-            layout: Some(&pipeline_layout),
-            vertex: wgpu::VertexState {
-                entry_point: Some("vs_main"),
-                module: &module,
-                buffers: &[wgpu::VertexBufferLayout {
-                    array_stride: 4 * 4,
-                    step_mode: wgpu::VertexStepMode::Vertex,
-                    // 0: vec2 position
-                    // 1: vec2 texture coordinates
-                    attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2],
-                }],
-                compilation_options: Default::default(),
-            },
-            primitive: wgpu::PrimitiveState {
-                topology: wgpu::PrimitiveTopology::TriangleList,
-                unclipped_depth: false,
-                conservative: false,
-                cull_mode: None,
-                front_face: wgpu::FrontFace::default(),
-                polygon_mode: wgpu::PolygonMode::default(),
-                strip_index_format: None,
-            },
-            depth_stencil: None,
-            multisample: wgpu::MultisampleState {
-                alpha_to_coverage_enabled: false,
-                count: 1,
-                mask: !0,
-            },
-
-            fragment: Some(wgpu::FragmentState {
-                module: &module,
-                entry_point: Some(if surface_format.is_srgb() {
-                    "fs_main_srgb_framebuffer"
-                } else {
-                    "fs_main_linear_framebuffer"
-                }),
-                targets: &[Some(wgpu::ColorTargetState {
-                    format: surface_format,
-                    blend: Some(wgpu::BlendState::REPLACE),
-                    write_mask: wgpu::ColorWrites::ALL,
-                })],
-                compilation_options: Default::default(),
-            }),
-            multiview: None,
-            cache: None,
-        });
+        let pipeline = create_pipeline(device, &bind_group_layout, surface_format, sample_count);
         let vertices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
             label: None,
-            contents: bytemuck::cast_slice(&get_vertices(has_menu, height, scale_factor)),
+            contents: bytemuck::cast_slice(&get_vertices(height_offset, height)),
             usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
         });
+        let transform = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&ViewTransform::default().to_uniform()),
+            usage: wgpu::BufferUsages::UNIFORM | wgpu::BufferUsages::COPY_DST,
+        });
 
         Self {
             bind_group_layout,
             pipeline,
             sampler,
             vertices,
+            transform,
         }
     }
 
-    pub fn update_resolution(
-        &self,
-        descriptors: &Descriptors,
-        has_menu: bool,
-        height: u32,
-        scale_factor: f64,
-    ) {
+    pub fn update_transform(&self, descriptors: &Descriptors, transform: ViewTransform) {
+        descriptors.queue.write_buffer(
+            &self.transform,
+            0,
+            bytemuck::cast_slice(&transform.to_uniform()),
+        );
+    }
+
+    pub fn update_resolution(&self, descriptors: &Descriptors, height_offset: f64, height: u32) {
         descriptors.queue.write_buffer(
             &self.vertices,
             0,
-            bytemuck::cast_slice(&get_vertices(has_menu, height, scale_factor)),
+            bytemuck::cast_slice(&get_vertices(height_offset, height)),
         );
     }
+
+    /// Creates a renderer drawing the same movie views onto a whole surface of another window,
+    /// such as the picture-in-picture window.
+    ///
+    /// The sampler and view transform are shared with this renderer,
+    /// so the movie is zoomed and panned identically in both windows.
+    pub fn share(&self, device: &wgpu::Device, surface_format: wgpu::TextureFormat) -> Self {
+        let vertices = device.create_buffer_init(&wgpu::util::BufferInitDescriptor {
+            label: None,
+            contents: bytemuck::cast_slice(&get_vertices(0.0, 1)),
+            usage: wgpu::BufferUsages::VERTEX | wgpu::BufferUsages::COPY_DST,
+        });
+        Self {
+            bind_group_layout: self.bind_group_layout.clone(),
+            pipeline: create_pipeline(device, &self.bind_group_layout, surface_format, 1),
+            sampler: self.sampler.clone(),
+            vertices,
+            transform: self.transform.clone(),
+        }
+    }
+}
+
+/// Creates the pipeline drawing movie views to a surface of the given format.
+fn create_pipeline(
+    device: &wgpu::Device,
+    bind_group_layout: &wgpu::BindGroupLayout,
+    surface_format: wgpu::TextureFormat,
+    sample_count: u32,
+) -> wgpu::RenderPipeline {
+    let module = device.create_shader_module(wgpu::ShaderModuleDescriptor {
+        label: None,
+        source: wgpu::ShaderSource::Wgsl(Cow::Borrowed(include_str!("blit.wgsl"))),
+    });
+    let pipeline_layout = device.create_pipeline_layout(&wgpu::PipelineLayoutDescriptor {
+        label: None,
+        bind_group_layouts: &[bind_group_layout],
+        push_constant_ranges: &[],
+    });
+    device.create_render_pipeline(&wgpu::RenderPipelineDescriptor {
+        label: None,
+        layout: Some(&pipeline_layout),
+        vertex: wgpu::VertexState {
+            entry_point: Some("vs_main"),
+            module: &module,
+            buffers: &[wgpu::VertexBufferLayout {
+                array_stride: 4 * 4,
+                step_mode: wgpu::VertexStepMode::Vertex,
+                // 0: vec2 position
+                // 1: vec2 texture coordinates
+                attributes: &wgpu::vertex_attr_array![0 => Float32x2, 1 => Float32x2],
+            }],
+            compilation_options: Default::default(),
+        },
+        primitive: wgpu::PrimitiveState {
+            topology: wgpu::PrimitiveTopology::TriangleList,
+            unclipped_depth: false,
+            conservative: false,
+            cull_mode: None,
+            front_face: wgpu::FrontFace::default(),
+            polygon_mode: wgpu::PolygonMode::default(),
+            strip_index_format: None,
+        },
+        depth_stencil: None,
+        // The movie is drawn in the same render pass as the GUI, so this must match its sample count.
+        multisample: wgpu::MultisampleState {
+            alpha_to_coverage_enabled: false,
+            count: sample_count,
+            mask: !0,
+        },
+
+        fragment: Some(wgpu::FragmentState {
+            module: &module,
+            entry_point: Some(if surface_format.is_srgb() {
+                "fs_main_srgb_framebuffer"
+            } else {
+                "fs_main_linear_framebuffer"
+            }),
+            targets: &[Some(wgpu::ColorTargetState {
+                format: surface_format,
+                blend: Some(wgpu::BlendState::REPLACE),
+                write_mask: wgpu::ColorWrites::ALL,
+            })],
+            compilation_options: Default::default(),
+        }),
+        multiview: None,
+        cache: None,
+    })
 }
 
 #[derive(Debug)]
@@ -176,7 +429,9 @@ impl MovieView {
             sample_count: 1,
             dimension: wgpu::TextureDimension::D2,
             format: wgpu::TextureFormat::Rgba8Unorm,
-            usage: wgpu::TextureUsages::RENDER_ATTACHMENT | wgpu::TextureUsages::TEXTURE_BINDING,
+            usage: wgpu::TextureUsages::RENDER_ATTACHMENT
+                | wgpu::TextureUsages::TEXTURE_BINDING
+                | wgpu::TextureUsages::COPY_SRC,
             view_formats: &[],
         });
         let view = texture.create_view(&Default::default());
@@ -192,6 +447,10 @@ impl MovieView {
                     binding: 1,
                     resource: wgpu::BindingResource::Sampler(&renderer.sampler),
                 },
+                wgpu::BindGroupEntry {
+                    binding: 2,
+                    resource: renderer.transform.as_entire_binding(),
+                },
             ],
         });
         Self {
@@ -211,6 +470,50 @@ impl MovieView {
         render_pass.set_vertex_buffer(0, renderer.vertices.slice(..));
         render_pass.draw(0..6, 0..1);
     }
+
+    /// Reads back the current contents of the movie texture.
+    ///
+    /// This blocks until the GPU has finished rendering the movie,
+    /// and only contains the movie itself (no menus or other UI).
+    pub fn capture(&self, descriptors: &Descriptors) -> image::RgbaImage {
+        let size = self.texture.size();
+        let dimensions = BufferDimensions::new(
+            size.width as usize,
+            size.height as usize,
+            self.texture.format(),
+        );
+        let buffer = descriptors.device.create_buffer(&wgpu::BufferDescriptor {
+            label: Some("Movie view capture buffer"),
+            size: dimensions.size(),
+            usage: wgpu::BufferUsages::COPY_DST | wgpu::BufferUsages::MAP_READ,
+            mapped_at_creation: false,
+        });
+        let mut encoder =
+            descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Movie view capture encoder"),
+                });
+        encoder.copy_texture_to_buffer(
+            wgpu::TexelCopyTextureInfo {
+                texture: &self.texture,
+                mip_level: 0,
+                origin: wgpu::Origin3d::ZERO,
+                aspect: wgpu::TextureAspect::All,
+            },
+            wgpu::TexelCopyBufferInfo {
+                buffer: &buffer,
+                layout: wgpu::TexelCopyBufferLayout {
+                    offset: 0,
+                    bytes_per_row: Some(dimensions.padded_bytes_per_row),
+                    rows_per_image: None,
+                },
+            },
+            size,
+        );
+        let index = descriptors.queue.submit(Some(encoder.finish()));
+        buffer_to_image(&descriptors.device, &buffer, &dimensions, Some(index), size)
+    }
 }
 
 impl RenderTarget for MovieView {
@@ -261,3 +564,53 @@ impl RenderTargetFrame for MovieViewFrame {
         &self.0
     }
 }
+
+/// Returns the movie view that the player renders to.
+pub fn player_movie_view(player: &mut Player) -> &MovieView {
+    <dyn Any>::downcast_ref::<WgpuRenderBackend<MovieView>>(player.renderer_mut())
+        .expect("Renderer must be correct type")
+        .target()
+}
+
+/// Returns true if the last rendered frame of the movie may look different from the one before it.
+///
+/// Besides the frame's commands, this accounts for bitmaps whose pixels changed since it was last called.
+pub fn movie_frame_changed(player: &mut Player) -> bool {
+    let textures_modified =
+        <dyn Any>::downcast_mut::<WgpuRenderBackend<MovieView>>(player.renderer_mut())
+            .expect("Renderer must be correct type")
+            .take_textures_modified();
+    textures_modified || player.last_frame_changed()
+}
+
+/// Renders the movie alone to an image of the given size, and reads it back.
+///
+/// This only needs the graphics device, not a window or its surface, and none of the GUI is drawn.
+/// If the movie view already has an up-to-date frame of that size, it's read back as is.
+/// Otherwise, the player is rendered at that size and then restored to its previous one;
+/// note that this fires a resize event in `noScale` movies.
+pub fn render_movie_to_image(
+    descriptors: &Descriptors,
+    player: &mut Player,
+    width: u32,
+    height: u32,
+) -> image::RgbaImage {
+    let dimensions = player.viewport_dimensions();
+    if (dimensions.width, dimensions.height) == (width, height) {
+        if player.needs_render() {
+            player.render();
+        }
+        return player_movie_view(player).capture(descriptors);
+    }
+
+    player.set_viewport_dimensions(ViewportDimensions {
+        width: width.max(1),
+        height: height.max(1),
+        scale_factor: dimensions.scale_factor,
+    });
+    player.render();
+    let image = player_movie_view(player).capture(descriptors);
+    player.set_viewport_dimensions(dimensions);
+    player.render();
+    image
+}
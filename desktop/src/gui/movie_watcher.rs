@@ -0,0 +1,76 @@
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
+use std::path::{Path, PathBuf};
+use std::sync::atomic::{AtomicBool, Ordering};
+use std::sync::Arc;
+use std::time::{Duration, Instant, SystemTime};
+
+/// How often the movie file is checked for changes.
+const POLL_INTERVAL: Duration = Duration::from_millis(250);
+
+/// How long the movie file has to stay unchanged after a change before it's reloaded,
+/// so that a compiler writing it in several steps only causes a single reload.
+const SETTLE_TIME: Duration = Duration::from_millis(500);
+
+/// Watches the file of a local movie, sending [`RuffleEvent::MovieFileChanged`] when it changes.
+///
+/// The file is polled on a thread of its own, which stops shortly after the watcher is dropped.
+/// While the file is missing, such as when a compiler deletes it before writing it again,
+/// no change is reported until it's back.
+pub struct MovieFileWatcher {
+    path: PathBuf,
+    stopped: Arc<AtomicBool>,
+}
+
+impl MovieFileWatcher {
+    pub fn start(path: PathBuf, event_loop: RuffleEventProxy) -> std::io::Result<Self> {
+        let stopped = Arc::new(AtomicBool::new(false));
+        let thread_stopped = stopped.clone();
+        let thread_path = path.clone();
+        std::thread::Builder::new()
+            .name("movie file watcher".to_string())
+            .spawn(move || {
+                let mut last_version = file_version(&thread_path);
+                let mut changed_at = None;
+                while !thread_stopped.load(Ordering::Relaxed) {
+                    std::thread::sleep(POLL_INTERVAL);
+
+                    let version = file_version(&thread_path);
+                    if version != last_version {
+                        last_version = version;
+                        changed_at = Some(Instant::now());
+                        continue;
+                    }
+
+                    let settled = changed_at.is_some_and(|at| at.elapsed() >= SETTLE_TIME);
+                    if settled && version.is_some() {
+                        changed_at = None;
+                        let event = RuffleEvent::MovieFileChanged(thread_path.clone());
+                        if thread_stopped.load(Ordering::Relaxed)
+                            || event_loop.send_event(event).is_err()
+                        {
+                            break;
+                        }
+                    }
+                }
+            })?;
+
+        Ok(Self { path, stopped })
+    }
+
+    pub fn path(&self) -> &Path {
+        &self.path
+    }
+}
+
+impl Drop for MovieFileWatcher {
+    fn drop(&mut self) {
+        self.stopped.store(true, Ordering::Relaxed);
+    }
+}
+
+/// Identifies the current contents of the file by its modification time and size,
+/// or returns `None` if it's missing.
+fn file_version(path: &Path) -> Option<(SystemTime, u64)> {
+    let metadata = std::fs::metadata(path).ok()?;
+    Some((metadata.modified().ok()?, metadata.len()))
+}
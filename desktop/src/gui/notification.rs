@@ -0,0 +1,43 @@
+use crate::gui::text;
+use egui::{Align2, Area, Frame, Id, Order};
+use std::time::{Duration, Instant};
+use unic_langid::LanguageIdentifier;
+
+/// How long a notification stays on screen.
+const NOTIFICATION_DURATION: Duration = Duration::from_secs(2);
+
+/// A short message shown at the bottom of the window for a moment,
+/// used to confirm actions that have no other visible effect.
+#[derive(Default)]
+pub struct Notification {
+    /// The text ID of the message, and when it was triggered.
+    message: Option<(&'static str, Instant)>,
+}
+
+impl Notification {
+    pub fn set(&mut self, message: &'static str) {
+        self.message = Some((message, Instant::now()));
+    }
+
+    pub fn show(&mut self, locale: &LanguageIdentifier, egui_ctx: &egui::Context) {
+        let Some((message, shown_at)) = self.message else {
+            return;
+        };
+        let shown_for = shown_at.elapsed();
+        if shown_for >= NOTIFICATION_DURATION {
+            self.message = None;
+            return;
+        }
+        egui_ctx.request_repaint_after(NOTIFICATION_DURATION - shown_for);
+
+        Area::new(Id::new("notification"))
+            .order(Order::Foreground)
+            .anchor(Align2::CENTER_BOTTOM, egui::vec2(0.0, -16.0))
+            .interactable(false)
+            .show(egui_ctx, |ui| {
+                Frame::popup(ui.style()).show(ui, |ui| {
+                    ui.label(text(locale, message));
+                });
+            });
+    }
+}
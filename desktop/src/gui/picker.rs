@@ -10,13 +10,16 @@ use std::{
 };
 use winit::window::Window;
 
+/// The extensions of the files that Ruffle can open as movies.
+pub const MOVIE_EXTENSIONS: [&str; 3] = ["swf", "spl", "ruf"];
+
 #[derive(Clone)]
 pub struct FilePicker {
     data: Arc<FilePickerData>,
+    parent: Weak<Window>,
 }
 
 struct FilePickerData {
-    parent: Weak<Window>,
     picking: AtomicBool,
     preferences: GlobalPreferences,
 }
@@ -25,10 +28,20 @@ impl FilePicker {
     pub fn new(parent: Weak<Window>, preferences: GlobalPreferences) -> Self {
         Self {
             data: Arc::new(FilePickerData {
-                parent,
                 picking: AtomicBool::new(false),
                 preferences,
             }),
+            parent,
+        }
+    }
+
+    /// Returns a picker showing its dialogs over another window.
+    ///
+    /// It's still shared with this one, so that only one dialog is open at a time.
+    pub fn with_parent(&self, parent: Weak<Window>) -> Self {
+        Self {
+            data: self.data.clone(),
+            parent,
         }
     }
 
@@ -37,7 +50,7 @@ impl FilePicker {
         let mut dialog = AsyncFileDialog::new()
             .add_filter(
                 text(locale, "file-picker-filter-supported"),
-                &["swf", "spl", "ruf"],
+                &MOVIE_EXTENSIONS,
             )
             .add_filter(text(locale, "file-picker-filter-swf"), &["swf"])
             .add_filter(text(locale, "file-picker-filter-spl"), &["spl"])
@@ -56,11 +69,22 @@ impl FilePicker {
         }
     }
 
+    pub async fn pick_recording_directory(&self) -> Option<PathBuf> {
+        let locale = &self.data.preferences.language();
+        let dialog = AsyncFileDialog::new().set_title(text(locale, "file-picker-title-record"));
+
+        if let Some(result) = self.show_dialog(dialog, |d| d.pick_folder()) {
+            result.await.map(|h| h.into())
+        } else {
+            None
+        }
+    }
+
     pub fn show_dialog<F, O>(&self, mut dialog: AsyncFileDialog, f: F) -> Option<O>
     where
         F: FnOnce(AsyncFileDialog) -> O,
     {
-        if let Some(parent) = self.data.parent.upgrade() {
+        if let Some(parent) = self.parent.upgrade() {
             dialog = dialog.set_parent(&parent);
         }
 
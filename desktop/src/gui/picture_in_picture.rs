@@ -0,0 +1,212 @@
+use crate::gui::movie::{MovieView, MovieViewRenderer};
+use anyhow::anyhow;
+use ruffle_render_wgpu::descriptors::Descriptors;
+use std::sync::Arc;
+use winit::dpi::{PhysicalPosition, PhysicalSize};
+use winit::window::{CursorIcon, ResizeDirection, Window};
+
+/// The width of the picture-in-picture window when it's opened, in logical pixels.
+pub const PICTURE_IN_PICTURE_WIDTH: f64 = 320.0;
+
+/// How close to its edges a press resizes the picture-in-picture window, in logical pixels.
+const RESIZE_BORDER: f64 = 8.0;
+
+/// A small frameless window showing the movie of a main window, scaled to fit.
+///
+/// It draws the same movie view texture as the main window, so the movie keeps playing
+/// (and is only rendered once) no matter which of them is visible.
+///
+/// Having no decorations, it's resized by dragging its edges and moved by dragging it with Alt held.
+pub struct PictureInPicture {
+    // The surface must be dropped before the window it belongs to.
+    surface: wgpu::Surface<'static>,
+    surface_format: wgpu::TextureFormat,
+    size: PhysicalSize<u32>,
+    renderer: MovieViewRenderer,
+    window: Arc<Window>,
+}
+
+impl PictureInPicture {
+    pub fn new(
+        window: Arc<Window>,
+        descriptors: &Descriptors,
+        movie_view_renderer: &MovieViewRenderer,
+    ) -> anyhow::Result<Self> {
+        let surface = descriptors.wgpu_instance.create_surface(window.clone())?;
+        let surface_format = surface
+            .get_capabilities(&descriptors.adapter)
+            .formats
+            .first()
+            .cloned()
+            .ok_or_else(|| anyhow!("The surface doesn't support any format"))?;
+        let renderer = movie_view_renderer.share(&descriptors.device, surface_format);
+        let this = Self {
+            surface,
+            surface_format,
+            size: window.inner_size(),
+            renderer,
+            window,
+        };
+        this.configure_surface(descriptors);
+        Ok(this)
+    }
+
+    pub fn window(&self) -> &Arc<Window> {
+        &self.window
+    }
+
+    /// Resizes the surface to the new size of the window.
+    ///
+    /// If the window was resized to another aspect ratio than the movie view's,
+    /// it's resized again to match it, along the side that was changed.
+    pub fn resize(
+        &mut self,
+        descriptors: &Descriptors,
+        size: PhysicalSize<u32>,
+        aspect_ratio: f64,
+    ) {
+        let mut size = size;
+        if size.width > 0 && size.height > 0 {
+            let snapped = if size.width != self.size.width {
+                PhysicalSize::new(
+                    size.width,
+                    (size.width as f64 / aspect_ratio).round().max(1.0) as u32,
+                )
+            } else {
+                PhysicalSize::new(
+                    (size.height as f64 * aspect_ratio).round().max(1.0) as u32,
+                    size.height,
+                )
+            };
+            // Allow for rounding, so that we don't keep resizing back and forth.
+            if snapped.width.abs_diff(size.width) > 1 || snapped.height.abs_diff(size.height) > 1 {
+                if let Some(new_size) = self.window.request_inner_size(snapped) {
+                    size = new_size;
+                }
+            }
+        }
+        self.size = size;
+        self.configure_surface(descriptors);
+    }
+
+    /// The edge or corner that a press at `position` resizes the window from,
+    /// if it's close enough to one.
+    fn resize_direction(&self, position: PhysicalPosition<f64>) -> Option<ResizeDirection> {
+        let border = RESIZE_BORDER * self.window.scale_factor();
+        let west = position.x < border;
+        let east = position.x >= self.size.width as f64 - border;
+        let north = position.y < border;
+        let south = position.y >= self.size.height as f64 - border;
+        Some(match (north, south, west, east) {
+            (true, _, true, _) => ResizeDirection::NorthWest,
+            (true, _, _, true) => ResizeDirection::NorthEast,
+            (_, true, true, _) => ResizeDirection::SouthWest,
+            (_, true, _, true) => ResizeDirection::SouthEast,
+            (true, _, _, _) => ResizeDirection::North,
+            (_, true, _, _) => ResizeDirection::South,
+            (_, _, true, _) => ResizeDirection::West,
+            (_, _, _, true) => ResizeDirection::East,
+            _ => return None,
+        })
+    }
+
+    /// Shows a resize cursor while the mouse is over the edges of the window.
+    pub fn hover(&self, position: PhysicalPosition<f64>) {
+        self.window.set_cursor(
+            self.resize_direction(position)
+                .map_or(CursorIcon::Default, CursorIcon::from),
+        );
+    }
+
+    /// Starts resizing the window if `position` is on one of its edges,
+    /// or moving it if `move_window` is set.
+    ///
+    /// Returns true if it was, so that the press isn't given to the movie.
+    pub fn drag(&self, position: PhysicalPosition<f64>, move_window: bool) -> bool {
+        let result = match self.resize_direction(position) {
+            Some(direction) => self.window.drag_resize_window(direction),
+            None if move_window => self.window.drag_window(),
+            None => return false,
+        };
+        if let Err(e) = result {
+            tracing::warn!("Couldn't drag the picture-in-picture window: {e}");
+            return false;
+        }
+        true
+    }
+
+    fn configure_surface(&self, descriptors: &Descriptors) {
+        // A minimized window is 0×0, which can't be configured.
+        if self.size.width == 0 || self.size.height == 0 {
+            return;
+        }
+        self.surface.configure(
+            &descriptors.device,
+            &wgpu::SurfaceConfiguration {
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                format: self.surface_format,
+                width: self.size.width,
+                height: self.size.height,
+                present_mode: Default::default(),
+                desired_maximum_frame_latency: 2,
+                alpha_mode: Default::default(),
+                view_formats: Default::default(),
+            },
+        );
+    }
+
+    /// Converts a position in this window to a position relative to the movie view,
+    /// i.e. in the range of 0 to 1 across the whole window.
+    pub fn window_to_view_position(&self, position: PhysicalPosition<f64>) -> (f64, f64) {
+        (
+            position.x / (self.size.width as f64).max(1.0),
+            position.y / (self.size.height as f64).max(1.0),
+        )
+    }
+
+    pub fn render(&mut self, descriptors: &Descriptors, movie_view: Option<&MovieView>) {
+        if self.size.width == 0 || self.size.height == 0 {
+            return;
+        }
+        let surface_texture = match self.surface.get_current_texture() {
+            Ok(surface_texture) => surface_texture,
+            Err(e) => {
+                // Unlike the main window, there's nothing here worth telling the user about;
+                // just try again with a fresh surface on the next frame.
+                tracing::warn!("Picture-in-picture surface became unavailable: {e:?}");
+                self.configure_surface(descriptors);
+                return;
+            }
+        };
+
+        let mut encoder =
+            descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("Picture-in-picture encoder"),
+                });
+        {
+            let surface_view = surface_texture.texture.create_view(&Default::default());
+            let mut render_pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view: &surface_view,
+                        resolve_target: None,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store: wgpu::StoreOp::Store,
+                        },
+                    })],
+                    label: Some("Picture-in-picture render pass"),
+                    ..Default::default()
+                })
+                .forget_lifetime();
+            if let Some(movie_view) = movie_view {
+                movie_view.render(&self.renderer, &mut render_pass);
+            }
+        }
+        descriptors.queue.submit([encoder.finish()]);
+        self.window.pre_present_notify();
+        surface_texture.present();
+    }
+}
@@ -0,0 +1,156 @@
+use std::fs::File;
+use std::io::{BufWriter, Write};
+use std::path::{Path, PathBuf};
+use std::sync::mpsc::{sync_channel, SyncSender, TrySendError};
+use std::thread::JoinHandle;
+use std::time::{Duration, Instant};
+
+/// Name of the file listing the presentation time of every recorded frame.
+///
+/// It uses the "timestamp format v2" understood by mkvmerge and ffmpeg,
+/// so that dropped frames don't desynchronize the resulting video.
+const TIMESTAMPS_FILE_NAME: &str = "timestamps.txt";
+
+/// How many captured frames may wait to be written before new ones are dropped,
+/// which bounds the memory used when encoding can't keep up with rendering.
+const MAX_PENDING_FRAMES: usize = 8;
+
+struct RecordedFrame {
+    index: u64,
+    timestamp: Duration,
+    image: image::RgbaImage,
+}
+
+/// Records the movie view to a sequence of PNG images.
+///
+/// Frames are captured at most `fps` times per second, and each one is
+/// timestamped with the time it was actually rendered at, rather than
+/// assuming a constant cadence. Encoding and writing happen on a separate
+/// thread so that recording doesn't stall rendering more than necessary;
+/// frames captured while that thread is too far behind are dropped.
+pub struct MovieRecorder {
+    directory: PathBuf,
+    frame_interval: Duration,
+    started_at: Instant,
+    next_capture_at: Instant,
+    frame_count: u64,
+    dropped_frames: u64,
+    sender: Option<SyncSender<RecordedFrame>>,
+    writer: Option<JoinHandle<()>>,
+}
+
+impl MovieRecorder {
+    pub fn start(directory: PathBuf, fps: f64) -> std::io::Result<Self> {
+        std::fs::create_dir_all(&directory)?;
+        let mut timestamps = BufWriter::new(File::create(directory.join(TIMESTAMPS_FILE_NAME))?);
+        writeln!(timestamps, "# timestamp format v2")?;
+
+        let (sender, receiver) = sync_channel::<RecordedFrame>(MAX_PENDING_FRAMES);
+        let writer_directory = directory.clone();
+        let writer = std::thread::Builder::new()
+            .name("movie recorder".to_string())
+            .spawn(move || {
+                for frame in receiver {
+                    let path = frame_path(&writer_directory, frame.index);
+                    if let Err(e) = frame.image.save_with_format(&path, image::ImageFormat::Png) {
+                        tracing::error!("Couldn't save recorded frame to {path:?}: {e}");
+                        continue;
+                    }
+                    if let Err(e) =
+                        writeln!(timestamps, "{:.3}", frame.timestamp.as_secs_f64() * 1000.0)
+                    {
+                        tracing::error!("Couldn't write recording timestamps: {e}");
+                    }
+                }
+                if let Err(e) = timestamps.flush() {
+                    tracing::error!("Couldn't write recording timestamps: {e}");
+                }
+            })?;
+
+        let now = Instant::now();
+        Ok(Self {
+            directory,
+            frame_interval: Duration::from_secs_f64(1.0 / fps.max(1.0)),
+            started_at: now,
+            next_capture_at: now,
+            frame_count: 0,
+            dropped_frames: 0,
+            sender: Some(sender),
+            writer: Some(writer),
+        })
+    }
+
+    pub fn directory(&self) -> &Path {
+        &self.directory
+    }
+
+    /// Returns true if enough time has passed since the last captured frame.
+    pub fn wants_frame(&self) -> bool {
+        Instant::now() >= self.next_capture_at
+    }
+
+    pub fn push_frame(&mut self, image: image::RgbaImage) {
+        let now = Instant::now();
+        let frame = RecordedFrame {
+            index: self.frame_count,
+            timestamp: now - self.started_at,
+            image,
+        };
+
+        // If we fell behind, don't try to catch up with a burst of captures;
+        // the timestamps already account for the gap.
+        let scheduled = self.next_capture_at + self.frame_interval;
+        self.next_capture_at = if scheduled < now {
+            now + self.frame_interval
+        } else {
+            scheduled
+        };
+
+        if let Some(sender) = &self.sender {
+            // A dropped frame is left out of the timestamps, so the previous one is shown longer.
+            match sender.try_send(frame) {
+                Ok(()) => self.frame_count += 1,
+                Err(TrySendError::Full(_)) => self.dropped_frames += 1,
+                Err(TrySendError::Disconnected(_)) => {}
+            }
+        }
+    }
+
+    /// Stops recording, waiting for all pending frames to be written.
+    pub fn stop(mut self) {
+        self.finish();
+    }
+
+    fn finish(&mut self) {
+        // Dropping the sender ends the writer's loop.
+        self.sender = None;
+        if let Some(writer) = self.writer.take() {
+            if writer.join().is_err() {
+                tracing::error!("Movie recorder thread panicked");
+            }
+        }
+        tracing::info!(
+            "Recorded {} frames to {:?}",
+            self.frame_count,
+            self.directory
+        );
+        if self.dropped_frames > 0 {
+            tracing::warn!(
+                "Dropped {} frames while recording, as they couldn't be written fast enough",
+                self.dropped_frames
+            );
+        }
+    }
+}
+
+impl Drop for MovieRecorder {
+    fn drop(&mut self) {
+        if self.writer.is_some() {
+            self.finish();
+        }
+    }
+}
+
+fn frame_path(directory: &Path, index: u64) -> PathBuf {
+    directory.join(format!("frame_{index:06}.png"))
+}
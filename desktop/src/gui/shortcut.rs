@@ -0,0 +1,156 @@
+use egui::{Key, KeyboardShortcut, Modifiers};
+use std::fmt;
+use std::str::FromStr;
+use winit::keyboard::{Key as WinitKey, ModifiersState, NamedKey};
+
+/// A keyboard shortcut that can be configured by the user, such as `Alt+Enter`.
+///
+/// It's stored in the preferences as its modifiers and key name joined with `+`,
+/// where `Ctrl` means Cmd on macOS.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct Shortcut(pub KeyboardShortcut);
+
+impl Shortcut {
+    pub const fn new(modifiers: Modifiers, key: Key) -> Self {
+        Self(KeyboardShortcut::new(modifiers, key))
+    }
+
+    /// Returns true if pressing `key` with `modifiers` held triggers this shortcut.
+    pub fn matches(&self, key: &WinitKey, modifiers: ModifiersState) -> bool {
+        let Some(key) = winit_to_egui_key(key) else {
+            return false;
+        };
+        let command = if cfg!(target_os = "macos") {
+            modifiers.super_key()
+        } else {
+            modifiers.control_key()
+        };
+        let expected = self.0.modifiers;
+        key == self.0.logical_key
+            && expected.alt == modifiers.alt_key()
+            && expected.shift == modifiers.shift_key()
+            && expected.command == command
+    }
+
+    /// Returns true if `key` is the key of this shortcut, whatever the modifiers held.
+    ///
+    /// This is what a key release is matched against, as the modifiers may be released first.
+    pub fn matches_key(&self, key: &WinitKey) -> bool {
+        winit_to_egui_key(key) == Some(self.0.logical_key)
+    }
+
+    /// Creates a shortcut from a key press reported by egui.
+    pub fn from_egui(key: Key, modifiers: Modifiers) -> Self {
+        Self::new(
+            Modifiers {
+                alt: modifiers.alt,
+                shift: modifiers.shift,
+                command: modifiers.command,
+                ..Modifiers::NONE
+            },
+            key,
+        )
+    }
+}
+
+impl fmt::Display for Shortcut {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        let modifiers = self.0.modifiers;
+        if modifiers.command {
+            write!(f, "Ctrl+")?;
+        }
+        if modifiers.alt {
+            write!(f, "Alt+")?;
+        }
+        if modifiers.shift {
+            write!(f, "Shift+")?;
+        }
+        write!(f, "{}", self.0.logical_key.name())
+    }
+}
+
+impl FromStr for Shortcut {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts: Vec<&str> = s.split('+').map(str::trim).collect();
+        let key = parts.pop().and_then(Key::from_name).ok_or(())?;
+        let mut modifiers = Modifiers::NONE;
+        for part in parts {
+            match part.to_ascii_lowercase().as_str() {
+                "ctrl" | "cmd" => modifiers.command = true,
+                "alt" => modifiers.alt = true,
+                "shift" => modifiers.shift = true,
+                _ => return Err(()),
+            }
+        }
+        Ok(Self::new(modifiers, key))
+    }
+}
+
+fn winit_to_egui_key(key: &WinitKey) -> Option<Key> {
+    match key {
+        WinitKey::Named(named) => named_key_to_egui(*named),
+        WinitKey::Character(character) => Key::from_name(character),
+        _ => None,
+    }
+}
+
+fn named_key_to_egui(key: NamedKey) -> Option<Key> {
+    Some(match key {
+        NamedKey::ArrowDown => Key::ArrowDown,
+        NamedKey::ArrowLeft => Key::ArrowLeft,
+        NamedKey::ArrowRight => Key::ArrowRight,
+        NamedKey::ArrowUp => Key::ArrowUp,
+        NamedKey::Escape => Key::Escape,
+        NamedKey::Tab => Key::Tab,
+        NamedKey::Backspace => Key::Backspace,
+        NamedKey::Enter => Key::Enter,
+        NamedKey::Space => Key::Space,
+        NamedKey::Insert => Key::Insert,
+        NamedKey::Delete => Key::Delete,
+        NamedKey::Home => Key::Home,
+        NamedKey::End => Key::End,
+        NamedKey::PageUp => Key::PageUp,
+        NamedKey::PageDown => Key::PageDown,
+        NamedKey::Copy => Key::Copy,
+        NamedKey::Cut => Key::Cut,
+        NamedKey::Paste => Key::Paste,
+        NamedKey::F1 => Key::F1,
+        NamedKey::F2 => Key::F2,
+        NamedKey::F3 => Key::F3,
+        NamedKey::F4 => Key::F4,
+        NamedKey::F5 => Key::F5,
+        NamedKey::F6 => Key::F6,
+        NamedKey::F7 => Key::F7,
+        NamedKey::F8 => Key::F8,
+        NamedKey::F9 => Key::F9,
+        NamedKey::F10 => Key::F10,
+        NamedKey::F11 => Key::F11,
+        NamedKey::F12 => Key::F12,
+        NamedKey::F13 => Key::F13,
+        NamedKey::F14 => Key::F14,
+        NamedKey::F15 => Key::F15,
+        NamedKey::F16 => Key::F16,
+        NamedKey::F17 => Key::F17,
+        NamedKey::F18 => Key::F18,
+        NamedKey::F19 => Key::F19,
+        NamedKey::F20 => Key::F20,
+        NamedKey::F21 => Key::F21,
+        NamedKey::F22 => Key::F22,
+        NamedKey::F23 => Key::F23,
+        NamedKey::F24 => Key::F24,
+        NamedKey::F25 => Key::F25,
+        NamedKey::F26 => Key::F26,
+        NamedKey::F27 => Key::F27,
+        NamedKey::F28 => Key::F28,
+        NamedKey::F29 => Key::F29,
+        NamedKey::F30 => Key::F30,
+        NamedKey::F31 => Key::F31,
+        NamedKey::F32 => Key::F32,
+        NamedKey::F33 => Key::F33,
+        NamedKey::F34 => Key::F34,
+        NamedKey::F35 => Key::F35,
+        _ => return None,
+    })
+}
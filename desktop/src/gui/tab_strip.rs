@@ -0,0 +1,54 @@
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
+use crate::gui::{text, TAB_STRIP_HEIGHT};
+use egui::{ScrollArea, TopBottomPanel};
+use unic_langid::LanguageIdentifier;
+
+/// The tabs of the movies open in the window, shown under the menu bar while there's more than one.
+///
+/// Clicking a tab selects it, and middle-clicking it or its close button closes it.
+#[derive(Default)]
+pub struct TabStrip {
+    names: Vec<String>,
+    active: usize,
+}
+
+impl TabStrip {
+    pub fn set_tabs(&mut self, names: Vec<String>, active: usize) {
+        self.names = names;
+        self.active = active;
+    }
+
+    /// A single movie doesn't need any tabs, so the strip is only shown once there's a choice.
+    pub fn has_tabs(&self) -> bool {
+        self.names.len() > 1
+    }
+
+    pub fn show(
+        &self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        event_loop: &RuffleEventProxy,
+    ) {
+        TopBottomPanel::top("tab_strip")
+            .exact_height(TAB_STRIP_HEIGHT as f32)
+            .show(egui_ctx, |ui| {
+                ScrollArea::horizontal().show(ui, |ui| {
+                    ui.horizontal_centered(|ui| {
+                        for (index, name) in self.names.iter().enumerate() {
+                            let tab = ui.selectable_label(index == self.active, name);
+                            if tab.clicked() {
+                                let _ = event_loop.send_event(RuffleEvent::SelectTab(index));
+                            }
+                            let close = ui
+                                .small_button("×")
+                                .on_hover_text(text(locale, "tab-close"));
+                            if tab.middle_clicked() || close.clicked() {
+                                let _ = event_loop.send_event(RuffleEvent::CloseTab(index));
+                            }
+                            ui.separator();
+                        }
+                    });
+                });
+            });
+    }
+}
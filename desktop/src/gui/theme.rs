@@ -106,8 +106,23 @@ impl ThemeController {
     }
 
     async fn set_theme_preference(&self, theme_preference: ThemePreference) {
-        let theme = match theme_preference {
-            ThemePreference::System => {
+        {
+            // Store the preference even if the system theme can't be read below,
+            // so that theme changes reported later are still applied.
+            let mut data = self.data();
+            data.theme_preference = theme_preference;
+
+            // Let winit follow the OS theme again, or force the chosen one,
+            // so that the window decorations match and `get_system_theme` reads the OS theme.
+            #[cfg(not(target_os = "linux"))]
+            if let Some(window) = data.window.upgrade() {
+                window.set_theme(theme_preference.forced_theme());
+            }
+        }
+
+        let theme = match theme_preference.forced_theme() {
+            Some(theme) => theme,
+            None => {
                 if let Ok(theme) = self
                     .get_system_theme()
                     .await
@@ -118,12 +133,8 @@ impl ThemeController {
                     return;
                 }
             }
-            ThemePreference::Light => Theme::Light,
-            ThemePreference::Dark => Theme::Dark,
         };
-        let mut data = self.data();
-        data.theme_preference = theme_preference;
-        self.set_theme_internal(data, theme);
+        self.set_theme_internal(self.data(), theme);
     }
 
     fn set_theme_internal(&self, data: MutexGuard<'_, ThemeControllerData>, theme: Theme) {
@@ -189,6 +200,15 @@ impl ThemePreference {
             ThemePreference::Dark => Some("dark"),
         }
     }
+
+    /// The theme to use regardless of the OS theme, if any.
+    pub fn forced_theme(&self) -> Option<Theme> {
+        match self {
+            ThemePreference::System => None,
+            ThemePreference::Light => Some(Theme::Light),
+            ThemePreference::Dark => Some(Theme::Dark),
+        }
+    }
 }
 
 impl FromStr for ThemePreference {
@@ -0,0 +1,145 @@
+use crate::gui::{text, TIMELINE_BAR_HEIGHT};
+use egui::{Slider, TopBottomPanel};
+use ruffle_core::focus_tracker::DisplayObject;
+use ruffle_core::Player;
+use unic_langid::LanguageIdentifier;
+
+/// The state of the main timeline of the movie, as of the last time the GUI was shown.
+#[derive(Clone, Copy)]
+struct Timeline {
+    current_frame: u16,
+    total_frames: u16,
+    frames_loaded: u16,
+    playing: bool,
+}
+
+/// A bar under the menu bar to follow and seek through the main timeline of the movie,
+/// for linear animations.
+///
+/// Once it's enabled, it's shown whenever the root of the movie is a movie clip with more than one frame,
+/// so that it stays out of the way of games and applications that only use a single frame.
+#[derive(Default)]
+pub struct TimelineBar {
+    enabled: bool,
+    visible: bool,
+    timeline: Option<Timeline>,
+    /// Whether the timeline was playing when the slider started being dragged,
+    /// so that it's resumed once the slider is let go of.
+    resume_after_seek: Option<bool>,
+}
+
+impl TimelineBar {
+    pub fn set_enabled(&mut self, enabled: bool) {
+        self.enabled = enabled;
+    }
+
+    pub fn is_visible(&self) -> bool {
+        self.visible
+    }
+
+    /// Reads the state of the main timeline of the player, which may show or hide the bar.
+    pub fn update(&mut self, player: Option<&mut Player>) {
+        self.timeline = player.and_then(|player| {
+            player.mutate_with_update_context(|context| {
+                let Some(DisplayObject::MovieClip(root)) = context.stage.root_clip() else {
+                    return None;
+                };
+                Some(Timeline {
+                    current_frame: root.current_frame(),
+                    total_frames: root.total_frames(),
+                    frames_loaded: root.frames_loaded().clamp(0, u16::MAX as i32) as u16,
+                    playing: root.playing(),
+                })
+            })
+        });
+        self.visible = self.enabled && self.timeline.is_some_and(|t| t.total_frames > 1);
+        if self.timeline.is_none() {
+            self.resume_after_seek = None;
+        }
+    }
+
+    pub fn show(
+        &mut self,
+        locale: &LanguageIdentifier,
+        egui_ctx: &egui::Context,
+        player: Option<&mut Player>,
+    ) {
+        let (Some(timeline), Some(player)) = (self.timeline, player) else {
+            return;
+        };
+        TopBottomPanel::top("timeline_bar")
+            .exact_height(TIMELINE_BAR_HEIGHT as f32)
+            .show(egui_ctx, |ui| {
+                ui.horizontal_centered(|ui| {
+                    if timeline.playing && player.is_playing() {
+                        if ui
+                            .button("⏸")
+                            .on_hover_text(text(locale, "timeline-pause"))
+                            .clicked()
+                        {
+                            player.mutate_with_update_context(|context| {
+                                if let Some(DisplayObject::MovieClip(root)) =
+                                    context.stage.root_clip()
+                                {
+                                    root.stop(context);
+                                }
+                            });
+                            ui.ctx().request_repaint();
+                        }
+                    } else if ui
+                        .button("▶")
+                        .on_hover_text(text(locale, "timeline-play"))
+                        .clicked()
+                    {
+                        player.mutate_with_update_context(|context| {
+                            if let Some(DisplayObject::MovieClip(root)) = context.stage.root_clip()
+                            {
+                                root.play();
+                            }
+                        });
+                        // The timeline doesn't move while the whole player is paused.
+                        player.set_is_playing(true);
+                        ui.ctx().request_repaint();
+                    }
+
+                    ui.label(format!(
+                        "{} / {}",
+                        timeline.current_frame, timeline.total_frames
+                    ));
+
+                    let mut frame = timeline.current_frame;
+                    ui.spacing_mut().slider_width = ui.available_width();
+                    let mut response = ui.add(
+                        Slider::new(&mut frame, 1..=timeline.total_frames.max(1)).show_value(false),
+                    );
+                    if timeline.frames_loaded < timeline.total_frames {
+                        response = response.on_hover_text(text(locale, "timeline-loading"));
+                    }
+                    if response.changed() || response.drag_stopped() {
+                        let resume = *self.resume_after_seek.get_or_insert(timeline.playing);
+                        let dragging = response.dragged() && !response.drag_stopped();
+                        if !dragging {
+                            self.resume_after_seek = None;
+                        }
+                        seek(player, frame, dragging || !resume);
+                        ui.ctx().request_repaint();
+                    }
+                });
+            });
+    }
+}
+
+/// Moves the main timeline to the given frame, stopping there or playing on from it.
+///
+/// Frames that haven't been loaded yet can't be shown, so it stops short at the last loaded one.
+fn seek(player: &mut Player, frame: u16, stop: bool) {
+    player.mutate_with_update_context(|context| {
+        let Some(DisplayObject::MovieClip(root)) = context.stage.root_clip() else {
+            return;
+        };
+        let frames_loaded = root
+            .frames_loaded()
+            .clamp(1, root.total_frames().max(1) as i32);
+        root.goto_frame(context, frame.min(frames_loaded as u16), stop);
+    });
+}
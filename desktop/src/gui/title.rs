@@ -0,0 +1,225 @@
+use crate::gui::dialogs::dublin_core_title;
+use crate::gui::{text, text_with_args};
+use fluent_templates::fluent_bundle::FluentValue;
+use std::collections::HashMap;
+use std::time::{Duration, Instant};
+use unic_langid::LanguageIdentifier;
+use winit::window::Window;
+
+/// The longest movie name shown in the title, in characters, before it's cut short.
+const MAX_NAME_LENGTH: usize = 64;
+
+/// How often the frame rate shown in the title is measured.
+const FPS_INTERVAL: Duration = Duration::from_secs(1);
+
+/// Everything that's shown in the title, which is only formatted again when it changes.
+#[derive(Clone, PartialEq, Default)]
+struct TitleState {
+    movie_name: Option<String>,
+    paused: bool,
+    /// The frame the movie is paused at, if it's known.
+    paused_frame: Option<u16>,
+    fps: Option<u32>,
+    muted: bool,
+}
+
+/// Keeps the window title in sync with the open movie, such as `Ruffle - game.swf (Paused)`.
+#[derive(Default)]
+pub struct WindowTitle {
+    state: TitleState,
+    /// The state the window title was last set from.
+    shown: Option<TitleState>,
+    show_fps: bool,
+    frames_rendered: u32,
+    measuring_since: Option<Instant>,
+}
+
+impl WindowTitle {
+    pub fn new(show_fps: bool) -> Self {
+        Self {
+            show_fps,
+            ..Default::default()
+        }
+    }
+
+    /// Sets the name of the open movie, or `None` once it's closed.
+    pub fn set_movie_name(&mut self, name: Option<&str>) {
+        self.state = TitleState {
+            movie_name: name.map(truncate_name),
+            ..Default::default()
+        };
+        self.measuring_since = None;
+    }
+
+    pub fn set_show_fps(&mut self, show_fps: bool) {
+        self.show_fps = show_fps;
+        self.state.fps = None;
+        self.measuring_since = None;
+    }
+
+    /// Updates the title for a newly rendered frame, given whether the movie is paused,
+    /// the frame of its timeline it's at and whether it's muted.
+    ///
+    /// The frame rate is measured from how often this is called.
+    pub fn update(
+        &mut self,
+        window: &Window,
+        locale: &LanguageIdentifier,
+        paused: bool,
+        frame: Option<u16>,
+        muted: bool,
+    ) {
+        self.state.paused = paused;
+        self.state.muted = muted;
+        self.state.paused_frame = frame.filter(|_| paused);
+        if self.show_fps && self.state.movie_name.is_some() && !paused {
+            self.frames_rendered += 1;
+            let measuring_since = *self.measuring_since.get_or_insert_with(Instant::now);
+            let elapsed = measuring_since.elapsed();
+            if elapsed >= FPS_INTERVAL {
+                let fps = self.frames_rendered as f64 / elapsed.as_secs_f64();
+                self.state.fps = Some(fps.round() as u32);
+                self.frames_rendered = 0;
+                self.measuring_since = Some(Instant::now());
+            }
+        } else {
+            self.state.fps = None;
+            self.frames_rendered = 0;
+            self.measuring_since = None;
+        }
+        self.apply(window, locale);
+    }
+
+    /// Sets the window title, if anything in it has changed.
+    pub fn apply(&mut self, window: &Window, locale: &LanguageIdentifier) {
+        if self.shown.as_ref() == Some(&self.state) {
+            return;
+        }
+        window.set_title(&self.format(locale));
+        self.shown = Some(self.state.clone());
+    }
+
+    fn format(&self, locale: &LanguageIdentifier) -> String {
+        let Some(movie_name) = &self.state.movie_name else {
+            return "Ruffle".to_string();
+        };
+        let mut title = format!("Ruffle - {movie_name}");
+        if let Some(frame) = self.state.paused_frame {
+            title.push(' ');
+            title.push_str(&text_with_args(
+                locale,
+                "window-title-paused-at-frame",
+                &HashMap::from([("frame".into(), FluentValue::from(frame))]),
+            ));
+        } else if self.state.paused {
+            title.push(' ');
+            title.push_str(&text(locale, "window-title-paused"));
+        }
+        if self.state.muted {
+            title.push(' ');
+            title.push_str(&text(locale, "window-title-muted"));
+        }
+        if let Some(fps) = self.state.fps {
+            title.push_str(" - ");
+            title.push_str(&text_with_args(
+                locale,
+                "window-title-fps",
+                &HashMap::from([("fps".into(), FluentValue::from(fps))]),
+            ));
+        }
+        title
+    }
+}
+
+/// Returns the title of a movie from its XMP metadata, i.e. the contents of `<dc:title>`.
+///
+/// The title may be given directly or as a list of alternatives in different languages,
+/// in which case the default one is used. It's read the same way as in the metadata dialog.
+pub fn metadata_title(metadata: &str) -> Option<String> {
+    dublin_core_title(metadata)
+}
+
+/// Cuts a long movie name short with an ellipsis, so that the title stays readable.
+fn truncate_name(name: &str) -> String {
+    if name.chars().count() <= MAX_NAME_LENGTH {
+        return name.to_string();
+    }
+    let mut truncated: String = name.chars().take(MAX_NAME_LENGTH - 1).collect();
+    truncated.push('…');
+    truncated
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn short_name_is_kept() {
+        assert_eq!(truncate_name("game.swf"), "game.swf");
+        let name = "a".repeat(MAX_NAME_LENGTH);
+        assert_eq!(truncate_name(&name), name);
+    }
+
+    #[test]
+    fn long_name_is_truncated() {
+        let truncated = truncate_name(&"a".repeat(MAX_NAME_LENGTH + 1));
+        assert_eq!(truncated.chars().count(), MAX_NAME_LENGTH);
+        assert!(truncated.ends_with('…'));
+    }
+
+    #[test]
+    fn multibyte_name_is_truncated_by_characters() {
+        let name = "ゲーム".repeat(MAX_NAME_LENGTH);
+        let truncated = truncate_name(&name);
+        assert_eq!(truncated.chars().count(), MAX_NAME_LENGTH);
+        assert!(name.starts_with(truncated.trim_end_matches('…')));
+        assert!(truncated.ends_with('…'));
+
+        let name = "ゲーム".repeat(MAX_NAME_LENGTH / 3);
+        assert_eq!(truncate_name(&name), name);
+    }
+
+    #[test]
+    fn title_of_movie() {
+        let locale: LanguageIdentifier = "en-US".parse().unwrap();
+        let mut title = WindowTitle::default();
+        assert_eq!(title.format(&locale), "Ruffle");
+        title.set_movie_name(Some("game.swf"));
+        assert_eq!(title.format(&locale), "Ruffle - game.swf");
+        title.set_movie_name(None);
+        assert_eq!(title.format(&locale), "Ruffle");
+    }
+
+    #[test]
+    fn plain_title() {
+        assert_eq!(
+            metadata_title(
+                "<rdf:Description><dc:title>Game &amp; Watch</dc:title></rdf:Description>"
+            ),
+            Some("Game & Watch".to_string())
+        );
+    }
+
+    #[test]
+    fn default_alternative_title() {
+        let metadata = r#"<dc:title><rdf:Alt><rdf:li xml:lang="x-default">English</rdf:li><rdf:li xml:lang="fr">Français</rdf:li></rdf:Alt></dc:title>"#;
+        assert_eq!(metadata_title(metadata), Some("English".to_string()));
+        let metadata = r#"<dc:title><rdf:Alt><rdf:li xml:lang="fr">Français</rdf:li><rdf:li xml:lang="x-default">English</rdf:li></rdf:Alt></dc:title>"#;
+        assert_eq!(metadata_title(metadata), Some("English".to_string()));
+    }
+
+    #[test]
+    fn no_title() {
+        assert_eq!(metadata_title(""), None);
+        assert_eq!(
+            metadata_title("<rdf:Description><dc:creator>Someone</dc:creator></rdf:Description>"),
+            None
+        );
+        assert_eq!(
+            metadata_title("<dc:title><rdf:Alt></rdf:Alt></dc:title>"),
+            None
+        );
+        assert_eq!(metadata_title("<dc:title>   </dc:title>"), None);
+        assert_eq!(metadata_title("<dc:title>Unclosed"), None);
+    }
+}
@@ -0,0 +1,281 @@
+use crate::gui::controller::create_msaa_view;
+use anyhow::anyhow;
+use egui::{
+    Context, DeferredViewportUiCallback, ViewportBuilder, ViewportCommand, ViewportEvent,
+    ViewportId, ViewportOutput,
+};
+use ruffle_render_wgpu::descriptors::Descriptors;
+use std::mem;
+use std::sync::Arc;
+use std::time::{Duration, Instant};
+use winit::dpi::PhysicalSize;
+use winit::event::WindowEvent;
+use winit::event_loop::ActiveEventLoop;
+use winit::window::Window;
+
+/// A native window showing a deferred egui viewport, such as a panel popped out of the main window.
+///
+/// Child viewports share the egui context and renderer of the main window,
+/// but have a surface and input state of their own.
+/// Their input only ever goes to egui, never to the movie.
+pub struct ChildViewport {
+    // The surface must be dropped before the window it belongs to.
+    surface: wgpu::Surface<'static>,
+    id: ViewportId,
+    egui_winit: egui_winit::State,
+    surface_format: wgpu::TextureFormat,
+    sample_count: u32,
+    msaa_view: Option<wgpu::TextureView>,
+    size: PhysicalSize<u32>,
+    viewport_ui_cb: Arc<DeferredViewportUiCallback>,
+    /// Whether the user asked to close the window, which is passed on to egui with the next frame.
+    close_requested: bool,
+    last_update: Instant,
+    repaint_after: Duration,
+    window: Arc<Window>,
+}
+
+impl ChildViewport {
+    /// Creates the window of a viewport that was just shown for the first time.
+    ///
+    /// Viewports without a UI callback are immediate ones, which aren't supported.
+    #[expect(clippy::too_many_arguments)]
+    pub fn new(
+        event_loop: &ActiveEventLoop,
+        egui_ctx: &Context,
+        id: ViewportId,
+        builder: &ViewportBuilder,
+        viewport_ui_cb: Option<Arc<DeferredViewportUiCallback>>,
+        descriptors: &Descriptors,
+        surface_format: wgpu::TextureFormat,
+        sample_count: u32,
+    ) -> anyhow::Result<Self> {
+        let viewport_ui_cb =
+            viewport_ui_cb.ok_or_else(|| anyhow!("Immediate viewports aren't supported"))?;
+        let window = Arc::new(egui_winit::create_window(egui_ctx, event_loop, builder)?);
+        let surface = descriptors.wgpu_instance.create_surface(window.clone())?;
+        if !surface
+            .get_capabilities(&descriptors.adapter)
+            .formats
+            .contains(&surface_format)
+        {
+            return Err(anyhow!(
+                "The surface doesn't support the format of the main window ({surface_format:?})"
+            ));
+        }
+        let mut egui_winit = egui_winit::State::new(
+            egui_ctx.clone(),
+            id,
+            window.as_ref(),
+            Some(window.scale_factor() as f32),
+            None,
+            None,
+        );
+        egui_winit.set_max_texture_side(descriptors.limits.max_texture_dimension_2d as usize);
+
+        let mut this = Self {
+            surface,
+            id,
+            egui_winit,
+            surface_format,
+            sample_count,
+            msaa_view: None,
+            size: window.inner_size(),
+            viewport_ui_cb,
+            close_requested: false,
+            last_update: Instant::now(),
+            repaint_after: Duration::ZERO,
+            window,
+        };
+        this.resize(descriptors, this.size);
+        this.window.request_redraw();
+        Ok(this)
+    }
+
+    pub fn window(&self) -> &Arc<Window> {
+        &self.window
+    }
+
+    /// Takes the UI callback and requests of a newer frame of the parent viewport.
+    pub fn update(&mut self, output: ViewportOutput) {
+        if let Some(viewport_ui_cb) = output.viewport_ui_cb {
+            self.viewport_ui_cb = viewport_ui_cb;
+        }
+        for command in output.commands {
+            match command {
+                ViewportCommand::Title(title) => self.window.set_title(&title),
+                ViewportCommand::Focus => self.window.focus_window(),
+                ViewportCommand::Visible(visible) => self.window.set_visible(visible),
+                ViewportCommand::Minimized(minimized) => self.window.set_minimized(minimized),
+                command => tracing::debug!("Ignoring unsupported viewport command {command:?}"),
+            }
+        }
+        self.schedule_repaint(output.repaint_delay);
+    }
+
+    fn schedule_repaint(&mut self, repaint_delay: Duration) {
+        let now = Instant::now();
+        let Some(next_repaint) = now.checked_add(repaint_delay) else {
+            return;
+        };
+        if self
+            .next_repaint_time()
+            .is_none_or(|current| next_repaint < current)
+        {
+            self.last_update = now;
+            self.repaint_after = repaint_delay;
+        }
+    }
+
+    /// Passes an event of the window on to egui.
+    pub fn handle_event(&mut self, descriptors: &Descriptors, event: &WindowEvent) {
+        match event {
+            WindowEvent::Resized(size) => self.resize(descriptors, *size),
+            WindowEvent::CloseRequested => {
+                // egui decides whether to actually close it, by no longer showing the viewport.
+                self.close_requested = true;
+                self.window.request_redraw();
+                return;
+            }
+            _ => {}
+        }
+        let response = self.egui_winit.on_window_event(&self.window, event);
+        if response.repaint {
+            self.window.request_redraw();
+        }
+    }
+
+    fn resize(&mut self, descriptors: &Descriptors, size: PhysicalSize<u32>) {
+        // A minimized window is 0×0, which can't be configured.
+        if size.width == 0 || size.height == 0 {
+            return;
+        }
+        self.size = size;
+        self.surface.configure(
+            &descriptors.device,
+            &wgpu::SurfaceConfiguration {
+                usage: wgpu::TextureUsages::RENDER_ATTACHMENT,
+                format: self.surface_format,
+                width: size.width,
+                height: size.height,
+                present_mode: Default::default(),
+                desired_maximum_frame_latency: 2,
+                alpha_mode: Default::default(),
+                view_formats: Default::default(),
+            },
+        );
+        self.msaa_view = create_msaa_view(
+            &descriptors.device,
+            self.surface_format,
+            size,
+            self.sample_count,
+        );
+    }
+
+    pub fn needs_render(&self) -> bool {
+        Instant::now().duration_since(self.last_update) >= self.repaint_after
+    }
+
+    pub fn next_repaint_time(&self) -> Option<Instant> {
+        self.last_update.checked_add(self.repaint_after)
+    }
+
+    /// Runs the UI of the viewport and draws it to its window.
+    ///
+    /// Returns true if the UI asked for the main window to be repainted right away.
+    pub fn render(
+        &mut self,
+        descriptors: &Descriptors,
+        egui_renderer: &mut egui_wgpu::Renderer,
+    ) -> bool {
+        let surface_texture = match self.surface.get_current_texture() {
+            Ok(surface_texture) => surface_texture,
+            Err(e) => {
+                tracing::warn!("Viewport surface became unavailable: {e:?}, reconfiguring");
+                self.resize(descriptors, self.size);
+                self.window.request_redraw();
+                return false;
+            }
+        };
+
+        let mut raw_input = self.egui_winit.take_egui_input(&self.window);
+        if mem::take(&mut self.close_requested) {
+            raw_input
+                .viewports
+                .entry(self.id)
+                .or_default()
+                .events
+                .push(ViewportEvent::Close);
+        }
+        let viewport_ui_cb = self.viewport_ui_cb.clone();
+        let egui_ctx = self.egui_winit.egui_ctx().clone();
+        let full_output = egui_ctx.run(raw_input, |context| viewport_ui_cb(context));
+        self.last_update = Instant::now();
+        self.repaint_after = full_output
+            .viewport_output
+            .get(&self.id)
+            .map_or(Duration::MAX, |output| output.repaint_delay);
+        let repaint_root = full_output
+            .viewport_output
+            .get(&ViewportId::ROOT)
+            .is_some_and(|output| output.repaint_delay.is_zero());
+        self.egui_winit
+            .handle_platform_output(&self.window, full_output.platform_output);
+
+        let clipped_primitives =
+            egui_ctx.tessellate(full_output.shapes, full_output.pixels_per_point);
+        let screen_descriptor = egui_wgpu::ScreenDescriptor {
+            size_in_pixels: [self.size.width, self.size.height],
+            pixels_per_point: full_output.pixels_per_point,
+        };
+        let mut encoder =
+            descriptors
+                .device
+                .create_command_encoder(&wgpu::CommandEncoderDescriptor {
+                    label: Some("egui viewport encoder"),
+                });
+        for (id, image_delta) in &full_output.textures_delta.set {
+            egui_renderer.update_texture(&descriptors.device, &descriptors.queue, *id, image_delta);
+        }
+        let mut command_buffers = egui_renderer.update_buffers(
+            &descriptors.device,
+            &descriptors.queue,
+            &mut encoder,
+            &clipped_primitives,
+            &screen_descriptor,
+        );
+
+        {
+            let surface_view = surface_texture.texture.create_view(&Default::default());
+            let (view, resolve_target, store) = match &self.msaa_view {
+                Some(msaa_view) => (msaa_view, Some(&surface_view), wgpu::StoreOp::Discard),
+                None => (&surface_view, None, wgpu::StoreOp::Store),
+            };
+            let mut render_pass = encoder
+                .begin_render_pass(&wgpu::RenderPassDescriptor {
+                    color_attachments: &[Some(wgpu::RenderPassColorAttachment {
+                        view,
+                        resolve_target,
+                        ops: wgpu::Operations {
+                            load: wgpu::LoadOp::Clear(wgpu::Color::BLACK),
+                            store,
+                        },
+                    })],
+                    label: Some("egui viewport render pass"),
+                    ..Default::default()
+                })
+                .forget_lifetime();
+            egui_renderer.render(&mut render_pass, &clipped_primitives, &screen_descriptor);
+        }
+
+        for id in &full_output.textures_delta.free {
+            egui_renderer.free_texture(id);
+        }
+
+        command_buffers.push(encoder.finish());
+        descriptors.queue.submit(command_buffers);
+        self.window.pre_present_notify();
+        surface_texture.present();
+        repaint_root
+    }
+}
@@ -48,6 +48,12 @@ impl PathOrUrlField {
         value.lock().expect("Non-poisoned value")
     }
 
+    /// Replaces the text of the field, which is validated the next time it's shown.
+    pub fn set_value(&mut self, value: &str) {
+        *Self::lock_value(&self.value) = value.to_string();
+        self.result = None;
+    }
+
     pub fn ui(&mut self, locale: &LanguageIdentifier, ui: &mut Ui) -> &mut Self {
         ui.with_layout(egui::Layout::right_to_left(egui::Align::Center), |ui| {
             if ui.button(text(locale, "browse")).clicked() {
@@ -2,9 +2,9 @@ use crate::backends::{
     DesktopExternalInterfaceProvider, DesktopFSCommandProvider, DesktopNavigatorInterface,
     DesktopUiBackend,
 };
-use crate::cli::FilesystemAccessMode;
 use crate::cli::GameModePreference;
-use crate::custom_event::RuffleEvent;
+use crate::cli::{ContextMenuMode, FilesystemAccessMode};
+use crate::custom_event::{RuffleEvent, RuffleEventProxy};
 use crate::gui::{FilePicker, MovieView};
 use crate::preferences::GlobalPreferences;
 use crate::{CALLSTACK, RENDER_INFO, SWF_INFO};
@@ -29,12 +29,14 @@ use ruffle_render_wgpu::descriptors::Descriptors;
 use std::borrow::Cow;
 use std::collections::{HashMap, HashSet};
 use std::fmt::Debug;
+use std::mem;
 use std::path::PathBuf;
 use std::rc::Rc;
+use std::str::FromStr;
+use std::sync::atomic::{AtomicU64, Ordering};
 use std::sync::{Arc, Mutex, MutexGuard};
 use std::time::Duration;
 use url::Url;
-use winit::event_loop::EventLoopProxy;
 use winit::window::Window;
 
 /// Options used when creating a Player (& passed through to a PlayerBuilder).
@@ -46,11 +48,15 @@ pub struct LaunchOptions {
     pub socket_allowed: HashSet<String>,
     pub tcp_connections: Option<SocketMode>,
     pub fullscreen: bool,
+    /// Whether the movie is rendered without its background, for a transparent window.
+    pub transparent: bool,
     pub save_directory: PathBuf,
     pub cache_directory: PathBuf,
     pub filesystem_access_mode: FilesystemAccessMode,
     pub gamepad_button_mapping: HashMap<GamepadButton, KeyCode>,
     pub avm2_optimizer_enabled: bool,
+    /// When right clicks open the context menu, overriding the preference for this movie.
+    pub context_menu_mode: Option<ContextMenuMode>,
 }
 
 impl From<&GlobalPreferences> for LaunchOptions {
@@ -83,8 +89,8 @@ impl From<&GlobalPreferences> for LaunchOptions {
                 spoof_url: value.cli.spoof_url.clone(),
                 referer: value.cli.referer.clone(),
                 cookie: value.cli.cookie.clone(),
-                player_version: value.cli.player_version,
-                player_runtime: value.cli.player_runtime,
+                player_version: value.player_version(),
+                player_runtime: value.player_runtime(),
                 frame_rate: value.cli.frame_rate,
                 dummy_external_interface: if value.cli.dummy_external_interface {
                     Some(true)
@@ -94,6 +100,7 @@ impl From<&GlobalPreferences> for LaunchOptions {
             },
             proxy: value.cli.proxy.clone(),
             fullscreen: value.cli.fullscreen,
+            transparent: value.cli.transparent,
             save_directory: value.cli.save_directory.clone(),
             cache_directory: value.cli.cache_directory.clone(),
             filesystem_access_mode: value.cli.filesystem_access_mode,
@@ -101,12 +108,13 @@ impl From<&GlobalPreferences> for LaunchOptions {
             tcp_connections: value.cli.tcp_connections,
             gamepad_button_mapping: HashMap::from_iter(value.cli.gamepad_button.iter().cloned()),
             avm2_optimizer_enabled: !value.cli.no_avm2_optimizer,
+            context_menu_mode: value.cli.context_menu,
         }
     }
 }
 
 #[derive(Clone)]
-struct WinitWaker(EventLoopProxy<RuffleEvent>);
+struct WinitWaker(RuffleEventProxy);
 
 impl PollRequester for WinitWaker {
     fn request_poll(&self) {
@@ -116,11 +124,30 @@ impl PollRequester for WinitWaker {
     }
 }
 
+/// Identifies the tab of a movie, however the tabs around it are opened, closed or moved.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct TabId(u64);
+
+impl TabId {
+    fn next() -> Self {
+        static NEXT_ID: AtomicU64 = AtomicU64::new(0);
+        Self(NEXT_ID.fetch_add(1, Ordering::Relaxed))
+    }
+}
+
 /// Represents a current Player and any associated state with that player,
 /// which may be lost when this Player is closed (dropped)
 struct ActivePlayer {
+    id: TabId,
     player: Arc<Mutex<Player>>,
     executor: Arc<AsyncExecutor<WinitWaker>>,
+    /// The readable name of the movie, such as its file name.
+    movie_name: String,
+    /// The URL and options the movie was opened with, to reopen it with.
+    movie_url: Url,
+    options: LaunchOptions,
+    /// Whether the movie was paused because another tab was selected, and should be resumed with it.
+    paused_in_background: bool,
 
     #[cfg(target_os = "linux")]
     _gamemode_session: crate::dbus::GameModeSession,
@@ -130,7 +157,7 @@ impl ActivePlayer {
     #[expect(clippy::too_many_arguments)]
     pub fn new(
         opt: &LaunchOptions,
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
         movie_url: &Url,
         window: Arc<Window>,
         descriptors: Arc<Descriptors>,
@@ -139,7 +166,12 @@ impl ActivePlayer {
         preferences: GlobalPreferences,
         file_picker: FilePicker,
     ) -> Self {
+        let id = TabId::next();
+        let event_loop = event_loop.for_tab(id);
         let mut builder = PlayerBuilder::new();
+        let requested_url = movie_url.clone();
+        let movie_options = preferences.movie_options(movie_url);
+        let requested_options = opt.clone();
 
         match CpalAudioBackend::new(preferences.output_device_name().as_deref()) {
             Ok(audio) => {
@@ -202,11 +234,13 @@ impl ActivePlayer {
                     socket_allowed: opt.socket_allowed.clone(),
                     tcp_connections: opt.tcp_connections,
                     fullscreen: opt.fullscreen,
+                    transparent: opt.transparent,
                     save_directory: opt.save_directory.clone(),
                     cache_directory: opt.cache_directory.clone(),
                     filesystem_access_mode: opt.filesystem_access_mode,
                     gamepad_button_mapping: opt.gamepad_button_mapping.clone(),
                     avm2_optimizer_enabled: opt.avm2_optimizer_enabled,
+                    context_menu_mode: opt.context_menu_mode,
                 })
             }
         };
@@ -230,6 +264,7 @@ impl ActivePlayer {
             DesktopNavigatorInterface::new(
                 preferences.clone(),
                 event_loop.clone(),
+                id,
                 movie_url.to_file_path().ok(),
                 opt.filesystem_access_mode,
             ),
@@ -324,28 +359,40 @@ impl ActivePlayer {
             .with_autoplay(true)
             .with_letterbox(opt.player.letterbox.unwrap_or(Letterbox::On))
             .with_max_execution_duration(opt.player.max_execution_duration.unwrap_or(Duration::MAX))
-            .with_quality(opt.player.quality.unwrap_or(StageQuality::High))
+            .with_quality(
+                opt.player
+                    .quality
+                    .or(movie_options.as_ref().and_then(|options| options.quality))
+                    .unwrap_or(StageQuality::High),
+            )
             .with_align(
-                opt.player.align.unwrap_or_default(),
+                opt.player
+                    .align
+                    .or(movie_options.as_ref().and_then(|options| options.align))
+                    .unwrap_or_default(),
                 opt.player.force_align.unwrap_or_default(),
             )
             .with_scale_mode(
-                opt.player.scale.unwrap_or_default(),
+                opt.player
+                    .scale
+                    .or(movie_options
+                        .as_ref()
+                        .and_then(|options| options.scale_mode))
+                    .unwrap_or_default(),
                 opt.player.force_scale.unwrap_or_default(),
             )
             .with_fullscreen(opt.fullscreen)
             .with_load_behavior(opt.player.load_behavior.unwrap_or(LoadBehavior::Streaming))
             .with_spoofed_url(opt.player.spoof_url.clone().map(|url| url.to_string()))
             .with_page_url(opt.player.spoof_url.clone().map(|url| url.to_string()))
-            .with_player_version(opt.player.player_version)
+            .with_player_version(opt.player.player_version.map(|version| version.major))
+            .with_full_player_version(opt.player.player_version.map(|version| version.to_string()))
             .with_player_runtime(opt.player.player_runtime.unwrap_or_default())
             .with_frame_rate(opt.player.frame_rate)
             .with_avm2_optimizer_enabled(opt.avm2_optimizer_enabled);
         let player = builder.build();
 
-        window.set_title(&format!("Ruffle - {readable_name}"));
-
-        SWF_INFO.with(|i| *i.borrow_mut() = Some(readable_name));
+        SWF_INFO.with(|i| *i.borrow_mut() = Some(readable_name.clone()));
 
         let on_metadata = move |swf_header: &ruffle_core::swf::HeaderExt| {
             let _ = event_loop.send_event(RuffleEvent::OnMetadata(swf_header.clone()));
@@ -356,6 +403,9 @@ impl ActivePlayer {
             CALLSTACK.with(|callstack| {
                 *callstack.borrow_mut() = Some(player_lock.callstack());
             });
+            if opt.transparent {
+                player_lock.set_window_mode("transparent");
+            }
             player_lock.fetch_root_movie(
                 movie_url.to_string(),
                 opt.player.parameters.to_owned(),
@@ -419,29 +469,78 @@ impl ActivePlayer {
         }
 
         Self {
+            id,
             player,
             executor,
+            movie_name: readable_name,
+            movie_url: requested_url,
+            options: requested_options,
+            paused_in_background: false,
             #[cfg(target_os = "linux")]
             _gamemode_session: crate::dbus::GameModeSession::new(gamemode_enable),
         }
     }
 }
 
-/// Owner of a Ruffle Player (via ActivePlayer),
-/// responsible for either creating, destroying or communicating with that player.
+/// What happens to the movie while the window isn't focused.
+#[derive(Default, Clone, Copy, PartialEq, Eq, Debug)]
+pub enum UnfocusedBehavior {
+    /// The movie keeps playing as usual.
+    #[default]
+    Continue,
+
+    /// The movie and its audio are paused until the window is focused again.
+    Pause,
+
+    /// The movie keeps playing, but its audio is muted.
+    Mute,
+}
+
+impl UnfocusedBehavior {
+    pub fn as_str(&self) -> Option<&'static str> {
+        match self {
+            UnfocusedBehavior::Continue => None,
+            UnfocusedBehavior::Pause => Some("pause"),
+            UnfocusedBehavior::Mute => Some("mute"),
+        }
+    }
+}
+
+impl FromStr for UnfocusedBehavior {
+    type Err = ();
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        match s {
+            "pause" => Ok(UnfocusedBehavior::Pause),
+            "mute" => Ok(UnfocusedBehavior::Mute),
+            _ => Err(()),
+        }
+    }
+}
+
+/// Owner of the Ruffle Players open in a window (via ActivePlayer), one per tab,
+/// responsible for either creating, destroying or communicating with those players.
+///
+/// Only the player of the selected tab is shown; the others are paused in the background.
 pub struct PlayerController {
-    player: Option<ActivePlayer>,
-    event_loop: EventLoopProxy<RuffleEvent>,
+    tabs: Vec<ActivePlayer>,
+    /// The index of the selected tab in `tabs`, if there are any.
+    active_tab: usize,
+    event_loop: RuffleEventProxy,
     window: Arc<Window>,
     descriptors: Arc<Descriptors>,
     font_database: Rc<fontdb::Database>,
     preferences: GlobalPreferences,
     file_picker: FilePicker,
+    /// Whether the movie was paused because the window lost focus, and should be resumed with it.
+    paused_while_unfocused: bool,
+    /// The volume to restore once the window is focused again, if it was muted when focus was lost.
+    volume_while_focused: Option<f32>,
 }
 
 impl PlayerController {
     pub fn new(
-        event_loop: EventLoopProxy<RuffleEvent>,
+        event_loop: RuffleEventProxy,
         window: Arc<Window>,
         descriptors: Arc<Descriptors>,
         font_database: fontdb::Database,
@@ -449,18 +548,23 @@ impl PlayerController {
         file_picker: FilePicker,
     ) -> Self {
         Self {
-            player: None,
+            tabs: Vec::new(),
+            active_tab: 0,
             event_loop,
             window,
             descriptors,
             font_database: Rc::new(font_database),
             preferences,
             file_picker,
+            paused_while_unfocused: false,
+            volume_while_focused: None,
         }
     }
 
+    /// Opens a movie in a new tab, which becomes the selected one.
     pub fn create(&mut self, opt: &LaunchOptions, movie_url: &Url, movie_view: MovieView) {
-        self.player = Some(ActivePlayer::new(
+        self.send_active_tab_to_background();
+        self.tabs.push(ActivePlayer::new(
             opt,
             self.event_loop.clone(),
             movie_url,
@@ -471,14 +575,197 @@ impl PlayerController {
             self.preferences.clone(),
             self.file_picker.clone(),
         ));
+        self.active_tab = self.tabs.len() - 1;
+        self.paused_while_unfocused = false;
+        self.volume_while_focused = None;
     }
 
+    /// Opens the movie in the selected tab again with the same options, keeping the tab in its place.
+    pub fn reload(&mut self, movie_view: MovieView) {
+        if self.active_tab >= self.tabs.len() {
+            return;
+        }
+        // The old player goes first, so that it stops playing sound before the new one starts.
+        let old = self.tabs.remove(self.active_tab);
+        let (movie_url, opt) = (old.movie_url.clone(), old.options.clone());
+        drop(old);
+        self.tabs.insert(
+            self.active_tab,
+            ActivePlayer::new(
+                &opt,
+                self.event_loop.clone(),
+                &movie_url,
+                self.window.clone(),
+                self.descriptors.clone(),
+                movie_view,
+                self.font_database.clone(),
+                self.preferences.clone(),
+                self.file_picker.clone(),
+            ),
+        );
+        self.paused_while_unfocused = false;
+        self.volume_while_focused = None;
+    }
+
+    /// Closes the selected tab, selecting the one after it, or the one before if it was the last.
     pub fn destroy(&mut self) {
-        self.player = None;
+        self.close_tab(self.active_tab);
+    }
+
+    /// Closes the tab at the given index, destroying its player.
+    pub fn close_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() {
+            return;
+        }
+        let was_active = index == self.active_tab;
+        self.tabs.remove(index);
+        if index < self.active_tab || self.active_tab >= self.tabs.len() {
+            self.active_tab = self.active_tab.saturating_sub(1);
+        }
+        if was_active {
+            self.paused_while_unfocused = false;
+            self.volume_while_focused = None;
+            self.bring_active_tab_to_foreground();
+        }
+    }
+
+    /// Selects the tab at the given index, pausing the one that was selected before.
+    pub fn select_tab(&mut self, index: usize) {
+        if index >= self.tabs.len() || index == self.active_tab {
+            return;
+        }
+        self.send_active_tab_to_background();
+        self.active_tab = index;
+        self.paused_while_unfocused = false;
+        self.volume_while_focused = None;
+        self.bring_active_tab_to_foreground();
+    }
+
+    /// The index of the selected tab.
+    pub fn active_tab(&self) -> usize {
+        self.active_tab
     }
 
+    /// Returns true if the tab with the given ID is the selected one.
+    pub fn is_active_tab(&self, id: TabId) -> bool {
+        self.tabs
+            .get(self.active_tab)
+            .is_some_and(|tab| tab.id == id)
+    }
+
+    /// The index of the tab with the given ID, if it's still open.
+    pub fn tab_position(&self, id: TabId) -> Option<usize> {
+        self.tabs.iter().position(|tab| tab.id == id)
+    }
+
+    /// The number of open tabs.
+    pub fn tab_count(&self) -> usize {
+        self.tabs.len()
+    }
+
+    /// Returns the readable names of the movies open in each tab.
+    pub fn tab_names(&self) -> Vec<String> {
+        self.tabs.iter().map(|tab| tab.movie_name.clone()).collect()
+    }
+
+    /// Returns the URL and options the movie in the selected tab was opened with, if any.
+    pub fn movie(&self) -> Option<(&Url, &LaunchOptions)> {
+        self.tabs
+            .get(self.active_tab)
+            .map(|tab| (&tab.movie_url, &tab.options))
+    }
+
+    /// Pauses the selected tab, as only the selected one is shown.
+    fn send_active_tab_to_background(&mut self) {
+        let Some(tab) = self.tabs.get_mut(self.active_tab) else {
+            return;
+        };
+        let mut player = tab
+            .player
+            .try_lock()
+            .expect("Player lock must be available");
+        // A movie that was already paused by the user stays paused when it's selected again.
+        tab.paused_in_background = player.is_playing();
+        player.set_is_playing(false);
+    }
+
+    /// Resumes the selected tab, if it was paused by [`Self::send_active_tab_to_background`].
+    fn bring_active_tab_to_foreground(&mut self) {
+        let Some(tab) = self.tabs.get_mut(self.active_tab) else {
+            return;
+        };
+        if mem::take(&mut tab.paused_in_background) {
+            tab.player
+                .try_lock()
+                .expect("Player lock must be available")
+                .set_is_playing(true);
+        }
+    }
+
+    /// Pauses the movie in the selected tab and advances it by the given number of frames,
+    /// right away rather than at its frame rate.
+    ///
+    /// Each frame is a regular tick of a frame's worth of time, so that timers and streams move along with it.
+    /// Sound stays paused meanwhile, to avoid unpleasant short bursts of it.
+    pub fn step_frames(&self, frames: u32) {
+        let Some(mut player) = self.get() else {
+            return;
+        };
+        let frame_time = 1000.0 / player.frame_rate();
+        for _ in 0..frames {
+            // The player suspends itself again after the frame.
+            player.suspend_after_next_frame();
+            player.tick(frame_time);
+        }
+        player.set_is_playing(false);
+    }
+
+    /// Tells the movie that the window gained or lost focus,
+    /// and pauses or mutes it while unfocused according to `unfocused_behavior`.
+    ///
+    /// The movie receives the focus events either way, as they're sent while it's playing.
+    pub fn set_window_focused(&mut self, focused: bool, unfocused_behavior: UnfocusedBehavior) {
+        if focused {
+            let resume = std::mem::take(&mut self.paused_while_unfocused);
+            let volume = self.volume_while_focused.take();
+            if let Some(mut player) = self.get() {
+                if resume {
+                    player.set_is_playing(true);
+                }
+                if let Some(volume) = volume {
+                    player.set_volume(volume);
+                }
+            }
+            self.handle_event(PlayerEvent::FocusGained);
+            return;
+        }
+
+        self.handle_event(PlayerEvent::FocusLost);
+        let Some(mut player) = self.get() else {
+            return;
+        };
+        let (paused, volume) = match unfocused_behavior {
+            UnfocusedBehavior::Continue => (false, None),
+            // A movie that was already paused by the user stays paused when focus returns.
+            UnfocusedBehavior::Pause if player.is_playing() => {
+                player.set_is_playing(false);
+                (true, None)
+            }
+            UnfocusedBehavior::Pause => (false, None),
+            UnfocusedBehavior::Mute => {
+                let volume = player.volume();
+                player.set_volume(0.0);
+                (false, Some(volume))
+            }
+        };
+        drop(player);
+        self.paused_while_unfocused = paused;
+        self.volume_while_focused = volume;
+    }
+
+    /// Returns the player of the selected tab, if any.
     pub fn get(&self) -> Option<MutexGuard<'_, Player>> {
-        match &self.player {
+        match self.tabs.get(self.active_tab) {
             None => None,
             // We don't want to return None when the lock fails to grab as that's a fatal error, not a lack of player
             Some(player) => Some(
@@ -490,6 +777,13 @@ impl PlayerController {
         }
     }
 
+    /// Returns the readable name of the movie in the selected tab, if any.
+    pub fn movie_name(&self) -> Option<&str> {
+        self.tabs
+            .get(self.active_tab)
+            .map(|player| player.movie_name.as_str())
+    }
+
     pub fn handle_event(&self, event: PlayerEvent) -> bool {
         if let Some(mut player) = self.get() {
             if player.is_playing() {
@@ -500,9 +794,10 @@ impl PlayerController {
         false
     }
 
+    /// Polls the tasks of all tabs, so that movies keep loading in the background.
     pub fn poll(&self) {
-        if let Some(player) = &self.player {
-            player.executor.poll_all()
+        for tab in &self.tabs {
+            tab.executor.poll_all()
         }
     }
 }
@@ -3,22 +3,65 @@ mod write;
 
 pub mod storage;
 
-use crate::cli::{GameModePreference, OpenUrlMode, Opt};
-use crate::gui::ThemePreference;
+use crate::cli::{ContextMenuMode, GameModePreference, OpenUrlMode, Opt};
+use crate::gui::{
+    BackgroundColor, FullscreenMonitor, KeyRemaps, MenuVisibility, Shortcut, ThemePreference,
+    VideoModePreference,
+};
 use crate::log::FilenamePattern;
+use crate::player::UnfocusedBehavior;
 use crate::preferences::read::read_preferences;
 use crate::preferences::write::PreferencesWriter;
 use anyhow::{Context, Error};
+use egui::{Key, Modifiers};
 use ruffle_core::backend::ui::US_ENGLISH;
+use ruffle_core::{PlayerRuntime, StageAlign, StageScaleMode};
 use ruffle_frontend_utils::bookmarks::{read_bookmarks, Bookmarks, BookmarksWriter};
 use ruffle_frontend_utils::parse::DocumentHolder;
+use ruffle_frontend_utils::player_options::PlayerVersion;
 use ruffle_frontend_utils::recents::{read_recents, Recents, RecentsWriter};
+use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+use std::ops::RangeInclusive;
 use std::sync::{Arc, Mutex};
+use std::time::Duration;
 use sys_locale::get_locale;
 use tokio::sync::broadcast;
 use tokio::sync::broadcast::{Receiver, Sender};
 use unic_langid::LanguageIdentifier;
+use url::Url;
+
+/// The allowed values of the frame latency preference.
+pub const FRAME_LATENCY_RANGE: RangeInclusive<u32> = 1..=3;
+
+/// The allowed major versions of the emulated player version.
+pub const PLAYER_VERSION_RANGE: RangeInclusive<u8> = 1..=32;
+
+/// Brings the major version of the given player version into [`PLAYER_VERSION_RANGE`].
+pub fn clamp_player_version(version: PlayerVersion) -> PlayerVersion {
+    PlayerVersion {
+        major: version
+            .major
+            .clamp(*PLAYER_VERSION_RANGE.start(), *PLAYER_VERSION_RANGE.end()),
+        ..version
+    }
+}
+
+/// Parses a player version typed by the user, which must have a major version that's emulated.
+pub fn parse_player_version(text: &str) -> Option<PlayerVersion> {
+    text.parse()
+        .ok()
+        .filter(|version| clamp_player_version(*version) == *version)
+}
+
+/// The allowed values of the scale factor override preference.
+pub const SCALE_FACTOR_OVERRIDE_RANGE: RangeInclusive<f64> = 0.5..=4.0;
+
+/// The allowed values of the GUI sample count preference.
+pub const UI_SAMPLE_COUNTS: [u32; 3] = [1, 2, 4];
+
+/// The maximum number of movies whose window geometry is remembered.
+pub const WINDOW_GEOMETRY_LIMIT: usize = 100;
 
 /// The preferences that relate to the application itself.
 ///
@@ -144,6 +187,14 @@ impl GlobalPreferences {
             .clone()
     }
 
+    /// What happens to the movie while the window isn't focused.
+    pub fn unfocused_behavior(&self) -> UnfocusedBehavior {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .unfocused_behavior
+    }
+
     pub fn mute(&self) -> bool {
         self.preferences
             .lock()
@@ -151,6 +202,14 @@ impl GlobalPreferences {
             .mute
     }
 
+    /// The shortcut that mutes or unmutes the movie, without changing its volume.
+    pub fn mute_shortcut(&self) -> Shortcut {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .mute_shortcut
+    }
+
     pub fn preferred_volume(&self) -> f32 {
         self.cli.volume.unwrap_or_else(|| {
             self.preferences
@@ -213,6 +272,261 @@ impl GlobalPreferences {
         self.watchers.theme_preference_watcher.subscribe()
     }
 
+    /// The maximum number of frames the GPU may queue up before presentation.
+    ///
+    /// Lower values reduce input lag, which matters for input-sensitive content,
+    /// while higher values give slow GPUs more headroom and smooth out frame pacing.
+    pub fn frame_latency(&self) -> u32 {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .frame_latency
+            .clamp(*FRAME_LATENCY_RANGE.start(), *FRAME_LATENCY_RANGE.end())
+    }
+
+    pub fn frame_latency_watcher(&self) -> Receiver<u32> {
+        self.watchers.frame_latency_watcher.subscribe()
+    }
+
+    /// The number of MSAA samples used when rendering the GUI on top of the movie.
+    ///
+    /// This doesn't affect the movie itself, which is antialiased according to its quality.
+    pub fn ui_sample_count(&self) -> u32 {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .ui_sample_count
+    }
+
+    /// Whether resizing the window keeps the aspect ratio of the movie.
+    pub fn lock_aspect_ratio(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .lock_aspect_ratio
+    }
+
+    /// The scale factor to use instead of the one reported by the OS, if any.
+    ///
+    /// This is meant for reproducing HiDPI issues on a monitor without scaling.
+    pub fn scale_factor_override(&self) -> Option<f64> {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .scale_factor_override
+    }
+
+    pub fn scale_factor_override_watcher(&self) -> Receiver<Option<f64>> {
+        self.watchers.scale_factor_override_watcher.subscribe()
+    }
+
+    /// Whether the window geometry is remembered for each movie and restored when it's opened again.
+    pub fn remember_window_geometry(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .remember_window_geometry
+    }
+
+    /// The geometry of the window when Ruffle was last closed, if it should be restored.
+    pub fn main_window_geometry(&self) -> Option<MainWindowGeometry> {
+        let preferences = self.preferences.lock().expect("Non-poisoned preferences");
+        if !preferences.remember_window_geometry {
+            return None;
+        }
+        preferences.main_window_geometry
+    }
+
+    /// The window geometry saved for the given movie, if it should be restored.
+    pub fn window_geometry(&self, url: &Url) -> Option<WindowGeometry> {
+        let preferences = self.preferences.lock().expect("Non-poisoned preferences");
+        if !preferences.remember_window_geometry {
+            return None;
+        }
+        preferences
+            .window_geometry
+            .iter()
+            .find(|geometry| &geometry.url == url)
+            .cloned()
+    }
+
+    pub fn background_color(&self) -> BackgroundColor {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .background_color
+    }
+
+    pub fn background_color_watcher(&self) -> Receiver<BackgroundColor> {
+        self.watchers.background_color_watcher.subscribe()
+    }
+
+    pub fn menu_visibility(&self) -> MenuVisibility {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .menu_visibility
+    }
+
+    pub fn menu_visibility_watcher(&self) -> Receiver<MenuVisibility> {
+        self.watchers.menu_visibility_watcher.subscribe()
+    }
+
+    /// Whether the movie is redrawn less often while it looks the same, to save power.
+    pub fn idle_power_save(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .idle_power_save
+    }
+
+    pub fn idle_power_save_watcher(&self) -> Receiver<bool> {
+        self.watchers.idle_power_save_watcher.subscribe()
+    }
+
+    /// Whether touches only ever act as the mouse, rather than two fingers zooming and panning the movie.
+    pub fn touch_as_mouse(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .touch_as_mouse
+    }
+
+    /// How long a finger has to be held still to open the context menu,
+    /// or `None` if long-presses are ignored.
+    pub fn long_press_duration(&self) -> Option<Duration> {
+        let milliseconds = self
+            .preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .long_press_duration;
+        (milliseconds > 0).then(|| Duration::from_millis(milliseconds.into()))
+    }
+
+    /// Whether the frame rate is shown in the window title while a movie is playing.
+    pub fn show_fps_in_title(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .show_fps_in_title
+    }
+
+    pub fn show_fps_in_title_watcher(&self) -> Receiver<bool> {
+        self.watchers.show_fps_in_title_watcher.subscribe()
+    }
+
+    pub fn fullscreen_toggle_shortcut(&self) -> Shortcut {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .fullscreen
+            .toggle_shortcut
+    }
+
+    pub fn fullscreen_exit_shortcut(&self) -> Shortcut {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .fullscreen
+            .exit_shortcut
+    }
+
+    /// Whether the exit shortcut must be held to leave fullscreen,
+    /// so that short presses can still be used by the movie.
+    pub fn fullscreen_hold_to_exit(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .fullscreen
+            .hold_to_exit
+    }
+
+    /// How long the mouse has to be left alone in fullscreen before the cursor is hidden,
+    /// or `None` if it's always shown.
+    pub fn fullscreen_hide_cursor_after(&self) -> Option<Duration> {
+        let seconds = self
+            .preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .fullscreen
+            .hide_cursor_after;
+        (seconds > 0).then(|| Duration::from_secs(seconds.into()))
+    }
+
+    /// Whether fullscreen should change the display mode, instead of using a borderless window.
+    pub fn fullscreen_exclusive(&self) -> bool {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .fullscreen
+            .exclusive
+    }
+
+    pub fn fullscreen_monitor(&self) -> FullscreenMonitor {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .fullscreen
+            .monitor
+            .clone()
+    }
+
+    /// The video mode to use for exclusive fullscreen, or `None` for the monitor's best one.
+    pub fn fullscreen_video_mode(&self) -> Option<VideoModePreference> {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .fullscreen
+            .video_mode
+    }
+
+    /// The key the movie at `url` gets when `key` is pressed, if it's remapped.
+    ///
+    /// Remaps for the movie itself take priority over the ones for all movies.
+    pub fn key_remap(&self, url: Option<&Url>, key: Key) -> Option<Key> {
+        let preferences = self.preferences.lock().expect("Non-poisoned preferences");
+        url.and_then(|url| {
+            preferences
+                .movie_key_remaps
+                .iter()
+                .find(|movie| &movie.url == url)
+        })
+        .and_then(|movie| movie.remaps.get(key))
+        .or_else(|| preferences.key_remaps.get(key))
+    }
+
+    /// The keys remapped for all movies.
+    pub fn key_remaps(&self) -> KeyRemaps {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .key_remaps
+            .clone()
+    }
+
+    /// The keys remapped only for the movie at `url`.
+    pub fn movie_key_remaps(&self, url: &Url) -> KeyRemaps {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .movie_key_remaps
+            .iter()
+            .find(|movie| &movie.url == url)
+            .map(|movie| movie.remaps.clone())
+            .unwrap_or_default()
+    }
+
+    /// The options the movie at `url` was last opened with from the open dialog, if it had any.
+    pub fn movie_options(&self, url: &Url) -> Option<MovieOptions> {
+        self.preferences
+            .lock()
+            .expect("Non-poisoned preferences")
+            .movie_options
+            .iter()
+            .find(|movie| &movie.url == url)
+            .cloned()
+    }
+
     pub fn open_url_mode(&self) -> OpenUrlMode {
         self.cli.open_url_mode.unwrap_or_else(|| {
             self.preferences
@@ -222,6 +536,35 @@ impl GlobalPreferences {
         })
     }
 
+    pub fn context_menu_mode(&self) -> ContextMenuMode {
+        self.cli.context_menu.unwrap_or_else(|| {
+            self.preferences
+                .lock()
+                .expect("Non-poisoned preferences")
+                .context_menu_mode
+        })
+    }
+
+    /// The runtime that movies are opened with by default, if one was chosen.
+    pub fn player_runtime(&self) -> Option<PlayerRuntime> {
+        self.cli.player_runtime.or_else(|| {
+            self.preferences
+                .lock()
+                .expect("Non-poisoned preferences")
+                .player_runtime
+        })
+    }
+
+    /// The player version that movies are opened with by default, if one was chosen.
+    pub fn player_version(&self) -> Option<PlayerVersion> {
+        self.cli.player_version.or_else(|| {
+            self.preferences
+                .lock()
+                .expect("Non-poisoned preferences")
+                .player_version
+        })
+    }
+
     pub fn ime_enabled(&self) -> Option<bool> {
         self.preferences
             .lock()
@@ -278,7 +621,9 @@ pub struct SavedGlobalPreferences {
     pub gamemode_preference: GameModePreference,
     pub language: LanguageIdentifier,
     pub output_device: Option<String>,
+    pub unfocused_behavior: UnfocusedBehavior,
     pub mute: bool,
+    pub mute_shortcut: Shortcut,
     pub volume: f32,
     pub enable_openh264: bool,
     pub recent_limit: usize,
@@ -286,7 +631,28 @@ pub struct SavedGlobalPreferences {
     pub storage: StoragePreferences,
     pub theme_preference: ThemePreference,
     pub open_url_mode: OpenUrlMode,
+    pub context_menu_mode: ContextMenuMode,
+    pub player_runtime: Option<PlayerRuntime>,
+    pub player_version: Option<PlayerVersion>,
     pub ime_enabled: Option<bool>,
+    pub frame_latency: u32,
+    pub ui_sample_count: u32,
+    pub scale_factor_override: Option<f64>,
+    pub background_color: BackgroundColor,
+    pub menu_visibility: MenuVisibility,
+    pub show_fps_in_title: bool,
+    pub idle_power_save: bool,
+    pub touch_as_mouse: bool,
+    /// In milliseconds, where 0 turns long-presses off.
+    pub long_press_duration: u32,
+    pub lock_aspect_ratio: bool,
+    pub remember_window_geometry: bool,
+    pub window_geometry: Vec<WindowGeometry>,
+    pub main_window_geometry: Option<MainWindowGeometry>,
+    pub fullscreen: FullscreenPreferences,
+    pub key_remaps: KeyRemaps,
+    pub movie_key_remaps: Vec<MovieKeyRemaps>,
+    pub movie_options: Vec<MovieOptions>,
 }
 
 impl Default for SavedGlobalPreferences {
@@ -302,7 +668,9 @@ impl Default for SavedGlobalPreferences {
             gamemode_preference: Default::default(),
             language: locale,
             output_device: None,
+            unfocused_behavior: Default::default(),
             mute: false,
+            mute_shortcut: Shortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::M),
             volume: 1.0,
             enable_openh264: true,
             recent_limit: 10,
@@ -310,7 +678,27 @@ impl Default for SavedGlobalPreferences {
             storage: Default::default(),
             theme_preference: Default::default(),
             open_url_mode: Default::default(),
+            context_menu_mode: Default::default(),
+            player_runtime: None,
+            player_version: None,
             ime_enabled: None,
+            frame_latency: 2,
+            ui_sample_count: 1,
+            scale_factor_override: None,
+            background_color: Default::default(),
+            menu_visibility: Default::default(),
+            show_fps_in_title: false,
+            idle_power_save: true,
+            touch_as_mouse: false,
+            long_press_duration: 500,
+            lock_aspect_ratio: false,
+            remember_window_geometry: true,
+            window_geometry: Vec::new(),
+            main_window_geometry: None,
+            fullscreen: Default::default(),
+            key_remaps: Default::default(),
+            movie_key_remaps: Vec::new(),
+            movie_options: Vec::new(),
         }
     }
 }
@@ -325,15 +713,129 @@ pub struct StoragePreferences {
     pub backend: storage::StorageBackend,
 }
 
+/// The last geometry of the window while a movie was open, in physical pixels.
+#[derive(Clone, PartialEq, Debug)]
+pub struct WindowGeometry {
+    pub url: Url,
+    /// The outer position of the window.
+    pub x: i32,
+    pub y: i32,
+    /// The inner size of the window, including the menu bar.
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+    pub fullscreen: bool,
+}
+
+/// The last geometry of the window when Ruffle was closed, in physical pixels.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+pub struct MainWindowGeometry {
+    /// The outer position of the window.
+    pub x: i32,
+    pub y: i32,
+    /// The inner size of the window, including the menu bar.
+    pub width: u32,
+    pub height: u32,
+    pub maximized: bool,
+}
+
+/// The keys remapped for a single movie, on top of the ones for all movies.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MovieKeyRemaps {
+    pub url: Url,
+    pub remaps: KeyRemaps,
+}
+
+/// The options a single movie was last opened with from the open dialog,
+/// and the display settings last chosen for it, to open it with them again.
+#[derive(Clone, PartialEq, Debug)]
+pub struct MovieOptions {
+    pub url: Url,
+    /// The parameters (FlashVars) of the movie.
+    pub parameters: Vec<(String, String)>,
+    /// The URL the movie is told that it was loaded from.
+    pub spoof_url: Option<Url>,
+    /// Whether relative URLs are resolved against `spoof_url` rather than where the movie actually is.
+    pub spoof_url_as_base: bool,
+    /// The quality chosen from the View menu while the movie was running.
+    pub quality: Option<StageQuality>,
+    /// The scale mode chosen from the View menu while the movie was running.
+    pub scale_mode: Option<StageScaleMode>,
+    /// The stage alignment chosen from the View menu while the movie was running.
+    pub align: Option<StageAlign>,
+}
+
+impl MovieOptions {
+    /// Options for the given movie that are all at their defaults.
+    pub fn new(url: Url) -> Self {
+        Self {
+            url,
+            parameters: vec![],
+            spoof_url: None,
+            spoof_url_as_base: false,
+            quality: None,
+            scale_mode: None,
+            align: None,
+        }
+    }
+
+    /// Whether there's nothing to remember, as the options are all at their defaults.
+    pub fn is_empty(&self) -> bool {
+        self.parameters.is_empty()
+            && self.spoof_url.is_none()
+            && self.quality.is_none()
+            && self.scale_mode.is_none()
+            && self.align.is_none()
+    }
+}
+
+#[derive(PartialEq, Debug)]
+pub struct FullscreenPreferences {
+    pub toggle_shortcut: Shortcut,
+    pub exit_shortcut: Shortcut,
+    pub hold_to_exit: bool,
+    /// How many seconds the mouse has to be left alone before the cursor is hidden, or 0 to never hide it.
+    pub hide_cursor_after: u32,
+    pub exclusive: bool,
+    pub monitor: FullscreenMonitor,
+    pub video_mode: Option<VideoModePreference>,
+}
+
+impl Default for FullscreenPreferences {
+    fn default() -> Self {
+        Self {
+            toggle_shortcut: Shortcut::new(Modifiers::NONE, Key::F11),
+            exit_shortcut: Shortcut::new(Modifiers::NONE, Key::Escape),
+            hold_to_exit: false,
+            hide_cursor_after: 3,
+            exclusive: false,
+            monitor: Default::default(),
+            video_mode: None,
+        }
+    }
+}
+
 #[derive(Clone)]
 pub struct GlobalPreferencesWatchers {
     theme_preference_watcher: Arc<Sender<ThemePreference>>,
+    frame_latency_watcher: Arc<Sender<u32>>,
+    scale_factor_override_watcher: Arc<Sender<Option<f64>>>,
+    background_color_watcher: Arc<Sender<BackgroundColor>>,
+    menu_visibility_watcher: Arc<Sender<MenuVisibility>>,
+    show_fps_in_title_watcher: Arc<Sender<bool>>,
+    idle_power_save_watcher: Arc<Sender<bool>>,
 }
 
 impl Default for GlobalPreferencesWatchers {
     fn default() -> Self {
         Self {
             theme_preference_watcher: Arc::new(broadcast::channel(1).0),
+            frame_latency_watcher: Arc::new(broadcast::channel(1).0),
+            scale_factor_override_watcher: Arc::new(broadcast::channel(1).0),
+            background_color_watcher: Arc::new(broadcast::channel(1).0),
+            menu_visibility_watcher: Arc::new(broadcast::channel(1).0),
+            show_fps_in_title_watcher: Arc::new(broadcast::channel(1).0),
+            idle_power_save_watcher: Arc::new(broadcast::channel(1).0),
         }
     }
 }
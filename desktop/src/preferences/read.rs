@@ -1,8 +1,14 @@
-use crate::preferences::SavedGlobalPreferences;
+use crate::gui::KeyRemaps;
+use crate::preferences::{
+    clamp_player_version, MainWindowGeometry, MovieKeyRemaps, MovieOptions, SavedGlobalPreferences,
+    WindowGeometry, FRAME_LATENCY_RANGE, SCALE_FACTOR_OVERRIDE_RANGE, UI_SAMPLE_COUNTS,
+};
+use egui::Key;
 use ruffle_frontend_utils::parse::{
-    DocumentHolder, ParseContext, ParseDetails, ParseWarning, ReadExt,
+    DocumentHolder, ItemExt, ParseContext, ParseDetails, ParseWarning, ReadExt,
 };
-use toml_edit::DocumentMut;
+use ruffle_render::quality::StageQuality;
+use toml_edit::{DocumentMut, Table, TableLike};
 
 /// Read the given preferences into a **guaranteed valid** `SavedGlobalPreferences`,
 /// recording any possible warnings encountered along the way.
@@ -43,7 +49,11 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.output_device = Some(value);
     };
 
-    if let Some(value) = document.get_float(&mut cx, "volume") {
+    if let Some(value) = document.parse_from_str(&mut cx, "unfocused_behavior") {
+        result.unfocused_behavior = value;
+    };
+
+    if let Some(value) = document.get_float_like(&mut cx, "volume") {
         result.volume = value.clamp(0.0, 1.0) as f32;
     };
 
@@ -51,6 +61,10 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.mute = value;
     };
 
+    if let Some(value) = document.parse_from_str(&mut cx, "mute_shortcut") {
+        result.mute_shortcut = value;
+    };
+
     if let Some(value) = document.get_bool(&mut cx, "enable_openh264") {
         result.enable_openh264 = value;
     };
@@ -59,10 +73,112 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.recent_limit = value as usize;
     }
 
+    if let Some(value) = document.get_integer(&mut cx, "frame_latency") {
+        result.frame_latency = value.clamp(
+            *FRAME_LATENCY_RANGE.start() as i64,
+            *FRAME_LATENCY_RANGE.end() as i64,
+        ) as u32;
+    }
+
+    if let Some(value) = document.get_integer(&mut cx, "ui_sample_count") {
+        match UI_SAMPLE_COUNTS
+            .into_iter()
+            .find(|count| *count as i64 == value)
+        {
+            Some(count) => result.ui_sample_count = count,
+            None => {
+                cx.push_key("ui_sample_count");
+                cx.unsupported_value(value.to_string());
+                cx.pop_key();
+            }
+        }
+    }
+
+    if let Some(value) = document.get_float_like(&mut cx, "scale_factor_override") {
+        result.scale_factor_override = Some(value.clamp(
+            *SCALE_FACTOR_OVERRIDE_RANGE.start(),
+            *SCALE_FACTOR_OVERRIDE_RANGE.end(),
+        ));
+    }
+
     if let Some(value) = document.parse_from_str(&mut cx, "theme") {
         result.theme_preference = value;
     }
 
+    if let Some(value) = document.parse_from_str(&mut cx, "background_color") {
+        result.background_color = value;
+    }
+
+    if let Some(value) = document.parse_from_str(&mut cx, "menu_visibility") {
+        result.menu_visibility = value;
+    }
+
+    if let Some(value) = document.get_bool(&mut cx, "show_fps_in_title") {
+        result.show_fps_in_title = value;
+    }
+
+    if let Some(value) = document.get_bool(&mut cx, "idle_power_save") {
+        result.idle_power_save = value;
+    }
+
+    if let Some(value) = document.get_bool(&mut cx, "touch_as_mouse") {
+        result.touch_as_mouse = value;
+    }
+
+    if let Some(value) = document.get_integer(&mut cx, "long_press_duration") {
+        result.long_press_duration = value.clamp(0, u32::MAX as i64) as u32;
+    }
+
+    if let Some(value) = document.get_bool(&mut cx, "lock_aspect_ratio") {
+        result.lock_aspect_ratio = value;
+    }
+
+    if let Some(value) = document.get_bool(&mut cx, "remember_window_geometry") {
+        result.remember_window_geometry = value;
+    }
+
+    document.get_array_of_tables(&mut cx, "window_geometry", |cx, geometries| {
+        for geometry in geometries.iter() {
+            // Incomplete entries can't be restored, so they're skipped.
+            let (Some(url), Some(x), Some(y), Some(width), Some(height)) = (
+                geometry.parse_from_str(cx, "url"),
+                geometry.get_integer(cx, "x"),
+                geometry.get_integer(cx, "y"),
+                geometry.get_integer(cx, "width"),
+                geometry.get_integer(cx, "height"),
+            ) else {
+                continue;
+            };
+            result.window_geometry.push(WindowGeometry {
+                url,
+                x: x.clamp(i32::MIN as i64, i32::MAX as i64) as i32,
+                y: y.clamp(i32::MIN as i64, i32::MAX as i64) as i32,
+                width: width.clamp(1, u32::MAX as i64) as u32,
+                height: height.clamp(1, u32::MAX as i64) as u32,
+                maximized: geometry.get_bool(cx, "maximized").unwrap_or_default(),
+                fullscreen: geometry.get_bool(cx, "fullscreen").unwrap_or_default(),
+            });
+        }
+    });
+
+    document.get_table_like(&mut cx, "main_window", |cx, main_window| {
+        let (Some(x), Some(y), Some(width), Some(height)) = (
+            main_window.get_integer(cx, "x"),
+            main_window.get_integer(cx, "y"),
+            main_window.get_integer(cx, "width"),
+            main_window.get_integer(cx, "height"),
+        ) else {
+            return;
+        };
+        result.main_window_geometry = Some(MainWindowGeometry {
+            x: x.clamp(i32::MIN as i64, i32::MAX as i64) as i32,
+            y: y.clamp(i32::MIN as i64, i32::MAX as i64) as i32,
+            width: width.clamp(1, u32::MAX as i64) as u32,
+            height: height.clamp(1, u32::MAX as i64) as u32,
+            maximized: main_window.get_bool(cx, "maximized").unwrap_or_default(),
+        });
+    });
+
     if let Some(value) = document.parse_from_str(&mut cx, "gamemode") {
         result.gamemode_preference = value;
     }
@@ -71,6 +187,18 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.open_url_mode = value;
     }
 
+    if let Some(value) = document.parse_from_str(&mut cx, "context_menu_mode") {
+        result.context_menu_mode = value;
+    }
+
+    if let Some(value) = document.parse_from_str(&mut cx, "player_runtime") {
+        result.player_runtime = Some(value);
+    }
+
+    if let Some(value) = document.get_player_version(&mut cx, "player_version") {
+        result.player_version = Some(clamp_player_version(value));
+    }
+
     document.get_table_like(&mut cx, "log", |cx, log| {
         if let Some(value) = log.parse_from_str(cx, "filename_pattern") {
             result.log.filename_pattern = value;
@@ -87,21 +215,166 @@ pub fn read_preferences(input: &str) -> ParseDetails<SavedGlobalPreferences> {
         result.ime_enabled = ime.get_bool(cx, "enabled");
     });
 
+    document.get_table_like(&mut cx, "fullscreen", |cx, fullscreen| {
+        if let Some(value) = fullscreen.parse_from_str(cx, "toggle_shortcut") {
+            result.fullscreen.toggle_shortcut = value;
+        }
+        if let Some(value) = fullscreen.parse_from_str(cx, "exit_shortcut") {
+            result.fullscreen.exit_shortcut = value;
+        }
+        if let Some(value) = fullscreen.get_bool(cx, "hold_to_exit") {
+            result.fullscreen.hold_to_exit = value;
+        }
+        if let Some(value) = fullscreen.get_integer(cx, "hide_cursor_after") {
+            result.fullscreen.hide_cursor_after = value.clamp(0, u32::MAX as i64) as u32;
+        }
+        if let Some(value) = fullscreen.get_bool(cx, "exclusive") {
+            result.fullscreen.exclusive = value;
+        }
+        if let Some(value) = fullscreen.parse_from_str(cx, "monitor") {
+            result.fullscreen.monitor = value;
+        }
+        if let Some(value) = fullscreen.parse_from_str(cx, "video_mode") {
+            result.fullscreen.video_mode = Some(value);
+        }
+    });
+
+    document.get_table_like(&mut cx, "key_remaps", |cx, remaps| {
+        result.key_remaps = read_key_remaps(cx, remaps);
+    });
+
+    document.get_array_of_tables(&mut cx, "movie_key_remaps", |cx, movies| {
+        for movie in movies.iter() {
+            let Some(url) = movie.parse_from_str(cx, "url") else {
+                continue;
+            };
+            let remaps = movie
+                .get_table_like(cx, "remaps", read_key_remaps)
+                .unwrap_or_default();
+            result.movie_key_remaps.push(MovieKeyRemaps { url, remaps });
+        }
+    });
+
+    document.get_array_of_tables(&mut cx, "movie_options", |cx, movies| {
+        for movie in movies.iter() {
+            let Some(url) = movie.parse_from_str(cx, "url") else {
+                continue;
+            };
+            let parameters = read_parameters(cx, movie);
+            result.movie_options.push(MovieOptions {
+                url,
+                parameters,
+                spoof_url: movie.parse_from_str(cx, "spoof_url"),
+                spoof_url_as_base: movie.get_bool(cx, "spoof_url_as_base").unwrap_or_default(),
+                quality: read_quality(cx, movie, "quality"),
+                scale_mode: movie.parse_from_str(cx, "scale_mode"),
+                align: movie.parse_from_str(cx, "align"),
+            });
+        }
+    });
+
     ParseDetails {
         warnings: cx.warnings,
         result: DocumentHolder::new(result, document),
     }
 }
 
+/// Reads a table of key names to the names of the keys they're remapped to, such as `Q = "A"`.
+fn read_key_remaps<'a>(cx: &mut ParseContext<'a>, table: &'a dyn TableLike) -> KeyRemaps {
+    let mut remaps = KeyRemaps::default();
+    for (name, item) in table.iter() {
+        cx.push_key(name);
+        if let Some(value) = item.as_str_or_warn(cx) {
+            match (Key::from_name(name), Key::from_name(value)) {
+                (Some(from), Some(to)) => remaps.set(from, to),
+                (None, _) => cx.unsupported_value(name.to_owned()),
+                (_, None) => cx.unsupported_value(value.to_owned()),
+            }
+        }
+        cx.pop_key();
+    }
+    remaps
+}
+
+/// Reads a stage quality by the name that ActionScript gives it, such as `"8x8linear"`.
+///
+/// Unlike [`StageQuality::from_str`], this covers all of the qualities.
+fn read_quality<'a>(
+    cx: &mut ParseContext<'a>,
+    table: &'a dyn TableLike,
+    key: &'static str,
+) -> Option<StageQuality> {
+    let mut result = None;
+    cx.push_key(key);
+    if let Some(name) = table.get(key).and_then(|item| item.as_str_or_warn(cx)) {
+        result = [
+            StageQuality::Low,
+            StageQuality::Medium,
+            StageQuality::High,
+            StageQuality::Best,
+            StageQuality::High8x8,
+            StageQuality::High8x8Linear,
+            StageQuality::High16x16,
+            StageQuality::High16x16Linear,
+        ]
+        .into_iter()
+        .find(|quality| quality.to_string() == name);
+        if result.is_none() {
+            cx.unsupported_value(name.to_owned());
+        }
+    }
+    cx.pop_key();
+    result
+}
+
+/// Reads the parameters of a movie, an array of `[name, value]` pairs such as `[["level", "3"]]`,
+/// keeping their order and any names given more than once.
+fn read_parameters<'a>(cx: &mut ParseContext<'a>, movie: &'a Table) -> Vec<(String, String)> {
+    let mut parameters = Vec::new();
+    let Some(item) = movie.get("parameters") else {
+        return parameters;
+    };
+    cx.push_key("parameters");
+    if let Some(pairs) = item.as_array() {
+        for pair in pairs.iter() {
+            let Some(pair) = pair.as_array() else {
+                cx.unexpected_type("array", pair.type_name());
+                continue;
+            };
+            let name = pair.get(0).and_then(|name| name.as_str());
+            let value = pair.get(1).and_then(|value| value.as_str());
+            if let (2, Some(name), Some(value)) = (pair.len(), name, value) {
+                parameters.push((name.to_owned(), value.to_owned()));
+            } else {
+                cx.unsupported_value(pair.to_string().trim().to_owned());
+            }
+        }
+    } else {
+        cx.unexpected_type("array", item.type_name());
+    }
+    cx.pop_key();
+    parameters
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
-    use crate::cli::{GameModePreference, OpenUrlMode};
-    use crate::gui::ThemePreference;
+    use crate::cli::{ContextMenuMode, GameModePreference, OpenUrlMode};
+    use crate::gui::{
+        BackgroundColor, FullscreenMonitor, MenuVisibility, Shortcut, ThemePreference,
+        VideoModePreference,
+    };
     use crate::log::FilenamePattern;
-    use crate::preferences::{storage::StorageBackend, LogPreferences, StoragePreferences};
+    use crate::player::UnfocusedBehavior;
+    use crate::preferences::{
+        storage::StorageBackend, FullscreenPreferences, LogPreferences, StoragePreferences,
+    };
+    use egui::{Key, Modifiers};
     use fluent_templates::loader::langid;
+    use ruffle_core::{PlayerRuntime, StageAlign, StageScaleMode};
+    use ruffle_frontend_utils::player_options::PlayerVersion;
     use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
+    use url::Url;
 
     #[test]
     fn invalid_toml() {
@@ -269,6 +542,45 @@ mod tests {
         );
     }
 
+    #[test]
+    fn unfocused_behavior() {
+        let result = read_preferences("unfocused_behavior = \"pause\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                unfocused_behavior: UnfocusedBehavior::Pause,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("unfocused_behavior = \"mute\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                unfocused_behavior: UnfocusedBehavior::Mute,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("unfocused_behavior = \"stop\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                unfocused_behavior: UnfocusedBehavior::Continue,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "stop".to_string(),
+                path: "unfocused_behavior".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
     #[test]
     fn mute() {
         let result = read_preferences("mute = \"false\"");
@@ -309,6 +621,29 @@ mod tests {
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
     }
 
+    #[test]
+    fn mute_shortcut() {
+        let result = read_preferences("mute_shortcut = \"Alt+M\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                mute_shortcut: Shortcut::new(Modifiers::ALT, Key::M),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("mute_shortcut = \"Ctrl+\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "Ctrl+".to_string(),
+                path: "mute_shortcut".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
     #[test]
     fn volume() {
         let result = read_preferences("volume = \"0.5\"");
@@ -321,7 +656,7 @@ mod tests {
         );
         assert_eq!(
             vec![ParseWarning::UnexpectedType {
-                expected: "float",
+                expected: "float or integer",
                 actual: "string",
                 path: "volume".to_string()
             }],
@@ -347,6 +682,16 @@ mod tests {
             result.values()
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("volume = 0");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                volume: 0.0,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
     }
 
     #[test]
@@ -545,87 +890,104 @@ mod tests {
     }
 
     #[test]
-    fn theme() {
-        let result = read_preferences("theme = \"light\"");
+    fn frame_latency() {
+        let result = read_preferences("frame_latency = \"1\"");
         assert_eq!(
             &SavedGlobalPreferences {
-                theme_preference: ThemePreference::Light,
+                frame_latency: 2,
                 ..Default::default()
             },
             result.values()
         );
-        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "integer",
+                actual: "string",
+                path: "frame_latency".to_string(),
+            }],
+            result.warnings
+        );
 
-        let result = read_preferences("theme = \"dark\"");
+        let result = read_preferences("frame_latency = 1");
         assert_eq!(
             &SavedGlobalPreferences {
-                theme_preference: ThemePreference::Dark,
+                frame_latency: 1,
                 ..Default::default()
             },
             result.values()
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
 
-        let result = read_preferences("theme = \"default\"");
+        let result = read_preferences("frame_latency = 0");
         assert_eq!(
             &SavedGlobalPreferences {
-                theme_preference: ThemePreference::System,
+                frame_latency: 1,
                 ..Default::default()
             },
             result.values()
         );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("frame_latency = 10");
         assert_eq!(
-            vec![ParseWarning::UnsupportedValue {
-                value: "default".to_string(),
-                path: "theme".to_string(),
-            }],
-            result.warnings
+            &SavedGlobalPreferences {
+                frame_latency: 3,
+                ..Default::default()
+            },
+            result.values()
         );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
 
-        let result = read_preferences("theme = 9");
+    #[test]
+    fn ui_sample_count() {
+        let result = read_preferences("ui_sample_count = 4");
         assert_eq!(
             &SavedGlobalPreferences {
-                theme_preference: ThemePreference::System,
+                ui_sample_count: 4,
                 ..Default::default()
             },
             result.values()
         );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("ui_sample_count = 3");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
         assert_eq!(
-            vec![ParseWarning::UnexpectedType {
-                expected: "string",
-                actual: "integer",
-                path: "theme".to_string(),
+            vec![ParseWarning::UnsupportedValue {
+                value: "3".to_string(),
+                path: "ui_sample_count".to_string(),
             }],
             result.warnings
         );
     }
 
     #[test]
-    fn gamemode() {
-        let result = read_preferences("gamemode = \"on\"");
+    fn theme() {
+        let result = read_preferences("theme = \"light\"");
         assert_eq!(
             &SavedGlobalPreferences {
-                gamemode_preference: GameModePreference::On,
+                theme_preference: ThemePreference::Light,
                 ..Default::default()
             },
             result.values()
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
 
-        let result = read_preferences("gamemode = \"off\"");
+        let result = read_preferences("theme = \"dark\"");
         assert_eq!(
             &SavedGlobalPreferences {
-                gamemode_preference: GameModePreference::Off,
+                theme_preference: ThemePreference::Dark,
                 ..Default::default()
             },
             result.values()
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
 
-        let result = read_preferences("gamemode = \"default\"");
+        let result = read_preferences("theme = \"default\"");
         assert_eq!(
             &SavedGlobalPreferences {
-                gamemode_preference: GameModePreference::Default,
+                theme_preference: ThemePreference::System,
                 ..Default::default()
             },
             result.values()
@@ -633,15 +995,15 @@ mod tests {
         assert_eq!(
             vec![ParseWarning::UnsupportedValue {
                 value: "default".to_string(),
-                path: "gamemode".to_string(),
+                path: "theme".to_string(),
             }],
             result.warnings
         );
 
-        let result = read_preferences("gamemode = 1");
+        let result = read_preferences("theme = 9");
         assert_eq!(
             &SavedGlobalPreferences {
-                gamemode_preference: GameModePreference::Default,
+                theme_preference: ThemePreference::System,
                 ..Default::default()
             },
             result.values()
@@ -650,114 +1012,829 @@ mod tests {
             vec![ParseWarning::UnexpectedType {
                 expected: "string",
                 actual: "integer",
-                path: "gamemode".to_string(),
+                path: "theme".to_string(),
             }],
             result.warnings
         );
     }
 
     #[test]
-    fn open_url_mode() {
-        let result = read_preferences("open_url_mode = \"allow\"");
+    fn background_color() {
+        let result = read_preferences("background_color = \"white\"");
         assert_eq!(
             &SavedGlobalPreferences {
-                open_url_mode: OpenUrlMode::Allow,
+                background_color: BackgroundColor::White,
                 ..Default::default()
             },
             result.values()
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
 
-        let result = read_preferences("open_url_mode = \"deny\"");
+        let result = read_preferences("background_color = \"#1E90ff\"");
         assert_eq!(
             &SavedGlobalPreferences {
-                open_url_mode: OpenUrlMode::Deny,
+                background_color: BackgroundColor::Custom([0x1e, 0x90, 0xff]),
                 ..Default::default()
             },
             result.values()
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
 
-        let result = read_preferences("open_url_mode = \"confirm\"");
+        let result = read_preferences("background_color = \"#fff\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "#fff".to_string(),
+                path: "background_color".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn menu_visibility() {
+        let result = read_preferences("menu_visibility = \"auto-hide\"");
         assert_eq!(
             &SavedGlobalPreferences {
-                open_url_mode: OpenUrlMode::Confirm,
+                menu_visibility: MenuVisibility::AutoHide,
                 ..Default::default()
             },
             result.values()
         );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("menu_visibility = \"never\"");
         assert_eq!(
-            vec![ParseWarning::UnsupportedValue {
-                value: "confirm".to_string(),
-                path: "open_url_mode".to_string(),
-            }],
-            result.warnings
+            &SavedGlobalPreferences {
+                menu_visibility: MenuVisibility::Never,
+                ..Default::default()
+            },
+            result.values()
         );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
 
-        let result = read_preferences("open_url_mode = 1");
+        let result = read_preferences("menu_visibility = \"sometimes\"");
         assert_eq!(
             &SavedGlobalPreferences {
-                open_url_mode: OpenUrlMode::Confirm,
+                menu_visibility: MenuVisibility::Always,
                 ..Default::default()
             },
             result.values()
         );
         assert_eq!(
-            vec![ParseWarning::UnexpectedType {
-                expected: "string",
-                actual: "integer",
-                path: "open_url_mode".to_string(),
+            vec![ParseWarning::UnsupportedValue {
+                value: "sometimes".to_string(),
+                path: "menu_visibility".to_string(),
             }],
             result.warnings
         );
     }
 
     #[test]
-    fn ime_enabled() {
-        let result = read_preferences("ime = {enabled = true}");
+    fn scale_factor_override() {
+        let result = read_preferences("scale_factor_override = 2.0");
         assert_eq!(
             &SavedGlobalPreferences {
-                ime_enabled: Some(true),
+                scale_factor_override: Some(2.0),
                 ..Default::default()
             },
             result.values()
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
 
-        let result = read_preferences("ime.enabled = false");
+        let result = read_preferences("scale_factor_override = 3");
         assert_eq!(
             &SavedGlobalPreferences {
-                ime_enabled: Some(false),
+                scale_factor_override: Some(3.0),
                 ..Default::default()
             },
             result.values()
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
 
-        let result = read_preferences("[ime]\nenabled = false\n");
+        let result = read_preferences("scale_factor_override = 10.0");
         assert_eq!(
             &SavedGlobalPreferences {
-                ime_enabled: Some(false),
+                scale_factor_override: Some(4.0),
                 ..Default::default()
             },
             result.values()
         );
         assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
 
-        let result = read_preferences("ime.enabled = \"x\"");
+        let result = read_preferences("scale_factor_override = \"2x\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "float or integer",
+                actual: "string",
+                path: "scale_factor_override".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn show_fps_in_title() {
+        let result = read_preferences("show_fps_in_title = true");
         assert_eq!(
             &SavedGlobalPreferences {
-                ime_enabled: None,
+                show_fps_in_title: true,
                 ..Default::default()
             },
             result.values()
         );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn idle_power_save() {
+        let result = read_preferences("idle_power_save = false");
         assert_eq!(
-            vec![ParseWarning::UnexpectedType {
-                expected: "boolean",
-                actual: "string",
-                path: "ime.enabled".to_string(),
-            }],
+            &SavedGlobalPreferences {
+                idle_power_save: false,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn touch_as_mouse() {
+        let result = read_preferences("touch_as_mouse = true");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                touch_as_mouse: true,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("touch_as_mouse = 1");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "integer",
+                path: "touch_as_mouse".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn long_press_duration() {
+        let result = read_preferences("long_press_duration = 800");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                long_press_duration: 800,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("long_press_duration = -1");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                long_press_duration: 0,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("long_press_duration = true");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "integer",
+                actual: "boolean",
+                path: "long_press_duration".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn lock_aspect_ratio() {
+        let result = read_preferences("lock_aspect_ratio = true");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                lock_aspect_ratio: true,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("lock_aspect_ratio = \"yes\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "string",
+                path: "lock_aspect_ratio".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn remember_window_geometry() {
+        let result = read_preferences("remember_window_geometry = false");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                remember_window_geometry: false,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn window_geometry() {
+        let result = read_preferences(
+            "[[window_geometry]]\nurl = \"file:///movie.swf\"\nx = -1280\ny = 40\nwidth = 800\nheight = 624\nmaximized = true\n[[window_geometry]]\nurl = \"file:///incomplete.swf\"\nx = 0",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                window_geometry: vec![WindowGeometry {
+                    url: Url::parse("file:///movie.swf").unwrap(),
+                    x: -1280,
+                    y: 40,
+                    width: 800,
+                    height: 624,
+                    maximized: true,
+                    fullscreen: false,
+                }],
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("window_geometry = \"big\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "array of tables",
+                actual: "string",
+                path: "window_geometry".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn main_window_geometry() {
+        let result = read_preferences(
+            "[main_window]\nx = 200\ny = -8\nwidth = 1024\nheight = 768\nmaximized = true",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                main_window_geometry: Some(MainWindowGeometry {
+                    x: 200,
+                    y: -8,
+                    width: 1024,
+                    height: 768,
+                    maximized: true,
+                }),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("[main_window]\nx = 200\ny = -8");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn fullscreen() {
+        let result = read_preferences(
+            "[fullscreen]\ntoggle_shortcut = \"Alt+Enter\"\nexit_shortcut = \"Ctrl+Shift+F\"\nhold_to_exit = true",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                fullscreen: FullscreenPreferences {
+                    toggle_shortcut: Shortcut::new(Modifiers::ALT, Key::Enter),
+                    exit_shortcut: Shortcut::new(Modifiers::COMMAND.plus(Modifiers::SHIFT), Key::F),
+                    hold_to_exit: true,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("fullscreen = { toggle_shortcut = \"Hyper+F11\" }");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "Hyper+F11".to_string(),
+                path: "fullscreen.toggle_shortcut".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn fullscreen_hide_cursor_after() {
+        let result = read_preferences("[fullscreen]\nhide_cursor_after = 10");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                fullscreen: FullscreenPreferences {
+                    hide_cursor_after: 10,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("[fullscreen]\nhide_cursor_after = -1");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                fullscreen: FullscreenPreferences {
+                    hide_cursor_after: 0,
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("[fullscreen]\nhide_cursor_after = \"3\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "integer",
+                actual: "string",
+                path: "fullscreen.hide_cursor_after".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn fullscreen_exclusive() {
+        let result = read_preferences(
+            "[fullscreen]\nexclusive = true\nmonitor = \"DELL U2415\"\nvideo_mode = \"1920x1080@59.94Hz\"",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                fullscreen: FullscreenPreferences {
+                    exclusive: true,
+                    monitor: FullscreenMonitor::Name("DELL U2415".to_string()),
+                    video_mode: Some(VideoModePreference {
+                        width: 1920,
+                        height: 1080,
+                        refresh_rate_millihertz: 59940,
+                    }),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("fullscreen = { monitor = \"#1\" }");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                fullscreen: FullscreenPreferences {
+                    monitor: FullscreenMonitor::Index(1),
+                    ..Default::default()
+                },
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("fullscreen = { video_mode = \"1920x1080\" }");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "1920x1080".to_string(),
+                path: "fullscreen.video_mode".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn gamemode() {
+        let result = read_preferences("gamemode = \"on\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                gamemode_preference: GameModePreference::On,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("gamemode = \"off\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                gamemode_preference: GameModePreference::Off,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("gamemode = \"default\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                gamemode_preference: GameModePreference::Default,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "default".to_string(),
+                path: "gamemode".to_string(),
+            }],
+            result.warnings
+        );
+
+        let result = read_preferences("gamemode = 1");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                gamemode_preference: GameModePreference::Default,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "string",
+                actual: "integer",
+                path: "gamemode".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn open_url_mode() {
+        let result = read_preferences("open_url_mode = \"allow\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                open_url_mode: OpenUrlMode::Allow,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("open_url_mode = \"deny\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                open_url_mode: OpenUrlMode::Deny,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("open_url_mode = \"confirm\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                open_url_mode: OpenUrlMode::Confirm,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "confirm".to_string(),
+                path: "open_url_mode".to_string(),
+            }],
+            result.warnings
+        );
+
+        let result = read_preferences("open_url_mode = 1");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                open_url_mode: OpenUrlMode::Confirm,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "string",
+                actual: "integer",
+                path: "open_url_mode".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn context_menu_mode() {
+        let result = read_preferences("context_menu_mode = \"ctrl-click\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                context_menu_mode: ContextMenuMode::CtrlClick,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("context_menu_mode = \"disabled\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                context_menu_mode: ContextMenuMode::Disabled,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("context_menu_mode = \"shown\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                context_menu_mode: ContextMenuMode::Shown,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "shown".to_string(),
+                path: "context_menu_mode".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn player_runtime() {
+        let result = read_preferences("player_runtime = \"air\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                player_runtime: Some(PlayerRuntime::AIR),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("player_runtime = \"flash_player\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                player_runtime: Some(PlayerRuntime::FlashPlayer),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("player_runtime = \"lightspark\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "lightspark".to_string(),
+                path: "player_runtime".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn player_version() {
+        let result = read_preferences("player_version = 10");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                player_version: Some(PlayerVersion::new(10)),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("player_version = \"32,0,0,465\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                player_version: Some(PlayerVersion {
+                    major: 32,
+                    minor: 0,
+                    build: 0,
+                    revision: 465,
+                }),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("player_version = \"64,0,0,1\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                player_version: Some(PlayerVersion {
+                    revision: 1,
+                    ..PlayerVersion::new(32)
+                }),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("player_version = \"WIN 32,0,0,465\"");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "WIN 32,0,0,465".to_string(),
+                path: "player_version".to_string(),
+            }],
+            result.warnings
+        );
+
+        let result = read_preferences("player_version = true");
+        assert_eq!(&SavedGlobalPreferences::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "integer or string",
+                actual: "boolean",
+                path: "player_version".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn ime_enabled() {
+        let result = read_preferences("ime = {enabled = true}");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                ime_enabled: Some(true),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("ime.enabled = false");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                ime_enabled: Some(false),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("[ime]\nenabled = false\n");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                ime_enabled: Some(false),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read_preferences("ime.enabled = \"x\"");
+        assert_eq!(
+            &SavedGlobalPreferences {
+                ime_enabled: None,
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![ParseWarning::UnexpectedType {
+                expected: "boolean",
+                actual: "string",
+                path: "ime.enabled".to_string(),
+            }],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn key_remaps() {
+        let result = read_preferences(
+            "[key_remaps]\nQ = \"A\"\nW = \"ArrowUp\"\nNope = \"A\"\nE = \"Nope\"",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                key_remaps: KeyRemaps::from_iter([(Key::Q, Key::A), (Key::W, Key::ArrowUp)]),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![
+                ParseWarning::UnsupportedValue {
+                    value: "Nope".to_string(),
+                    path: "key_remaps.Nope".to_string(),
+                },
+                ParseWarning::UnsupportedValue {
+                    value: "Nope".to_string(),
+                    path: "key_remaps.E".to_string(),
+                },
+            ],
+            result.warnings
+        );
+    }
+
+    #[test]
+    fn movie_key_remaps() {
+        let result = read_preferences(
+            "[[movie_key_remaps]]\nurl = \"file:///movie.swf\"\nremaps = { Z = \"Space\" }\n[[movie_key_remaps]]\nremaps = { X = \"A\" }",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                movie_key_remaps: vec![MovieKeyRemaps {
+                    url: Url::parse("file:///movie.swf").unwrap(),
+                    remaps: KeyRemaps::from_iter([(Key::Z, Key::Space)]),
+                }],
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+    }
+
+    #[test]
+    fn movie_options() {
+        let result = read_preferences(
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nparameters = [[\"level\", \"3\"], [\"sitelock\", \"off\"], [\"bad\", 1], \"loose\", [\"level\", \"4\"]]\n[[movie_options]]\nparameters = [[\"a\", \"b\"]]\n[[movie_options]]\nurl = \"file:///game.swf\"\nparameters = { level = \"3\" }\nspoof_url = \"https://example.org/game.swf\"\nspoof_url_as_base = true\n[[movie_options]]\nurl = \"file:///other.swf\"\nquality = \"8x8linear\"\nscale_mode = \"no_border\"\nalign = \"top_left\"\n[[movie_options]]\nurl = \"file:///bad.swf\"\nquality = \"fabulous\"",
+        );
+        assert_eq!(
+            &SavedGlobalPreferences {
+                movie_options: vec![
+                    MovieOptions {
+                        url: Url::parse("https://example.org/game.swf").unwrap(),
+                        parameters: vec![
+                            ("level".to_string(), "3".to_string()),
+                            ("sitelock".to_string(), "off".to_string()),
+                            ("level".to_string(), "4".to_string()),
+                        ],
+                        spoof_url: None,
+                        spoof_url_as_base: false,
+                        quality: None,
+                        scale_mode: None,
+                        align: None,
+                    },
+                    MovieOptions {
+                        url: Url::parse("file:///game.swf").unwrap(),
+                        parameters: vec![],
+                        spoof_url: Some(Url::parse("https://example.org/game.swf").unwrap()),
+                        spoof_url_as_base: true,
+                        quality: None,
+                        scale_mode: None,
+                        align: None,
+                    },
+                    MovieOptions {
+                        quality: Some(StageQuality::High8x8Linear),
+                        scale_mode: Some(StageScaleMode::NoBorder),
+                        align: Some(StageAlign::TOP | StageAlign::LEFT),
+                        ..MovieOptions::new(Url::parse("file:///other.swf").unwrap())
+                    },
+                    MovieOptions::new(Url::parse("file:///bad.swf").unwrap()),
+                ],
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(
+            vec![
+                ParseWarning::UnsupportedValue {
+                    value: "[\"bad\", 1]".to_string(),
+                    path: "movie_options.parameters".to_string(),
+                },
+                ParseWarning::UnexpectedType {
+                    expected: "array",
+                    actual: "string",
+                    path: "movie_options.parameters".to_string(),
+                },
+                ParseWarning::UnexpectedType {
+                    expected: "array",
+                    actual: "inline table",
+                    path: "movie_options.parameters".to_string(),
+                },
+                ParseWarning::UnsupportedValue {
+                    value: "fabulous".to_string(),
+                    path: "movie_options.quality".to_string(),
+                },
+            ],
             result.warnings
         );
     }
@@ -1,12 +1,25 @@
-use crate::cli::{GameModePreference, OpenUrlMode};
-use crate::gui::ThemePreference;
+use crate::cli::{ContextMenuMode, GameModePreference, OpenUrlMode};
+use crate::gui::{
+    BackgroundColor, FullscreenMonitor, KeyRemaps, MenuVisibility, Shortcut, ThemePreference,
+    VideoModePreference,
+};
 use crate::log::FilenamePattern;
+use crate::player::UnfocusedBehavior;
 use crate::preferences::storage::StorageBackend;
-use crate::preferences::{GlobalPreferencesWatchers, SavedGlobalPreferences};
+use crate::preferences::{
+    clamp_player_version, GlobalPreferencesWatchers, MainWindowGeometry, MovieKeyRemaps,
+    MovieOptions, SavedGlobalPreferences, WindowGeometry, FRAME_LATENCY_RANGE,
+    SCALE_FACTOR_OVERRIDE_RANGE, UI_SAMPLE_COUNTS, WINDOW_GEOMETRY_LIMIT,
+};
+use ruffle_core::{PlayerRuntime, StageAlign, StageScaleMode};
 use ruffle_frontend_utils::parse::DocumentHolder;
+use ruffle_frontend_utils::player_options::PlayerVersion;
+use ruffle_frontend_utils::write::TableExt;
+use ruffle_render::quality::StageQuality;
 use ruffle_render_wgpu::clap::{GraphicsBackend, PowerPreference};
-use toml_edit::value;
+use toml_edit::{value, Array, InlineTable, Item, Table};
 use unic_langid::LanguageIdentifier;
+use url::Url;
 
 pub struct PreferencesWriter<'a>(
     &'a mut DocumentHolder<SavedGlobalPreferences>,
@@ -54,6 +67,17 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_unfocused_behavior(&mut self, unfocused_behavior: UnfocusedBehavior) {
+        self.0.edit(|values, toml_document| {
+            if let Some(unfocused_behavior) = unfocused_behavior.as_str() {
+                toml_document["unfocused_behavior"] = value(unfocused_behavior);
+            } else {
+                toml_document.remove("unfocused_behavior");
+            }
+            values.unfocused_behavior = unfocused_behavior;
+        })
+    }
+
     pub fn set_mute(&mut self, mute: bool) {
         self.0.edit(|values, toml_document| {
             toml_document["mute"] = value(mute);
@@ -61,6 +85,13 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_mute_shortcut(&mut self, shortcut: Shortcut) {
+        self.0.edit(|values, toml_document| {
+            toml_document["mute_shortcut"] = value(shortcut.to_string());
+            values.mute_shortcut = shortcut;
+        })
+    }
+
     pub fn set_volume(&mut self, volume: f32) {
         self.0.edit(|values, toml_document| {
             toml_document["volume"] = value(volume as f64);
@@ -96,6 +127,48 @@ impl<'a> PreferencesWriter<'a> {
         })
     }
 
+    pub fn set_frame_latency(&mut self, frame_latency: u32) {
+        let frame_latency =
+            frame_latency.clamp(*FRAME_LATENCY_RANGE.start(), *FRAME_LATENCY_RANGE.end());
+        self.0.edit(|values, toml_document| {
+            toml_document["frame_latency"] = value(frame_latency as i64);
+            values.frame_latency = frame_latency;
+        });
+        if let Some(watcher) = self.1.map(|w| &w.frame_latency_watcher) {
+            let _ = watcher.send(frame_latency);
+        }
+    }
+
+    pub fn set_ui_sample_count(&mut self, ui_sample_count: u32) {
+        if !UI_SAMPLE_COUNTS.contains(&ui_sample_count) {
+            return;
+        }
+        self.0.edit(|values, toml_document| {
+            toml_document["ui_sample_count"] = value(ui_sample_count as i64);
+            values.ui_sample_count = ui_sample_count;
+        });
+    }
+
+    pub fn set_scale_factor_override(&mut self, scale_factor_override: Option<f64>) {
+        let scale_factor_override = scale_factor_override.map(|scale_factor| {
+            scale_factor.clamp(
+                *SCALE_FACTOR_OVERRIDE_RANGE.start(),
+                *SCALE_FACTOR_OVERRIDE_RANGE.end(),
+            )
+        });
+        self.0.edit(|values, toml_document| {
+            if let Some(scale_factor_override) = scale_factor_override {
+                toml_document["scale_factor_override"] = value(scale_factor_override);
+            } else {
+                toml_document.remove("scale_factor_override");
+            }
+            values.scale_factor_override = scale_factor_override;
+        });
+        if let Some(watcher) = self.1.map(|w| &w.scale_factor_override_watcher) {
+            let _ = watcher.send(scale_factor_override);
+        }
+    }
+
     pub fn set_theme_preference(&mut self, theme_preference: ThemePreference) {
         self.0.edit(|values, toml_document| {
             if let Some(theme_preference) = theme_preference.as_str() {
@@ -110,6 +183,295 @@ impl<'a> PreferencesWriter<'a> {
         }
     }
 
+    pub fn set_background_color(&mut self, background_color: BackgroundColor) {
+        self.0.edit(|values, toml_document| {
+            if let Some(background_color) = background_color.as_preference() {
+                toml_document["background_color"] = value(background_color);
+            } else {
+                toml_document.remove("background_color");
+            }
+            values.background_color = background_color;
+        });
+        if let Some(watcher) = self.1.map(|w| &w.background_color_watcher) {
+            let _ = watcher.send(background_color);
+        }
+    }
+
+    pub fn set_menu_visibility(&mut self, menu_visibility: MenuVisibility) {
+        self.0.edit(|values, toml_document| {
+            if let Some(menu_visibility) = menu_visibility.as_str() {
+                toml_document["menu_visibility"] = value(menu_visibility);
+            } else {
+                toml_document.remove("menu_visibility");
+            }
+            values.menu_visibility = menu_visibility;
+        });
+        if let Some(watcher) = self.1.map(|w| &w.menu_visibility_watcher) {
+            let _ = watcher.send(menu_visibility);
+        }
+    }
+
+    pub fn set_show_fps_in_title(&mut self, show_fps_in_title: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["show_fps_in_title"] = value(show_fps_in_title);
+            values.show_fps_in_title = show_fps_in_title;
+        });
+        if let Some(watcher) = self.1.map(|w| &w.show_fps_in_title_watcher) {
+            let _ = watcher.send(show_fps_in_title);
+        }
+    }
+
+    pub fn set_idle_power_save(&mut self, idle_power_save: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["idle_power_save"] = value(idle_power_save);
+            values.idle_power_save = idle_power_save;
+        });
+        if let Some(watcher) = self.1.map(|w| &w.idle_power_save_watcher) {
+            let _ = watcher.send(idle_power_save);
+        }
+    }
+
+    pub fn set_touch_as_mouse(&mut self, touch_as_mouse: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["touch_as_mouse"] = value(touch_as_mouse);
+            values.touch_as_mouse = touch_as_mouse;
+        })
+    }
+
+    pub fn set_long_press_duration(&mut self, milliseconds: u32) {
+        self.0.edit(|values, toml_document| {
+            toml_document["long_press_duration"] = value(milliseconds as i64);
+            values.long_press_duration = milliseconds;
+        })
+    }
+
+    pub fn set_lock_aspect_ratio(&mut self, lock_aspect_ratio: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["lock_aspect_ratio"] = value(lock_aspect_ratio);
+            values.lock_aspect_ratio = lock_aspect_ratio;
+        })
+    }
+
+    pub fn set_remember_window_geometry(&mut self, remember_window_geometry: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["remember_window_geometry"] = value(remember_window_geometry);
+            values.remember_window_geometry = remember_window_geometry;
+        })
+    }
+
+    /// Saves the window geometry of a movie, replacing any previous one for the same URL.
+    ///
+    /// The most recently saved entry goes last, and the oldest ones are evicted
+    /// once there are more than [`WINDOW_GEOMETRY_LIMIT`].
+    pub fn set_window_geometry(&mut self, geometry: WindowGeometry) {
+        self.0.edit(|values, toml_document| {
+            let array = toml_document.get_or_create_array_of_tables("window_geometry");
+
+            // Entries are matched by URL rather than by index,
+            // as the invalid ones in the document were skipped when reading.
+            array.retain(|table| {
+                table.get("url").and_then(|url| url.as_str()) != Some(geometry.url.as_str())
+            });
+            values.window_geometry.retain(|x| x.url != geometry.url);
+
+            while array.len() >= WINDOW_GEOMETRY_LIMIT {
+                use crate::cli::{ContextMenuMode, GameModePreference, OpenUrlMode};
+            }
+            if values.window_geometry.len() >= WINDOW_GEOMETRY_LIMIT {
+                let excess = values.window_geometry.len() + 1 - WINDOW_GEOMETRY_LIMIT;
+                values.window_geometry.drain(..excess);
+            }
+
+            let mut table = Table::new();
+            table["url"] = value(geometry.url.as_str());
+            table["x"] = value(geometry.x as i64);
+            table["y"] = value(geometry.y as i64);
+            table["width"] = value(geometry.width as i64);
+            table["height"] = value(geometry.height as i64);
+            table["maximized"] = value(geometry.maximized);
+            table["fullscreen"] = value(geometry.fullscreen);
+            array.push(table);
+            values.window_geometry.push(geometry);
+        })
+    }
+
+    pub fn set_main_window_geometry(&mut self, geometry: MainWindowGeometry) {
+        self.0.edit(|values, toml_document| {
+            toml_document["main_window"]["x"] = value(geometry.x as i64);
+            toml_document["main_window"]["y"] = value(geometry.y as i64);
+            toml_document["main_window"]["width"] = value(geometry.width as i64);
+            toml_document["main_window"]["height"] = value(geometry.height as i64);
+            toml_document["main_window"]["maximized"] = value(geometry.maximized);
+            values.main_window_geometry = Some(geometry);
+        })
+    }
+
+    pub fn set_fullscreen_toggle_shortcut(&mut self, shortcut: Shortcut) {
+        self.0.edit(|values, toml_document| {
+            toml_document["fullscreen"]["toggle_shortcut"] = value(shortcut.to_string());
+            values.fullscreen.toggle_shortcut = shortcut;
+        })
+    }
+
+    pub fn set_fullscreen_exit_shortcut(&mut self, shortcut: Shortcut) {
+        self.0.edit(|values, toml_document| {
+            toml_document["fullscreen"]["exit_shortcut"] = value(shortcut.to_string());
+            values.fullscreen.exit_shortcut = shortcut;
+        })
+    }
+
+    pub fn set_fullscreen_hold_to_exit(&mut self, hold_to_exit: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["fullscreen"]["hold_to_exit"] = value(hold_to_exit);
+            values.fullscreen.hold_to_exit = hold_to_exit;
+        })
+    }
+
+    pub fn set_fullscreen_hide_cursor_after(&mut self, seconds: u32) {
+        self.0.edit(|values, toml_document| {
+            toml_document["fullscreen"]["hide_cursor_after"] = value(seconds as i64);
+            values.fullscreen.hide_cursor_after = seconds;
+        })
+    }
+
+    pub fn set_fullscreen_exclusive(&mut self, exclusive: bool) {
+        self.0.edit(|values, toml_document| {
+            toml_document["fullscreen"]["exclusive"] = value(exclusive);
+            values.fullscreen.exclusive = exclusive;
+        })
+    }
+
+    pub fn set_fullscreen_monitor(&mut self, monitor: FullscreenMonitor) {
+        self.0.edit(|values, toml_document| {
+            if let Some(monitor) = monitor.as_preference() {
+                toml_document["fullscreen"]["monitor"] = value(monitor);
+            } else {
+                toml_document["fullscreen"]["monitor"] = toml_edit::Item::None;
+            }
+            values.fullscreen.monitor = monitor;
+        })
+    }
+
+    pub fn set_fullscreen_video_mode(&mut self, video_mode: Option<VideoModePreference>) {
+        self.0.edit(|values, toml_document| {
+            if let Some(video_mode) = video_mode {
+                toml_document["fullscreen"]["video_mode"] = value(video_mode.to_string());
+            } else {
+                toml_document["fullscreen"]["video_mode"] = toml_edit::Item::None;
+            }
+            values.fullscreen.video_mode = video_mode;
+        })
+    }
+
+    pub fn set_key_remaps(&mut self, remaps: KeyRemaps) {
+        self.0.edit(|values, toml_document| {
+            if remaps.is_empty() {
+                toml_document.remove("key_remaps");
+            } else {
+                let mut table = Table::new();
+                for (from, to) in remaps.iter() {
+                    table[from.name()] = value(to.name());
+                }
+                toml_document["key_remaps"] = Item::Table(table);
+            }
+            values.key_remaps = remaps;
+        })
+    }
+
+    /// Saves the keys remapped only for the movie at `url`, replacing any previous ones.
+    pub fn set_movie_key_remaps(&mut self, url: &Url, remaps: KeyRemaps) {
+        self.0.edit(|values, toml_document| {
+            let array = toml_document.get_or_create_array_of_tables("movie_key_remaps");
+
+            // Entries are matched by URL rather than by index,
+            // as the invalid ones in the document were skipped when reading.
+            array.retain(|table| {
+                table.get("url").and_then(|url| url.as_str()) != Some(url.as_str())
+            });
+            values.movie_key_remaps.retain(|x| &x.url != url);
+
+            if !remaps.is_empty() {
+                let mut inline = InlineTable::new();
+                for (from, to) in remaps.iter() {
+                    inline.insert(from.name(), to.name().into());
+                }
+                let mut table = Table::new();
+                table["url"] = value(url.as_str());
+                table["remaps"] = value(inline);
+                array.push(table);
+                values.movie_key_remaps.push(MovieKeyRemaps {
+                    url: url.clone(),
+                    remaps,
+                });
+            }
+            if array.is_empty() {
+                toml_document.remove("movie_key_remaps");
+            }
+        })
+    }
+
+    /// Saves the options a movie was opened with, replacing any previous ones of the same movie.
+    pub fn set_movie_options(&mut self, options: MovieOptions) {
+        self.0.edit(|values, toml_document| {
+            let array = toml_document.get_or_create_array_of_tables("movie_options");
+
+            // Entries are matched by URL rather than by index,
+            // as the invalid ones in the document were skipped when reading.
+            array.retain(|table| {
+                table.get("url").and_then(|url| url.as_str()) != Some(options.url.as_str())
+            });
+            values.movie_options.retain(|x| x.url != options.url);
+
+            if !options.is_empty() {
+                let mut table = Table::new();
+                table["url"] = value(options.url.as_str());
+                if !options.parameters.is_empty() {
+                    // Pairs rather than a table, as a movie may be given the same parameter twice.
+                    let pairs: Array = options
+                        .parameters
+                        .iter()
+                        .map(|(name, parameter)| Array::from_iter([name, parameter]))
+                        .collect();
+                    table["parameters"] = value(pairs);
+                }
+                if let Some(spoof_url) = &options.spoof_url {
+                    table["spoof_url"] = value(spoof_url.as_str());
+                    if options.spoof_url_as_base {
+                        table["spoof_url_as_base"] = value(true);
+                    }
+                }
+                if let Some(quality) = options.quality {
+                    table["quality"] = value(quality.to_string());
+                }
+                if let Some(scale_mode) = options.scale_mode {
+                    table["scale_mode"] = value(scale_mode_name(scale_mode));
+                }
+                if let Some(align) = options.align.and_then(align_name) {
+                    table["align"] = value(align);
+                }
+                array.push(table);
+                values.movie_options.push(options);
+            }
+            if array.is_empty() {
+                toml_document.remove("movie_options");
+            }
+        })
+    }
+
+    /// Changes some of the options remembered for the movie, keeping the others.
+    pub fn update_movie_options(&mut self, url: &Url, update: impl FnOnce(&mut MovieOptions)) {
+        let mut options = self
+            .0
+            .values()
+            .movie_options
+            .iter()
+            .find(|options| &options.url == url)
+            .cloned()
+            .unwrap_or_else(|| MovieOptions::new(url.clone()));
+        update(&mut options);
+        self.set_movie_options(options);
+    }
+
     pub fn set_gamemode_preference(&mut self, gamemode_preference: GameModePreference) {
         self.0.edit(|values, toml_document| {
             if let Some(gamemode_preference) = gamemode_preference.as_str() {
@@ -132,6 +494,44 @@ impl<'a> PreferencesWriter<'a> {
         });
     }
 
+    pub fn set_context_menu_mode(&mut self, context_menu_mode: ContextMenuMode) {
+        self.0.edit(|values, toml_document| {
+            if let Some(context_menu_mode) = context_menu_mode.as_str() {
+                toml_document["context_menu_mode"] = value(context_menu_mode);
+            } else {
+                toml_document.remove("context_menu_mode");
+            }
+            values.context_menu_mode = context_menu_mode;
+        });
+    }
+
+    pub fn set_player_runtime(&mut self, player_runtime: Option<PlayerRuntime>) {
+        self.0.edit(|values, toml_document| {
+            match player_runtime {
+                Some(PlayerRuntime::FlashPlayer) => {
+                    toml_document["player_runtime"] = value("flash_player")
+                }
+                Some(PlayerRuntime::AIR) => toml_document["player_runtime"] = value("air"),
+                None => {
+                    toml_document.remove("player_runtime");
+                }
+            }
+            values.player_runtime = player_runtime;
+        });
+    }
+
+    pub fn set_player_version(&mut self, player_version: Option<PlayerVersion>) {
+        let player_version = player_version.map(clamp_player_version);
+        self.0.edit(|values, toml_document| {
+            if let Some(player_version) = player_version {
+                toml_document["player_version"] = value(player_version.to_string());
+            } else {
+                toml_document.remove("player_version");
+            }
+            values.player_version = player_version;
+        });
+    }
+
     pub fn set_ime_enabled(&mut self, ime_enabled: Option<bool>) {
         self.0.edit(|values, toml_document| {
             if let Some(ime_enabled) = ime_enabled {
@@ -144,11 +544,42 @@ impl<'a> PreferencesWriter<'a> {
     }
 }
 
+/// The name of the scale mode, as read by [`StageScaleMode::from_str`].
+fn scale_mode_name(scale_mode: StageScaleMode) -> &'static str {
+    match scale_mode {
+        StageScaleMode::ExactFit => "exact_fit",
+        StageScaleMode::NoBorder => "no_border",
+        StageScaleMode::NoScale => "no_scale",
+        StageScaleMode::ShowAll => "show_all",
+    }
+}
+
+/// The name of the alignment, as read by [`StageAlign::from_str`],
+/// if it has one, as conflicting flags such as top and bottom together don't.
+fn align_name(align: StageAlign) -> Option<&'static str> {
+    [
+        ("center", StageAlign::empty()),
+        ("top", StageAlign::TOP),
+        ("bottom", StageAlign::BOTTOM),
+        ("left", StageAlign::LEFT),
+        ("right", StageAlign::RIGHT),
+        ("top_left", StageAlign::TOP | StageAlign::LEFT),
+        ("top_right", StageAlign::TOP | StageAlign::RIGHT),
+        ("bottom_left", StageAlign::BOTTOM | StageAlign::LEFT),
+        ("bottom_right", StageAlign::BOTTOM | StageAlign::RIGHT),
+    ]
+    .into_iter()
+    .find(|(_, value)| *value == align)
+    .map(|(name, _)| name)
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
     use crate::preferences::read::read_preferences;
+    use egui::{Key, Modifiers};
     use fluent_templates::loader::langid;
+    use url::Url;
 
     ruffle_frontend_utils::define_serialization_test_helpers!(
         read_preferences,
@@ -216,6 +647,34 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_unfocused_behavior() {
+        test(
+            "",
+            |writer| writer.set_unfocused_behavior(UnfocusedBehavior::Pause),
+            "unfocused_behavior = \"pause\"\n",
+        );
+        test(
+            "unfocused_behavior = \"pause\"",
+            |writer| writer.set_unfocused_behavior(UnfocusedBehavior::Mute),
+            "unfocused_behavior = \"mute\"\n",
+        );
+        test(
+            "unfocused_behavior = \"mute\"",
+            |writer| writer.set_unfocused_behavior(UnfocusedBehavior::Continue),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_mute_shortcut() {
+        test(
+            "",
+            |writer| writer.set_mute_shortcut(Shortcut::new(Modifiers::COMMAND, Key::M)),
+            "mute_shortcut = \"Ctrl+M\"\n",
+        );
+    }
+
     #[test]
     fn set_volume() {
         test("", |writer| writer.set_volume(0.5), "volume = 0.5\n");
@@ -297,6 +756,53 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_frame_latency() {
+        test(
+            "",
+            |writer| writer.set_frame_latency(1),
+            "frame_latency = 1\n",
+        );
+        test(
+            "frame_latency = 1",
+            |writer| writer.set_frame_latency(5),
+            "frame_latency = 3\n",
+        );
+    }
+
+    #[test]
+    fn set_ui_sample_count() {
+        test(
+            "",
+            |writer| writer.set_ui_sample_count(4),
+            "ui_sample_count = 4\n",
+        );
+        test(
+            "ui_sample_count = 2\n",
+            |writer| writer.set_ui_sample_count(8),
+            "ui_sample_count = 2\n",
+        );
+    }
+
+    #[test]
+    fn set_scale_factor_override() {
+        test(
+            "",
+            |writer| writer.set_scale_factor_override(Some(1.5)),
+            "scale_factor_override = 1.5\n",
+        );
+        test(
+            "scale_factor_override = 1.5",
+            |writer| writer.set_scale_factor_override(Some(0.1)),
+            "scale_factor_override = 0.5\n",
+        );
+        test(
+            "scale_factor_override = 1.5",
+            |writer| writer.set_scale_factor_override(None),
+            "",
+        );
+    }
+
     #[test]
     fn set_theme() {
         test(
@@ -311,6 +817,229 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_show_fps_in_title() {
+        test(
+            "",
+            |writer| writer.set_show_fps_in_title(true),
+            "show_fps_in_title = true\n",
+        );
+    }
+
+    #[test]
+    fn set_idle_power_save() {
+        test(
+            "",
+            |writer| writer.set_idle_power_save(false),
+            "idle_power_save = false\n",
+        );
+    }
+
+    #[test]
+    fn set_touch_as_mouse() {
+        test(
+            "",
+            |writer| writer.set_touch_as_mouse(true),
+            "touch_as_mouse = true\n",
+        );
+    }
+
+    #[test]
+    fn set_long_press_duration() {
+        test(
+            "",
+            |writer| writer.set_long_press_duration(0),
+            "long_press_duration = 0\n",
+        );
+        test(
+            "long_press_duration = 0",
+            |writer| writer.set_long_press_duration(800),
+            "long_press_duration = 800\n",
+        );
+    }
+
+    #[test]
+    fn set_lock_aspect_ratio() {
+        test(
+            "",
+            |writer| writer.set_lock_aspect_ratio(true),
+            "lock_aspect_ratio = true\n",
+        );
+        test(
+            "lock_aspect_ratio = true",
+            |writer| writer.set_lock_aspect_ratio(false),
+            "lock_aspect_ratio = false\n",
+        );
+    }
+
+    #[test]
+    fn set_remember_window_geometry() {
+        test(
+            "",
+            |writer| writer.set_remember_window_geometry(false),
+            "remember_window_geometry = false\n",
+        );
+    }
+
+    #[test]
+    fn set_window_geometry() {
+        test(
+            "",
+            |writer| {
+                writer.set_window_geometry(WindowGeometry {
+                    url: Url::parse("file:///movie.swf").unwrap(),
+                    x: 100,
+                    y: -20,
+                    width: 550,
+                    height: 424,
+                    maximized: false,
+                    fullscreen: true,
+                })
+            },
+            "[[window_geometry]]\nurl = \"file:///movie.swf\"\nx = 100\ny = -20\nwidth = 550\nheight = 424\nmaximized = false\nfullscreen = true\n",
+        );
+
+        test(
+            "[[window_geometry]]\nurl = \"file:///movie.swf\"\nx = 0\ny = 0\nwidth = 1\nheight = 1\n[[window_geometry]]\nurl = \"file:///other.swf\"\nx = 0\ny = 0\nwidth = 2\nheight = 2\n",
+            |writer| {
+                writer.set_window_geometry(WindowGeometry {
+                    url: Url::parse("file:///movie.swf").unwrap(),
+                    x: 10,
+                    y: 20,
+                    width: 800,
+                    height: 600,
+                    maximized: true,
+                    fullscreen: false,
+                })
+            },
+            "[[window_geometry]]\nurl = \"file:///other.swf\"\nx = 0\ny = 0\nwidth = 2\nheight = 2\n\n[[window_geometry]]\nurl = \"file:///movie.swf\"\nx = 10\ny = 20\nwidth = 800\nheight = 600\nmaximized = true\nfullscreen = false\n",
+        );
+    }
+
+    #[test]
+    fn set_main_window_geometry() {
+        test(
+            "",
+            |writer| {
+                writer.set_main_window_geometry(MainWindowGeometry {
+                    x: 200,
+                    y: -8,
+                    width: 1024,
+                    height: 768,
+                    maximized: false,
+                })
+            },
+            "[main_window]\nx = 200\ny = -8\nwidth = 1024\nheight = 768\nmaximized = false\n",
+        );
+        test(
+            "[main_window]\nx = 0\ny = 0\nwidth = 1\nheight = 1\nmaximized = false\n",
+            |writer| {
+                writer.set_main_window_geometry(MainWindowGeometry {
+                    x: 10,
+                    y: 20,
+                    width: 800,
+                    height: 600,
+                    maximized: true,
+                })
+            },
+            "[main_window]\nx = 10\ny = 20\nwidth = 800\nheight = 600\nmaximized = true\n",
+        );
+    }
+
+    #[test]
+    fn set_fullscreen_shortcuts() {
+        test(
+            "",
+            |writer| {
+                writer.set_fullscreen_toggle_shortcut(Shortcut::new(Modifiers::ALT, Key::Enter))
+            },
+            "fullscreen = { toggle_shortcut = \"Alt+Enter\" }\n",
+        );
+        test(
+            "[fullscreen]\ntoggle_shortcut = \"Alt+Enter\"\n",
+            |writer| {
+                writer.set_fullscreen_exit_shortcut(Shortcut::new(
+                    Modifiers::COMMAND.plus(Modifiers::SHIFT),
+                    Key::F,
+                ));
+                writer.set_fullscreen_hold_to_exit(true);
+            },
+            "[fullscreen]\ntoggle_shortcut = \"Alt+Enter\"\nexit_shortcut = \"Ctrl+Shift+F\"\nhold_to_exit = true\n",
+        );
+    }
+
+    #[test]
+    fn set_fullscreen_hide_cursor_after() {
+        test(
+            "",
+            |writer| writer.set_fullscreen_hide_cursor_after(0),
+            "fullscreen = { hide_cursor_after = 0 }\n",
+        );
+        test(
+            "[fullscreen]\nhide_cursor_after = 0\n",
+            |writer| writer.set_fullscreen_hide_cursor_after(10),
+            "[fullscreen]\nhide_cursor_after = 10\n",
+        );
+    }
+
+    #[test]
+    fn set_fullscreen_exclusive() {
+        test(
+            "",
+            |writer| {
+                writer.set_fullscreen_exclusive(true);
+                writer.set_fullscreen_monitor(FullscreenMonitor::Name("DELL U2415".to_string()));
+                writer.set_fullscreen_video_mode(Some(VideoModePreference {
+                    width: 1920,
+                    height: 1080,
+                    refresh_rate_millihertz: 59940,
+                }));
+            },
+            "fullscreen = { exclusive = true, monitor = \"DELL U2415\", video_mode = \"1920x1080@59.940Hz\" }\n",
+        );
+        test(
+            "[fullscreen]\nexclusive = true\nmonitor = \"DELL U2415\"\nvideo_mode = \"1920x1080@59.940Hz\"\n",
+            |writer| {
+                writer.set_fullscreen_monitor(FullscreenMonitor::Current);
+                writer.set_fullscreen_video_mode(None);
+            },
+            "[fullscreen]\nexclusive = true\n",
+        );
+        test(
+            "",
+            |writer| writer.set_fullscreen_monitor(FullscreenMonitor::Index(1)),
+            "fullscreen = { monitor = \"#1\" }\n",
+        );
+    }
+
+    #[test]
+    fn set_background_color() {
+        test(
+            "",
+            |writer| writer.set_background_color(BackgroundColor::Custom([0x1e, 0x90, 0xff])),
+            "background_color = \"#1e90ff\"\n",
+        );
+        test(
+            "background_color = \"black\"",
+            |writer| writer.set_background_color(BackgroundColor::Stage),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_menu_visibility() {
+        test(
+            "",
+            |writer| writer.set_menu_visibility(MenuVisibility::AutoHide),
+            "menu_visibility = \"auto-hide\"\n",
+        );
+        test(
+            "menu_visibility = \"never\"",
+            |writer| writer.set_menu_visibility(MenuVisibility::Always),
+            "",
+        );
+    }
+
     #[test]
     fn set_gamemode() {
         test(
@@ -339,6 +1068,137 @@ mod tests {
         );
     }
 
+    #[test]
+    fn set_context_menu_mode() {
+        test(
+            "",
+            |writer| writer.set_context_menu_mode(ContextMenuMode::CtrlClick),
+            "context_menu_mode = \"ctrl-click\"\n",
+        );
+        test(
+            "context_menu_mode = \"ctrl-click\"",
+            |writer| writer.set_context_menu_mode(ContextMenuMode::Disabled),
+            "context_menu_mode = \"disabled\"\n",
+        );
+        test(
+            "context_menu_mode = \"disabled\"",
+            |writer| writer.set_context_menu_mode(ContextMenuMode::Shown),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_key_remaps() {
+        test(
+            "",
+            |writer| {
+                writer.set_key_remaps(KeyRemaps::from_iter([
+                    (Key::Q, Key::A),
+                    (Key::W, Key::ArrowUp),
+                ]))
+            },
+            "[key_remaps]\nQ = \"A\"\nW = \"Up\"\n",
+        );
+        test(
+            "[key_remaps]\nQ = \"A\"\n",
+            |writer| writer.set_key_remaps(KeyRemaps::default()),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_movie_key_remaps() {
+        let url = Url::parse("file:///movie.swf").unwrap();
+        test(
+            "",
+            |writer| {
+                writer.set_movie_key_remaps(&url, KeyRemaps::from_iter([(Key::Z, Key::Space)]))
+            },
+            "[[movie_key_remaps]]\nurl = \"file:///movie.swf\"\nremaps = { Z = \"Space\" }\n",
+        );
+        test(
+            "[[movie_key_remaps]]\nurl = \"file:///movie.swf\"\nremaps = { Z = \"Space\" }\n",
+            |writer| writer.set_movie_key_remaps(&url, KeyRemaps::default()),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_movie_options() {
+        let url = Url::parse("https://example.org/game.swf").unwrap();
+        test(
+            "",
+            |writer| {
+                writer.set_movie_options(MovieOptions {
+                    url: url.clone(),
+                    parameters: vec![
+                        ("level".to_string(), "3".to_string()),
+                        ("sitelock".to_string(), "off".to_string()),
+                        ("level".to_string(), "4".to_string()),
+                    ],
+                    spoof_url: None,
+                    spoof_url_as_base: false,
+                    quality: None,
+                    scale_mode: None,
+                    align: None,
+                })
+            },
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nparameters = [[\"level\", \"3\"], [\"sitelock\", \"off\"], [\"level\", \"4\"]]\n",
+        );
+        test(
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nparameters = [[\"level\", \"3\"]]\n",
+            |writer| {
+                writer.set_movie_options(MovieOptions {
+                    url: url.clone(),
+                    parameters: vec![],
+                    spoof_url: Some(Url::parse("https://example.com/game.swf").unwrap()),
+                    spoof_url_as_base: true,
+                    quality: None,
+                    scale_mode: None,
+                    align: None,
+                })
+            },
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nspoof_url = \"https://example.com/game.swf\"\nspoof_url_as_base = true\n",
+        );
+        test(
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nparameters = [[\"level\", \"3\"]]\n",
+            |writer| writer.set_movie_options(MovieOptions::new(url.clone())),
+            "",
+        );
+    }
+
+    #[test]
+    fn update_movie_options() {
+        let url = Url::parse("https://example.org/game.swf").unwrap();
+        test(
+            "",
+            |writer| {
+                writer
+                    .update_movie_options(&url, |options| options.quality = Some(StageQuality::Low))
+            },
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nquality = \"low\"\n",
+        );
+        test(
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nparameters = [[\"level\", \"3\"]]\nquality = \"low\"\n",
+            |writer| {
+                writer.update_movie_options(&url, |options| {
+                    options.quality = Some(StageQuality::High16x16)
+                })
+            },
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nparameters = [[\"level\", \"3\"]]\nquality = \"16x16\"\n",
+        );
+        test(
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nquality = \"low\"\n",
+            |writer| {
+                writer.update_movie_options(&url, |options| {
+                    options.scale_mode = Some(StageScaleMode::NoScale);
+                    options.align = Some(StageAlign::BOTTOM | StageAlign::RIGHT);
+                })
+            },
+            "[[movie_options]]\nurl = \"https://example.org/game.swf\"\nquality = \"low\"\nscale_mode = \"no_scale\"\nalign = \"bottom_right\"\n",
+        );
+    }
+
     #[test]
     fn set_ime_enabled() {
         test(
@@ -357,4 +1217,54 @@ mod tests {
             "",
         );
     }
+
+    #[test]
+    fn set_player_runtime() {
+        test(
+            "",
+            |writer| writer.set_player_runtime(Some(PlayerRuntime::AIR)),
+            "player_runtime = \"air\"\n",
+        );
+        test(
+            "player_runtime = \"air\"",
+            |writer| writer.set_player_runtime(Some(PlayerRuntime::FlashPlayer)),
+            "player_runtime = \"flash_player\"\n",
+        );
+        test(
+            "player_runtime = \"air\"",
+            |writer| writer.set_player_runtime(None),
+            "",
+        );
+    }
+
+    #[test]
+    fn set_player_version() {
+        test(
+            "",
+            |writer| writer.set_player_version(Some(PlayerVersion::new(10))),
+            "player_version = \"10,0,0,0\"\n",
+        );
+        test(
+            "player_version = 10",
+            |writer| {
+                writer.set_player_version(Some(PlayerVersion {
+                    major: 32,
+                    minor: 0,
+                    build: 0,
+                    revision: 465,
+                }))
+            },
+            "player_version = \"32,0,0,465\"\n",
+        );
+        test(
+            "player_version = 10",
+            |writer| writer.set_player_version(Some(PlayerVersion::new(64))),
+            "player_version = \"32,0,0,0\"\n",
+        );
+        test(
+            "player_version = 10",
+            |writer| writer.set_player_version(None),
+            "",
+        );
+    }
 }
@@ -1,4 +1,5 @@
 use anyhow::{anyhow, Error};
+use egui::Key as EguiKey;
 use gilrs::Button;
 use ruffle_core::events::{
     GamepadButton, KeyDescriptor, KeyLocation, LogicalKey, NamedKey as RuffleNamedKey, PhysicalKey,
@@ -72,6 +73,128 @@ pub fn winit_input_to_ruffle_key_descriptor(event: &KeyEvent) -> KeyDescriptor {
     }
 }
 
+/// Converts a key that a remapped key is delivered to the movie as into a Ruffle `KeyDescriptor`,
+/// as if that key was pressed on a US keyboard.
+pub fn egui_key_to_ruffle_key_descriptor(key: EguiKey) -> KeyDescriptor {
+    let named = |physical_key, named_key| (physical_key, LogicalKey::Named(named_key));
+    let character = |physical_key, ch| (physical_key, LogicalKey::Character(ch));
+    let (physical_key, logical_key) = match key {
+        EguiKey::ArrowDown => named(PhysicalKey::ArrowDown, RuffleNamedKey::ArrowDown),
+        EguiKey::ArrowLeft => named(PhysicalKey::ArrowLeft, RuffleNamedKey::ArrowLeft),
+        EguiKey::ArrowRight => named(PhysicalKey::ArrowRight, RuffleNamedKey::ArrowRight),
+        EguiKey::ArrowUp => named(PhysicalKey::ArrowUp, RuffleNamedKey::ArrowUp),
+        EguiKey::Escape => named(PhysicalKey::Escape, RuffleNamedKey::Escape),
+        EguiKey::Tab => named(PhysicalKey::Tab, RuffleNamedKey::Tab),
+        EguiKey::Backspace => named(PhysicalKey::Backspace, RuffleNamedKey::Backspace),
+        EguiKey::Enter => named(PhysicalKey::Enter, RuffleNamedKey::Enter),
+        EguiKey::Insert => named(PhysicalKey::Insert, RuffleNamedKey::Insert),
+        EguiKey::Delete => named(PhysicalKey::Delete, RuffleNamedKey::Delete),
+        EguiKey::Home => named(PhysicalKey::Home, RuffleNamedKey::Home),
+        EguiKey::End => named(PhysicalKey::End, RuffleNamedKey::End),
+        EguiKey::PageUp => named(PhysicalKey::PageUp, RuffleNamedKey::PageUp),
+        EguiKey::PageDown => named(PhysicalKey::PageDown, RuffleNamedKey::PageDown),
+        EguiKey::Copy => named(PhysicalKey::Unknown, RuffleNamedKey::Copy),
+        EguiKey::Cut => named(PhysicalKey::Unknown, RuffleNamedKey::Cut),
+        EguiKey::Paste => named(PhysicalKey::Unknown, RuffleNamedKey::Paste),
+        EguiKey::Space => character(PhysicalKey::Space, ' '),
+        EguiKey::Colon => character(PhysicalKey::Semicolon, ':'),
+        EguiKey::Comma => character(PhysicalKey::Comma, ','),
+        EguiKey::Backslash => character(PhysicalKey::Backslash, '\\'),
+        EguiKey::Slash => character(PhysicalKey::Slash, '/'),
+        EguiKey::Pipe => character(PhysicalKey::Backslash, '|'),
+        EguiKey::Questionmark => character(PhysicalKey::Slash, '?'),
+        EguiKey::Exclamationmark => character(PhysicalKey::Digit1, '!'),
+        EguiKey::OpenBracket => character(PhysicalKey::BracketLeft, '['),
+        EguiKey::CloseBracket => character(PhysicalKey::BracketRight, ']'),
+        EguiKey::OpenCurlyBracket => character(PhysicalKey::BracketLeft, '{'),
+        EguiKey::CloseCurlyBracket => character(PhysicalKey::BracketRight, '}'),
+        EguiKey::Backtick => character(PhysicalKey::Backquote, '`'),
+        EguiKey::Minus => character(PhysicalKey::Minus, '-'),
+        EguiKey::Period => character(PhysicalKey::Period, '.'),
+        EguiKey::Plus => character(PhysicalKey::Equal, '+'),
+        EguiKey::Equals => character(PhysicalKey::Equal, '='),
+        EguiKey::Semicolon => character(PhysicalKey::Semicolon, ';'),
+        EguiKey::Quote => character(PhysicalKey::Quote, '\''),
+        EguiKey::Num0 => character(PhysicalKey::Digit0, '0'),
+        EguiKey::Num1 => character(PhysicalKey::Digit1, '1'),
+        EguiKey::Num2 => character(PhysicalKey::Digit2, '2'),
+        EguiKey::Num3 => character(PhysicalKey::Digit3, '3'),
+        EguiKey::Num4 => character(PhysicalKey::Digit4, '4'),
+        EguiKey::Num5 => character(PhysicalKey::Digit5, '5'),
+        EguiKey::Num6 => character(PhysicalKey::Digit6, '6'),
+        EguiKey::Num7 => character(PhysicalKey::Digit7, '7'),
+        EguiKey::Num8 => character(PhysicalKey::Digit8, '8'),
+        EguiKey::Num9 => character(PhysicalKey::Digit9, '9'),
+        EguiKey::A => character(PhysicalKey::KeyA, 'a'),
+        EguiKey::B => character(PhysicalKey::KeyB, 'b'),
+        EguiKey::C => character(PhysicalKey::KeyC, 'c'),
+        EguiKey::D => character(PhysicalKey::KeyD, 'd'),
+        EguiKey::E => character(PhysicalKey::KeyE, 'e'),
+        EguiKey::F => character(PhysicalKey::KeyF, 'f'),
+        EguiKey::G => character(PhysicalKey::KeyG, 'g'),
+        EguiKey::H => character(PhysicalKey::KeyH, 'h'),
+        EguiKey::I => character(PhysicalKey::KeyI, 'i'),
+        EguiKey::J => character(PhysicalKey::KeyJ, 'j'),
+        EguiKey::K => character(PhysicalKey::KeyK, 'k'),
+        EguiKey::L => character(PhysicalKey::KeyL, 'l'),
+        EguiKey::M => character(PhysicalKey::KeyM, 'm'),
+        EguiKey::N => character(PhysicalKey::KeyN, 'n'),
+        EguiKey::O => character(PhysicalKey::KeyO, 'o'),
+        EguiKey::P => character(PhysicalKey::KeyP, 'p'),
+        EguiKey::Q => character(PhysicalKey::KeyQ, 'q'),
+        EguiKey::R => character(PhysicalKey::KeyR, 'r'),
+        EguiKey::S => character(PhysicalKey::KeyS, 's'),
+        EguiKey::T => character(PhysicalKey::KeyT, 't'),
+        EguiKey::U => character(PhysicalKey::KeyU, 'u'),
+        EguiKey::V => character(PhysicalKey::KeyV, 'v'),
+        EguiKey::W => character(PhysicalKey::KeyW, 'w'),
+        EguiKey::X => character(PhysicalKey::KeyX, 'x'),
+        EguiKey::Y => character(PhysicalKey::KeyY, 'y'),
+        EguiKey::Z => character(PhysicalKey::KeyZ, 'z'),
+        EguiKey::F1 => named(PhysicalKey::F1, RuffleNamedKey::F1),
+        EguiKey::F2 => named(PhysicalKey::F2, RuffleNamedKey::F2),
+        EguiKey::F3 => named(PhysicalKey::F3, RuffleNamedKey::F3),
+        EguiKey::F4 => named(PhysicalKey::F4, RuffleNamedKey::F4),
+        EguiKey::F5 => named(PhysicalKey::F5, RuffleNamedKey::F5),
+        EguiKey::F6 => named(PhysicalKey::F6, RuffleNamedKey::F6),
+        EguiKey::F7 => named(PhysicalKey::F7, RuffleNamedKey::F7),
+        EguiKey::F8 => named(PhysicalKey::F8, RuffleNamedKey::F8),
+        EguiKey::F9 => named(PhysicalKey::F9, RuffleNamedKey::F9),
+        EguiKey::F10 => named(PhysicalKey::F10, RuffleNamedKey::F10),
+        EguiKey::F11 => named(PhysicalKey::F11, RuffleNamedKey::F11),
+        EguiKey::F12 => named(PhysicalKey::F12, RuffleNamedKey::F12),
+        EguiKey::F13 => named(PhysicalKey::F13, RuffleNamedKey::F13),
+        EguiKey::F14 => named(PhysicalKey::F14, RuffleNamedKey::F14),
+        EguiKey::F15 => named(PhysicalKey::F15, RuffleNamedKey::F15),
+        EguiKey::F16 => named(PhysicalKey::F16, RuffleNamedKey::F16),
+        EguiKey::F17 => named(PhysicalKey::F17, RuffleNamedKey::F17),
+        EguiKey::F18 => named(PhysicalKey::F18, RuffleNamedKey::F18),
+        EguiKey::F19 => named(PhysicalKey::F19, RuffleNamedKey::F19),
+        EguiKey::F20 => named(PhysicalKey::F20, RuffleNamedKey::F20),
+        EguiKey::F21 => named(PhysicalKey::F21, RuffleNamedKey::F21),
+        EguiKey::F22 => named(PhysicalKey::F22, RuffleNamedKey::F22),
+        EguiKey::F23 => named(PhysicalKey::F23, RuffleNamedKey::F23),
+        EguiKey::F24 => named(PhysicalKey::F24, RuffleNamedKey::F24),
+        EguiKey::F25 => named(PhysicalKey::F25, RuffleNamedKey::F25),
+        EguiKey::F26 => named(PhysicalKey::F26, RuffleNamedKey::F26),
+        EguiKey::F27 => named(PhysicalKey::F27, RuffleNamedKey::F27),
+        EguiKey::F28 => named(PhysicalKey::F28, RuffleNamedKey::F28),
+        EguiKey::F29 => named(PhysicalKey::F29, RuffleNamedKey::F29),
+        EguiKey::F30 => named(PhysicalKey::F30, RuffleNamedKey::F30),
+        EguiKey::F31 => named(PhysicalKey::F31, RuffleNamedKey::F31),
+        EguiKey::F32 => named(PhysicalKey::F32, RuffleNamedKey::F32),
+        EguiKey::F33 => named(PhysicalKey::F33, RuffleNamedKey::F33),
+        EguiKey::F34 => named(PhysicalKey::F34, RuffleNamedKey::F34),
+        EguiKey::F35 => named(PhysicalKey::F35, RuffleNamedKey::F35),
+        _ => (PhysicalKey::Unknown, LogicalKey::Unknown),
+    };
+    KeyDescriptor {
+        physical_key,
+        logical_key,
+        key_location: KeyLocation::Standard,
+    }
+}
+
 fn map_physical_key(event: &KeyEvent) -> PhysicalKey {
     match event.physical_key {
         WinitPhysicalKey::Code(key_code) => match key_code {
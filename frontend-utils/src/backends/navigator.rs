@@ -1,7 +1,7 @@
 mod fetch;
 
 use crate::backends::executor::{spawn_tokio, FutureSpawner};
-use crate::backends::navigator::fetch::{Response, ResponseBody};
+use crate::backends::navigator::fetch::{ProgressReporter, Response, ResponseBody};
 use crate::content::PlayingContent;
 use async_channel::{Receiver, Sender, TryRecvError};
 use async_io::Timer;
@@ -14,6 +14,7 @@ use ruffle_core::backend::navigator::{
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
 use ruffle_core::socket::{ConnectionState, SocketAction, SocketHandle};
+use std::cell::Cell;
 use std::collections::HashSet;
 use std::fs::File;
 use std::io;
@@ -37,6 +38,22 @@ pub trait NavigatorInterface: Clone + Send + 'static {
         host: &str,
         port: u16,
     ) -> impl std::future::Future<Output = bool> + Send;
+
+    /// Reports how far along the download of the root movie from the network is.
+    ///
+    /// This is called with [`DownloadProgress::Finished`] once the download is over,
+    /// whether it succeeded, failed or was dropped.
+    fn root_movie_download_progress(&self, url: &Url, progress: DownloadProgress);
+}
+
+/// How far along the download of the root movie is.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum DownloadProgress {
+    /// This many bytes were received so far, out of the total if the server sent its length.
+    Receiving { loaded: u64, total: Option<u64> },
+
+    /// The download is over.
+    Finished,
 }
 
 /// Implementation of `NavigatorBackend` for non-web environments that can call
@@ -59,6 +76,9 @@ pub struct ExternalNavigatorBackend<F: FutureSpawner, I: NavigatorInterface> {
 
     content: Rc<PlayingContent>,
 
+    /// Whether the root movie was fetched already, so that loading it again isn't reported.
+    root_movie_fetched: Cell<bool>,
+
     interface: I,
 }
 
@@ -125,6 +145,7 @@ impl<F: FutureSpawner, I: NavigatorInterface> ExternalNavigatorBackend<F, I> {
             socket_allowed,
             socket_mode,
             content,
+            root_movie_fetched: Cell::new(false),
             interface,
         }
     }
@@ -218,63 +239,75 @@ impl<F: FutureSpawner + 'static, I: NavigatorInterface> NavigatorBackend
                     Ok(response)
                 })
             }
-            _ => Box::pin(async move {
-                let client = client.ok_or_else(|| ErrorResponse {
-                    url: processed_url.to_string(),
-                    error: Error::FetchError("Network unavailable".to_string()),
-                })?;
-
-                let mut request_builder = match request.method() {
-                    NavigationMethod::Get => client.get(processed_url.clone()),
-                    NavigationMethod::Post => client.post(processed_url.clone()),
-                };
-                let (body_data, mime) = request.body().clone().unwrap_or_default();
-                for (name, val) in request.headers().iter() {
-                    request_builder = request_builder.header(name, val);
-                }
-                request_builder = request_builder.header("Content-Type", &mime);
-
-                request_builder = request_builder.body(body_data);
+            _ => {
+                // Only the first download of the root movie is reported,
+                // as the movie may load itself again later on.
+                let root_movie_url = self.content.initial_swf_url();
+                let is_root_movie = processed_url == self.pre_process_url(root_movie_url.clone())
+                    && !self.root_movie_fetched.replace(true);
+                let progress = is_root_movie
+                    .then(|| ProgressReporter::new(root_movie_url.clone(), self.interface.clone()));
+                Box::pin(async move {
+                    let client = client.ok_or_else(|| ErrorResponse {
+                        url: processed_url.to_string(),
+                        error: Error::FetchError("Network unavailable".to_string()),
+                    })?;
 
-                let response = spawn_tokio(request_builder.send()).await.map_err(|e| {
-                    let inner = if e.is_connect() {
-                        Error::InvalidDomain(processed_url.to_string())
-                    } else {
-                        Error::FetchError(e.to_string())
+                    let mut request_builder = match request.method() {
+                        NavigationMethod::Get => client.get(processed_url.clone()),
+                        NavigationMethod::Post => client.post(processed_url.clone()),
                     };
-                    ErrorResponse {
-                        url: processed_url.to_string(),
-                        error: inner,
+                    let (body_data, mime) = request.body().clone().unwrap_or_default();
+                    for (name, val) in request.headers().iter() {
+                        request_builder = request_builder.header(name, val);
                     }
-                })?;
-
-                let url = response.url().to_string();
-                let text_encoding = response
-                    .headers()
-                    .get("Content-Type")
-                    .and_then(|content_type| content_type.to_str().ok())
-                    .and_then(get_encoding);
-                let status = response.status().as_u16();
-                let redirected = *response.url() != processed_url;
-                if !response.status().is_success() {
-                    let error = Error::HttpNotOk(
-                        format!("Got {}", response.status()),
+                    request_builder = request_builder.header("Content-Type", &mime);
+
+                    request_builder = request_builder.body(body_data);
+
+                    let response = spawn_tokio(request_builder.send()).await.map_err(|e| {
+                        let inner = if e.is_connect() {
+                            Error::InvalidDomain(processed_url.to_string())
+                        } else {
+                            Error::FetchError(e.to_string())
+                        };
+                        ErrorResponse {
+                            url: processed_url.to_string(),
+                            error: inner,
+                        }
+                    })?;
+
+                    let url = response.url().to_string();
+                    let text_encoding = response
+                        .headers()
+                        .get("Content-Type")
+                        .and_then(|content_type| content_type.to_str().ok())
+                        .and_then(get_encoding);
+                    let status = response.status().as_u16();
+                    let redirected = *response.url() != processed_url;
+                    if !response.status().is_success() {
+                        let error = Error::HttpNotOk(
+                            format!("Got {}", response.status()),
+                            status,
+                            redirected,
+                            response.content_length().unwrap_or_default(),
+                        );
+                        return Err(ErrorResponse { url, error });
+                    }
+
+                    let response: Box<dyn SuccessResponse> = Box::new(Response {
+                        url,
+                        response_body: ResponseBody::Network(
+                            Arc::new(Mutex::new(Some(response))),
+                            progress,
+                        ),
+                        text_encoding,
                         status,
                         redirected,
-                        response.content_length().unwrap_or_default(),
-                    );
-                    return Err(ErrorResponse { url, error });
-                }
-
-                let response: Box<dyn SuccessResponse> = Box::new(Response {
-                    url,
-                    response_body: ResponseBody::Network(Arc::new(Mutex::new(Some(response)))),
-                    text_encoding,
-                    status,
-                    redirected,
-                });
-                Ok(response)
-            }),
+                    });
+                    Ok(response)
+                })
+            }
         }
     }
 
@@ -488,6 +521,8 @@ mod tests {
         async fn confirm_socket(&self, _host: &str, _port: u16) -> bool {
             true
         }
+
+        fn root_movie_download_progress(&self, _url: &Url, _progress: DownloadProgress) {}
     }
 
     const TIMEOUT_ZERO: Duration = Duration::ZERO;
@@ -1,8 +1,11 @@
+use crate::backends::navigator::{DownloadProgress, NavigatorInterface};
 use reqwest::Response as ReqwestResponse;
 use ruffle_core::backend::navigator::{OwnedFuture, SuccessResponse};
 use ruffle_core::loader::Error;
 use ruffle_core::swf::Encoding;
 use std::sync::{Arc, Mutex};
+use std::time::{Duration, Instant};
+use url::Url;
 
 pub enum ResponseBody {
     /// The response's body comes from a file.
@@ -13,7 +16,59 @@ pub enum ResponseBody {
     /// This has to be stored in shared ownership so that we can return
     /// owned futures. A synchronous lock is used here as we do not
     /// expect contention on this lock.
-    Network(Arc<Mutex<Option<ReqwestResponse>>>),
+    ///
+    /// The progress of reading the whole body is reported, if it's the root movie.
+    Network(
+        Arc<Mutex<Option<ReqwestResponse>>>,
+        Option<ProgressReporter>,
+    ),
+}
+
+/// Reports the progress of downloading the root movie to the [`NavigatorInterface`],
+/// and that the download is over once it's dropped.
+pub struct ProgressReporter {
+    report: Box<dyn Fn(DownloadProgress)>,
+    /// When progress was last reported, so that it isn't reported for every single chunk.
+    last_report: Option<Instant>,
+}
+
+impl ProgressReporter {
+    /// How often progress is reported at most.
+    const INTERVAL: Duration = Duration::from_millis(100);
+
+    /// Starts reporting the download of the movie at the given URL, as it's being requested.
+    pub fn new(url: Url, interface: impl NavigatorInterface) -> Self {
+        let report = Box::new(move |progress| {
+            interface.root_movie_download_progress(&url, progress);
+        });
+        report(DownloadProgress::Receiving {
+            loaded: 0,
+            total: None,
+        });
+        Self {
+            report,
+            last_report: None,
+        }
+    }
+
+    /// Reports the bytes received so far, unless progress was reported very recently.
+    fn receiving(&mut self, loaded: u64, total: Option<u64>) {
+        let now = Instant::now();
+        if self
+            .last_report
+            .is_some_and(|last_report| now - last_report < Self::INTERVAL)
+        {
+            return;
+        }
+        self.last_report = Some(now);
+        (self.report)(DownloadProgress::Receiving { loaded, total });
+    }
+}
+
+impl Drop for ProgressReporter {
+    fn drop(&mut self) {
+        (self.report)(DownloadProgress::Finished);
+    }
 }
 
 pub struct Response {
@@ -29,22 +84,37 @@ impl SuccessResponse for Response {
         std::borrow::Cow::Borrowed(&self.url)
     }
 
-    #[expect(clippy::await_holding_lock)]
     fn body(self: Box<Self>) -> OwnedFuture<Vec<u8>, Error> {
         match self.response_body {
             ResponseBody::File(file) => {
                 Box::pin(async move { file.map_err(|e| Error::FetchError(e.to_string())) })
             }
-            ResponseBody::Network(response) => Box::pin(async move {
-                Ok(response
+            ResponseBody::Network(response, progress) => Box::pin(async move {
+                let mut response = response
                     .lock()
                     .expect("working lock during fetch body read")
                     .take()
-                    .expect("Body cannot already be consumed")
-                    .bytes()
+                    .expect("Body cannot already be consumed");
+                let Some(mut progress) = progress else {
+                    return Ok(response
+                        .bytes()
+                        .await
+                        .map_err(|e| Error::FetchError(e.to_string()))?
+                        .to_vec());
+                };
+
+                let total = response.content_length();
+                progress.receiving(0, total);
+                let mut body = Vec::new();
+                while let Some(chunk) = response
+                    .chunk()
                     .await
                     .map_err(|e| Error::FetchError(e.to_string()))?
-                    .to_vec())
+                {
+                    body.extend_from_slice(&chunk);
+                    progress.receiving(body.len() as u64, total);
+                }
+                Ok(body)
             }),
         }
     }
@@ -78,7 +148,7 @@ impl SuccessResponse for Response {
                     }
                 })
             }
-            ResponseBody::Network(response) => {
+            ResponseBody::Network(response, _) => {
                 let response = response.clone();
                 Box::pin(async move {
                     let lock = response.try_lock();
@@ -109,7 +179,7 @@ impl SuccessResponse for Response {
     fn expected_length(&self) -> Result<Option<u64>, Error> {
         match &self.response_body {
             ResponseBody::File(file) => Ok(file.as_ref().map(|file| file.len() as u64).ok()),
-            ResponseBody::Network(response) => {
+            ResponseBody::Network(response, _) => {
                 let lock = response.lock().expect("no recursive locks");
                 let response = lock.as_ref().expect("Body cannot already be consumed");
                 Ok(response.content_length())
@@ -117,3 +187,86 @@ impl SuccessResponse for Response {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::fs::File;
+    use std::io;
+    use std::path::Path;
+
+    /// Records every progress reported to it.
+    #[derive(Clone, Default)]
+    struct RecordingInterface(Arc<Mutex<Vec<DownloadProgress>>>);
+
+    impl RecordingInterface {
+        fn reports(&self) -> Vec<DownloadProgress> {
+            self.0.lock().expect("Non-poisoned lock").clone()
+        }
+    }
+
+    impl NavigatorInterface for RecordingInterface {
+        fn navigate_to_website(&self, _url: Url) {}
+
+        async fn open_file(&self, path: &Path) -> io::Result<File> {
+            File::open(path)
+        }
+
+        async fn confirm_socket(&self, _host: &str, _port: u16) -> bool {
+            true
+        }
+
+        fn root_movie_download_progress(&self, _url: &Url, progress: DownloadProgress) {
+            self.0.lock().expect("Non-poisoned lock").push(progress);
+        }
+    }
+
+    fn receiving(loaded: u64, total: Option<u64>) -> DownloadProgress {
+        DownloadProgress::Receiving { loaded, total }
+    }
+
+    #[test]
+    fn progress_is_throttled() {
+        let interface = RecordingInterface::default();
+        let url = Url::parse("https://example.com/movie.swf").unwrap();
+        let mut reporter = ProgressReporter::new(url, interface.clone());
+        assert_eq!(interface.reports(), vec![receiving(0, None)]);
+
+        reporter.receiving(10, Some(100));
+        reporter.receiving(20, Some(100));
+        reporter.receiving(30, Some(100));
+        assert_eq!(
+            interface.reports(),
+            vec![receiving(0, None), receiving(10, Some(100))]
+        );
+
+        // Pretend the last report is old enough to be followed by another one.
+        reporter.last_report = Some(Instant::now() - ProgressReporter::INTERVAL);
+        reporter.receiving(40, Some(100));
+        assert_eq!(
+            interface.reports(),
+            vec![
+                receiving(0, None),
+                receiving(10, Some(100)),
+                receiving(40, Some(100))
+            ]
+        );
+    }
+
+    #[test]
+    fn finished_is_reported_on_drop() {
+        let interface = RecordingInterface::default();
+        let url = Url::parse("https://example.com/movie.swf").unwrap();
+        let mut reporter = ProgressReporter::new(url, interface.clone());
+        reporter.receiving(10, None);
+        drop(reporter);
+        assert_eq!(
+            interface.reports(),
+            vec![
+                receiving(0, None),
+                receiving(10, None),
+                DownloadProgress::Finished
+            ]
+        );
+    }
+}
@@ -1,3 +1,4 @@
+use crate::player_options::PlayerVersion;
 use std::fmt;
 use std::fmt::Formatter;
 use std::ops::Deref;
@@ -264,6 +265,36 @@ pub trait ReadExt<'a> {
         cx.pop_key();
         result
     }
+
+    /// Reads a player version, either only its major version as an integer
+    /// or a full version such as `"32,0,0,465"` as a string.
+    fn get_player_version(
+        &'a self,
+        cx: &mut ParseContext,
+        key: &'static str,
+    ) -> Option<PlayerVersion> {
+        let mut result = None;
+        cx.push_key(key);
+
+        if let Some(item) = self.get_impl(key) {
+            if let Some(major) = item.as_integer() {
+                match u8::try_from(major) {
+                    Ok(major) if major > 0 => result = Some(PlayerVersion::new(major)),
+                    _ => cx.unsupported_value(major.to_string()),
+                }
+            } else if let Some(version) = item.as_str() {
+                match version.parse() {
+                    Ok(version) => result = Some(version),
+                    Err(()) => cx.unsupported_value(version.to_owned()),
+                }
+            } else {
+                cx.unexpected_type("integer or string", item.type_name());
+            }
+        }
+
+        cx.pop_key();
+        result
+    }
 }
 
 /// Extension trait to provide casting methods with warning capabilities.
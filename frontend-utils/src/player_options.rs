@@ -4,9 +4,73 @@ pub use read::read_player_options;
 use ruffle_core::config::Letterbox;
 use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
 use ruffle_render::quality::StageQuality;
+use std::fmt;
+use std::str::FromStr;
 use std::time::Duration;
 use url::Url;
 
+/// A Flash Player version to emulate, such as `32,0,0,465`,
+/// in the form reported by `Capabilities.version` after the platform name.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct PlayerVersion {
+    /// The major version, which is the one that decides how the player behaves.
+    pub major: u8,
+    pub minor: u16,
+    pub build: u16,
+    pub revision: u16,
+}
+
+impl PlayerVersion {
+    pub const fn new(major: u8) -> Self {
+        Self {
+            major,
+            minor: 0,
+            build: 0,
+            revision: 0,
+        }
+    }
+}
+
+impl fmt::Display for PlayerVersion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{},{},{},{}",
+            self.major, self.minor, self.build, self.revision
+        )
+    }
+}
+
+impl FromStr for PlayerVersion {
+    type Err = ();
+
+    /// Parses a version such as `32,0,0,465` or `32.0.0.465`, where omitted parts are 0.
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut parts = s.trim().split([',', '.']).map(str::trim);
+        let major = parts
+            .next()
+            .and_then(|major| major.parse::<u8>().ok())
+            .filter(|major| *major > 0)
+            .ok_or(())?;
+        let mut rest = [0u16; 3];
+        for part in &mut rest {
+            if let Some(value) = parts.next() {
+                *part = value.parse().map_err(|_| ())?;
+            }
+        }
+        if parts.next().is_some() {
+            return Err(());
+        }
+        let [minor, build, revision] = rest;
+        Ok(Self {
+            major,
+            minor,
+            build,
+            revision,
+        })
+    }
+}
+
 #[derive(Default, Debug, PartialEq, Clone)]
 pub struct PlayerOptions {
     pub parameters: Vec<(String, String)>,
@@ -23,7 +87,7 @@ pub struct PlayerOptions {
     pub spoof_url: Option<Url>,
     pub referer: Option<Url>,
     pub cookie: Option<String>,
-    pub player_version: Option<u8>,
+    pub player_version: Option<PlayerVersion>,
     pub player_runtime: Option<PlayerRuntime>,
     pub frame_rate: Option<f64>,
     pub dummy_external_interface: Option<bool>,
@@ -57,3 +121,33 @@ impl PlayerOptions {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn parse_player_version() {
+        let full = PlayerVersion {
+            major: 32,
+            minor: 0,
+            build: 0,
+            revision: 465,
+        };
+        assert_eq!("32,0,0,465".parse(), Ok(full));
+        assert_eq!("32.0.0.465".parse(), Ok(full));
+        assert_eq!(
+            " 10, 1 ".parse(),
+            Ok(PlayerVersion {
+                minor: 1,
+                ..PlayerVersion::new(10)
+            })
+        );
+        assert_eq!("9".parse(), Ok(PlayerVersion::new(9)));
+        assert_eq!(full.to_string(), "32,0,0,465");
+
+        for invalid in ["", "0", "256", "WIN 32,0,0,465", "32,0,0,465,1", "32,,0"] {
+            assert_eq!(invalid.parse::<PlayerVersion>(), Err(()), "{invalid:?}");
+        }
+    }
+}
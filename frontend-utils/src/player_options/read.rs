@@ -58,7 +58,7 @@ pub fn read_player_options<'a>(
     result.spoof_url = table.parse_from_str(cx, "spoof_url");
 
     // Player version
-    result.player_version = table.get_integer(cx, "version").map(|x| x as u8);
+    result.player_version = table.get_player_version(cx, "version");
 
     // Player runtime
     result.player_runtime = table.parse_from_str(cx, "runtime");
@@ -76,6 +76,7 @@ pub fn read_player_options<'a>(
 mod tests {
     use super::*;
     use crate::parse::{DocumentHolder, ParseDetails, ParseWarning};
+    use crate::player_options::PlayerVersion;
     use ruffle_core::config::Letterbox;
     use ruffle_core::{LoadBehavior, PlayerRuntime, StageAlign, StageScaleMode};
     use ruffle_render::quality::StageQuality;
@@ -601,12 +602,32 @@ mod tests {
 
     #[test]
     fn version() {
-        let result = read("version = \"air\"");
+        let result = read("version = true");
         assert_eq!(&PlayerOptions::default(), result.values());
         assert_eq!(
             vec![ParseWarning::UnexpectedType {
-                expected: "integer",
-                actual: "string",
+                expected: "integer or string",
+                actual: "boolean",
+                path: "version".to_string()
+            }],
+            result.warnings
+        );
+
+        let result = read("version = \"air\"");
+        assert_eq!(&PlayerOptions::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "air".to_string(),
+                path: "version".to_string()
+            }],
+            result.warnings
+        );
+
+        let result = read("version = 300");
+        assert_eq!(&PlayerOptions::default(), result.values());
+        assert_eq!(
+            vec![ParseWarning::UnsupportedValue {
+                value: "300".to_string(),
                 path: "version".to_string()
             }],
             result.warnings
@@ -615,7 +636,22 @@ mod tests {
         let result = read("version = 26");
         assert_eq!(
             &PlayerOptions {
-                player_version: Some(26),
+                player_version: Some(PlayerVersion::new(26)),
+                ..Default::default()
+            },
+            result.values()
+        );
+        assert_eq!(Vec::<ParseWarning>::new(), result.warnings);
+
+        let result = read("version = \"32,0,0,465\"");
+        assert_eq!(
+            &PlayerOptions {
+                player_version: Some(PlayerVersion {
+                    major: 32,
+                    minor: 0,
+                    build: 0,
+                    revision: 465,
+                }),
                 ..Default::default()
             },
             result.values()
@@ -3,6 +3,7 @@ use crate::bitmap::{BitmapHandle, PixelSnapping};
 use crate::matrix::Matrix;
 use crate::pixel_bender::PixelBenderShaderHandle;
 use crate::transform::Transform;
+use std::sync::Arc;
 use swf::{BlendMode, Color};
 
 pub trait CommandHandler {
@@ -85,6 +86,19 @@ impl CommandList {
     pub fn drawing_mask(&self) -> bool {
         self.maskers_in_progress > 0
     }
+
+    /// Returns true if both lists draw the same thing, as far as can be told from the commands alone.
+    ///
+    /// Shapes, bitmaps and shaders are compared by handle, so a bitmap whose pixels changed
+    /// is still considered the same. Stage3D output is never considered the same.
+    pub fn is_equivalent(&self, other: &CommandList) -> bool {
+        self.commands.len() == other.commands.len()
+            && self
+                .commands
+                .iter()
+                .zip(&other.commands)
+                .all(|(lhs, rhs)| lhs.is_equivalent(rhs))
+    }
 }
 
 impl CommandHandler for CommandList {
@@ -217,3 +231,76 @@ pub enum Command {
     PopMask,
     Blend(CommandList, RenderBlendMode),
 }
+
+impl Command {
+    fn is_equivalent(&self, other: &Command) -> bool {
+        match (self, other) {
+            (
+                Command::RenderBitmap {
+                    bitmap,
+                    transform,
+                    smoothing,
+                    pixel_snapping,
+                },
+                Command::RenderBitmap {
+                    bitmap: other_bitmap,
+                    transform: other_transform,
+                    smoothing: other_smoothing,
+                    pixel_snapping: other_pixel_snapping,
+                },
+            ) => {
+                Arc::ptr_eq(&bitmap.0, &other_bitmap.0)
+                    && transform == other_transform
+                    && smoothing == other_smoothing
+                    && pixel_snapping == other_pixel_snapping
+            }
+            (
+                Command::RenderShape { shape, transform },
+                Command::RenderShape {
+                    shape: other_shape,
+                    transform: other_transform,
+                },
+            ) => Arc::ptr_eq(&shape.0, &other_shape.0) && transform == other_transform,
+            (
+                Command::DrawRect { color, matrix },
+                Command::DrawRect {
+                    color: other_color,
+                    matrix: other_matrix,
+                },
+            )
+            | (
+                Command::DrawLine { color, matrix },
+                Command::DrawLine {
+                    color: other_color,
+                    matrix: other_matrix,
+                },
+            )
+            | (
+                Command::DrawLineRect { color, matrix },
+                Command::DrawLineRect {
+                    color: other_color,
+                    matrix: other_matrix,
+                },
+            ) => color == other_color && matrix == other_matrix,
+            (Command::PushMask, Command::PushMask)
+            | (Command::ActivateMask, Command::ActivateMask)
+            | (Command::DeactivateMask, Command::DeactivateMask)
+            | (Command::PopMask, Command::PopMask) => true,
+            (
+                Command::Blend(commands, blend_mode),
+                Command::Blend(other_commands, other_blend_mode),
+            ) => {
+                let same_blend_mode = match (blend_mode, other_blend_mode) {
+                    (RenderBlendMode::Builtin(lhs), RenderBlendMode::Builtin(rhs)) => lhs == rhs,
+                    (RenderBlendMode::Shader(lhs), RenderBlendMode::Shader(rhs)) => {
+                        Arc::ptr_eq(&lhs.0, &rhs.0)
+                    }
+                    _ => false,
+                };
+                same_blend_mode && commands.is_equivalent(other_commands)
+            }
+            // The contents of a Stage3D buffer change whenever it's presented.
+            _ => false,
+        }
+    }
+}
@@ -51,6 +51,8 @@ pub struct WgpuRenderBackend<T: RenderTarget> {
     pub(crate) offscreen_buffer_pool: Arc<BufferPool<wgpu::Buffer, BufferDimensions>>,
     dynamic_transforms: DynamicTransforms,
     active_frame: ActiveFrame,
+    /// Set when the pixels of an existing texture are changed, see [`Self::take_textures_modified`].
+    textures_modified: bool,
 }
 
 impl WgpuRenderBackend<SwapChainTarget> {
@@ -230,6 +232,7 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
             offscreen_buffer_pool: Arc::new(offscreen_buffer_pool),
             dynamic_transforms: transforms,
             active_frame,
+            textures_modified: false,
         })
     }
 
@@ -335,6 +338,14 @@ impl<T: RenderTarget> WgpuRenderBackend<T> {
         &self.target
     }
 
+    /// Returns true if the pixels of any existing texture were changed since this was last called,
+    /// such as by drawing into a `BitmapData` or presenting a Stage3D buffer.
+    ///
+    /// Together with the commands of a frame, this tells whether the frame may look different.
+    pub fn take_textures_modified(&mut self) -> bool {
+        std::mem::take(&mut self.textures_modified)
+    }
+
     pub fn device(&self) -> &wgpu::Device {
         &self.descriptors.device
     }
@@ -416,6 +427,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
     fn context3d_present(&mut self, context: &mut dyn Context3D) -> Result<(), BitmapError> {
         let context = <dyn Any>::downcast_mut::<WgpuContext3D>(context).unwrap();
         context.present();
+        self.textures_modified = true;
         Ok(())
     }
 
@@ -671,6 +683,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         mut region: PixelRegion,
     ) -> Result<(), BitmapError> {
         let texture = as_texture(handle);
+        self.textures_modified = true;
 
         let mut bitmap = bitmap.to_rgba();
         if self.clamp_bitmap(&mut bitmap) {
@@ -719,6 +732,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         bounds: PixelRegion,
     ) -> Option<Box<dyn SyncHandle>> {
         let texture = as_texture(&handle);
+        self.textures_modified = true;
 
         let extent = wgpu::Extent3d {
             width: texture.texture.width(),
@@ -788,6 +802,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         filter: Filter,
     ) -> Option<Box<dyn SyncHandle>> {
         let source_texture = as_texture(&source);
+        self.textures_modified = true;
         let dest_texture = as_texture(&destination);
 
         let copy_area = PixelRegion::for_whole_size(
@@ -859,6 +874,7 @@ impl<T: RenderTarget + 'static> RenderBackend for WgpuRenderBackend<T> {
         arguments: &[PixelBenderShaderArgument],
         target: &PixelBenderTarget,
     ) -> Result<PixelBenderOutput, BitmapError> {
+        self.textures_modified = true;
         let output_channels = shader
             .0
             .parsed_shader()
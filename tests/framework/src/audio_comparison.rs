@@ -0,0 +1,258 @@
+use crate::util::{write_bytes, Mismatch};
+use anyhow::{anyhow, Result};
+use serde::Deserialize;
+use vfs::VfsPath;
+
+/// The sample rate of the sound captured from the movie, and of the WAV files it's compared to.
+pub const SAMPLE_RATE: u32 = 44100;
+
+/// The number of channels of the sound captured from the movie, and of the WAV files it's compared to.
+pub const NUM_CHANNELS: u16 = 2;
+
+/// The file that the sound of the movie is compared to, relative to the test directory.
+pub const EXPECTED_AUDIO_PATH: &str = "audio.expected.wav";
+
+/// The file that the sound of the movie is saved to when it doesn't match.
+const ACTUAL_AUDIO_PATH: &str = "audio.actual.wav";
+
+/// A comparison of all the sound played by the movie during the test with a recording of it.
+///
+/// The sound is compared as 16-bit stereo samples at 44.1 kHz, which the expected WAV file has to use too.
+#[derive(Deserialize, Default, Clone, Debug)]
+#[serde(default, deny_unknown_fields)]
+pub struct AudioComparison {
+    /// How much a sample may differ from the expected one and still be considered the same.
+    pub tolerance: u16,
+    /// The most samples allowed to differ by more than the tolerance.
+    pub max_outliers: usize,
+}
+
+impl AudioComparison {
+    pub fn test(
+        &self,
+        actual_samples: &[i16],
+        expected_samples: &[i16],
+        test_path: &VfsPath,
+        known_failure: bool,
+    ) -> Result<()> {
+        let save_actual_audio = || {
+            if !known_failure {
+                // If we're expecting failure, spamming files isn't productive.
+                write_bytes(
+                    &test_path.join(ACTUAL_AUDIO_PATH)?,
+                    &encode_wav(actual_samples),
+                )?;
+            }
+            Ok::<_, anyhow::Error>(())
+        };
+
+        if actual_samples.len() != expected_samples.len() {
+            save_actual_audio()?;
+            return Err(Mismatch(format!(
+                "Audio is not the right length. Expected = {} samples, actual = {} samples.",
+                expected_samples.len(),
+                actual_samples.len()
+            ))
+            .into());
+        }
+
+        let differences = actual_samples
+            .iter()
+            .zip(expected_samples)
+            .map(|(actual, expected)| actual.abs_diff(*expected));
+        let (outliers, max_difference) =
+            differences.fold((0, 0), |(outliers, max_difference), difference| {
+                (
+                    outliers + (difference > self.tolerance) as usize,
+                    max_difference.max(difference),
+                )
+            });
+
+        if outliers <= self.max_outliers {
+            println!("Audio comparison succeeded: {outliers} outliers found, max difference {max_difference}");
+            return Ok(());
+        }
+
+        save_actual_audio()?;
+        Err(Mismatch(format!(
+            "Audio comparison failed: Number of outliers ({outliers}) is bigger than allowed limit of {}. \
+            Max difference is {max_difference}",
+            self.max_outliers
+        ))
+        .into())
+    }
+}
+
+/// Converts the samples mixed by the audio backend to the 16-bit samples they're compared as.
+pub fn to_pcm16(samples: &[f32]) -> Vec<i16> {
+    samples
+        .iter()
+        .map(|sample| (sample.clamp(-1.0, 1.0) * i16::MAX as f32).round() as i16)
+        .collect()
+}
+
+/// Encodes interleaved stereo samples as a 16-bit PCM WAV file.
+pub fn encode_wav(samples: &[i16]) -> Vec<u8> {
+    const BYTES_PER_SAMPLE: u16 = 2;
+    let block_align = NUM_CHANNELS * BYTES_PER_SAMPLE;
+    let data_size = (samples.len() * BYTES_PER_SAMPLE as usize) as u32;
+
+    let mut wav = Vec::with_capacity(44 + data_size as usize);
+    wav.extend_from_slice(b"RIFF");
+    wav.extend_from_slice(&(36 + data_size).to_le_bytes());
+    wav.extend_from_slice(b"WAVE");
+    wav.extend_from_slice(b"fmt ");
+    wav.extend_from_slice(&16u32.to_le_bytes());
+    // Uncompressed PCM.
+    wav.extend_from_slice(&1u16.to_le_bytes());
+    wav.extend_from_slice(&NUM_CHANNELS.to_le_bytes());
+    wav.extend_from_slice(&SAMPLE_RATE.to_le_bytes());
+    wav.extend_from_slice(&(SAMPLE_RATE * block_align as u32).to_le_bytes());
+    wav.extend_from_slice(&block_align.to_le_bytes());
+    wav.extend_from_slice(&(BYTES_PER_SAMPLE * 8).to_le_bytes());
+    wav.extend_from_slice(b"data");
+    wav.extend_from_slice(&data_size.to_le_bytes());
+    for sample in samples {
+        wav.extend_from_slice(&sample.to_le_bytes());
+    }
+    wav
+}
+
+/// Decodes the samples of a 16-bit PCM WAV file, which has to be stereo at 44.1 kHz.
+pub fn decode_wav(wav: &[u8]) -> Result<Vec<i16>> {
+    if wav.len() < 12 || &wav[0..4] != b"RIFF" || &wav[8..12] != b"WAVE" {
+        return Err(anyhow!("Not a WAV file"));
+    }
+
+    let mut format_checked = false;
+    let mut chunks = &wav[12..];
+    while chunks.len() >= 8 {
+        let id = &chunks[0..4];
+        let size = u32::from_le_bytes([chunks[4], chunks[5], chunks[6], chunks[7]]) as usize;
+        let Some(data) = chunks.get(8..8 + size) else {
+            return Err(anyhow!(
+                "WAV chunk {:?} is cut short",
+                String::from_utf8_lossy(id)
+            ));
+        };
+        match id {
+            b"fmt " => {
+                if data.len() < 16 {
+                    return Err(anyhow!("WAV format chunk is too short"));
+                }
+                let read_u16 = |offset: usize| u16::from_le_bytes([data[offset], data[offset + 1]]);
+                let format = read_u16(0);
+                let channels = read_u16(2);
+                let sample_rate = u32::from_le_bytes([data[4], data[5], data[6], data[7]]);
+                let bits_per_sample = read_u16(14);
+                if format != 1
+                    || channels != NUM_CHANNELS
+                    || sample_rate != SAMPLE_RATE
+                    || bits_per_sample != 16
+                {
+                    return Err(anyhow!(
+                        "WAV file has to be 16-bit PCM with {NUM_CHANNELS} channels at {SAMPLE_RATE} Hz, \
+                        but it's format {format} with {channels} channels of {bits_per_sample} bits at {sample_rate} Hz"
+                    ));
+                }
+                format_checked = true;
+            }
+            b"data" => {
+                if !format_checked {
+                    return Err(anyhow!("WAV data chunk comes before the format chunk"));
+                }
+                return Ok(data
+                    .chunks_exact(2)
+                    .map(|sample| i16::from_le_bytes([sample[0], sample[1]]))
+                    .collect());
+            }
+            _ => {}
+        }
+        // Chunks are padded to an even size.
+        let padded_size = size + size % 2;
+        chunks = chunks.get(8 + padded_size..).unwrap_or_default();
+    }
+    Err(anyhow!("WAV file has no data chunk"))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Replaces the little-endian `u16` at the given offset of a WAV file.
+    fn set_u16(wav: &mut [u8], offset: usize, value: u16) {
+        wav[offset..offset + 2].copy_from_slice(&value.to_le_bytes());
+    }
+
+    #[test]
+    fn round_trip() {
+        let samples = [0, 1, -1, i16::MAX, i16::MIN, 1234, -4321, 0];
+        let wav = encode_wav(&samples);
+        assert_eq!(wav.len(), 44 + samples.len() * 2);
+        assert_eq!(decode_wav(&wav).unwrap(), samples);
+    }
+
+    #[test]
+    fn round_trip_empty() {
+        assert_eq!(decode_wav(&encode_wav(&[])).unwrap(), Vec::<i16>::new());
+    }
+
+    #[test]
+    fn truncated_header() {
+        let wav = encode_wav(&[1, 2, 3, 4]);
+        assert!(decode_wav(&wav[..8]).is_err());
+        // The format chunk claims 16 bytes, but only some of them are there.
+        assert!(decode_wav(&wav[..30]).is_err());
+        // The data chunk claims more samples than there are.
+        assert!(decode_wav(&wav[..wav.len() - 1]).is_err());
+    }
+
+    #[test]
+    fn not_a_wav_file() {
+        let mut wav = encode_wav(&[1, 2]);
+        wav[8..12].copy_from_slice(b"AVI ");
+        assert!(decode_wav(&wav).is_err());
+    }
+
+    #[test]
+    fn non_pcm16_format() {
+        let wav = encode_wav(&[1, 2]);
+
+        // IEEE float instead of PCM.
+        let mut float = wav.clone();
+        set_u16(&mut float, 20, 3);
+        assert!(decode_wav(&float).is_err());
+
+        let mut mono = wav.clone();
+        set_u16(&mut mono, 22, 1);
+        assert!(decode_wav(&mono).is_err());
+
+        let mut eight_bit = wav;
+        set_u16(&mut eight_bit, 34, 8);
+        assert!(decode_wav(&eight_bit).is_err());
+    }
+
+    #[test]
+    fn extra_chunks_before_data() {
+        let samples = [5, -5, 10, -10];
+        let wav = encode_wav(&samples);
+        let (header, data) = wav.split_at(36);
+
+        // An odd-sized chunk, followed by its padding byte.
+        let mut with_extra_chunk = header.to_vec();
+        with_extra_chunk.extend_from_slice(b"LIST");
+        with_extra_chunk.extend_from_slice(&3u32.to_le_bytes());
+        with_extra_chunk.extend_from_slice(b"abc\0");
+        with_extra_chunk.extend_from_slice(data);
+        assert_eq!(decode_wav(&with_extra_chunk).unwrap(), samples);
+    }
+
+    #[test]
+    fn data_before_format() {
+        let wav = encode_wav(&[1, 2]);
+        let mut swapped = wav[..12].to_vec();
+        swapped.extend_from_slice(&wav[36..]);
+        swapped.extend_from_slice(&wav[12..36]);
+        assert!(decode_wav(&swapped).is_err());
+    }
+}
@@ -3,7 +3,7 @@ mod log;
 mod navigator;
 mod ui;
 
-pub use audio::TestAudioBackend;
+pub use audio::{AudioRecording, TestAudioBackend};
 pub use log::TestLogBackend;
 pub use navigator::TestNavigatorBackend;
-pub use ui::TestUiBackend;
+pub use ui::{CursorRecording, TestUiBackend};
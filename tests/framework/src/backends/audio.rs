@@ -1,40 +1,53 @@
+use crate::audio_comparison::{NUM_CHANNELS, SAMPLE_RATE};
 use ruffle_core::backend::audio::{
     swf, AudioBackend, AudioMixer, DecodeError, RegisterError, SoundHandle, SoundInstanceHandle,
     SoundStreamInfo, SoundTransform,
 };
 use ruffle_core::impl_audio_mixer_backend;
+use std::sync::{Arc, Mutex};
+
+/// All the sound mixed by a [`TestAudioBackend`], as interleaved stereo samples.
+pub type AudioRecording = Arc<Mutex<Vec<f32>>>;
 
 pub struct TestAudioBackend {
     mixer: AudioMixer,
     buffer: Vec<f32>,
+    recording: Option<AudioRecording>,
 }
 
-impl Default for TestAudioBackend {
-    fn default() -> Self {
+impl TestAudioBackend {
+    const NUM_CHANNELS: u8 = NUM_CHANNELS as u8;
+    const SAMPLE_RATE: u32 = SAMPLE_RATE;
+
+    /// Creates a backend that appends the sound it mixes to the given recording, if any.
+    pub fn new(recording: Option<AudioRecording>) -> Self {
         Self {
             mixer: AudioMixer::new(Self::NUM_CHANNELS, Self::SAMPLE_RATE),
             buffer: vec![],
+            recording,
         }
     }
 }
 
-impl TestAudioBackend {
-    const NUM_CHANNELS: u8 = 2;
-    const SAMPLE_RATE: u32 = 44100;
-}
-
 impl AudioBackend for TestAudioBackend {
     impl_audio_mixer_backend!(mixer);
     fn play(&mut self) {}
     fn pause(&mut self) {}
 
     fn set_frame_rate(&mut self, frame_rate: f64) {
-        let new_buffer_size =
-            ((Self::NUM_CHANNELS as u32 * Self::SAMPLE_RATE) as f64 / frame_rate).round() as usize;
-        self.buffer.resize(new_buffer_size, 0.0);
+        // Rounded to whole sample frames first, so that every channel gets the same number of samples.
+        let sample_frames = (Self::SAMPLE_RATE as f64 / frame_rate).round() as usize;
+        self.buffer
+            .resize(sample_frames * Self::NUM_CHANNELS as usize, 0.0);
     }
     fn tick(&mut self) {
         debug_assert!(!self.buffer.is_empty());
         self.mixer.mix::<f32>(self.buffer.as_mut());
+        if let Some(recording) = &self.recording {
+            recording
+                .lock()
+                .expect("Non-poisoned recording")
+                .extend_from_slice(&self.buffer);
+        }
     }
 }
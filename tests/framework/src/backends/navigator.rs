@@ -1,11 +1,12 @@
 use crate::backends::TestLogBackend;
+use crate::options::MockedResponses;
 use crate::util::read_bytes;
 use async_channel::{Receiver, Sender};
 use percent_encoding::percent_decode_str;
 use ruffle_core::backend::log::LogBackend;
 use ruffle_core::backend::navigator::{
-    async_return, create_fetch_error, ErrorResponse, NavigationMethod, NavigatorBackend,
-    NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse,
+    async_return, create_fetch_error, get_encoding, ErrorResponse, NavigationMethod,
+    NavigatorBackend, NullExecutor, NullSpawner, OwnedFuture, Request, SuccessResponse,
 };
 use ruffle_core::indexmap::IndexMap;
 use ruffle_core::loader::Error;
@@ -21,6 +22,7 @@ struct TestResponse {
     url: String,
     body: Vec<u8>,
     chunk_gotten: bool,
+    text_encoding: Option<&'static Encoding>,
     status: u16,
     redirected: bool,
 }
@@ -35,7 +37,7 @@ impl SuccessResponse for TestResponse {
     }
 
     fn text_encoding(&self) -> Option<&'static Encoding> {
-        None
+        self.text_encoding
     }
 
     fn status(&self) -> u16 {
@@ -72,10 +74,14 @@ impl SuccessResponse for TestResponse {
 ///
 /// These are formatted as query params, rather than domains/whole URLs, so that real/real-invalid
 /// URLs can be used in Flash Player when writing tests
+///
+/// Otherwise, the response comes from the files of the test, unless there are `mocked_responses`,
+/// in which case the test panics for any URL that doesn't have one.
 pub struct TestNavigatorBackend {
     spawner: NullSpawner,
     relative_base_path: VfsPath,
     socket_events: Option<Vec<SocketEvent>>,
+    mocked_responses: MockedResponses,
     log: Option<TestLogBackend>,
 }
 
@@ -84,17 +90,72 @@ impl TestNavigatorBackend {
         path: VfsPath,
         executor: &NullExecutor,
         socket_events: Option<Vec<SocketEvent>>,
+        mocked_responses: MockedResponses,
         log: Option<TestLogBackend>,
     ) -> Result<Self, std::io::Error> {
         Ok(Self {
             spawner: executor.spawner(),
             relative_base_path: path,
             socket_events,
+            mocked_responses,
             log,
         })
     }
 }
 
+impl TestNavigatorBackend {
+    /// Serves the mocked response for `url`, panicking if there's none,
+    /// so that tests can't quietly depend on a request going unanswered.
+    fn fetch_mocked_response(
+        &self,
+        url: Url,
+    ) -> OwnedFuture<Box<dyn SuccessResponse>, ErrorResponse> {
+        let mocked_response = match self.mocked_responses.find(url.as_str()) {
+            Ok(Some(mocked_response)) => mocked_response,
+            Ok(None) => panic!("No mocked response matches {url}, add one to mocked_responses"),
+            Err(e) => panic!("{e}"),
+        };
+        let body = self
+            .relative_base_path
+            .join(&mocked_response.path)
+            .and_then(|path| read_bytes(&path));
+        let body = match body {
+            Ok(body) => body,
+            Err(e) => panic!(
+                "Couldn't read the mocked response {} for {url}: {e}",
+                mocked_response.path
+            ),
+        };
+        let status = mocked_response.status;
+        let text_encoding = mocked_response
+            .content_type
+            .as_deref()
+            .and_then(get_encoding);
+
+        Box::pin(async move {
+            if !(200..300).contains(&status) {
+                let error =
+                    Error::HttpNotOk(format!("Got {status}"), status, false, body.len() as u64);
+                return Err(ErrorResponse {
+                    url: url.to_string(),
+                    error,
+                });
+            }
+
+            let response: Box<dyn SuccessResponse> = Box::new(TestResponse {
+                url: url.to_string(),
+                body,
+                chunk_gotten: false,
+                text_encoding,
+                status,
+                redirected: false,
+            });
+
+            Ok(response)
+        })
+    }
+}
+
 impl NavigatorBackend for TestNavigatorBackend {
     fn navigate_to_url(
         &self,
@@ -123,6 +184,7 @@ impl NavigatorBackend for TestNavigatorBackend {
                     url: request.url().to_string(),
                     body: b"Hello, World!".to_vec(),
                     chunk_gotten: false,
+                    text_encoding: None,
                     status: 200,
                     redirected: false,
                 });
@@ -180,6 +242,10 @@ impl NavigatorBackend for TestNavigatorBackend {
             Err(e) => return async_return(create_fetch_error(request.url(), e)),
         };
 
+        if !self.mocked_responses.is_empty() {
+            return self.fetch_mocked_response(url);
+        }
+
         let base_path = self.relative_base_path.clone();
 
         Box::pin(async move {
@@ -237,6 +303,7 @@ impl NavigatorBackend for TestNavigatorBackend {
                 url: url.to_string(),
                 body,
                 chunk_gotten: false,
+                text_encoding: None,
                 status: 0,
                 redirected: false,
             });
@@ -5,10 +5,11 @@ use chrono::{DateTime, Utc};
 use ruffle_core::{
     backend::ui::{
         DialogLoaderError, DialogResultFuture, FileDialogResult, FileFilter, FontDefinition,
-        FullscreenError, LanguageIdentifier, MouseCursor, UiBackend, US_ENGLISH,
+        FullscreenError, LanguageIdentifier, MouseCursor, UiBackend,
     },
     FontFileData, FontQuery,
 };
+use std::sync::{Arc, Mutex};
 use url::Url;
 
 /// A simulated file dialog response, for use in tests
@@ -73,6 +74,9 @@ impl FileDialogResult for TestFileDialogResult {
     }
 }
 
+/// The mouse cursor last requested by the content, shared with the test runner.
+pub type CursorRecording = Arc<Mutex<MouseCursor>>;
+
 /// This is an implementation of [`UiBackend`], designed for use in tests
 ///
 /// Fundamentally, this is mostly the same as [`NullUiBackend`] with the following differences:
@@ -81,21 +85,28 @@ impl FileDialogResult for TestFileDialogResult {
 /// * Attempting to display a file save dialog with a file name hint of "debug-success.txt" will simulate successfully selecting a destination
 ///   otherwise a user cancellation will be simulated
 /// * Simulated in-memory clipboard
+/// * The requested mouse cursor is recorded, if asked to
 pub struct TestUiBackend {
     fonts: HashMap<FontQuery, Font>,
     font_sorts: HashMap<FontQuery, Vec<FontQuery>>,
     clipboard: String,
+    language: LanguageIdentifier,
+    cursor_recording: Option<CursorRecording>,
 }
 
 impl TestUiBackend {
     pub fn new(
         fonts: HashMap<FontQuery, Font>,
         font_sorts: HashMap<FontQuery, Vec<FontQuery>>,
+        language: LanguageIdentifier,
+        cursor_recording: Option<CursorRecording>,
     ) -> Self {
         Self {
             fonts,
             font_sorts,
             clipboard: "".to_string(),
+            language,
+            cursor_recording,
         }
     }
 }
@@ -107,7 +118,17 @@ impl UiBackend for TestUiBackend {
 
     fn set_mouse_visible(&mut self, _visible: bool) {}
 
-    fn set_mouse_cursor(&mut self, _cursor: MouseCursor) {}
+    fn set_mouse_lock(&mut self, _locked: bool) {}
+
+    fn mouse_locked(&self) -> bool {
+        false
+    }
+
+    fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
+        if let Some(cursor_recording) = &self.cursor_recording {
+            *cursor_recording.lock().expect("Non-poisoned recording") = cursor;
+        }
+    }
 
     fn clipboard_content(&mut self) -> String {
         self.clipboard.clone()
@@ -131,7 +152,7 @@ impl UiBackend for TestUiBackend {
     fn close_virtual_keyboard(&self) {}
 
     fn language(&self) -> LanguageIdentifier {
-        US_ENGLISH.clone()
+        self.language.clone()
     }
 
     fn display_unsupported_video(&self, _url: Url) {}
@@ -39,6 +39,11 @@ pub trait RenderInterface {
     /// This name may be used in file paths, so it should contain appropriate characters for such.
     fn name(&self) -> String;
 
+    /// Gets the name of the graphics backend that renders, such as `vulkan` or `metal`.
+    ///
+    /// Test expressions can check it with the `renderer` key, to vary by renderer.
+    fn renderer(&self) -> &'static str;
+
     /// Capture the stage rendered out by the given render backend.
     ///
     /// The provided backend is guaranteed to be the same one paired with this interface.
@@ -0,0 +1,87 @@
+use serde::Serialize;
+use std::fs::OpenOptions;
+use std::io::Write;
+use std::path::Path;
+
+/// The environment variable naming the file that the image comparison report is written to.
+///
+/// Without it, no report is kept.
+pub const IMAGE_REPORT_ENV_VAR: &str = "RUFFLE_IMAGE_REPORT";
+
+/// The outcome of a single check of an image comparison, as written to the report.
+#[derive(Serialize, Debug)]
+pub struct ImageCheckReport {
+    /// The directory of the test.
+    pub test: String,
+    /// The name of the compared image.
+    pub image: String,
+    /// The name of the render backend the image was captured with.
+    pub environment: String,
+    /// The index of the check among those of the image comparison.
+    pub check: usize,
+    pub passed: bool,
+    pub known_failure: bool,
+    /// Whether the image comparison only warns when the check fails,
+    /// so that a check that didn't pass is a warning rather than a failure.
+    pub warn_only: bool,
+    pub max_difference: u8,
+    #[serde(flatten)]
+    pub measurement: ImageCheckMeasurement,
+    /// The difference images that were saved because the check failed.
+    pub difference_images: Vec<String>,
+}
+
+/// What a check measured, and the limits it was held to.
+#[derive(Serialize, Debug)]
+#[serde(tag = "kind", rename_all = "snake_case")]
+pub enum ImageCheckMeasurement {
+    Outliers {
+        color_outliers: usize,
+        alpha_outliers: usize,
+        /// The tolerance of each channel, in RGBA order.
+        tolerance: [u8; 4],
+        max_outliers: usize,
+        max_color_outliers: Option<usize>,
+        max_alpha_outliers: Option<usize>,
+        /// The sum of the differences of all compared pixel channels.
+        total_difference: u64,
+        /// The mean difference of the compared pixel channels.
+        mean_difference: f64,
+        max_total_difference: Option<u64>,
+        max_mean_difference: Option<f64>,
+        downsample: Option<u32>,
+    },
+    Ssim {
+        ssim: f64,
+        min_ssim: f64,
+    },
+}
+
+/// Adds the outcome of a check to the report, when one was asked for with [`IMAGE_REPORT_ENV_VAR`].
+///
+/// Each check is appended to the file as a line of JSON, so that the report accumulates
+/// the checks of the whole run even when every test runs in a process of its own, as with nextest.
+pub fn record(check: ImageCheckReport) {
+    let Some(path) = std::env::var_os(IMAGE_REPORT_ENV_VAR) else {
+        return;
+    };
+    if let Err(e) = append_line(Path::new(&path), &check) {
+        eprintln!(
+            "Couldn't write the image comparison report to {}: {e}",
+            Path::new(&path).display()
+        );
+    }
+}
+
+/// Appends a value to a JSON Lines file in a single write,
+/// so that the lines of tests running at the same time don't get mixed up.
+fn append_line(path: &Path, value: &impl Serialize) -> anyhow::Result<()> {
+    let mut line = serde_json::to_vec(value)?;
+    line.push(b'\n');
+    OpenOptions::new()
+        .create(true)
+        .append(true)
+        .open(path)?
+        .write_all(&line)?;
+    Ok(())
+}
@@ -8,6 +8,9 @@ pub enum ImageTrigger {
     LastFrame,
     SpecificIteration(u32),
     FsCommand,
+    /// When the main timeline of the movie enters the given frame (1-based),
+    /// however many ticks or frames that takes.
+    Frame(u32),
 }
 
 impl<'de> Deserialize<'de> for ImageTrigger {
@@ -25,7 +28,7 @@ impl Visitor<'_> for ImageTriggerVisitor {
     type Value = ImageTrigger;
 
     fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
-        formatter.write_str("one of either: a numeric frame/tick number, the string \"last_frame\", or the string \"fs_command\"")
+        formatter.write_str("one of either: a numeric frame/tick number, the string \"last_frame\", the string \"fs_command\", or a timeline frame as the string \"frame:N\"")
     }
 
     fn visit_i64<E>(self, value: i64) -> Result<Self::Value, E>
@@ -60,10 +63,21 @@ impl Visitor<'_> for ImageTriggerVisitor {
             Ok(ImageTrigger::LastFrame)
         } else if value == "fs_command" {
             Ok(ImageTrigger::FsCommand)
+        } else if let Some(frame) = value.strip_prefix("frame:") {
+            frame
+                .trim()
+                .parse::<u32>()
+                .map(ImageTrigger::Frame)
+                .map_err(|_| E::custom(format!("invalid timeline frame: {value}")))
         } else {
             Err(E::unknown_variant(
                 value,
-                &["'last_frame'", "'fs_command'", "a frame/tick number"],
+                &[
+                    "'last_frame'",
+                    "'fs_command'",
+                    "a frame/tick number",
+                    "'frame:N'",
+                ],
             ))
         }
     }
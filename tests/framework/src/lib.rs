@@ -1,5 +1,7 @@
+pub mod audio_comparison;
 pub mod environment;
 pub mod fs_commands;
+pub mod image_report;
 pub mod image_trigger;
 pub mod options;
 pub mod runner;
@@ -1,20 +1,30 @@
-use crate::backends::TestAudioBackend;
+use crate::audio_comparison::AudioComparison;
+use crate::backends::{AudioRecording, TestAudioBackend};
 use crate::environment::{Environment, RenderInterface};
+use crate::image_report::{self, ImageCheckMeasurement, ImageCheckReport};
 use crate::image_trigger::ImageTrigger;
-use crate::util::write_image;
+use crate::util::{write_image, Mismatch};
 use anyhow::{anyhow, Result};
 use approx::relative_eq;
+use chrono::DateTime;
 use image::ImageFormat;
 use regex::Regex;
+use ruffle_core::backend::ui::{LanguageIdentifier, MouseCursor, US_ENGLISH};
+use ruffle_core::config::Letterbox;
 use ruffle_core::tag_utils::SwfMovie;
 use ruffle_core::{
-    FontQuery, FontType, PlayerBuilder, PlayerMode, PlayerRuntime, ViewportDimensions,
+    FontQuery, FontType, PlayerBuilder, PlayerMode, PlayerRuntime, StageAlign, StageScaleMode,
+    ViewportDimensions,
 };
+use ruffle_input_format::AutomatedEvent;
 use ruffle_render::backend::RenderBackend;
 use ruffle_render::quality::StageQuality;
-use serde::Deserialize;
+use serde::{Deserialize, Deserializer};
 use std::borrow::Cow;
+use std::cmp::Reverse;
 use std::collections::{HashMap, HashSet};
+use std::fmt;
+use std::str::FromStr;
 use std::time::Duration;
 use vfs::VfsPath;
 
@@ -24,14 +34,32 @@ pub struct TestOptions {
     pub num_frames: Option<u32>,
     pub num_ticks: Option<u32>,
     pub tick_rate: Option<f64>,
+    /// How the movie is moved forward, which has to be given when both `num_frames` and `num_ticks` are.
+    pub advance: Option<AdvanceMode>,
     pub output_path: String,
     pub sleep_to_meet_frame_rate: bool,
+    /// How long the whole test may take in wall-clock time, including any sleeping between frames.
+    pub timeout: Option<Duration>,
     pub image_comparisons: HashMap<String, ImageComparison>,
+    /// A comparison of the sound played during the whole test with `audio.expected.wav`.
+    pub audio_comparison: Option<AudioComparison>,
+    /// A check of the color of the stage at the end of the test, see [`StageColorAssertion`].
+    pub stage_color: Option<StageColorAssertion>,
     pub ignore: bool,
-    pub known_failure: bool,
+    pub known_failure: KnownFailure,
     pub approximations: Option<Approximations>,
+    /// Patterns of lines that are left out of both the actual and the expected output before comparing them,
+    /// for lines that are never the same, such as timestamps.
+    pub ignored_line_patterns: Vec<String>,
+    /// Replacements made in both the actual and the expected output before comparing them,
+    /// for parts of lines that are never the same, such as temporary paths.
+    pub output_substitutions: Vec<OutputSubstitution>,
     pub player_options: PlayerOptions,
     pub log_fetch: bool,
+    /// Input to deliver to the movie as the test runs, instead of an `input.json`.
+    pub input_events: Vec<ScheduledInputEvent>,
+    /// Responses served instead of fetching their URLs, see [`MockedResponses`].
+    pub mocked_responses: MockedResponses,
     pub required_features: RequiredFeatures,
     pub fonts: HashMap<String, FontOptions>,
     pub font_sorts: HashMap<String, FontSortOptions>,
@@ -43,14 +71,22 @@ impl Default for TestOptions {
             num_frames: None,
             num_ticks: None,
             tick_rate: None,
+            advance: None,
             output_path: "output.txt".to_string(),
             sleep_to_meet_frame_rate: false,
+            timeout: None,
             image_comparisons: Default::default(),
+            audio_comparison: None,
+            stage_color: None,
             ignore: false,
-            known_failure: false,
+            known_failure: KnownFailure::default(),
             approximations: None,
+            ignored_line_patterns: Default::default(),
+            output_substitutions: Default::default(),
             player_options: PlayerOptions::default(),
             log_fetch: false,
+            input_events: Default::default(),
+            mocked_responses: Default::default(),
             required_features: RequiredFeatures::default(),
             fonts: Default::default(),
             font_sorts: Default::default(),
@@ -65,39 +101,442 @@ impl TestOptions {
         Ok(result)
     }
 
+    /// How the test moves the movie forward, which follows whichever of `num_ticks` and `num_frames`
+    /// is set unless it's given.
+    pub fn advance_mode(&self) -> AdvanceMode {
+        self.advance.unwrap_or(if self.num_ticks.is_some() {
+            AdvanceMode::Ticks
+        } else {
+            AdvanceMode::Frames
+        })
+    }
+
+    /// How many ticks or frames the test runs for, depending on its advance mode.
+    pub fn num_iterations(&self) -> Option<u32> {
+        match self.advance_mode() {
+            AdvanceMode::Frames => self.num_frames,
+            AdvanceMode::Ticks | AdvanceMode::ExactTicks => self.num_ticks,
+        }
+    }
+
+    /// The patterns of lines to leave out of the output before comparing it.
+    pub fn ignored_line_patterns(&self) -> Vec<Regex> {
+        self.ignored_line_patterns
+            .iter()
+            .map(|pattern| Regex::new(pattern).unwrap())
+            .collect()
+    }
+
+    /// The patterns to replace in the output before comparing it, each with its replacement.
+    pub fn output_substitutions(&self) -> Vec<(Regex, &str)> {
+        self.output_substitutions
+            .iter()
+            .map(|substitution| {
+                (
+                    Regex::new(&substitution.pattern).unwrap(),
+                    substitution.replacement.as_str(),
+                )
+            })
+            .collect()
+    }
+
+    /// Whether this test is expected to fail, which inverts its result.
+    pub fn is_known_failure(&self) -> bool {
+        self.known_failure.is_set()
+    }
+
     fn validate(&self) -> Result<()> {
+        if self.num_frames.is_some() && self.num_ticks.is_some() && self.advance.is_none() {
+            return Err(anyhow!(
+                "Both num_frames and num_ticks are set, set 'advance' to tell which one the test runs for"
+            ));
+        }
+
+        for pattern in &self.ignored_line_patterns {
+            if let Err(e) = Regex::new(pattern) {
+                return Err(anyhow!("Invalid ignored line pattern '{pattern}': {e}"));
+            }
+        }
+
+        for substitution in &self.output_substitutions {
+            if let Err(e) = Regex::new(&substitution.pattern) {
+                return Err(anyhow!(
+                    "Invalid output substitution pattern '{}': {e}",
+                    substitution.pattern
+                ));
+            }
+        }
+
+        if self.required_features.video && !self.player_options.with_video {
+            return Err(anyhow!(
+                "The test requires the video feature, but doesn't set player_options.with_video"
+            ));
+        }
+        if self.required_features.renderer && !self.player_options.has_renderer() {
+            return Err(anyhow!(
+                "The test requires the renderer feature, but doesn't set player_options.with_renderer"
+            ));
+        }
+
+        if let Some(stage_color) = &self.stage_color {
+            if stage_color.source == StageColorSource::Average
+                && !self.player_options.has_renderer()
+            {
+                return Err(anyhow!(
+                    "The average stage color can only be checked with a renderer, set player_options.with_renderer"
+                ));
+            }
+        }
+
         if !self.image_comparisons.is_empty() {
             let mut seen_triggers = HashSet::new();
-            for comparison in self.image_comparisons.values() {
-                if comparison.trigger != ImageTrigger::FsCommand
-                    && !seen_triggers.insert(comparison.trigger)
-                {
+            for (name, comparison) in &self.image_comparisons {
+                match comparison.occurrence {
+                    Some(0) => {
+                        return Err(anyhow!(
+                            "Image comparison '{name}' has occurrence 0, but occurrences are counted from 1"
+                        ));
+                    }
+                    Some(occurrence)
+                        if occurrence > 1
+                            && !matches!(
+                                comparison.trigger,
+                                ImageTrigger::FsCommand | ImageTrigger::Frame(_)
+                            ) =>
+                    {
+                        return Err(anyhow!(
+                            "Image comparison '{name}' is set to occurrence {occurrence} of {:?}, which only happens once",
+                            comparison.trigger
+                        ));
+                    }
+                    // Matched by its name instead.
+                    None if comparison.trigger == ImageTrigger::FsCommand => continue,
+                    _ => {}
+                }
+                if let ImageTrigger::Frame(frame) = comparison.trigger {
+                    if frame == 0 || frame > u16::MAX as u32 {
+                        return Err(anyhow!(
+                            "Image comparison '{name}' is set to timeline frame {frame}, which doesn't exist"
+                        ));
+                    }
+                    // The timeline can't get further than one frame per frame run, barring gotos.
+                    if let Some(num_frames) = self.num_frames.filter(|num_frames| {
+                        self.advance_mode() == AdvanceMode::Frames && frame > *num_frames
+                    }) {
+                        return Err(anyhow!(
+                            "Image comparison '{name}' is set to timeline frame {frame}, but the test only runs {num_frames} frames"
+                        ));
+                    }
+                }
+                let occurrence = comparison.occurrence.unwrap_or(1);
+                if !seen_triggers.insert((comparison.trigger, occurrence)) {
                     return Err(anyhow!(
-                        "Multiple captures are set to trigger {:?}. This likely isn't intended!",
+                        "Multiple captures are set to trigger {:?} (occurrence {occurrence}). This likely isn't intended!",
                         comparison.trigger
                     ));
                 }
             }
         }
 
+        if self.audio_comparison.is_some() && !self.player_options.with_audio {
+            return Err(anyhow!(
+                "An audio comparison needs an audio backend, set 'player_options.with_audio'"
+            ));
+        }
+
+        for input_event in &self.input_events {
+            if input_event.tick == 0 {
+                return Err(anyhow!(
+                    "Input events are delivered after a tick, so their tick must be at least 1"
+                ));
+            }
+            if matches!(input_event.event, AutomatedEvent::Wait) {
+                return Err(anyhow!(
+                    "Input events can't wait, give the tick to deliver the next ones at instead"
+                ));
+            }
+        }
+
+        if let Some(fake_time) = &self.player_options.fake_time {
+            if DateTime::from_timestamp_millis(fake_time.start).is_none() {
+                return Err(anyhow!(
+                    "Fake time starts at {}, which is out of range",
+                    fake_time.start
+                ));
+            }
+            if fake_time
+                .tick_advance
+                .is_some_and(|tick_advance| !tick_advance.is_finite() || tick_advance < 0.0)
+            {
+                return Err(anyhow!(
+                    "Fake time can't advance by {:?} every tick",
+                    fake_time.tick_advance
+                ));
+            }
+        }
+
         Ok(())
     }
 
+    /// The input events as the input of an injector, split into ticks by [`AutomatedEvent::Wait`],
+    /// after checking that their mouse positions are on the stage.
+    pub fn scheduled_input(
+        &self,
+        viewport_dimensions: ViewportDimensions,
+    ) -> Result<Vec<AutomatedEvent>> {
+        let mut input_events = self.input_events.iter().collect::<Vec<_>>();
+        // Stable, so that events of the same tick stay in order.
+        input_events.sort_by_key(|input_event| input_event.tick);
+
+        let mut events = vec![];
+        // The injector delivers the input before the first `Wait` after the first tick.
+        let mut tick = 1;
+        for input_event in input_events {
+            if let AutomatedEvent::MouseMove { pos }
+            | AutomatedEvent::MouseDown { pos, .. }
+            | AutomatedEvent::MouseUp { pos, .. } = &input_event.event
+            {
+                if !(0.0..=viewport_dimensions.width as f64).contains(&pos.0)
+                    || !(0.0..=viewport_dimensions.height as f64).contains(&pos.1)
+                {
+                    return Err(anyhow!(
+                        "Input event at tick {} is at ({}, {}), outside of the {}x{} stage",
+                        input_event.tick,
+                        pos.0,
+                        pos.1,
+                        viewport_dimensions.width,
+                        viewport_dimensions.height
+                    ));
+                }
+            }
+            while tick < input_event.tick {
+                events.push(AutomatedEvent::Wait);
+                tick += 1;
+            }
+            events.push(input_event.event.clone());
+        }
+        Ok(events)
+    }
+
     pub fn output_path(&self, test_directory: &VfsPath) -> Result<VfsPath> {
         Ok(test_directory.join(&self.output_path)?)
     }
 }
 
+/// An input event to deliver at a given point of the test, see [`TestOptions::input_events`].
+///
+/// The event takes the same fields as in an `input.json`, next to the tick.
+#[derive(Clone, Deserialize)]
+pub struct ScheduledInputEvent {
+    /// The frame or tick (1-based) after which the event is delivered, before the next one runs.
+    pub tick: u32,
+    #[serde(flatten)]
+    pub event: AutomatedEvent,
+}
+
+/// Compares numbers in the output approximately, rather than exactly.
+///
+/// Finite numbers match if they're within `epsilon` or `max_relative` of each other (see [`relative_eq!`]).
+/// Non-finite numbers, such as the `NaN`, `Infinity` and `-Infinity` that Flash traces,
+/// only match the same non-finite number: `NaN` matches `NaN`, and an infinity
+/// matches an infinity of the same sign. A finite number never matches a non-finite one.
+///
+/// Each of the `number_patterns` may have a tolerance of its own, overriding these defaults.
 #[derive(Clone, Deserialize, Default)]
 #[serde(default, deny_unknown_fields)]
 pub struct Approximations {
-    number_patterns: Vec<String>,
+    number_patterns: Vec<NumberPattern>,
     epsilon: Option<f64>,
     max_relative: Option<f64>,
 }
 
 impl Approximations {
     pub fn compare(&self, actual: f64, expected: f64) -> Result<()> {
+        self.tolerance().compare(actual, expected)
+    }
+
+    /// The tolerance used for whole lines that are numbers, and for patterns without their own.
+    fn tolerance(&self) -> Tolerance {
+        Tolerance {
+            epsilon: self.epsilon,
+            max_relative: self.max_relative,
+        }
+    }
+
+    /// The patterns of numbers to compare approximately, each with the tolerance to compare them with.
+    pub fn number_patterns(&self) -> Vec<(Regex, Tolerance)> {
+        self.number_patterns
+            .iter()
+            .map(|pattern| match pattern {
+                NumberPattern::Pattern(pattern) => (Regex::new(pattern).unwrap(), self.tolerance()),
+                NumberPattern::WithTolerance(pattern) => (
+                    Regex::new(&pattern.pattern).unwrap(),
+                    Tolerance {
+                        epsilon: pattern.epsilon.or(self.epsilon),
+                        max_relative: pattern.max_relative.or(self.max_relative),
+                    },
+                ),
+            })
+            .collect()
+    }
+}
+
+/// A replacement of every match of a regex in the output of a test.
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct OutputSubstitution {
+    pub pattern: String,
+    /// What each match is replaced with, which may refer to capture groups like `$1`.
+    pub replacement: String,
+}
+
+/// How the runner moves the movie forward on every iteration of a test.
+#[derive(Clone, Copy, Debug, Deserialize, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum AdvanceMode {
+    /// Runs a frame of the movie directly, without ticking the player, for `num_frames` iterations.
+    Frames,
+
+    /// Ticks the player by the tick rate and renders, for `num_ticks` iterations.
+    Ticks,
+
+    /// Ticks the player by the tick rate like [`AdvanceMode::Ticks`], but only renders to capture images,
+    /// so that the side effects of rendering don't change what timer-driven code sees.
+    ExactTicks,
+}
+
+/// Whether a test is known to fail: either a plain boolean, the reason as a string,
+/// or a table with the `reason` and the `issue` tracking it.
+#[derive(Clone, Debug)]
+pub enum KnownFailure {
+    Flag(bool),
+    Reason(String),
+    Details(KnownFailureDetails),
+}
+
+#[derive(Clone, Debug, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct KnownFailureDetails {
+    reason: Option<String>,
+    issue: Option<String>,
+}
+
+impl Default for KnownFailure {
+    fn default() -> Self {
+        Self::Flag(false)
+    }
+}
+
+impl<'de> Deserialize<'de> for KnownFailure {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        struct KnownFailureVisitor;
+
+        impl<'de> serde::de::Visitor<'de> for KnownFailureVisitor {
+            type Value = KnownFailure;
+
+            fn expecting(&self, formatter: &mut fmt::Formatter) -> fmt::Result {
+                formatter.write_str("true, false, a reason, or a table with `reason` and `issue`")
+            }
+
+            fn visit_bool<E: serde::de::Error>(self, value: bool) -> Result<Self::Value, E> {
+                Ok(KnownFailure::Flag(value))
+            }
+
+            fn visit_str<E: serde::de::Error>(self, value: &str) -> Result<Self::Value, E> {
+                Ok(KnownFailure::Reason(value.to_string()))
+            }
+
+            fn visit_map<A>(self, map: A) -> Result<Self::Value, A::Error>
+            where
+                A: serde::de::MapAccess<'de>,
+            {
+                KnownFailureDetails::deserialize(serde::de::value::MapAccessDeserializer::new(map))
+                    .map(KnownFailure::Details)
+            }
+        }
+
+        deserializer.deserialize_any(KnownFailureVisitor)
+    }
+}
+
+impl KnownFailure {
+    pub fn is_set(&self) -> bool {
+        !matches!(self, Self::Flag(false))
+    }
+
+    /// Why the test fails, if that's been written down.
+    pub fn reason(&self) -> Option<&str> {
+        match self {
+            Self::Flag(_) => None,
+            Self::Reason(reason) => Some(reason),
+            Self::Details(details) => details.reason.as_deref(),
+        }
+    }
+
+    /// The issue tracking the failure, usually its URL.
+    pub fn issue(&self) -> Option<&str> {
+        match self {
+            Self::Details(details) => details.issue.as_deref(),
+            _ => None,
+        }
+    }
+}
+
+impl fmt::Display for KnownFailure {
+    /// Describes the failure for messages about the test, e.g. "(reason: ..., tracked in ...)".
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match (self.reason(), self.issue()) {
+            (Some(reason), Some(issue)) => write!(f, "(reason: {reason}, tracked in {issue})"),
+            (Some(reason), None) => write!(f, "(reason: {reason})"),
+            (None, Some(issue)) => write!(f, "(tracked in {issue})"),
+            (None, None) => write!(f, "(no reason given)"),
+        }
+    }
+}
+
+/// An entry of [`Approximations::number_patterns`], either just the regex,
+/// or a table with the regex and the tolerance to use for its captures.
+#[derive(Clone, Deserialize)]
+#[serde(untagged)]
+enum NumberPattern {
+    Pattern(String),
+    WithTolerance(NumberPatternWithTolerance),
+}
+
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+struct NumberPatternWithTolerance {
+    pattern: String,
+    epsilon: Option<f64>,
+    max_relative: Option<f64>,
+}
+
+/// How far apart two numbers may be while still being considered equal, see [`Approximations`].
+#[derive(Clone, Copy, Debug)]
+pub struct Tolerance {
+    epsilon: Option<f64>,
+    max_relative: Option<f64>,
+}
+
+impl Tolerance {
+    pub fn compare(&self, actual: f64, expected: f64) -> Result<()> {
+        if !actual.is_finite() || !expected.is_finite() {
+            // `relative_eq!` never matches NaN, and has no tolerance to speak of for infinities.
+            let same = (actual.is_nan() && expected.is_nan()) || actual == expected;
+            return if same {
+                Ok(())
+            } else {
+                Err(anyhow!(
+                    "Approximation failed: expected {}, found {}. Non-finite numbers only match the same non-finite number",
+                    expected,
+                    actual
+                ))
+            };
+        }
+
         let result = match (self.epsilon, self.max_relative) {
             (Some(epsilon), Some(max_relative)) => relative_eq!(
                 actual,
@@ -124,12 +563,76 @@ impl Approximations {
             ))
         }
     }
+}
 
-    pub fn number_patterns(&self) -> Vec<Regex> {
-        self.number_patterns
+/// Responses to serve instead of fetching URLs, keyed by the URL or by a glob of URLs.
+///
+/// URLs are matched after being resolved, so relative ones look like `file:///data.xml`.
+/// In a glob, `*` matches any number of characters and `?` matches a single one.
+///
+/// Once there are any, fetching a URL that matches none of them fails the test,
+/// rather than falling back to the files of the test.
+#[derive(Clone, Deserialize, Default)]
+#[serde(transparent)]
+pub struct MockedResponses(HashMap<String, MockedResponse>);
+
+impl MockedResponses {
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// Finds the response for `url`: the one listed for exactly that URL,
+    /// or else the one whose glob matches it.
+    ///
+    /// Fails if several globs match the URL, as there's no telling which one was meant.
+    pub fn find(&self, url: &str) -> Result<Option<&MockedResponse>> {
+        if let Some(response) = self.0.get(url) {
+            return Ok(Some(response));
+        }
+
+        let mut matches = self
+            .0
             .iter()
-            .map(|p| Regex::new(p).unwrap())
-            .collect()
+            .filter(|(pattern, _)| glob_regex(pattern).is_match(url));
+        match (matches.next(), matches.next()) {
+            (Some((_, response)), None) => Ok(Some(response)),
+            (None, _) => Ok(None),
+            (Some((first, _)), Some((second, _))) => Err(anyhow!(
+                "Multiple mocked responses match {url}, including '{first}' and '{second}'"
+            )),
+        }
+    }
+}
+
+fn glob_regex(glob: &str) -> Regex {
+    let mut pattern = String::from("^");
+    for c in glob.chars() {
+        match c {
+            '*' => pattern.push_str(".*"),
+            '?' => pattern.push('.'),
+            c => pattern.push_str(&regex::escape(c.encode_utf8(&mut [0; 4]))),
+        }
+    }
+    pattern.push('$');
+    Regex::new(&pattern).expect("Escaped glob should be a valid regex")
+}
+
+/// A response to serve instead of fetching a URL, see [`MockedResponses`].
+#[derive(Clone, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct MockedResponse {
+    /// The file containing the body of the response, relative to the test directory.
+    pub path: String,
+    /// The HTTP status code. Like with a real server, the fetch fails unless it's 2xx.
+    #[serde(default = "MockedResponse::default_status")]
+    pub status: u16,
+    /// The `Content-Type` header, whose charset is used to decode text.
+    pub content_type: Option<String>,
+}
+
+impl MockedResponse {
+    fn default_status() -> u16 {
+        200
     }
 }
 
@@ -138,11 +641,26 @@ impl Approximations {
 pub struct RequiredFeatures {
     lzma: bool,
     jpegxr: bool,
+    /// Whether a video decoder has to be built in, as `with_video` does nothing without one.
+    video: bool,
+    /// Whether the environment has to be able to render,
+    /// even if the test would otherwise run without a renderer where none is available.
+    renderer: bool,
 }
 
 impl RequiredFeatures {
-    pub fn can_run(&self) -> bool {
-        (!self.lzma || cfg!(feature = "lzma")) && (!self.jpegxr || cfg!(feature = "jpegxr"))
+    pub fn can_run(&self, check_renderer: bool, environment: &impl Environment) -> bool {
+        (!self.lzma || cfg!(feature = "lzma"))
+            && (!self.jpegxr || cfg!(feature = "jpegxr"))
+            && (!self.video
+                || cfg!(any(
+                    feature = "ruffle_video_external",
+                    feature = "ruffle_video_software"
+                )))
+            // As with `with_renderer`, creating a renderer is only checked when the test is actually run.
+            && (!self.renderer
+                || !check_renderer
+                || environment.is_render_supported(&RenderOptions::default()))
     }
 }
 
@@ -156,32 +674,65 @@ pub struct PlayerOptions {
     with_video: bool,
     runtime: PlayerRuntime,
     mode: Option<PlayerMode>,
+    random_seed: Option<u64>,
+    fake_time: Option<FakeTimeOptions>,
+    /// The initial scale mode of the stage, such as `no_scale`, which the movie may still change.
+    #[serde(deserialize_with = "deserialize_from_str")]
+    scale_mode: Option<StageScaleMode>,
+    /// The initial alignment of the stage, such as `top_left`, which the movie may still change.
+    #[serde(deserialize_with = "deserialize_from_str")]
+    align: Option<StageAlign>,
+    /// When the area of the viewport outside of the stage is hidden.
+    letterbox: Option<Letterbox>,
+    /// The language of the player, such as `ar-EG`, which defaults to `en-US` regardless of the system.
+    #[serde(deserialize_with = "deserialize_from_str")]
+    language: Option<LanguageIdentifier>,
+    /// Whether the root movie goes back to its first frame after its last one, which it does by default.
+    #[serde(rename = "loop")]
+    loop_root_movie: Option<bool>,
 }
 
 impl PlayerOptions {
-    pub fn setup(&self, mut player_builder: PlayerBuilder) -> Result<PlayerBuilder> {
+    /// Applies these options to the player, recording its sound to `audio_recording` if given.
+    pub fn setup(
+        &self,
+        mut player_builder: PlayerBuilder,
+        audio_recording: Option<AudioRecording>,
+    ) -> Result<PlayerBuilder> {
         if let Some(max_execution_duration) = self.max_execution_duration {
             player_builder = player_builder.with_max_execution_duration(max_execution_duration);
         }
 
         if let Some(render_options) = &self.with_renderer {
-            player_builder = player_builder.with_quality(match render_options.sample_count {
-                16 => StageQuality::High16x16,
-                8 => StageQuality::High8x8,
-                4 => StageQuality::High,
-                2 => StageQuality::Medium,
-                _ => StageQuality::Low,
-            });
+            player_builder = player_builder.with_quality(render_options.quality());
         }
 
         if self.with_audio {
-            player_builder = player_builder.with_audio(TestAudioBackend::default());
+            player_builder = player_builder.with_audio(TestAudioBackend::new(audio_recording));
         }
 
         player_builder = player_builder
             .with_player_runtime(self.runtime)
             // Assume flashplayerdebugger is used in tests
-            .with_player_mode(self.mode.unwrap_or(PlayerMode::Debug));
+            .with_player_mode(self.mode.unwrap_or(PlayerMode::Debug))
+            .with_random_seed(self.random_seed)
+            .with_fake_time(
+                self.fake_time
+                    .and_then(|fake_time| DateTime::from_timestamp_millis(fake_time.start)),
+            );
+
+        if let Some(scale_mode) = self.scale_mode {
+            player_builder = player_builder.with_scale_mode(scale_mode, false);
+        }
+        if let Some(align) = self.align {
+            player_builder = player_builder.with_align(align, false);
+        }
+        if let Some(letterbox) = self.letterbox {
+            player_builder = player_builder.with_letterbox(letterbox);
+        }
+        if let Some(loop_root_movie) = self.loop_root_movie {
+            player_builder = player_builder.with_loop(loop_root_movie);
+        }
 
         if self.with_video {
             #[cfg(feature = "ruffle_video_external")]
@@ -212,6 +763,19 @@ impl PlayerOptions {
         Ok(player_builder)
     }
 
+    /// How much the fake clock moves forward with every tick taking `tick_time` milliseconds,
+    /// or `None` if time isn't faked.
+    pub fn fake_time_advance(&self, tick_time: f64) -> Option<Duration> {
+        self.fake_time.map(|fake_time| {
+            Duration::from_secs_f64(fake_time.tick_advance.unwrap_or(tick_time) / 1000.0)
+        })
+    }
+
+    /// Whether the test is run with a renderer, at least where one is available.
+    pub fn has_renderer(&self) -> bool {
+        self.with_renderer.is_some()
+    }
+
     pub fn can_run(&self, check_renderer: bool, environment: &impl Environment) -> bool {
         if let Some(render) = &self.with_renderer {
             // If we don't actually want to check the renderer (ie we're just listing potential tests),
@@ -223,6 +787,10 @@ impl PlayerOptions {
         true
     }
 
+    pub fn language(&self) -> LanguageIdentifier {
+        self.language.clone().unwrap_or_else(|| US_ENGLISH.clone())
+    }
+
     pub fn viewport_dimensions(&self, movie: &SwfMovie) -> ViewportDimensions {
         self.viewport_dimensions
             .unwrap_or_else(|| ViewportDimensions {
@@ -245,19 +813,201 @@ impl PlayerOptions {
     }
 }
 
+/// Deserializes an optional value from a string, for types that are only parsed with [`FromStr`].
+fn deserialize_from_str<'de, D, T>(deserializer: D) -> Result<Option<T>, D::Error>
+where
+    D: Deserializer<'de>,
+    T: FromStr,
+{
+    let value = String::deserialize(deserializer)?;
+    T::from_str(&value)
+        .map(Some)
+        .map_err(|_| serde::de::Error::custom(format!("invalid value \"{value}\"")))
+}
+
 #[derive(Deserialize, Default, Clone, Debug)]
 #[serde(default, deny_unknown_fields)]
 pub struct ImageComparison {
     tolerance: Option<u8>,
     max_outliers: Option<usize>,
     checks: Vec<ImageComparisonCheck>,
+    expected_images: Vec<ExpectedImage>,
+    /// The format of the images written for this comparison, see [`ImageFileFormat`].
+    format: ImageFileFormat,
+    /// Whether both images are cropped to their non-transparent content before comparing them,
+    /// for content whose transparent padding differs between renderers.
+    crop_transparent: bool,
     pub trigger: ImageTrigger,
+    /// Which time the trigger fires that this comparison is captured at, counting from 1,
+    /// so that several comparisons can share a trigger.
+    ///
+    /// Every `captureImage` fscommand is an occurrence of [`ImageTrigger::FsCommand`], whatever its name,
+    /// and every time the timeline enters the frame is one of [`ImageTrigger::Frame`].
+    /// Without an occurrence, a comparison triggered by fscommand is matched by its name instead.
+    pub occurrence: Option<u32>,
+    /// How much the differences are scaled up in the saved color difference image,
+    /// defaulting to [`DEFAULT_DIFFERENCE_GAIN`].
+    difference_gain: Option<f32>,
+    /// Whether a failed check only prints a warning instead of failing the test,
+    /// for comparisons that are flaky on some hardware.
+    ///
+    /// The difference images are still saved, and the warning is printed in the output of the test
+    /// and kept in the image comparison report.
+    warn_only: bool,
+    /// The mouse cursor the content is expected to request when this image is captured.
+    cursor: Option<ExpectedCursor>,
+}
+
+/// A mouse cursor that the content may request, as named in `test.toml`.
+#[derive(Deserialize, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum ExpectedCursor {
+    Arrow,
+    Hand,
+    #[serde(rename = "ibeam")]
+    IBeam,
+    Grab,
+}
+
+impl From<ExpectedCursor> for MouseCursor {
+    fn from(cursor: ExpectedCursor) -> Self {
+        match cursor {
+            ExpectedCursor::Arrow => MouseCursor::Arrow,
+            ExpectedCursor::Hand => MouseCursor::Hand,
+            ExpectedCursor::IBeam => MouseCursor::IBeam,
+            ExpectedCursor::Grab => MouseCursor::Grab,
+        }
+    }
 }
 
+/// The color of pixels outside the checked region in the saved color difference image.
+const IGNORED_PIXEL_COLOR: [u8; 3] = [255, 0, 255];
+
+/// How much the differences are scaled up in color difference images by default,
+/// as the small differences of most failures are barely visible otherwise.
+pub const DEFAULT_DIFFERENCE_GAIN: f32 = 4.0;
+
 fn calc_difference(lhs: u8, rhs: u8) -> u8 {
     (lhs as i16 - rhs as i16).unsigned_abs() as u8
 }
 
+/// A check of the color of the whole stage at the end of the test,
+/// for tests that only care about it and don't need an expected image.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+pub struct StageColorAssertion {
+    /// The expected color, in RGBA order.
+    color: [u8; 4],
+    /// The tolerance per channel to be considered "the same".
+    #[serde(default)]
+    tolerance: u8,
+    #[serde(default)]
+    source: StageColorSource,
+}
+
+/// Where the color checked by a [`StageColorAssertion`] comes from.
+#[derive(Deserialize, Clone, Copy, Debug, Default, PartialEq, Eq)]
+#[serde(rename_all = "snake_case")]
+pub enum StageColorSource {
+    /// The background color of the stage, which is known without rendering.
+    #[default]
+    Background,
+    /// The average color of the rendered frame, which needs a renderer.
+    Average,
+}
+
+impl StageColorAssertion {
+    pub fn source(&self) -> StageColorSource {
+        self.source
+    }
+
+    /// Compares the actual color, in RGBA order, to the expected one.
+    pub fn test(&self, actual_color: [u8; 4]) -> Result<()> {
+        let max_difference = actual_color
+            .iter()
+            .zip(self.color)
+            .map(|(actual, expected)| calc_difference(*actual, expected))
+            .max()
+            .unwrap_or_default();
+        if max_difference > self.tolerance {
+            return Err(anyhow!(
+                "Stage color ({:?}) is {actual_color:?}, but {:?} was expected. \
+                Max difference is {max_difference}, over the tolerance of {}",
+                self.source,
+                self.color,
+                self.tolerance
+            ));
+        }
+        Ok(())
+    }
+}
+
+/// The average color of all pixels of the image, in RGBA order.
+pub fn average_color(image: &image::RgbaImage) -> [u8; 4] {
+    let pixel_count = image.width() as u64 * image.height() as u64;
+    if pixel_count == 0 {
+        return [0; 4];
+    }
+    let mut sums = [0u64; 4];
+    for pixel in image.pixels() {
+        for (sum, channel) in sums.iter_mut().zip(pixel.0) {
+            *sum += channel as u64;
+        }
+    }
+    sums.map(|sum| ((sum + pixel_count / 2) / pixel_count) as u8)
+}
+
+/// Builds an image of how much each color channel differs between the images,
+/// which are expected to have the same size.
+///
+/// The differences are multiplied by `gain` (saturating at white), so that tiny ones become visible.
+/// A gain of 1 gives the raw absolute differences.
+pub fn difference_heatmap(
+    actual: &image::RgbaImage,
+    expected: &image::RgbaImage,
+    gain: f32,
+) -> image::RgbImage {
+    assert_eq!(
+        actual.dimensions(),
+        expected.dimensions(),
+        "Only images of the same size can be compared"
+    );
+    let scale =
+        |lhs: u8, rhs: u8| (calc_difference(lhs, rhs) as f32 * gain).clamp(0.0, 255.0) as u8;
+    image::RgbImage::from_fn(actual.width(), actual.height(), |x, y| {
+        let (actual, expected) = (actual.get_pixel(x, y), expected.get_pixel(x, y));
+        image::Rgb([
+            scale(actual[0], expected[0]),
+            scale(actual[1], expected[1]),
+            scale(actual[2], expected[2]),
+        ])
+    })
+}
+
+/// Crops the fully transparent rows and columns around the image.
+///
+/// An image without any visible pixels is left as it is.
+fn crop_transparent_borders(image: &image::RgbaImage) -> image::RgbaImage {
+    let visible = image
+        .enumerate_pixels()
+        .filter(|(_, _, pixel)| pixel[3] != 0)
+        .map(|(x, y, _)| (x, y));
+    let Some((left, top, right, bottom)) = visible.fold(None, |bounds, (x, y)| {
+        Some(match bounds {
+            Some((left, top, right, bottom)) => (
+                u32::min(left, x),
+                u32::min(top, y),
+                u32::max(right, x),
+                u32::max(bottom, y),
+            ),
+            None => (x, y, x, y),
+        })
+    }) else {
+        return image.clone();
+    };
+    image::imageops::crop_imm(image, left, top, right - left + 1, bottom - top + 1).to_image()
+}
+
 impl ImageComparison {
     fn checks(&self) -> Result<Cow<'_, [ImageComparisonCheck]>> {
         let has_simple_check = self.tolerance.is_some() || self.max_outliers.is_some();
@@ -271,11 +1021,66 @@ impl ImageComparison {
         if !self.checks.is_empty() {
             Ok(Cow::Borrowed(&self.checks))
         } else {
-            Ok(Cow::Owned(vec![ImageComparisonCheck {
-                tolerance: self.tolerance.unwrap_or_default(),
-                max_outliers: self.max_outliers.unwrap_or_default(),
-                filter: None,
-            }]))
+            Ok(Cow::Owned(vec![ImageComparisonCheck::Outliers(
+                OutlierCheck {
+                    tolerance: self.tolerance.unwrap_or_default(),
+                    max_outliers: self.max_outliers.unwrap_or_default(),
+                    ..Default::default()
+                },
+            )]))
+        }
+    }
+
+    /// Returns the file name of the image to compare to in this environment.
+    ///
+    /// This is `{name}.expected.png` (or the extension of another format),
+    /// unless other expected images are listed, in which case the first one whose filter matches is used.
+    pub fn expected_image_path(
+        &self,
+        name: &str,
+        render_interface: &dyn RenderInterface,
+    ) -> Result<String> {
+        if self.expected_images.is_empty() {
+            return Ok(format!("{name}.expected.{}", self.format.extension()));
+        }
+
+        for expected_image in &self.expected_images {
+            let filter_passed = expected_image
+                .filter
+                .as_ref()
+                .map(|f| f.evaluate(render_interface))
+                .unwrap_or(Ok(true))?;
+            if filter_passed {
+                return Ok(expected_image.path.clone());
+            }
+        }
+
+        let candidates = self
+            .expected_images
+            .iter()
+            .map(|expected_image| match &expected_image.filter {
+                Some(filter) => format!("{} (filter: {})", expected_image.path, filter.0),
+                None => expected_image.path.clone(),
+            })
+            .collect::<Vec<_>>()
+            .join(", ");
+        Err(anyhow!(
+            "Image '{name}' failed: No expected image matches this environment. Candidates: {candidates}"
+        ))
+    }
+
+    /// The mouse cursor the content is expected to request when this image is captured, if it's checked.
+    pub fn expected_cursor(&self) -> Option<MouseCursor> {
+        self.cursor.map(MouseCursor::from)
+    }
+
+    /// Checks the mouse cursor requested by the content when this image is captured, if one is expected.
+    pub fn test_cursor(&self, name: &str, actual_cursor: MouseCursor) -> Result<()> {
+        match self.expected_cursor() {
+            Some(expected_cursor) if expected_cursor != actual_cursor => Err(anyhow!(
+                "Image '{name}' failed: The cursor is {actual_cursor:?}, but {expected_cursor:?} was expected."
+            )),
+            _ => Ok(()),
         }
     }
 
@@ -285,36 +1090,56 @@ impl ImageComparison {
         actual_image: image::RgbaImage,
         expected_image: image::RgbaImage,
         test_path: &VfsPath,
-        environment_name: String,
+        render_interface: &dyn RenderInterface,
         known_failure: bool,
     ) -> Result<()> {
         use anyhow::Context;
 
+        let environment_name = render_interface.name();
+
         let save_actual_image = || {
             if !known_failure {
                 // If we're expecting failure, spamming files isn't productive.
                 write_image(
-                    &test_path.join(format!("{name}.actual-{environment_name}.png"))?,
+                    &test_path.join(format!(
+                        "{name}.actual-{environment_name}.{}",
+                        self.format.extension()
+                    ))?,
                     &actual_image,
-                    ImageFormat::Png,
+                    self.format.image_format(),
                 )
             } else {
                 Ok(())
             }
         };
 
+        let (actual_image, expected_image) = if self.crop_transparent {
+            (
+                Cow::Owned(crop_transparent_borders(&actual_image)),
+                Cow::Owned(crop_transparent_borders(&expected_image)),
+            )
+        } else {
+            (Cow::Borrowed(&actual_image), Cow::Borrowed(&expected_image))
+        };
+
         if actual_image.width() != expected_image.width()
             || actual_image.height() != expected_image.height()
         {
             save_actual_image()?;
-            return Err(anyhow!(
-                "'{}' image is not the right size. Expected = {}x{}, actual = {}x{}.",
+            return Err(Mismatch(format!(
+                "'{}' image is not the right size{}. Expected = {}x{}, actual = {}x{}.",
                 name,
+                if self.crop_transparent {
+                    " after cropping its transparent borders"
+                } else {
+                    ""
+                },
                 expected_image.width(),
                 expected_image.height(),
                 actual_image.width(),
                 actual_image.height()
-            ));
+            ))
+            .into());
         }
 
         let mut is_alpha_different = false;
@@ -333,22 +1158,177 @@ impl ImageComparison {
         for (i, check) in checks.iter().enumerate() {
             let check_name = format!("Image '{name}' check {i}");
             let filter_passed = check
-                .filter
-                .as_ref()
-                .map(|f| f.evaluate())
+                .filter()
+                .map(|f| f.evaluate(render_interface))
                 .unwrap_or(Ok(true))?;
             if !filter_passed {
                 println!("{check_name} skipped: Filtered out.");
                 continue;
             }
 
-            let outliers = Self::calculate_outliers(&difference_data, check.tolerance);
-            let max_outliers = check.max_outliers;
+            let region = check.region();
+            if let Some(region) = region {
+                if region.width == 0 || region.height == 0 {
+                    return Err(anyhow!("{check_name} failed: Region {region} is empty."));
+                }
+                if !region.fits(actual_image.width(), actual_image.height()) {
+                    return Err(anyhow!(
+                        "{check_name} failed: Region {region} doesn't lie within the image ({}x{}).",
+                        actual_image.width(),
+                        actual_image.height()
+                    ));
+                }
+            }
+            let difference_data = match region {
+                Some(region) => Cow::Owned(Self::mask_difference_data(
+                    &difference_data,
+                    actual_image.width(),
+                    region,
+                )),
+                None => Cow::Borrowed(&difference_data),
+            };
+            let is_alpha_different = is_alpha_different
+                && (region.is_none() || difference_data.chunks_exact(4).any(|p| p[3] != 0));
+
             let max_difference = Self::calculate_max_difference(&difference_data);
 
             any_check_executed = true;
-            if outliers <= max_outliers {
-                println!("{check_name} succeeded: {outliers} outliers found, max difference {max_difference}");
+            let (passed, message, measurement) = match check {
+                ImageComparisonCheck::Outliers(check) => {
+                    let (outliers, differences, downsampled) = match check.downsample {
+                        None | Some(1) => (
+                            Self::calculate_outliers(&difference_data, check.tolerances()),
+                            (
+                                Self::calculate_total_difference(&difference_data),
+                                match region {
+                                    Some(region) => {
+                                        region.width as usize * region.height as usize * 4
+                                    }
+                                    None => difference_data.len(),
+                                },
+                            ),
+                            String::new(),
+                        ),
+                        Some(factor) => {
+                            let (actual_image, expected_image) = match region {
+                                Some(region) => (
+                                    Cow::Owned(region.crop(&actual_image)),
+                                    Cow::Owned(region.crop(&expected_image)),
+                                ),
+                                None => (
+                                    Cow::Borrowed(&*actual_image),
+                                    Cow::Borrowed(&*expected_image),
+                                ),
+                            };
+                            let (width, height) = actual_image.dimensions();
+                            if factor == 0 || width % factor != 0 || height % factor != 0 {
+                                return Err(anyhow!(
+                                    "{check_name} failed: Downsample factor {factor} doesn't evenly divide \
+                                    the compared size ({width}x{height})."
+                                ));
+                            }
+                            let difference_data = Self::calculate_difference_data(
+                                &Self::downsample(&actual_image, factor),
+                                &Self::downsample(&expected_image, factor),
+                                &mut false,
+                            );
+                            (
+                                Self::calculate_outliers(&difference_data, check.tolerances()),
+                                (
+                                    Self::calculate_total_difference(&difference_data),
+                                    difference_data.len(),
+                                ),
+                                format!(" after downsampling by {factor}"),
+                            )
+                        }
+                    };
+                    let (color_outliers, alpha_outliers) = outliers;
+                    let outliers = color_outliers + alpha_outliers;
+                    let (total_difference, compared_channels) = differences;
+                    let mean_difference =
+                        Self::calculate_mean_difference(total_difference, compared_channels);
+                    let mut failures = check.failed_limits(color_outliers, alpha_outliers);
+                    failures
+                        .extend(check.failed_difference_limits(total_difference, mean_difference));
+                    let measurement = ImageCheckMeasurement::Outliers {
+                        color_outliers,
+                        alpha_outliers,
+                        tolerance: check.tolerances(),
+                        max_outliers: check.max_outliers,
+                        max_color_outliers: check.max_color_outliers,
+                        max_alpha_outliers: check.max_alpha_outliers,
+                        total_difference,
+                        mean_difference,
+                        max_total_difference: check.max_total_difference,
+                        max_mean_difference: check.max_mean_difference,
+                        downsample: check.downsample,
+                    };
+                    if failures.is_empty() {
+                        (
+                            true,
+                            format!(
+                                "{outliers} outliers found ({color_outliers} color, {alpha_outliers} alpha){downsampled}, \
+                                max difference {max_difference}, total difference {total_difference} \
+                                (mean {mean_difference:.4})"
+                            ),
+                            measurement,
+                        )
+                    } else {
+                        (
+                            false,
+                            format!(
+                                "{}{downsampled}. Found {color_outliers} color and {alpha_outliers} alpha outliers, \
+                                max difference is {max_difference}, total difference is {total_difference} \
+                                (mean {mean_difference:.4})",
+                                failures.join(", ")
+                            ),
+                            measurement,
+                        )
+                    }
+                }
+                ImageComparisonCheck::Ssim(check) => {
+                    let ssim = match region {
+                        Some(region) => Self::calculate_ssim(
+                            &region.crop(&actual_image),
+                            &region.crop(&expected_image),
+                        ),
+                        None => Self::calculate_ssim(&actual_image, &expected_image),
+                    };
+                    let min_ssim = check.min_ssim;
+                    let measurement = ImageCheckMeasurement::Ssim { ssim, min_ssim };
+                    if ssim >= min_ssim {
+                        (
+                            true,
+                            format!("SSIM is {ssim:.6}, max difference {max_difference}"),
+                            measurement,
+                        )
+                    } else {
+                        (
+                            false,
+                            format!(
+                                "SSIM ({ssim:.6}) is lower than allowed limit of {min_ssim}. \
+                                Max difference is {max_difference}"
+                            ),
+                            measurement,
+                        )
+                    }
+                }
+            };
+            let mut report = ImageCheckReport {
+                test: test_path.as_str().to_string(),
+                image: name.to_string(),
+                environment: environment_name.clone(),
+                check: i,
+                passed,
+                known_failure,
+                warn_only: self.warn_only,
+                max_difference,
+                measurement,
+                difference_images: vec![],
+            };
+            if passed {
+                image_report::record(report);
+                println!("{check_name} succeeded: {message}");
                 continue;
             }
 
@@ -356,26 +1336,26 @@ impl ImageComparison {
 
             save_actual_image()?;
 
-            let mut difference_color = Vec::with_capacity(
-                actual_image.width() as usize * actual_image.height() as usize * 3,
-            );
-            for p in difference_data.chunks_exact(4) {
-                difference_color.extend_from_slice(&p[..3]);
-            }
-
             if !known_failure {
                 // If we're expecting failure, spamming files isn't productive.
-                let difference_image = image::RgbImage::from_raw(
-                    actual_image.width(),
-                    actual_image.height(),
-                    difference_color,
-                )
-                .context("Couldn't create color difference image")?;
-                write_image(
-                    &test_path.join(format!("{name}.difference-color-{environment_name}.png"))?,
-                    &difference_image,
-                    ImageFormat::Png,
-                )?;
+                let mut difference_image = difference_heatmap(
+                    &actual_image,
+                    &expected_image,
+                    self.difference_gain.unwrap_or(DEFAULT_DIFFERENCE_GAIN),
+                );
+                if let Some(region) = region {
+                    for (x, y, pixel) in difference_image.enumerate_pixels_mut() {
+                        if !region.contains(x, y) {
+                            *pixel = image::Rgb(IGNORED_PIXEL_COLOR);
+                        }
+                    }
+                }
+                let difference_path =
+                    test_path.join(format!("{name}.difference-color-{environment_name}.png"))?;
+                write_image(&difference_path, &difference_image, ImageFormat::Png)?;
+                report
+                    .difference_images
+                    .push(difference_path.as_str().to_string());
             }
 
             if is_alpha_different {
@@ -394,20 +1374,23 @@ impl ImageComparison {
                         difference_alpha,
                     )
                     .context("Couldn't create alpha difference image")?;
-                    write_image(
-                        &test_path
-                            .join(format!("{name}.difference-alpha-{environment_name}.png"))?,
-                        &difference_image,
-                        ImageFormat::Png,
-                    )?;
+                    let difference_path = test_path
+                        .join(format!("{name}.difference-alpha-{environment_name}.png"))?;
+                    write_image(&difference_path, &difference_image, ImageFormat::Png)?;
+                    report
+                        .difference_images
+                        .push(difference_path.as_str().to_string());
                 }
             }
 
-            return Err(anyhow!(
-                "{check_name} failed: \
-                Number of outliers ({outliers}) is bigger than allowed limit of {max_outliers}. \
-                Max difference is {max_difference}",
-            ));
+            image_report::record(report);
+
+            if self.warn_only {
+                println!("Warning: {check_name} failed, but the comparison only warns: {message}");
+                continue;
+            }
+
+            return Err(Mismatch(format!("{check_name} failed: {message}")).into());
         }
 
         if !any_check_executed {
@@ -441,18 +1424,130 @@ impl ImageComparison {
             .collect()
     }
 
-    fn calculate_outliers(difference_data: &[u8], tolerance: u8) -> usize {
+    /// Clears the differences of the pixels outside the region, so that they're ignored.
+    fn mask_difference_data(difference_data: &[u8], width: u32, region: ImageRegion) -> Vec<u8> {
+        difference_data
+            .chunks_exact(4)
+            .enumerate()
+            .flat_map(|(i, p)| {
+                if region.contains(i as u32 % width, i as u32 / width) {
+                    [p[0], p[1], p[2], p[3]]
+                } else {
+                    [0; 4]
+                }
+            })
+            .collect()
+    }
+
+    /// Averages each block of `factor`x`factor` pixels into a single one,
+    /// so that differences in where exactly an edge falls mostly cancel out.
+    ///
+    /// The dimensions of the image must be multiples of the factor.
+    fn downsample(image: &image::RgbaImage, factor: u32) -> image::RgbaImage {
+        let count = factor * factor;
+        image::RgbaImage::from_fn(image.width() / factor, image.height() / factor, |x, y| {
+            let mut sums = [0u32; 4];
+            for py in y * factor..(y + 1) * factor {
+                for px in x * factor..(x + 1) * factor {
+                    for (sum, channel) in sums.iter_mut().zip(image.get_pixel(px, py).0) {
+                        *sum += channel as u32;
+                    }
+                }
+            }
+            image::Rgba(sums.map(|sum| ((sum + count / 2) / count) as u8))
+        })
+    }
+
+    /// Counts the pixel channels that differ by more than their tolerance, given in RGBA order.
+    ///
+    /// Returns the number of color (RGB) and alpha outliers separately.
+    fn calculate_outliers(difference_data: &[u8], tolerances: [u8; 4]) -> (usize, usize) {
         difference_data
             .chunks_exact(4)
-            .map(|colors| {
-                (colors[0] > tolerance) as usize
-                    + (colors[1] > tolerance) as usize
-                    + (colors[2] > tolerance) as usize
-                    + (colors[3] > tolerance) as usize
+            .fold((0, 0), |(color, alpha), colors| {
+                (
+                    color
+                        + (colors[0] > tolerances[0]) as usize
+                        + (colors[1] > tolerances[1]) as usize
+                        + (colors[2] > tolerances[2]) as usize,
+                    alpha + (colors[3] > tolerances[3]) as usize,
+                )
             })
+    }
+
+    /// Sums the differences of all pixel channels, so that many small differences add up
+    /// even when none of them is an outlier.
+    fn calculate_total_difference(difference_data: &[u8]) -> u64 {
+        difference_data
+            .iter()
+            .map(|&difference| difference as u64)
             .sum()
     }
 
+    /// The mean difference of the compared pixel channels, which is 0 when none are compared.
+    fn calculate_mean_difference(total_difference: u64, compared_channels: usize) -> f64 {
+        if compared_channels == 0 {
+            0.0
+        } else {
+            total_difference as f64 / compared_channels as f64
+        }
+    }
+
+    /// Calculates the mean structural similarity (SSIM) of the images, from 0 to 1 for identical images.
+    ///
+    /// Each channel is compared over 8x8 windows, moved by half their size,
+    /// and the result is the mean over all windows and channels.
+    fn calculate_ssim(actual_image: &image::RgbaImage, expected_image: &image::RgbaImage) -> f64 {
+        const WINDOW_SIZE: u32 = 8;
+        const C1: f64 = (0.01 * 255.0) * (0.01 * 255.0);
+        const C2: f64 = (0.03 * 255.0) * (0.03 * 255.0);
+
+        let (width, height) = actual_image.dimensions();
+        let window_width = WINDOW_SIZE.min(width);
+        let window_height = WINDOW_SIZE.min(height);
+        if window_width == 0 || window_height == 0 {
+            return 1.0;
+        }
+        let window_starts = |size: u32, window_size: u32| {
+            let step = (window_size / 2).max(1) as usize;
+            (0..=size - window_size).step_by(step)
+        };
+
+        let mut total = 0.0;
+        let mut count = 0;
+        for y in window_starts(height, window_height) {
+            for x in window_starts(width, window_width) {
+                for channel in 0..4 {
+                    let pixels = || {
+                        (y..y + window_height).flat_map(move |py| {
+                            (x..x + window_width).map(move |px| {
+                                (
+                                    actual_image.get_pixel(px, py)[channel] as f64,
+                                    expected_image.get_pixel(px, py)[channel] as f64,
+                                )
+                            })
+                        })
+                    };
+                    let n = (window_width * window_height) as f64;
+                    let (sum_a, sum_e) =
+                        pixels().fold((0.0, 0.0), |(sa, se), (a, e)| (sa + a, se + e));
+                    let (mean_a, mean_e) = (sum_a / n, sum_e / n);
+                    let (var_a, var_e, covariance) =
+                        pixels().fold((0.0, 0.0, 0.0), |(va, ve, cov), (a, e)| {
+                            let (da, de) = (a - mean_a, e - mean_e);
+                            (va + da * da, ve + de * de, cov + da * de)
+                        });
+                    let (var_a, var_e, covariance) = (var_a / n, var_e / n, covariance / n);
+
+                    total += ((2.0 * mean_a * mean_e + C1) * (2.0 * covariance + C2))
+                        / ((mean_a * mean_a + mean_e * mean_e + C1) * (var_a + var_e + C2));
+                    count += 1;
+                }
+            }
+        }
+        total / count as f64
+    }
+
     fn calculate_max_difference(difference_data: &[u8]) -> u8 {
         difference_data
             .chunks_exact(4)
@@ -462,20 +1557,263 @@ impl ImageComparison {
     }
 }
 
+/// The file format of the expected and actual images of a comparison.
+///
+/// These are all lossless, so the images decode to the exact same pixels.
+/// The difference images are always PNG, as they're only looked at and never committed.
+#[derive(Deserialize, Default, Clone, Copy, Debug, PartialEq, Eq)]
+#[serde(rename_all = "lowercase")]
+enum ImageFileFormat {
+    #[default]
+    Png,
+    Webp,
+    Qoi,
+}
+
+impl ImageFileFormat {
+    fn extension(self) -> &'static str {
+        match self {
+            ImageFileFormat::Png => "png",
+            ImageFileFormat::Webp => "webp",
+            ImageFileFormat::Qoi => "qoi",
+        }
+    }
+
+    fn image_format(self) -> ImageFormat {
+        match self {
+            ImageFileFormat::Png => ImageFormat::Png,
+            ImageFileFormat::Webp => ImageFormat::WebP,
+            ImageFileFormat::Qoi => ImageFormat::Qoi,
+        }
+    }
+}
+
+/// A check of an image comparison, whose kind is given by its `type` key and defaults to `outliers`.
+#[derive(Clone, Debug)]
+enum ImageComparisonCheck {
+    /// Passes when the structural similarity of the images is high enough,
+    /// which tolerates sub-pixel differences better than counting outliers.
+    Ssim(SsimCheck),
+
+    /// Passes when few enough pixel channels differ by more than the tolerance.
+    Outliers(OutlierCheck),
+}
+
+impl<'de> Deserialize<'de> for ImageComparisonCheck {
+    fn deserialize<D>(deserializer: D) -> Result<Self, D::Error>
+    where
+        D: Deserializer<'de>,
+    {
+        #[derive(Deserialize)]
+        #[serde(rename_all = "snake_case")]
+        enum CheckType {
+            Ssim,
+            Outliers,
+        }
+
+        // The type is taken out first, so that the keys of the check itself are all known ones.
+        let mut table = toml::Table::deserialize(deserializer)?;
+        let check_type = match table.remove("type") {
+            Some(check_type) => CheckType::deserialize(check_type).map_err(|_| {
+                serde::de::Error::custom("unknown check type, expected \"ssim\" or \"outliers\"")
+            })?,
+            None => CheckType::Outliers,
+        };
+        let table = toml::Value::Table(table);
+        match check_type {
+            CheckType::Ssim => SsimCheck::deserialize(table).map(ImageComparisonCheck::Ssim),
+            CheckType::Outliers => {
+                OutlierCheck::deserialize(table).map(ImageComparisonCheck::Outliers)
+            }
+        }
+        .map_err(serde::de::Error::custom)
+    }
+}
+
+impl ImageComparisonCheck {
+    fn filter(&self) -> Option<&TestExpression> {
+        match self {
+            ImageComparisonCheck::Ssim(check) => check.filter.as_ref(),
+            ImageComparisonCheck::Outliers(check) => check.filter.as_ref(),
+        }
+    }
+
+    fn region(&self) -> Option<ImageRegion> {
+        match self {
+            ImageComparisonCheck::Ssim(check) => check.region,
+            ImageComparisonCheck::Outliers(check) => check.region,
+        }
+    }
+}
+
 #[derive(Deserialize, Default, Clone, Debug)]
 #[serde(default, deny_unknown_fields)]
-struct ImageComparisonCheck {
+struct OutlierCheck {
     tolerance: u8,
+    tolerance_r: Option<u8>,
+    tolerance_g: Option<u8>,
+    tolerance_b: Option<u8>,
+    tolerance_a: Option<u8>,
+    /// The most outliers allowed across all channels,
+    /// or in either channel group that doesn't have a limit of its own.
     max_outliers: usize,
+    /// The most outliers allowed in the red, green and blue channels.
+    max_color_outliers: Option<usize>,
+    /// The most outliers allowed in the alpha channel.
+    max_alpha_outliers: Option<usize>,
+    /// The largest sum of the differences of all compared pixel channels allowed.
+    max_total_difference: Option<u64>,
+    /// The largest mean difference of the compared pixel channels allowed.
+    max_mean_difference: Option<f64>,
+    /// The factor by which both images are scaled down before counting the outliers.
+    downsample: Option<u32>,
+
+    region: Option<ImageRegion>,
+    filter: Option<TestExpression>,
+}
+
+impl OutlierCheck {
+    /// The tolerance of each channel in RGBA order, which defaults to `tolerance` when not given.
+    fn tolerances(&self) -> [u8; 4] {
+        [
+            self.tolerance_r.unwrap_or(self.tolerance),
+            self.tolerance_g.unwrap_or(self.tolerance),
+            self.tolerance_b.unwrap_or(self.tolerance),
+            self.tolerance_a.unwrap_or(self.tolerance),
+        ]
+    }
+
+    /// Describes each outlier limit that the given counts exceed.
+    ///
+    /// Without separate color or alpha limits, the combined count is compared to `max_outliers`.
+    fn failed_limits(&self, color_outliers: usize, alpha_outliers: usize) -> Vec<String> {
+        if self.max_color_outliers.is_none() && self.max_alpha_outliers.is_none() {
+            let outliers = color_outliers + alpha_outliers;
+            let max_outliers = self.max_outliers;
+            return if outliers > max_outliers {
+                vec![format!(
+                    "Number of outliers ({outliers}) is bigger than allowed limit of {max_outliers}"
+                )]
+            } else {
+                vec![]
+            };
+        }
+
+        let max_color_outliers = self.max_color_outliers.unwrap_or(self.max_outliers);
+        let max_alpha_outliers = self.max_alpha_outliers.unwrap_or(self.max_outliers);
+        let mut failures = vec![];
+        if color_outliers > max_color_outliers {
+            failures.push(format!(
+                "Number of color outliers ({color_outliers}) is bigger than allowed limit of {max_color_outliers}"
+            ));
+        }
+        if alpha_outliers > max_alpha_outliers {
+            failures.push(format!(
+                "Number of alpha outliers ({alpha_outliers}) is bigger than allowed limit of {max_alpha_outliers}"
+            ));
+        }
+        failures
+    }
+
+    /// Describes each limit on the summed differences that the given measurements exceed.
+    fn failed_difference_limits(&self, total_difference: u64, mean_difference: f64) -> Vec<String> {
+        let mut failures = vec![];
+        if let Some(max_total_difference) = self.max_total_difference {
+            if total_difference > max_total_difference {
+                failures.push(format!(
+                    "Total difference ({total_difference}) is bigger than allowed limit of {max_total_difference}"
+                ));
+            }
+        }
+        if let Some(max_mean_difference) = self.max_mean_difference {
+            if mean_difference > max_mean_difference {
+                failures.push(format!(
+                    "Mean difference ({mean_difference:.4}) is bigger than allowed limit of {max_mean_difference}"
+                ));
+            }
+        }
+        failures
+    }
+}
+
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct SsimCheck {
+    min_ssim: f64,
+
+    region: Option<ImageRegion>,
+    filter: Option<TestExpression>,
+}
+
+/// The part of the image that a check compares, when the rest isn't deterministic.
+#[derive(Deserialize, Clone, Copy, Debug)]
+#[serde(deny_unknown_fields)]
+struct ImageRegion {
+    x: u32,
+    y: u32,
+    width: u32,
+    height: u32,
+}
+
+impl ImageRegion {
+    fn fits(&self, image_width: u32, image_height: u32) -> bool {
+        self.width > 0
+            && self.height > 0
+            && self
+                .x
+                .checked_add(self.width)
+                .is_some_and(|right| right <= image_width)
+            && self
+                .y
+                .checked_add(self.height)
+                .is_some_and(|bottom| bottom <= image_height)
+    }
+
+    fn contains(&self, x: u32, y: u32) -> bool {
+        x >= self.x && x - self.x < self.width && y >= self.y && y - self.y < self.height
+    }
+
+    fn crop(&self, image: &image::RgbaImage) -> image::RgbaImage {
+        image::imageops::crop_imm(image, self.x, self.y, self.width, self.height).to_image()
+    }
+}
+
+impl fmt::Display for ImageRegion {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        write!(
+            f,
+            "{}x{} at ({}, {})",
+            self.width, self.height, self.x, self.y
+        )
+    }
+}
 
+/// An image to compare to, used only in the environments matching its filter.
+#[derive(Deserialize, Clone, Debug)]
+#[serde(deny_unknown_fields)]
+struct ExpectedImage {
+    path: String,
     filter: Option<TestExpression>,
 }
 
+/// A clock standing in for the system time, so that `getTimer` and `Date` give the same times every run.
+#[derive(Clone, Copy, Deserialize)]
+#[serde(deny_unknown_fields)]
+pub struct FakeTimeOptions {
+    /// When the movie starts, in milliseconds since the Unix epoch.
+    start: i64,
+    /// How many milliseconds pass with every tick, or the time of a tick if unset.
+    tick_advance: Option<f64>,
+}
+
 #[derive(Clone, Deserialize)]
 #[serde(default, deny_unknown_fields)]
 pub struct RenderOptions {
     optional: bool,
     pub sample_count: u32,
+    /// The quality of the stage, such as `best` or `high8x8`, regardless of the sample count.
+    #[serde(deserialize_with = "deserialize_quality")]
+    quality: Option<StageQuality>,
 }
 
 impl Default for RenderOptions {
@@ -483,10 +1821,50 @@ impl Default for RenderOptions {
         Self {
             optional: false,
             sample_count: 1,
+            quality: None,
         }
     }
 }
 
+impl RenderOptions {
+    /// The quality of the stage, which is derived from the sample count unless it's given.
+    pub fn quality(&self) -> StageQuality {
+        self.quality.unwrap_or(match self.sample_count {
+            16 => StageQuality::High16x16,
+            8 => StageQuality::High8x8,
+            4 => StageQuality::High,
+            2 => StageQuality::Medium,
+            _ => StageQuality::Low,
+        })
+    }
+}
+
+/// Deserializes a stage quality from its name, which unlike [`StageQuality::from_str`]
+/// covers all of the qualities.
+fn deserialize_quality<'de, D>(deserializer: D) -> Result<Option<StageQuality>, D::Error>
+where
+    D: Deserializer<'de>,
+{
+    let value = String::deserialize(deserializer)?;
+    let quality = match value.as_str() {
+        "low" => StageQuality::Low,
+        "medium" => StageQuality::Medium,
+        "high" => StageQuality::High,
+        "best" => StageQuality::Best,
+        "high8x8" => StageQuality::High8x8,
+        "high8x8linear" => StageQuality::High8x8Linear,
+        "high16x16" => StageQuality::High16x16,
+        "high16x16linear" => StageQuality::High16x16Linear,
+        _ => {
+            return Err(serde::de::Error::custom(format!(
+                "invalid quality \"{value}\", expected low, medium, high, best, \
+                high8x8, high8x8linear, high16x16 or high16x16linear"
+            )))
+        }
+    };
+    Ok(Some(quality))
+}
+
 #[derive(Deserialize, Default, Clone)]
 #[serde(default, deny_unknown_fields)]
 pub struct FontOptions {
@@ -494,19 +1872,57 @@ pub struct FontOptions {
     pub path: String,
     pub bold: bool,
     pub italic: bool,
+    /// The weight of the font from 100 to 900, overriding `bold`.
+    pub weight: Option<u16>,
+    /// The paths of further fonts used for the glyphs missing from this one, in order.
+    pub fallbacks: Vec<String>,
 }
 
 impl FontOptions {
+    /// The weight of the font, which is 700 for a bold font and 400 otherwise, unless it's given.
+    pub fn weight(&self) -> u16 {
+        self.weight.unwrap_or(if self.bold { 700 } else { 400 })
+    }
+
+    /// Whether the font is bold, which is the case from a weight of 600, like in CSS.
+    ///
+    /// Ruffle only distinguishes between regular and bold fonts,
+    /// so this is how `weight` is taken into account, see also [`weight_mismatch`].
+    pub fn is_bold(&self) -> bool {
+        self.weight() >= 600
+    }
+
     pub fn to_font_query(&self) -> FontQuery {
         FontQuery::new(
             FontType::Device,
             self.family.clone(),
-            self.bold,
+            self.is_bold(),
+            self.italic,
+        )
+    }
+
+    /// The query of the fallback font at the given index, which is registered under a name of its own
+    /// so that it's only used for the glyphs missing from this font.
+    pub fn to_fallback_font_query(&self, index: usize) -> FontQuery {
+        FontQuery::new(
+            FontType::Device,
+            format!("{} (fallback {})", self.family, index + 1),
+            self.is_bold(),
             self.italic,
         )
     }
 }
 
+/// How far a font weight is from the usual weight of a regular or bold font (400 or 700),
+/// ordered from the closest one. On a tie the heavier weight is closer, like in CSS.
+///
+/// Of several fonts of a family that Ruffle can't tell apart, such as a light and a regular one,
+/// the closest one is used.
+pub fn weight_mismatch(weight: u16) -> (u16, Reverse<u16>) {
+    let usual = if weight >= 600 { 700 } else { 400 };
+    (weight.abs_diff(usual), Reverse(weight))
+}
+
 #[derive(Deserialize, Default, Clone)]
 #[serde(default, deny_unknown_fields)]
 pub struct FontSortOptions {
@@ -522,18 +1938,45 @@ pub struct FontSortOptions {
 /// Currently the following variables are supported:
 /// * `os` --- refers to [`std::env::consts::OS`],
 /// * `arch` --- refers to [`std::env::consts::ARCH`],
-/// * `family` --- refers to [`std::env::consts::FAMILY`].
+/// * `family` --- refers to [`std::env::consts::FAMILY`],
+/// * `renderer` --- refers to [`RenderInterface::renderer`] of the renderer used by the test,
+/// * `feature` --- refers to the cargo features of this crate, see [`FEATURES`].
 ///
 /// Example expression:
 ///
 /// ```text
 /// not(os = "aarch64")
 /// ```
+///
+/// ```text
+/// all(os = "linux", renderer = "vulkan")
+/// ```
+///
+/// ```text
+/// feature = "jpegxr"
+/// ```
 #[derive(Deserialize, Clone, Debug)]
 struct TestExpression(String);
 
+/// The cargo features that test expressions may check, and whether they're compiled in.
+///
+/// `cfg!` only takes literals, so every feature has to be listed here.
+const FEATURES: &[(&str, bool)] = &[
+    ("jpegxr", cfg!(feature = "jpegxr")),
+    ("lzma", cfg!(feature = "lzma")),
+    (
+        "ruffle_video_software",
+        cfg!(feature = "ruffle_video_software"),
+    ),
+    (
+        "ruffle_video_external",
+        cfg!(feature = "ruffle_video_external"),
+    ),
+];
+
 impl TestExpression {
-    fn evaluate(&self) -> Result<bool> {
+    fn evaluate(&self, render_interface: &dyn RenderInterface) -> Result<bool> {
+        let renderer = render_interface.renderer();
         let cfg_parsed = cfg_expr::Expression::parse(&self.0)
             .map_err(|err| anyhow!("Cannot parse expression:\n{err}"))?;
         let mut unknown_pred = None;
@@ -547,6 +1990,16 @@ impl TestExpression {
             cfg_expr::Predicate::KeyValue { key, val } if *key == "family" => {
                 *val == std::env::consts::FAMILY
             }
+            cfg_expr::Predicate::KeyValue { key, val } if *key == "renderer" => *val == renderer,
+            cfg_expr::Predicate::Feature(feature) => {
+                match FEATURES.iter().find(|(name, _)| name == feature) {
+                    Some((_, enabled)) => *enabled,
+                    None => {
+                        unknown_pred = Some(format!("{pred:?}"));
+                        false
+                    }
+                }
+            }
             _ => {
                 unknown_pred = Some(format!("{pred:?}"));
                 false
@@ -558,3 +2011,773 @@ impl TestExpression {
         Ok(cfg_matches)
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn parse_check(check: &str) -> Result<ImageComparisonCheck, toml::de::Error> {
+        toml::from_str(check)
+    }
+
+    #[test]
+    fn check_defaults_to_outliers() {
+        let check = parse_check("tolerance = 2\nmax_outliers = 10").unwrap();
+        let ImageComparisonCheck::Outliers(check) = check else {
+            panic!("expected an outlier check, got {check:?}");
+        };
+        assert_eq!(check.tolerance, 2);
+        assert_eq!(check.max_outliers, 10);
+    }
+
+    #[test]
+    fn outlier_check_with_type() {
+        let check = parse_check("type = \"outliers\"\ntolerance_a = 3").unwrap();
+        let ImageComparisonCheck::Outliers(check) = check else {
+            panic!("expected an outlier check, got {check:?}");
+        };
+        assert_eq!(check.tolerances(), [0, 0, 0, 3]);
+    }
+
+    #[test]
+    fn ssim_check() {
+        let check = parse_check(
+            "type = \"ssim\"\nmin_ssim = 0.95\nregion = { x = 1, y = 2, width = 3, height = 4 }",
+        )
+        .unwrap();
+        let ImageComparisonCheck::Ssim(check) = check else {
+            panic!("expected an SSIM check, got {check:?}");
+        };
+        assert_eq!(check.min_ssim, 0.95);
+        assert!(check.region.is_some());
+    }
+
+    #[test]
+    fn unknown_check_type() {
+        let error = parse_check("type = \"psnr\"\nmin_ssim = 0.9").unwrap_err();
+        assert!(
+            error.to_string().contains("unknown check type"),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn mixed_check_keys() {
+        let error = parse_check("type = \"ssim\"\nmin_ssim = 0.9\nmax_outliers = 5").unwrap_err();
+        assert!(
+            error.to_string().contains("unknown field `max_outliers`"),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn ssim_keys_without_type() {
+        let error = parse_check("min_ssim = 0.9").unwrap_err();
+        assert!(
+            error.to_string().contains("unknown field `min_ssim`"),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn misspelled_check_key() {
+        let error = parse_check("tolerence = 1").unwrap_err();
+        assert!(
+            error.to_string().contains("unknown field `tolerence`"),
+            "unexpected error: {error}"
+        );
+    }
+
+    fn parse_known_failure(known_failure: &str) -> Result<KnownFailure, toml::de::Error> {
+        #[derive(Deserialize)]
+        struct Options {
+            known_failure: KnownFailure,
+        }
+        toml::from_str::<Options>(&format!("known_failure = {known_failure}"))
+            .map(|options| options.known_failure)
+    }
+
+    #[test]
+    fn known_failure_flag() {
+        let known_failure = parse_known_failure("true").unwrap();
+        assert!(known_failure.is_set());
+        assert_eq!(known_failure.reason(), None);
+        assert_eq!(known_failure.issue(), None);
+
+        assert!(!parse_known_failure("false").unwrap().is_set());
+    }
+
+    #[test]
+    fn known_failure_reason() {
+        let known_failure = parse_known_failure("\"Blend modes aren't implemented\"").unwrap();
+        assert!(known_failure.is_set());
+        assert_eq!(
+            known_failure.reason(),
+            Some("Blend modes aren't implemented")
+        );
+        assert_eq!(known_failure.issue(), None);
+    }
+
+    #[test]
+    fn known_failure_details() {
+        let known_failure = parse_known_failure(
+            "{ reason = \"Wrong kerning\", issue = \"https://github.com/ruffle-rs/ruffle/issues/1\" }",
+        )
+        .unwrap();
+        assert!(known_failure.is_set());
+        assert_eq!(known_failure.reason(), Some("Wrong kerning"));
+        assert_eq!(
+            known_failure.issue(),
+            Some("https://github.com/ruffle-rs/ruffle/issues/1")
+        );
+
+        let known_failure = parse_known_failure("{}").unwrap();
+        assert!(known_failure.is_set());
+        assert_eq!(known_failure.reason(), None);
+    }
+
+    #[test]
+    fn known_failure_errors() {
+        let error = parse_known_failure("{ reson = \"Typo\" }").unwrap_err();
+        assert!(
+            error.to_string().contains("unknown field `reson`"),
+            "unexpected error: {error}"
+        );
+
+        let error = parse_known_failure("1").unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("true, false, a reason, or a table with `reason` and `issue`"),
+            "unexpected error: {error}"
+        );
+    }
+
+    /// Renders nothing, only telling test expressions which renderer it is.
+    struct FakeRenderInterface(&'static str);
+
+    impl RenderInterface for FakeRenderInterface {
+        fn name(&self) -> String {
+            format!("fake-{}", self.0)
+        }
+
+        fn renderer(&self) -> &'static str {
+            self.0
+        }
+
+        fn capture(&self, _renderer: &mut dyn RenderBackend) -> image::RgbaImage {
+            unimplemented!("fake renderers can't capture")
+        }
+    }
+
+    fn parse_image_comparison(comparison: &str) -> ImageComparison {
+        toml::from_str(comparison).unwrap()
+    }
+
+    #[test]
+    fn default_expected_image_path() {
+        let comparison = parse_image_comparison("");
+        assert_eq!(
+            comparison
+                .expected_image_path("output", &FakeRenderInterface("vulkan"))
+                .unwrap(),
+            "output.expected.png"
+        );
+
+        let comparison = parse_image_comparison("format = \"webp\"");
+        assert_eq!(
+            comparison
+                .expected_image_path("output", &FakeRenderInterface("vulkan"))
+                .unwrap(),
+            "output.expected.webp"
+        );
+    }
+
+    #[test]
+    fn expected_image_path_per_renderer() {
+        let comparison = parse_image_comparison(
+            r#"expected_images = [
+                { path = "metal.png", filter = 'renderer = "metal"' },
+                { path = "vulkan.png", filter = 'renderer = "vulkan"' },
+                { path = "other.png" },
+            ]"#,
+        );
+        let path = |renderer| {
+            comparison
+                .expected_image_path("output", &FakeRenderInterface(renderer))
+                .unwrap()
+        };
+        assert_eq!(path("metal"), "metal.png");
+        assert_eq!(path("vulkan"), "vulkan.png");
+        assert_eq!(path("dx12"), "other.png");
+    }
+
+    #[test]
+    fn no_expected_image_matches() {
+        let comparison = parse_image_comparison(
+            r#"expected_images = [{ path = "metal.png", filter = 'renderer = "metal"' }]"#,
+        );
+        let error = comparison
+            .expected_image_path("output", &FakeRenderInterface("vulkan"))
+            .unwrap_err();
+        assert!(
+            error
+                .to_string()
+                .contains("Candidates: metal.png (filter: renderer = \"metal\")"),
+            "unexpected error: {error}"
+        );
+    }
+
+    fn font(bold: bool, weight: Option<u16>) -> FontOptions {
+        FontOptions {
+            family: "Test".to_string(),
+            bold,
+            weight,
+            ..Default::default()
+        }
+    }
+
+    #[test]
+    fn font_weight_decides_boldness() {
+        for weight in [100, 200, 300, 400, 500, 599] {
+            assert!(!font(true, Some(weight)).is_bold(), "{weight} is regular");
+        }
+        for weight in [600, 700, 800, 900] {
+            assert!(font(false, Some(weight)).is_bold(), "{weight} is bold");
+            assert!(font(false, Some(weight)).to_font_query().is_bold);
+        }
+        assert_eq!(font(false, Some(300)).weight(), 300);
+        assert_eq!(font(true, Some(300)).weight(), 300);
+    }
+
+    #[test]
+    fn font_bold_without_weight() {
+        assert_eq!(font(false, None).weight(), 400);
+        assert!(!font(false, None).is_bold());
+        assert_eq!(font(true, None).weight(), 700);
+        assert!(font(true, None).is_bold());
+    }
+
+    #[test]
+    fn weight_mismatch_prefers_usual_weights() {
+        let mut regular = [100, 300, 500, 200, 400];
+        regular.sort_by_key(|&weight| weight_mismatch(weight));
+        assert_eq!(regular, [400, 500, 300, 200, 100]);
+
+        let mut bold = [900, 600, 800, 700];
+        bold.sort_by_key(|&weight| weight_mismatch(weight));
+        assert_eq!(bold, [700, 800, 600, 900]);
+    }
+
+    #[test]
+    fn total_difference_sums_all_channels() {
+        assert_eq!(ImageComparison::calculate_total_difference(&[]), 0);
+        assert_eq!(
+            ImageComparison::calculate_total_difference(&[1, 2, 3, 4, 255, 0, 0, 255]),
+            520
+        );
+    }
+
+    #[test]
+    fn mean_difference_without_channels_is_zero() {
+        assert_eq!(ImageComparison::calculate_mean_difference(0, 0), 0.0);
+        assert_eq!(ImageComparison::calculate_mean_difference(520, 8), 65.0);
+    }
+
+    #[test]
+    fn difference_limits() {
+        let check = OutlierCheck {
+            max_total_difference: Some(100),
+            max_mean_difference: Some(0.5),
+            ..Default::default()
+        };
+        assert!(check.failed_difference_limits(100, 0.5).is_empty());
+        assert_eq!(
+            check.failed_difference_limits(101, 0.75),
+            [
+                "Total difference (101) is bigger than allowed limit of 100",
+                "Mean difference (0.7500) is bigger than allowed limit of 0.5",
+            ]
+        );
+        assert_eq!(
+            check.failed_difference_limits(50, 0.75),
+            ["Mean difference (0.7500) is bigger than allowed limit of 0.5"]
+        );
+
+        let unlimited = OutlierCheck::default();
+        assert!(unlimited
+            .failed_difference_limits(u64::MAX, f64::MAX)
+            .is_empty());
+    }
+
+    const REGION: ImageRegion = ImageRegion {
+        x: 1,
+        y: 2,
+        width: 3,
+        height: 4,
+    };
+
+    #[test]
+    fn region_fits_image() {
+        assert!(REGION.fits(4, 6));
+        assert!(REGION.fits(10, 10));
+        assert!(!REGION.fits(3, 6));
+        assert!(!REGION.fits(4, 5));
+
+        let empty = ImageRegion { width: 0, ..REGION };
+        assert!(!empty.fits(10, 10));
+        let overflowing = ImageRegion {
+            x: u32::MAX,
+            ..REGION
+        };
+        assert!(!overflowing.fits(u32::MAX, u32::MAX));
+    }
+
+    #[test]
+    fn region_contains_pixels() {
+        assert!(REGION.contains(1, 2));
+        assert!(REGION.contains(3, 5));
+        assert!(!REGION.contains(0, 2));
+        assert!(!REGION.contains(1, 1));
+        assert!(!REGION.contains(4, 2));
+        assert!(!REGION.contains(1, 6));
+        assert_eq!(REGION.to_string(), "3x4 at (1, 2)");
+    }
+
+    #[test]
+    fn region_crops_image() {
+        let image = image::RgbaImage::from_fn(5, 7, |x, y| image::Rgba([x as u8, y as u8, 0, 255]));
+        let cropped = REGION.crop(&image);
+        assert_eq!(cropped.dimensions(), (3, 4));
+        assert_eq!(cropped.get_pixel(0, 0).0, [1, 2, 0, 255]);
+        assert_eq!(cropped.get_pixel(2, 3).0, [3, 5, 0, 255]);
+    }
+
+    #[test]
+    fn differences_outside_region_are_masked() {
+        let region = ImageRegion {
+            x: 1,
+            y: 0,
+            width: 1,
+            height: 1,
+        };
+        // A 2x2 image, whose every channel differs by 9.
+        let masked = ImageComparison::mask_difference_data(&[9; 16], 2, region);
+        assert_eq!(masked, [0, 0, 0, 0, 9, 9, 9, 9, 0, 0, 0, 0, 0, 0, 0, 0]);
+    }
+
+    #[test]
+    fn channel_tolerances_default_to_tolerance() {
+        let check = OutlierCheck {
+            tolerance: 2,
+            tolerance_g: Some(5),
+            tolerance_a: Some(0),
+            ..Default::default()
+        };
+        assert_eq!(check.tolerances(), [2, 5, 2, 0]);
+    }
+
+    #[test]
+    fn outliers_per_channel_tolerance() {
+        // Two pixels, differing by 3 in every channel, then by 6.
+        let difference_data = [3, 3, 3, 3, 6, 6, 6, 6];
+        assert_eq!(
+            ImageComparison::calculate_outliers(&difference_data, [3; 4]),
+            (3, 1)
+        );
+        assert_eq!(
+            ImageComparison::calculate_outliers(&difference_data, [2, 3, 5, 6]),
+            (4, 0)
+        );
+        assert_eq!(
+            ImageComparison::calculate_outliers(&difference_data, [6; 4]),
+            (0, 0)
+        );
+    }
+
+    fn mocked_responses() -> MockedResponses {
+        toml::from_str(
+            r#"
+            "https://example.com/data.xml" = { path = "exact.xml" }
+            "https://example.com/*.xml" = { path = "any.xml", status = 404 }
+            "file:///image?.png" = { path = "image.png", content_type = "image/png" }
+            "#,
+        )
+        .unwrap()
+    }
+
+    fn mocked_path(responses: &MockedResponses, url: &str) -> Option<String> {
+        responses
+            .find(url)
+            .unwrap()
+            .map(|response| response.path.clone())
+    }
+
+    #[test]
+    fn mocked_response_exact_url() {
+        let responses = mocked_responses();
+        assert_eq!(
+            mocked_path(&responses, "https://example.com/data.xml").as_deref(),
+            Some("exact.xml")
+        );
+    }
+
+    #[test]
+    fn mocked_response_glob() {
+        let responses = mocked_responses();
+        let response = responses
+            .find("https://example.com/other.xml")
+            .unwrap()
+            .unwrap();
+        assert_eq!(response.path, "any.xml");
+        assert_eq!(response.status, 404);
+
+        let response = responses.find("file:///image1.png").unwrap().unwrap();
+        assert_eq!(response.path, "image.png");
+        assert_eq!(response.status, 200);
+        assert_eq!(response.content_type.as_deref(), Some("image/png"));
+
+        // `?` is a single character, and the dots aren't wildcards.
+        assert!(mocked_path(&responses, "file:///image12.png").is_none());
+        assert!(mocked_path(&responses, "file:///image1xpng").is_none());
+        assert!(mocked_path(&responses, "https://example.com/data.json").is_none());
+    }
+
+    #[test]
+    fn mocked_response_ambiguous_globs() {
+        let responses: MockedResponses = toml::from_str(
+            r#"
+            "https://example.com/*" = { path = "any" }
+            "*.xml" = { path = "xml" }
+            "#,
+        )
+        .unwrap();
+        let error = responses
+            .find("https://example.com/data.xml")
+            .err()
+            .unwrap();
+        assert!(
+            error
+                .to_string()
+                .starts_with("Multiple mocked responses match https://example.com/data.xml"),
+            "unexpected error: {error}"
+        );
+        assert_eq!(
+            mocked_path(&responses, "https://example.com/data.txt").as_deref(),
+            Some("any")
+        );
+    }
+
+    fn parse_test_options(options: &str) -> TestOptions {
+        toml::from_str(options).unwrap()
+    }
+
+    const VIEWPORT: ViewportDimensions = ViewportDimensions {
+        width: 100,
+        height: 80,
+        scale_factor: 1.0,
+    };
+
+    #[test]
+    fn input_events_are_scheduled_by_tick() {
+        let options = parse_test_options(
+            r#"
+            [[input_events]]
+            tick = 3
+            type = "FocusLost"
+
+            [[input_events]]
+            tick = 1
+            type = "MouseMove"
+            pos = [10.0, 20.0]
+
+            [[input_events]]
+            tick = 1
+            type = "FocusGained"
+            "#,
+        );
+        let events = options.scheduled_input(VIEWPORT).unwrap();
+        assert_eq!(
+            format!("{events:?}"),
+            "[MouseMove { pos: MousePosition(10.0, 20.0) }, FocusGained, Wait, Wait, FocusLost]"
+        );
+    }
+
+    #[test]
+    fn input_events_outside_of_stage() {
+        let options = parse_test_options(
+            r#"
+            [[input_events]]
+            tick = 2
+            type = "MouseUp"
+            pos = [100.0, 81.0]
+            btn = "Left"
+            "#,
+        );
+        let error = options.scheduled_input(VIEWPORT).unwrap_err();
+        assert_eq!(
+            error.to_string(),
+            "Input event at tick 2 is at (100, 81), outside of the 100x80 stage"
+        );
+    }
+
+    #[test]
+    fn no_input_events() {
+        let options = parse_test_options("");
+        assert!(options.scheduled_input(VIEWPORT).unwrap().is_empty());
+    }
+
+    #[test]
+    fn random_seed() {
+        let options = parse_test_options("[player_options]\nrandom_seed = 1234");
+        assert_eq!(options.player_options.random_seed, Some(1234));
+
+        let options = parse_test_options("");
+        assert_eq!(options.player_options.random_seed, None);
+    }
+
+    #[test]
+    fn fake_time_advance() {
+        let options = parse_test_options("[player_options]\nfake_time = { start = 0 }");
+        assert_eq!(
+            options.player_options.fake_time_advance(50.0),
+            Some(Duration::from_millis(50))
+        );
+
+        let options =
+            parse_test_options("[player_options]\nfake_time = { start = 0, tick_advance = 1000 }");
+        assert_eq!(
+            options.player_options.fake_time_advance(50.0),
+            Some(Duration::from_secs(1))
+        );
+
+        let options = parse_test_options("");
+        assert_eq!(options.player_options.fake_time_advance(50.0), None);
+    }
+
+    #[test]
+    fn invalid_fake_time() {
+        let options =
+            parse_test_options("[player_options]\nfake_time = { start = 9223372036854775807 }");
+        assert_eq!(
+            options.validate().unwrap_err().to_string(),
+            "Fake time starts at 9223372036854775807, which is out of range"
+        );
+
+        let options =
+            parse_test_options("[player_options]\nfake_time = { start = 0, tick_advance = -1 }");
+        assert_eq!(
+            options.validate().unwrap_err().to_string(),
+            "Fake time can't advance by Some(-1.0) every tick"
+        );
+
+        let options = parse_test_options("[player_options]\nfake_time = { start = 981152406000 }");
+        assert!(options.validate().is_ok());
+    }
+
+    #[test]
+    fn transparent_borders_are_cropped() {
+        let image = image::RgbaImage::from_fn(6, 5, |x, y| {
+            // Visible pixels at the top left and bottom right corners of a 3x2 area.
+            if (x, y) == (1, 2) || (x, y) == (3, 3) {
+                image::Rgba([x as u8, y as u8, 0, 1])
+            } else {
+                image::Rgba([255, 255, 255, 0])
+            }
+        });
+        let cropped = crop_transparent_borders(&image);
+        assert_eq!(cropped.dimensions(), (3, 2));
+        assert_eq!(cropped.get_pixel(0, 0).0, [1, 2, 0, 1]);
+        assert_eq!(cropped.get_pixel(2, 1).0, [3, 3, 0, 1]);
+        assert_eq!(cropped.get_pixel(1, 0).0, [255, 255, 255, 0]);
+    }
+
+    #[test]
+    fn invisible_image_is_not_cropped() {
+        let image = image::RgbaImage::from_pixel(4, 3, image::Rgba([255, 0, 0, 0]));
+        assert_eq!(crop_transparent_borders(&image), image);
+    }
+
+    #[test]
+    fn downsample_averages_blocks() {
+        // Two 2x2 blocks, whose channels are the red values below and their halves, rounded.
+        let red = [[10, 20, 0, 255], [30, 41, 0, 255]];
+        let image = image::RgbaImage::from_fn(4, 2, |x, y| {
+            let red = red[y as usize][x as usize];
+            image::Rgba([red, red / 2, 0, 255])
+        });
+        let downsampled = ImageComparison::downsample(&image, 2);
+        assert_eq!(downsampled.dimensions(), (2, 1));
+        // (10 + 20 + 30 + 41) / 4 = 25.25, and (5 + 10 + 15 + 20) / 4 = 12.5.
+        assert_eq!(downsampled.get_pixel(0, 0).0, [25, 13, 0, 255]);
+        // (0 + 255 + 0 + 255) / 4 = 127.5, and (0 + 127 + 0 + 127) / 4 = 63.5.
+        assert_eq!(downsampled.get_pixel(1, 0).0, [128, 64, 0, 255]);
+    }
+
+    #[test]
+    fn downsample_by_one_keeps_image() {
+        let image = image::RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 7, 9]));
+        assert_eq!(ImageComparison::downsample(&image, 1), image);
+    }
+
+    #[test]
+    fn combined_outlier_limit() {
+        let check = OutlierCheck {
+            max_outliers: 3,
+            ..Default::default()
+        };
+        assert!(check.failed_limits(2, 1).is_empty());
+        assert_eq!(
+            check.failed_limits(2, 2),
+            ["Number of outliers (4) is bigger than allowed limit of 3"]
+        );
+    }
+
+    #[test]
+    fn separate_color_and_alpha_outlier_limits() {
+        let check = OutlierCheck {
+            max_outliers: 3,
+            max_alpha_outliers: Some(0),
+            ..Default::default()
+        };
+        // The color outliers fall back to `max_outliers`.
+        assert!(check.failed_limits(3, 0).is_empty());
+        assert_eq!(
+            check.failed_limits(4, 1),
+            [
+                "Number of color outliers (4) is bigger than allowed limit of 3",
+                "Number of alpha outliers (1) is bigger than allowed limit of 0",
+            ]
+        );
+
+        let check = OutlierCheck {
+            max_color_outliers: Some(10),
+            ..Default::default()
+        };
+        assert!(check.failed_limits(10, 0).is_empty());
+        assert_eq!(
+            check.failed_limits(0, 1),
+            ["Number of alpha outliers (1) is bigger than allowed limit of 0"]
+        );
+    }
+
+    #[test]
+    fn stage_scale_mode_and_align() {
+        let options = parse_test_options(
+            "[player_options]\nscale_mode = \"no_scale\"\nalign = \"top_left\"\nletterbox = \"on\"",
+        );
+        let player_options = options.player_options;
+        assert_eq!(player_options.scale_mode, Some(StageScaleMode::NoScale));
+        assert_eq!(
+            player_options.align,
+            Some(StageAlign::TOP | StageAlign::LEFT)
+        );
+        assert_eq!(player_options.letterbox, Some(Letterbox::On));
+
+        let options = parse_test_options("");
+        assert_eq!(options.player_options.scale_mode, None);
+        assert_eq!(options.player_options.align, None);
+    }
+
+    #[test]
+    fn invalid_stage_scale_mode() {
+        let error = toml::from_str::<TestOptions>("[player_options]\nscale_mode = \"noScale\"")
+            .err()
+            .unwrap();
+        assert!(
+            error.to_string().contains("invalid value \"noScale\""),
+            "unexpected error: {error}"
+        );
+    }
+
+    #[test]
+    fn heatmap_of_differences() {
+        let actual = image::RgbaImage::from_pixel(2, 1, image::Rgba([10, 200, 30, 255]));
+        let mut expected = actual.clone();
+        expected.put_pixel(1, 0, image::Rgba([12, 100, 30, 0]));
+
+        let heatmap = difference_heatmap(&actual, &expected, 1.0);
+        assert_eq!(heatmap.dimensions(), (2, 1));
+        assert_eq!(heatmap.get_pixel(0, 0).0, [0, 0, 0]);
+        // Alpha isn't part of the heatmap.
+        assert_eq!(heatmap.get_pixel(1, 0).0, [2, 100, 0]);
+
+        // The gain makes small differences visible, saturating at white.
+        let heatmap = difference_heatmap(&actual, &expected, 10.0);
+        assert_eq!(heatmap.get_pixel(1, 0).0, [20, 255, 0]);
+    }
+
+    #[test]
+    #[should_panic(expected = "Only images of the same size can be compared")]
+    fn heatmap_of_differently_sized_images() {
+        difference_heatmap(
+            &image::RgbaImage::new(2, 1),
+            &image::RgbaImage::new(1, 2),
+            1.0,
+        );
+    }
+
+    #[test]
+    fn average_color_rounds() {
+        let image = image::RgbaImage::from_fn(2, 2, |x, y| {
+            if (x, y) == (0, 0) {
+                image::Rgba([255, 0, 2, 255])
+            } else {
+                image::Rgba([0, 0, 0, 255])
+            }
+        });
+        // 255 / 4 = 63.75, and 2 / 4 = 0.5.
+        assert_eq!(average_color(&image), [64, 0, 1, 255]);
+    }
+
+    #[test]
+    fn average_color_of_empty_image() {
+        assert_eq!(average_color(&image::RgbaImage::new(0, 3)), [0; 4]);
+    }
+
+    #[test]
+    fn stage_color_tolerance() {
+        let assertion: StageColorAssertion =
+            toml::from_str("color = [10, 20, 30, 255]\ntolerance = 2").unwrap();
+        assert_eq!(assertion.source(), StageColorSource::Background);
+        assert!(assertion.test([10, 20, 30, 255]).is_ok());
+        assert!(assertion.test([12, 18, 30, 253]).is_ok());
+        assert_eq!(
+            assertion.test([10, 20, 33, 255]).unwrap_err().to_string(),
+            "Stage color (Background) is [10, 20, 33, 255], but [10, 20, 30, 255] was expected. \
+            Max difference is 3, over the tolerance of 2"
+        );
+    }
+
+    #[test]
+    fn ssim_of_identical_images() {
+        let image = image::RgbaImage::from_fn(20, 12, |x, y| {
+            image::Rgba([(x * 12) as u8, (y * 20) as u8, ((x + y) * 7) as u8, 255])
+        });
+        assert_eq!(ImageComparison::calculate_ssim(&image, &image), 1.0);
+
+        // Images smaller than a window are compared as a whole.
+        let small = image::RgbaImage::from_fn(3, 2, |x, y| image::Rgba([x as u8, y as u8, 0, 255]));
+        assert_eq!(ImageComparison::calculate_ssim(&small, &small), 1.0);
+
+        let empty = image::RgbaImage::new(0, 0);
+        assert_eq!(ImageComparison::calculate_ssim(&empty, &empty), 1.0);
+    }
+
+    #[test]
+    fn ssim_of_different_images() {
+        let black = image::RgbaImage::from_pixel(16, 16, image::Rgba([0, 0, 0, 255]));
+        let white = image::RgbaImage::from_pixel(16, 16, image::Rgba([255, 255, 255, 255]));
+        // Only the alpha channel is the same.
+        let ssim = ImageComparison::calculate_ssim(&black, &white);
+        assert!(relative_eq!(ssim, 0.25, epsilon = 0.001), "SSIM is {ssim}");
+
+        // A slightly noisy image is still very similar, but not identical.
+        let noisy = image::RgbaImage::from_fn(16, 16, |x, y| {
+            let noise = ((x + y) % 2) as u8;
+            image::Rgba([noise, noise, noise, 255])
+        });
+        let ssim = ImageComparison::calculate_ssim(&black, &noisy);
+        assert!(ssim < 1.0 && ssim > 0.9, "SSIM is {ssim}");
+    }
+}
@@ -1,14 +1,20 @@
-use crate::backends::{TestLogBackend, TestNavigatorBackend, TestUiBackend};
+use crate::audio_comparison::{self, AudioComparison, EXPECTED_AUDIO_PATH};
+use crate::backends::{
+    AudioRecording, CursorRecording, TestLogBackend, TestNavigatorBackend, TestUiBackend,
+};
 use crate::environment::RenderInterface;
 use crate::fs_commands::{FsCommand, TestFsCommandProvider};
 use crate::image_trigger::ImageTrigger;
-use crate::options::{ImageComparison, TestOptions};
+use crate::options::{
+    average_color, AdvanceMode, ImageComparison, StageColorAssertion, StageColorSource, TestOptions,
+};
 use crate::test::Test;
-use crate::util::{read_bytes, write_image};
+use crate::util::{image_format, read_bytes, write_bytes, write_image, Mismatch};
 use anyhow::{anyhow, Error, Result};
-use image::ImageFormat;
 use pretty_assertions::Comparison;
+use regex::Regex;
 use ruffle_core::backend::navigator::NullExecutor;
+use ruffle_core::backend::ui::MouseCursor;
 use ruffle_core::events::{
     ImeEvent, KeyDescriptor, KeyLocation, LogicalKey, NamedKey, PhysicalKey,
     TextControlCode as RuffleTextControlCode,
@@ -16,16 +22,17 @@ use ruffle_core::events::{
 use ruffle_core::events::{MouseButton as RuffleMouseButton, MouseWheelDelta};
 use ruffle_core::limits::ExecutionLimit;
 use ruffle_core::tag_utils::SwfMovie;
-use ruffle_core::{Player, PlayerBuilder, PlayerEvent};
+use ruffle_core::{Color, Player, PlayerBuilder, PlayerEvent};
 use ruffle_input_format::{
     AutomatedEvent, AutomatedKey, InputInjector, MouseButton as InputMouseButton,
     TextControlCode as InputTextControlCode,
 };
 use ruffle_render::backend::{RenderBackend, ViewportDimensions};
 use ruffle_socket_format::SocketEvent;
+use std::borrow::Cow;
 use std::collections::HashMap;
 use std::sync::{mpsc, Arc, Mutex};
-use std::time::Duration;
+use std::time::{Duration, Instant};
 use vfs::VfsPath;
 
 #[derive(Copy, Clone, Eq, PartialEq)]
@@ -44,12 +51,26 @@ pub struct TestRunner {
     executor: NullExecutor,
     frame_time: f64,
     frame_time_duration: Duration,
+    /// How much the clock moves forward with every tick, if time is faked.
+    fake_time_advance: Option<Duration>,
     log: TestLogBackend,
     fs_commands: mpsc::Receiver<FsCommand>,
     render_interface: Option<Box<dyn RenderInterface>>,
     images: HashMap<String, ImageComparison>,
+    /// The sound played by the movie, if it's compared at the end of the test.
+    audio_recording: Option<AudioRecording>,
+    /// The mouse cursor requested by the movie, if any image comparison checks it.
+    cursor_recording: Option<CursorRecording>,
     remaining_iterations: u32,
     current_iteration: u32,
+    /// How many `captureImage` fscommands the movie has run so far.
+    fs_command_captures: u32,
+    /// The frame of the main timeline after the last tick, to tell when it enters another one.
+    timeline_frame: Option<u16>,
+    /// How many times the main timeline has entered each frame so far.
+    timeline_frame_entries: HashMap<u16, u32>,
+    /// When the test has to be finished by, if it has a timeout.
+    deadline: Option<Instant>,
 }
 
 impl TestRunner {
@@ -61,12 +82,17 @@ impl TestRunner {
         renderer: Option<(Box<dyn RenderInterface>, Box<dyn RenderBackend>)>,
         viewport_dimensions: ViewportDimensions,
     ) -> Result<Self> {
-        if test.options.num_frames.is_none() && test.options.num_ticks.is_none() {
+        let Some(remaining_iterations) = test.options.num_iterations() else {
             return Err(anyhow!(
-                "Test {} must specify at least one of num_frames or num_ticks",
-                &test.name
+                "Test {} must specify {} to advance in {:?} mode",
+                &test.name,
+                match test.options.advance_mode() {
+                    AdvanceMode::Frames => "num_frames",
+                    AdvanceMode::Ticks | AdvanceMode::ExactTicks => "num_ticks",
+                },
+                test.options.advance_mode()
             ));
-        }
+        };
 
         let executor = NullExecutor::new();
         let mut frame_time = 1000.0 / movie.frame_rate().to_f64();
@@ -75,6 +101,7 @@ impl TestRunner {
         }
 
         let frame_time_duration = Duration::from_millis(frame_time as u64);
+        let fake_time_advance = test.options.player_options.fake_time_advance(frame_time);
 
         let log = TestLogBackend::default();
         let (fs_command_provider, fs_commands) = TestFsCommandProvider::new();
@@ -82,15 +109,28 @@ impl TestRunner {
             test.root_path.clone(),
             &executor,
             socket_events,
+            test.options.mocked_responses.clone(),
             test.options.log_fetch.then(|| log.clone()),
         )?;
 
+        let cursor_recording = test
+            .options
+            .image_comparisons
+            .values()
+            .any(|comparison| comparison.expected_cursor().is_some())
+            .then(|| CursorRecording::new(Mutex::new(MouseCursor::Arrow)));
+
         let mut builder = PlayerBuilder::new()
             .with_log(log.clone())
             .with_navigator(navigator)
             .with_max_execution_duration(Duration::from_secs(300))
             .with_fs_commands(Box::new(fs_command_provider))
-            .with_ui(TestUiBackend::new(test.fonts()?, test.font_sorts()))
+            .with_ui(TestUiBackend::new(
+                test.fonts()?,
+                test.font_sorts(),
+                test.options.player_options.language(),
+                cursor_recording.clone(),
+            ))
             .with_viewport_dimensions(
                 viewport_dimensions.width,
                 viewport_dimensions.height,
@@ -104,23 +144,23 @@ impl TestRunner {
             None
         };
 
+        let audio_recording = test
+            .options
+            .audio_comparison
+            .is_some()
+            .then(AudioRecording::default);
+
         // Test player options may override anything set above
         let player = test
             .options
             .player_options
-            .setup(builder)?
+            .setup(builder, audio_recording.clone())?
             .with_movie(movie)
             .with_autoplay(true) //.tick() requires playback
             .build();
 
         let images = test.options.image_comparisons.clone();
 
-        let remaining_iterations = test
-            .options
-            .num_frames
-            .or(test.options.num_ticks)
-            .expect("valid iteration count");
-
         Ok(Self {
             root_path: test.root_path.clone(),
             output_path: test.output_path.clone(),
@@ -130,11 +170,21 @@ impl TestRunner {
             executor,
             frame_time,
             frame_time_duration,
+            fake_time_advance,
             log,
             fs_commands,
             images,
+            audio_recording,
+            cursor_recording,
             remaining_iterations,
             current_iteration: 0,
+            fs_command_captures: 0,
+            timeline_frame: None,
+            timeline_frame_entries: HashMap::new(),
+            deadline: test
+                .options
+                .timeout
+                .and_then(|timeout| Instant::now().checked_add(timeout)),
             options: test.options.clone(),
         })
     }
@@ -160,12 +210,21 @@ impl TestRunner {
             .preload(&mut ExecutionLimit::exhausted())
         {}
 
-        if self.options.num_ticks.is_some() {
-            self.player.lock().unwrap().tick(self.frame_time);
-        } else {
-            self.player.lock().unwrap().run_frame();
-            self.player.lock().unwrap().update_timers(self.frame_time);
-            self.player.lock().unwrap().audio_mut().tick();
+        match self.options.advance_mode() {
+            AdvanceMode::Ticks | AdvanceMode::ExactTicks => {
+                self.player.lock().unwrap().tick(self.frame_time);
+            }
+            AdvanceMode::Frames => {
+                self.player.lock().unwrap().run_frame();
+                self.player.lock().unwrap().update_timers(self.frame_time);
+                self.player.lock().unwrap().audio_mut().tick();
+            }
+        }
+        if let Some(fake_time_advance) = self.fake_time_advance {
+            self.player
+                .lock()
+                .unwrap()
+                .advance_fake_time(fake_time_advance);
         }
         self.remaining_iterations -= 1;
         self.current_iteration += 1;
@@ -174,13 +233,50 @@ impl TestRunner {
 
     /// After a tick, run any custom fdcommands that were queued up and perform any scheduled tests.
     pub fn test(&mut self) -> Result<TestStatus> {
+        if self
+            .deadline
+            .is_some_and(|deadline| Instant::now() >= deadline)
+        {
+            let unit = match self.options.advance_mode() {
+                AdvanceMode::Ticks | AdvanceMode::ExactTicks => "ticks",
+                AdvanceMode::Frames => "frames",
+            };
+            return Err(anyhow!(
+                "Test timed out after {:?}, having completed {} of {} {unit}",
+                self.options.timeout.unwrap_or_default(),
+                self.current_iteration,
+                self.current_iteration + self.remaining_iterations,
+            ));
+        }
+
         for command in self.fs_commands.try_iter() {
             match command {
                 FsCommand::Quit => {
                     self.remaining_iterations = 0;
                 }
                 FsCommand::CaptureImage(name) => {
-                    if let Some(image_comparison) = self.images.remove(&name) {
+                    self.fs_command_captures += 1;
+                    // A comparison for this occurrence takes priority over the name given by the movie.
+                    // Comparisons for another occurrence are never matched by name,
+                    // as that would use them up before their turn.
+                    let name = self
+                        .images
+                        .iter()
+                        .find(|(_k, v)| {
+                            v.trigger == ImageTrigger::FsCommand
+                                && v.occurrence == Some(self.fs_command_captures)
+                        })
+                        .map(|(k, _v)| k.to_owned())
+                        .unwrap_or(name);
+                    let for_this_occurrence = self.images.get(&name).is_some_and(|v| {
+                        v.occurrence.is_none_or(|n| n == self.fs_command_captures)
+                    });
+                    let image_comparison = if for_this_occurrence {
+                        self.images.remove(&name)
+                    } else {
+                        None
+                    };
+                    if let Some(image_comparison) = image_comparison {
                         if image_comparison.trigger != ImageTrigger::FsCommand {
                             return Err(anyhow!("Encountered fscommand to capture and compare image '{name}', but the trigger was expected to be {:?}", image_comparison.trigger));
                         }
@@ -189,8 +285,9 @@ impl TestRunner {
                             &self.player,
                             &name,
                             image_comparison,
-                            self.options.known_failure,
+                            self.options.is_known_failure(),
                             self.render_interface.as_deref(),
+                            self.cursor_recording.as_ref(),
                         )?;
                     } else {
                         return Err(anyhow!("Encountered fscommand to capture and compare image '{name}', but no [image_comparison] was set up for this."));
@@ -238,6 +335,17 @@ impl TestRunner {
                         _ => panic!("MouseWheel: expected only one of 'lines' or 'pixels'"),
                     },
                 },
+                AutomatedEvent::HorizontalMouseWheel { lines, pixels } => {
+                    PlayerEvent::HorizontalMouseWheel {
+                        delta: match (lines, pixels) {
+                            (Some(lines), None) => MouseWheelDelta::Lines(*lines),
+                            (None, Some(pixels)) => MouseWheelDelta::Pixels(*pixels),
+                            _ => panic!(
+                                "HorizontalMouseWheel: expected only one of 'lines' or 'pixels'"
+                            ),
+                        },
+                    }
+                }
                 AutomatedEvent::KeyDown { key } => PlayerEvent::KeyDown {
                     key: automated_key_to_descriptor(*key),
                 },
@@ -321,7 +429,9 @@ impl TestRunner {
             }
         });
         // Rendering has side-effects (such as processing 'DisplayObject.scrollRect' updates)
-        self.player.lock().unwrap().render();
+        if self.options.advance_mode() != AdvanceMode::ExactTicks {
+            self.player.lock().unwrap().render();
+        }
 
         if let Some(name) = self
             .images
@@ -338,11 +448,45 @@ impl TestRunner {
                 &self.player,
                 &name,
                 image_comparison,
-                self.options.known_failure,
+                self.options.is_known_failure(),
                 self.render_interface.as_deref(),
+                self.cursor_recording.as_ref(),
             )?;
         }
 
+        let timeline_frame = self.player.lock().unwrap().current_frame();
+        if timeline_frame != self.timeline_frame {
+            self.timeline_frame = timeline_frame;
+            if let Some(frame) = timeline_frame {
+                let entries = self.timeline_frame_entries.entry(frame).or_default();
+                *entries += 1;
+                let entries = *entries;
+                if let Some(name) = self
+                    .images
+                    .iter()
+                    .find(|(_k, v)| {
+                        v.trigger == ImageTrigger::Frame(frame as u32)
+                            && v.occurrence.unwrap_or(1) == entries
+                    })
+                    .map(|(k, _v)| k.to_owned())
+                {
+                    let image_comparison = self
+                        .images
+                        .remove(&name)
+                        .expect("Name was just retrieved from map, should not be missing!");
+                    capture_and_compare_image(
+                        &self.root_path,
+                        &self.player,
+                        &name,
+                        image_comparison,
+                        self.options.is_known_failure(),
+                        self.render_interface.as_deref(),
+                        self.cursor_recording.as_ref(),
+                    )?;
+                }
+            }
+        }
+
         if self.remaining_iterations == 0 {
             // Last iteration, let's check everything went well
 
@@ -362,8 +506,9 @@ impl TestRunner {
                     &self.player,
                     &name,
                     image_comparison,
-                    self.options.known_failure,
+                    self.options.is_known_failure(),
                     self.render_interface.as_deref(),
+                    self.cursor_recording.as_ref(),
                 )?;
             }
 
@@ -374,6 +519,29 @@ impl TestRunner {
                 ));
             }
 
+            if let Some(stage_color) = &self.options.stage_color {
+                check_stage_color(
+                    &self.player,
+                    stage_color,
+                    self.options.is_known_failure(),
+                    self.render_interface.as_deref(),
+                )?;
+            }
+
+            if let (Some(comparison), Some(audio_recording)) =
+                (&self.options.audio_comparison, &self.audio_recording)
+            {
+                let actual_samples = audio_comparison::to_pcm16(
+                    &audio_recording.lock().expect("Non-poisoned recording"),
+                );
+                compare_audio(
+                    &self.root_path,
+                    &actual_samples,
+                    comparison,
+                    self.options.is_known_failure(),
+                )?;
+            }
+
             self.executor.run();
 
             let trace = self.log.trace_output();
@@ -386,7 +554,8 @@ impl TestRunner {
 
         Ok(match self.remaining_iterations {
             0 => TestStatus::Finished,
-            _ if self.options.sleep_to_meet_frame_rate => {
+            // With a fake clock, sleeping wouldn't make any difference to what the movie sees.
+            _ if self.options.sleep_to_meet_frame_rate && self.fake_time_advance.is_none() => {
                 // If requested, ensure that the 'expected' amount of
                 // time actually elapses between frames. This is useful for
                 // tests that call 'flash.utils.getTimer()' and use
@@ -400,7 +569,14 @@ impl TestRunner {
                 // we ensure that the result of 'flash.utils.getTimer()' is consistent
                 // with timer execution (timers will see an elapsed time of *at least*
                 // the requested timer interval).
-                TestStatus::Sleep(self.frame_time_duration)
+                // The sleep never goes past the timeout, so that it's noticed right after it.
+                let sleep = match self.deadline {
+                    Some(deadline) => self
+                        .frame_time_duration
+                        .min(deadline.saturating_duration_since(Instant::now())),
+                    None => self.frame_time_duration,
+                };
+                TestStatus::Sleep(sleep)
             }
             _ => TestStatus::Continue,
         })
@@ -409,6 +585,17 @@ impl TestRunner {
     pub fn compare_output(&self, actual_output: &str) -> Result<()> {
         let expected_output = self.output_path.read_to_string()?.replace("\r\n", "\n");
 
+        let ignored_line_patterns = self.options.ignored_line_patterns();
+        let substitutions = self.options.output_substitutions();
+        let actual_output = &substitute(
+            remove_ignored_lines(actual_output, &ignored_line_patterns),
+            &substitutions,
+        );
+        let expected_output = substitute(
+            remove_ignored_lines(&expected_output, &ignored_line_patterns),
+            &substitutions,
+        );
+
         if let Some(approximations) = &self.options.approximations {
             let add_comparison_to_err = |err: Error| -> Error {
                 let left_pretty = PrettyString(actual_output);
@@ -430,11 +617,6 @@ impl TestRunner {
                 // If these are numbers, compare using approx_eq.
                 if let (Ok(actual), Ok(expected)) = (actual.parse::<f64>(), expected.parse::<f64>())
                 {
-                    // NaNs should be able to pass in an approx test.
-                    if actual.is_nan() && expected.is_nan() {
-                        continue;
-                    }
-
                     approximations
                         .compare(actual, expected)
                         .map_err(add_comparison_to_err)?;
@@ -442,7 +624,7 @@ impl TestRunner {
                     let mut found = false;
 
                     // Check each of the user-provided regexes for a match
-                    for pattern in approximations.number_patterns() {
+                    for (pattern, tolerance) in approximations.number_patterns() {
                         if let (Some(actual_captures), Some(expected_captures)) =
                             (pattern.captures(actual), pattern.captures(expected))
                         {
@@ -472,7 +654,7 @@ impl TestRunner {
                                     .as_str()
                                     .parse::<f64>()
                                     .expect("Failed to parse 'expected' capture group as float");
-                                approximations
+                                tolerance
                                     .compare(actual_num, expected_num)
                                     .map_err(add_comparison_to_err)?;
                             }
@@ -500,6 +682,64 @@ impl TestRunner {
     }
 }
 
+/// How long the watchdog of [`run_with_watchdog`] waits past the timeout,
+/// for [`TestRunner::test`] to notice it first and tell how far the test got.
+const WATCHDOG_GRACE: Duration = Duration::from_secs(1);
+
+/// Runs a test on a thread of its own, failing once it's still running after its timeout, if it has one.
+///
+/// [`TestRunner::test`] only notices the timeout between ticks, while this also catches a tick
+/// that never ends, such as one stuck in a script. The stuck thread can't be stopped, so it's left behind.
+/// A panic of the test is passed on to the caller.
+pub fn run_with_watchdog<R: Send + 'static>(
+    timeout: Option<Duration>,
+    run: impl FnOnce() -> R + Send + 'static,
+) -> Result<R> {
+    let Some(timeout) = timeout else {
+        return Ok(run());
+    };
+    let (sender, receiver) = mpsc::channel();
+    std::thread::Builder::new()
+        .name("test".to_string())
+        .spawn(move || {
+            let _ = sender.send(std::panic::catch_unwind(std::panic::AssertUnwindSafe(run)));
+        })?;
+    match receiver.recv_timeout(timeout + WATCHDOG_GRACE) {
+        Ok(Ok(result)) => Ok(result),
+        Ok(Err(panic)) => std::panic::resume_unwind(panic),
+        Err(mpsc::RecvTimeoutError::Timeout) => Err(anyhow!(
+            "Test timed out after {timeout:?}, stuck in the middle of a tick"
+        )),
+        Err(mpsc::RecvTimeoutError::Disconnected) => {
+            Err(anyhow!("Test thread ended without a result"))
+        }
+    }
+}
+
+/// Leaves out the lines of the output that match any of the patterns.
+fn remove_ignored_lines<'a>(output: &'a str, patterns: &[Regex]) -> Cow<'a, str> {
+    if patterns.is_empty() {
+        return Cow::Borrowed(output);
+    }
+    Cow::Owned(
+        output
+            .split_inclusive('\n')
+            .filter(|line| {
+                let line = line.strip_suffix('\n').unwrap_or(line);
+                !patterns.iter().any(|pattern| pattern.is_match(line))
+            })
+            .collect(),
+    )
+}
+
+/// Replaces every match of the patterns in the output, one pattern after another.
+fn substitute<'a>(mut output: Cow<'a, str>, substitutions: &[(Regex, &str)]) -> Cow<'a, str> {
+    for (pattern, replacement) in substitutions {
+        output = Cow::Owned(pattern.replace_all(&output, *replacement).into_owned());
+    }
+    output
+}
+
 fn capture_and_compare_image(
     base_path: &VfsPath,
     player: &Arc<Mutex<Player>>,
@@ -507,36 +747,73 @@ fn capture_and_compare_image(
     image_comparison: ImageComparison,
     known_failure: bool,
     render_interface: Option<&dyn RenderInterface>,
+    cursor_recording: Option<&CursorRecording>,
 ) -> Result<()> {
     use anyhow::Context;
 
+    if let Some(cursor_recording) = cursor_recording {
+        let actual_cursor = *cursor_recording.lock().expect("Non-poisoned recording");
+        image_comparison.test_cursor(name, actual_cursor)?;
+    }
+
     if let Some(render_interface) = render_interface {
         let mut player_lock = player.lock().unwrap();
         player_lock.render();
 
         let actual_image = render_interface.capture(player_lock.renderer_mut());
 
-        let expected_image_path = base_path.join(format!("{name}.expected.png"))?;
+        let bless_mode = BlessMode::from_env();
+        let expected_image_path =
+            base_path.join(image_comparison.expected_image_path(name, render_interface)?)?;
+        // The expected image is read and written in the format of its extension,
+        // which may differ from the format of the comparison for a listed expected image.
+        let expected_image_format = image_format(&expected_image_path)?;
         if expected_image_path.is_file()? {
-            let expected_image = image::load_from_memory(&read_bytes(&expected_image_path)?)
-                .context("Failed to open expected image")?
-                .into_rgba8();
+            let expected_image = image::load_from_memory_with_format(
+                &read_bytes(&expected_image_path)?,
+                expected_image_format,
+            )
+            .context("Failed to open expected image")?
+            .into_rgba8();
 
-            image_comparison.test(
+            let result = image_comparison.test(
                 name,
-                actual_image,
+                actual_image.clone(),
                 expected_image,
                 base_path,
-                render_interface.name(),
+                render_interface,
                 known_failure,
-            )?;
+            );
+            match result {
+                // If we're expecting this to be wrong, don't save a likely wrong image,
+                // and errors other than a mismatch aren't fixed by a new expected image.
+                Err(err)
+                    if bless_mode == BlessMode::All && !known_failure && err.is::<Mismatch>() =>
+                {
+                    write_image(&expected_image_path, &actual_image, expected_image_format)?;
+                    println!(
+                        "Blessed image '{name}', rewrote {} with the actual image. It failed with: {err}",
+                        expected_image_path.as_str()
+                    );
+                }
+                result => result?,
+            }
         } else if known_failure {
             return Err(anyhow!(
                 "No image to compare to, pretending this failed since we don't know if it worked."
             ));
+        } else if bless_mode != BlessMode::None {
+            write_image(&expected_image_path, &actual_image, expected_image_format)?;
+            println!(
+                "Warning: Image '{name}' had no expected image, created {} from the actual one.",
+                expected_image_path.as_str()
+            );
         } else {
-            // If we're expecting this to be wrong, don't save a likely wrong image
-            write_image(&expected_image_path, &actual_image, ImageFormat::Png)?;
+            return Err(anyhow!(
+                "Image '{name}' has no expected image at {}. \
+                Run with RUFFLE_BLESS=missing to create it from the actual one.",
+                expected_image_path.as_str()
+            ));
         }
     } else if known_failure {
         // It's possible that the trace output matched but the image might not.
@@ -549,6 +826,116 @@ fn capture_and_compare_image(
     Ok(())
 }
 
+fn check_stage_color(
+    player: &Arc<Mutex<Player>>,
+    stage_color: &StageColorAssertion,
+    known_failure: bool,
+    render_interface: Option<&dyn RenderInterface>,
+) -> Result<()> {
+    let actual_color = match stage_color.source() {
+        StageColorSource::Background => {
+            // The stage is cleared to white without a background color, so that's what's seen.
+            let color = player
+                .lock()
+                .unwrap()
+                .background_color()
+                .unwrap_or(Color::WHITE);
+            [color.r, color.g, color.b, color.a]
+        }
+        StageColorSource::Average => {
+            let Some(render_interface) = render_interface else {
+                if known_failure {
+                    return Err(anyhow!(
+                        "Not checking the stage color, pretending this failed since we don't know if it worked."
+                    ));
+                }
+                return Ok(());
+            };
+            let mut player_lock = player.lock().unwrap();
+            player_lock.render();
+            average_color(&render_interface.capture(player_lock.renderer_mut()))
+        }
+    };
+    stage_color.test(actual_color)
+}
+
+fn compare_audio(
+    base_path: &VfsPath,
+    actual_samples: &[i16],
+    audio_comparison: &AudioComparison,
+    known_failure: bool,
+) -> Result<()> {
+    let expected_audio_path = base_path.join(EXPECTED_AUDIO_PATH)?;
+    let bless_mode = BlessMode::from_env();
+    if expected_audio_path.is_file()? {
+        let expected_samples = audio_comparison::decode_wav(&read_bytes(&expected_audio_path)?)
+            .map_err(|e| anyhow!("Failed to read {}: {e}", expected_audio_path.as_str()))?;
+        let result =
+            audio_comparison.test(actual_samples, &expected_samples, base_path, known_failure);
+        match result {
+            // Known failures are expected to mismatch, so their expected audio is left alone,
+            // and errors other than a mismatch aren't fixed by a new recording.
+            Err(err) if bless_mode == BlessMode::All && !known_failure && err.is::<Mismatch>() => {
+                write_bytes(
+                    &expected_audio_path,
+                    &audio_comparison::encode_wav(actual_samples),
+                )?;
+                println!(
+                    "Blessed audio, rewrote {} with the actual audio. It failed with: {err}",
+                    expected_audio_path.as_str()
+                );
+            }
+            result => result?,
+        }
+    } else if known_failure {
+        return Err(anyhow!(
+            "No audio to compare to, pretending this failed since we don't know if it worked."
+        ));
+    } else if bless_mode != BlessMode::None {
+        write_bytes(
+            &expected_audio_path,
+            &audio_comparison::encode_wav(actual_samples),
+        )?;
+        println!(
+            "Warning: Audio had no expected recording, created {} from the actual one.",
+            expected_audio_path.as_str()
+        );
+    } else {
+        return Err(anyhow!(
+            "Audio has no expected recording at {}. \
+            Run with RUFFLE_BLESS=missing to create it from the actual one.",
+            expected_audio_path.as_str()
+        ));
+    }
+
+    Ok(())
+}
+
+/// Whether the tests may write expected images, set with the `RUFFLE_BLESS` environment variable.
+#[derive(Clone, Copy, PartialEq, Eq, Debug)]
+enum BlessMode {
+    /// Expected images are only read, and a missing one fails the test.
+    None,
+
+    /// Missing expected images are created from the actual ones (`RUFFLE_BLESS=missing`).
+    /// Existing expected images are never overwritten.
+    Missing,
+
+    /// Missing expected images are created, and those that don't match are overwritten
+    /// with the actual ones (`RUFFLE_BLESS=1`), except for known failures.
+    All,
+}
+
+impl BlessMode {
+    fn from_env() -> Self {
+        match std::env::var("RUFFLE_BLESS").as_deref() {
+            Ok("missing") => BlessMode::Missing,
+            Ok("1") => BlessMode::All,
+            _ => BlessMode::None,
+        }
+    }
+}
+
 /// Wrapper around string slice that makes debug output `{:?}` to print string same way as `{}`.
 /// Used in different `assert*!` macros in combination with `pretty_assertions` crate to make
 /// test failures to show nice diffs.
@@ -657,3 +1044,81 @@ fn automated_key_to_descriptor(automated_key: AutomatedKey) -> KeyDescriptor {
         key_location,
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn patterns(patterns: &[&str]) -> Vec<Regex> {
+        patterns
+            .iter()
+            .map(|pattern| Regex::new(pattern).unwrap())
+            .collect()
+    }
+
+    #[test]
+    fn ignored_lines_are_removed() {
+        let output = "keep\ndebug: noise\nkeep too\nWarning: more noise\n";
+        assert_eq!(
+            remove_ignored_lines(output, &patterns(&["^debug:", "noise$"])),
+            "keep\nkeep too\n"
+        );
+    }
+
+    #[test]
+    fn last_line_without_newline_can_be_ignored() {
+        assert_eq!(
+            remove_ignored_lines("keep\ndebug", &patterns(&["^debug$"])),
+            "keep\n"
+        );
+        assert_eq!(
+            remove_ignored_lines("debug\nkeep", &patterns(&["^debug$"])),
+            "keep"
+        );
+    }
+
+    #[test]
+    fn no_ignored_line_patterns() {
+        let output = "keep\ndebug\n";
+        assert!(matches!(
+            remove_ignored_lines(output, &[]),
+            Cow::Borrowed("keep\ndebug\n")
+        ));
+    }
+
+    fn substitutions<'a>(substitutions: &[(&str, &'a str)]) -> Vec<(Regex, &'a str)> {
+        substitutions
+            .iter()
+            .map(|(pattern, replacement)| (Regex::new(pattern).unwrap(), *replacement))
+            .collect()
+    }
+
+    #[test]
+    fn output_is_substituted() {
+        let output = Cow::Borrowed("took 37 ms\nid=1a2b at 0x1f\n");
+        assert_eq!(
+            substitute(
+                output,
+                &substitutions(&[
+                    (r"took \d+ ms", "took <time> ms"),
+                    (r"id=([0-9a-f]+)", "id=<$1>")
+                ])
+            ),
+            "took <time> ms\nid=<1a2b> at 0x1f\n"
+        );
+    }
+
+    #[test]
+    fn substitutions_apply_in_order() {
+        let output = Cow::Borrowed("a");
+        assert_eq!(
+            substitute(output, &substitutions(&[("a", "b"), ("b", "c")])),
+            "c"
+        );
+        let output = Cow::Borrowed("a");
+        assert_eq!(
+            substitute(output, &substitutions(&[("b", "c"), ("a", "b")])),
+            "b"
+        );
+    }
+}
@@ -1,11 +1,11 @@
 use std::collections::HashMap;
 
 use crate::environment::Environment;
-use crate::options::TestOptions;
+use crate::options::{weight_mismatch, FontOptions, TestOptions};
 use crate::runner::TestRunner;
 use crate::util::read_bytes;
 use anyhow::{anyhow, Result};
-use ruffle_core::{tag_utils::SwfMovie, FontQuery, FontType};
+use ruffle_core::{tag_utils::SwfMovie, FontQuery, FontType, ViewportDimensions};
 use ruffle_input_format::InputInjector;
 use ruffle_socket_format::SocketEvent;
 use vfs::VfsPath;
@@ -15,6 +15,8 @@ pub struct Font {
     pub family: String,
     pub bold: bool,
     pub italic: bool,
+    /// The weight of the font from 100 to 900, which Ruffle only tells apart as `bold`.
+    pub weight: u16,
 }
 
 pub struct Test {
@@ -53,7 +55,7 @@ impl Test {
             .player_options
             .create_renderer(environment, viewport_dimensions);
 
-        let injector = self.input_injector()?;
+        let injector = self.input_injector(viewport_dimensions)?;
         let socket_events = self.socket_events()?;
         let runner = TestRunner::new(
             self,
@@ -84,7 +86,20 @@ impl Test {
         })
     }
 
-    fn input_injector(&self) -> Result<InputInjector> {
+    fn input_injector(&self, viewport_dimensions: ViewportDimensions) -> Result<InputInjector> {
+        if !self.options.input_events.is_empty() {
+            if self.input_path.is_file()? {
+                return Err(anyhow!(
+                    "Test {} has both input_events and {}, only one of them may be used",
+                    self.name,
+                    self.input_path.as_str()
+                ));
+            }
+            return Ok(InputInjector::from_events(
+                self.options.scheduled_input(viewport_dimensions)?,
+            ));
+        }
+
         Ok(if self.input_path.is_file()? {
             InputInjector::from_reader(&read_bytes(&self.input_path)?[..])
                 .map_err(|e| anyhow!("Error reading {}: {e}", self.input_path.as_str()))?
@@ -93,50 +108,89 @@ impl Test {
         })
     }
 
+    /// Reads the fonts of the test, along with their fallback fonts.
     pub fn fonts(&self) -> Result<HashMap<FontQuery, Font>> {
-        self.options
-            .fonts
-            .values()
-            .map(|font| {
-                Ok((
-                    font.to_font_query(),
+        let mut fonts = HashMap::new();
+        for (name, font) in &self.options.fonts {
+            if let Some(weight) = font.weight {
+                if !(100..=900).contains(&weight) {
+                    return Err(anyhow!(
+                        "Font '{name}' has a weight of {weight}, which isn't between 100 and 900"
+                    ));
+                }
+            }
+
+            let paths = std::iter::once(&font.path).chain(&font.fallbacks);
+            for (index, path) in paths.enumerate() {
+                let query = match index {
+                    0 => font.to_font_query(),
+                    _ => font.to_fallback_font_query(index - 1),
+                };
+                // Fonts of a family with the same boldness can't be told apart, so only the
+                // one closest to a regular or bold weight is kept.
+                if fonts.get(&query).is_some_and(|other: &Font| {
+                    weight_mismatch(other.weight) <= weight_mismatch(font.weight())
+                }) {
+                    continue;
+                }
+                let family = query.name.clone();
+                fonts.insert(
+                    query,
                     Font {
-                        bytes: read_bytes(&self.root_path.join(&font.path)?)?.to_vec(),
-                        family: font.family.to_owned(),
-                        bold: font.bold,
+                        bytes: read_bytes(&self.root_path.join(path)?)?.to_vec(),
+                        family,
+                        bold: font.is_bold(),
                         italic: font.italic,
+                        weight: font.weight(),
                     },
-                ))
-            })
-            .collect()
+                );
+            }
+        }
+        Ok(fonts)
     }
 
+    /// Returns the fonts to use for each font query, in priority order.
+    ///
+    /// A font with fallbacks is followed by them, unless a font sort is given for it explicitly.
     pub fn font_sorts(&self) -> HashMap<FontQuery, Vec<FontQuery>> {
-        self.options
-            .font_sorts
+        let with_fallbacks = |font: &FontOptions| {
+            let fallbacks = (0..font.fallbacks.len()).map(|i| font.to_fallback_font_query(i));
+            std::iter::once(font.to_font_query())
+                .chain(fallbacks)
+                .collect::<Vec<_>>()
+        };
+        let fallback_sorts = self
+            .options
+            .fonts
             .values()
-            .map(|font_sort| {
-                let query = FontQuery::new(
-                    FontType::Device,
-                    font_sort.family.clone(),
-                    font_sort.bold,
-                    font_sort.italic,
-                );
-                let sort = font_sort
-                    .sort
-                    .iter()
-                    .filter_map(|name| Some(self.options.fonts.get(name)?.to_font_query()))
-                    .collect();
-                (query, sort)
-            })
-            .collect()
+            .filter(|font| !font.fallbacks.is_empty())
+            .map(|font| (font.to_font_query(), with_fallbacks(font)));
+        let explicit_sorts = self.options.font_sorts.values().map(|font_sort| {
+            let query = FontQuery::new(
+                FontType::Device,
+                font_sort.family.clone(),
+                font_sort.bold,
+                font_sort.italic,
+            );
+            let sort = font_sort
+                .sort
+                .iter()
+                .filter_map(|name| self.options.fonts.get(name))
+                .flat_map(with_fallbacks)
+                .collect();
+            (query, sort)
+        });
+        // Explicit font sorts are collected last, so that they replace the implicit ones.
+        fallback_sorts.chain(explicit_sorts).collect()
     }
 
     pub fn should_run(&self, check_renderer: bool, environment: &impl Environment) -> bool {
         if self.options.ignore {
             return false;
         }
-        self.options.required_features.can_run()
+        self.options
+            .required_features
+            .can_run(check_renderer, environment)
             && self
                 .options
                 .player_options
@@ -1,8 +1,24 @@
 use image::{EncodableLayout, ImageBuffer, ImageFormat, Pixel, PixelWithColorType};
+use std::fmt;
 use std::io::{Cursor, Read, Write};
 use std::ops::Deref;
 use vfs::{VfsError, VfsPath};
 
+/// The error of a comparison whose actual result doesn't match the expected one,
+/// as opposed to one that couldn't be made at all.
+///
+/// Only these are fixed by blessing the actual result.
+#[derive(Debug)]
+pub struct Mismatch(pub String);
+
+impl fmt::Display for Mismatch {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str(&self.0)
+    }
+}
+
+impl std::error::Error for Mismatch {}
+
 pub fn read_bytes(path: &VfsPath) -> Result<Vec<u8>, VfsError> {
     let mut bytes = Vec::with_capacity(path.metadata()?.len as usize);
     path.open_file()?.read_to_end(&mut bytes)?;
@@ -14,6 +30,12 @@ pub fn write_bytes(path: &VfsPath, data: &[u8]) -> Result<(), VfsError> {
     Ok(())
 }
 
+/// The format of the image file at the given path, as told by its extension.
+pub fn image_format(path: &VfsPath) -> anyhow::Result<ImageFormat> {
+    ImageFormat::from_path(path.as_str())
+        .map_err(|_| anyhow::anyhow!("Unknown image format of {}", path.as_str()))
+}
+
 pub fn write_image<P, Container>(
     path: &VfsPath,
     image: &ImageBuffer<P, Container>,
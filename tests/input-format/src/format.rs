@@ -8,7 +8,7 @@ use serde::{Deserialize, Serialize};
 /// pixels) or at 2x the size will see mouse clicks at its bottom right corner
 /// on (1280x960), relative to the window. That coordinate needs to be scaled
 /// down to match the desired stage.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct MousePosition(pub f64, pub f64);
 
 /// Which mouse button is being pressed or released.
@@ -104,7 +104,7 @@ pub enum AutomatedKey {
 ///
 /// A FlashTAS input file consists of a string of `AutomatedEvent`s which are
 /// played back by FlashTAS.
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 #[serde(tag = "type")]
 pub enum AutomatedEvent {
     /// End the current frame's input and wait for the next frame before
@@ -134,6 +134,12 @@ pub enum AutomatedEvent {
         pixels: Option<f64>,
     },
 
+    /// Sideways mouse scroll, such as tilting the wheel. Positive deltas scroll to the left.
+    HorizontalMouseWheel {
+        lines: Option<f64>,
+        pixels: Option<f64>,
+    },
+
     /// Press a key
     KeyDown { key: AutomatedKey },
 
@@ -165,7 +171,7 @@ pub enum AutomatedEvent {
     ImeCommit { text: String },
 }
 
-#[derive(Serialize, Deserialize, Debug)]
+#[derive(Serialize, Deserialize, Debug, Clone)]
 pub struct EventHandledAssertion {
     pub value: bool,
     pub message: String,
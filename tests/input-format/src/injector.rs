@@ -70,6 +70,16 @@ impl InputInjector {
         })
     }
 
+    /// Construct an input injector from a list of events, split into frames by
+    /// `AutomatedEvent::Wait`.
+    pub fn from_events(items: Vec<AutomatedEvent>) -> Self {
+        Self {
+            items,
+            pos: 0,
+            buttons: MouseButtons::empty(),
+        }
+    }
+
     /// Create an empty input injector with no input to inject.
     ///
     /// Useful to represent a missing input file in cases where providing one
@@ -101,6 +111,7 @@ impl InputInjector {
                     | AutomatedEvent::TextControl { .. }
                     | AutomatedEvent::SetClipboardText { .. }
                     | AutomatedEvent::MouseWheel { .. }
+                    | AutomatedEvent::HorizontalMouseWheel { .. }
                     | AutomatedEvent::ImePreedit { .. }
                     | AutomatedEvent::ImeCommit { .. }
                     | AutomatedEvent::FocusGained
@@ -68,6 +68,12 @@ mod renderer {
             }
         }
 
+        fn renderer(&self) -> &'static str {
+            descriptors().map_or("none", |descriptors| {
+                descriptors.adapter.get_info().backend.to_str()
+            })
+        }
+
         fn capture(&self, backend: &mut dyn RenderBackend) -> RgbaImage {
             let renderer =
                 <dyn Any>::downcast_mut::<WgpuRenderBackend<TextureTarget>>(backend).unwrap();
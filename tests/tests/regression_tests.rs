@@ -10,11 +10,12 @@ use anyhow::Result;
 use libtest_mimic::Trial;
 use ruffle_fs_tests_runner::{FsTestsRunner, TestLoaderParams};
 use ruffle_test_framework::options::TestOptions;
-use ruffle_test_framework::runner::TestStatus;
+use ruffle_test_framework::runner::{run_with_watchdog, TestStatus};
 use ruffle_test_framework::test::Test;
 use std::borrow::Cow;
 use std::panic::{catch_unwind, resume_unwind, AssertUnwindSafe};
 use std::path::PathBuf;
+use std::sync::Arc;
 use std::thread::sleep;
 
 mod environment;
@@ -81,26 +82,30 @@ fn load_test(params: TestLoaderParams) -> Trial {
 
     let ignore = !test.should_run(!args.list, &NativeEnvironment);
 
+    let test = Arc::new(test);
     let mut trial = Trial::test(test.name.to_string(), move || {
-        let test = AssertUnwindSafe(test);
-        let unwind_result = catch_unwind(|| {
-            let mut runner = test.create_test_runner(&NativeEnvironment)?;
+        let unwind_result = catch_unwind(AssertUnwindSafe(|| {
+            let timeout = test.options.timeout;
+            let test = test.clone();
+            run_with_watchdog(timeout, move || {
+                let mut runner = test.create_test_runner(&NativeEnvironment)?;
 
-            loop {
-                runner.tick();
-                match runner.test()? {
-                    TestStatus::Continue => {}
-                    TestStatus::Sleep(duration) => sleep(duration),
-                    TestStatus::Finished => break,
+                loop {
+                    runner.tick();
+                    match runner.test()? {
+                        TestStatus::Continue => {}
+                        TestStatus::Sleep(duration) => sleep(duration),
+                        TestStatus::Finished => break,
+                    }
                 }
-            }
 
-            Result::<_>::Ok(())
-        });
-        if test.options.known_failure {
+                Result::<_>::Ok(())
+            })?
+        }));
+        if test.options.is_known_failure() {
             match unwind_result {
                 Ok(Ok(())) => Err(
-                    format!("{} was known to be failing, but now passes successfully. Please update it and remove `known_failure = true`!", test.name).into()
+                    format!("{} was known to be failing {}, but now passes successfully. Please update it and remove `known_failure`!", test.name, test.options.known_failure).into()
                 ),
                 Ok(Err(_)) | Err(_) => Ok(()),
             }
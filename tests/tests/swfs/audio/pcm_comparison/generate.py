@@ -0,0 +1,64 @@
+# Generates test.swf and audio.expected.wav.
+#
+# The movie plays a short uncompressed stereo sound at a constant level on its first frame, and stops.
+# Its frame rate of 24 fps doesn't evenly divide the sample rate, so a tick mixes half a sample frame
+# more than 44100 / 24 would need.
+
+import struct
+
+SAMPLE_RATE = 44100
+FRAME_RATE = 24
+NUM_FRAMES = 5
+SOUND_FRAMES = 4410
+LEFT, RIGHT = 8000, -8000
+
+
+def tag(code, data):
+    if len(data) < 0x3f:
+        return struct.pack('<H', code << 6 | len(data)) + data
+    return struct.pack('<HI', code << 6 | 0x3f, len(data)) + data
+
+
+def rect(x_min, x_max, y_min, y_max):
+    bits = 12
+    value = bits
+    for coordinate in (x_min, x_max, y_min, y_max):
+        value = value << bits | coordinate & (1 << bits) - 1
+    length = 5 + 4 * bits
+    padding = -length % 8
+    return (value << padding).to_bytes((length + padding) // 8, 'big')
+
+
+def movie():
+    sound = struct.pack('<hh', LEFT, RIGHT) * SOUND_FRAMES
+    # Uncompressed little-endian, 44 kHz, 16-bit, stereo.
+    sound_format = 3 << 4 | 3 << 2 | 1 << 1 | 1
+    tags = b''.join([
+        tag(9, bytes([255, 255, 255])),  # SetBackgroundColor
+        tag(14, struct.pack('<HBI', 1, sound_format, SOUND_FRAMES) + sound),  # DefineSound
+        tag(15, struct.pack('<HB', 1, 0)),  # StartSound
+        tag(12, bytes([0x07, 0x00])),  # DoAction: stop
+        tag(1, b''),  # ShowFrame
+        tag(0, b''),  # End
+    ])
+    body = rect(0, 2000, 0, 2000) + struct.pack('<HH', FRAME_RATE << 8, 1) + tags
+    return b'FWS' + bytes([8]) + struct.pack('<I', 8 + len(body)) + body
+
+
+def expected_audio():
+    frames_per_tick = round(SAMPLE_RATE / FRAME_RATE)
+    total_frames = frames_per_tick * NUM_FRAMES
+    samples = struct.pack('<hh', LEFT, RIGHT) * SOUND_FRAMES
+    samples += bytes(4 * (total_frames - SOUND_FRAMES))
+    format_chunk = struct.pack('<HHIIHH', 1, 2, SAMPLE_RATE, SAMPLE_RATE * 4, 4, 16)
+    return (
+        b'RIFF' + struct.pack('<I', 36 + len(samples)) + b'WAVE'
+        + b'fmt ' + struct.pack('<I', len(format_chunk)) + format_chunk
+        + b'data' + struct.pack('<I', len(samples)) + samples
+    )
+
+
+with open('test.swf', 'wb') as f:
+    f.write(movie())
+with open('audio.expected.wav', 'wb') as f:
+    f.write(expected_audio())
@@ -0,0 +1,90 @@
+# Generates test.swf.
+#
+# The first frame creates a text field that's too narrow for its line of text,
+# which the input of the test scrolls sideways with the mouse wheel.
+# The next frames trace how far it's scrolled.
+
+import struct
+
+FRAME_RATE = 24
+
+
+def tag(code, data):
+    if len(data) < 0x3f:
+        return struct.pack('<H', code << 6 | len(data)) + data
+    return struct.pack('<HI', code << 6 | 0x3f, len(data)) + data
+
+
+def rect(x_min, x_max, y_min, y_max):
+    bits = 12
+    value = bits
+    for coordinate in (x_min, x_max, y_min, y_max):
+        value = value << bits | coordinate & (1 << bits) - 1
+    length = 5 + 4 * bits
+    padding = -length % 8
+    return (value << padding).to_bytes((length + padding) // 8, 'big')
+
+
+def push(*values):
+    data = b''
+    for value in values:
+        if isinstance(value, bool):
+            data += struct.pack('<BB', 5, value)  # Boolean
+        elif isinstance(value, int):
+            data += struct.pack('<Bi', 7, value)  # Integer
+        else:
+            data += b'\0' + value.encode() + b'\0'  # String
+    return struct.pack('<BH', 0x96, len(data)) + data  # ActionPush
+
+
+def get_member(name, member):
+    return push(name) + bytes([0x1c]) + push(member) + bytes([0x4e])  # ActionGetVariable, ActionGetMember
+
+
+def set_member(name, member, value):
+    return push(name) + bytes([0x1c]) + push(member, value) + bytes([0x4f])  # ActionGetVariable, ActionSetMember
+
+
+def create_text_field():
+    return b''.join([
+        # _root.createTextField("t", 1, 0, 0, 50, 20);
+        push(20, 50, 0, 0, 1, 't', 6, '_root'),
+        bytes([0x1c]),  # ActionGetVariable
+        push('createTextField'),
+        bytes([0x52]),  # ActionCallMethod
+        bytes([0x17]),  # ActionPop
+        # t.html = true;
+        set_member('t', 'html', True),
+        # t.htmlText = '<font face="Tinos" size="20">...</font>';
+        set_member('t', 'htmlText', '<font face="Tinos" size="20">' + 'scrolled sideways ' * 4 + '</font>'),
+        bytes([0]),  # End
+    ])
+
+
+def trace_scroll(trace_max):
+    actions = []
+    if trace_max:
+        # trace(t.maxhscroll > 20);
+        actions += [get_member('t', 'maxhscroll'), push(20), bytes([0x67, 0x26])]  # ActionGreater, ActionTrace
+    # trace(t.hscroll);
+    actions += [get_member('t', 'hscroll'), bytes([0x26])]  # ActionTrace
+    return b''.join(actions) + bytes([0])  # End
+
+
+def movie():
+    tags = [
+        tag(9, bytes([255, 255, 255])),  # SetBackgroundColor
+        tag(12, create_text_field()),  # DoAction
+        tag(1, b''),  # ShowFrame
+        tag(12, trace_scroll(True)),  # DoAction
+        tag(1, b''),  # ShowFrame
+        tag(12, trace_scroll(False)),  # DoAction
+        tag(1, b''),  # ShowFrame
+        tag(0, b''),  # End
+    ]
+    body = rect(0, 2000, 0, 2000) + struct.pack('<HH', FRAME_RATE << 8, 3) + b''.join(tags)
+    return b'FWS' + bytes([8]) + struct.pack('<I', 8 + len(body)) + body
+
+
+with open('test.swf', 'wb') as f:
+    f.write(movie())
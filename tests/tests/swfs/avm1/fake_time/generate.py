@@ -0,0 +1,67 @@
+# Generates test.swf.
+#
+# The movie has three frames, each tracing the time of the clock as seen by Date and getTimer,
+# so that the fake_time player option decides what it traces.
+
+import struct
+
+FRAME_RATE = 24
+NUM_FRAMES = 3
+
+
+def tag(code, data):
+    if len(data) < 0x3f:
+        return struct.pack('<H', code << 6 | len(data)) + data
+    return struct.pack('<HI', code << 6 | 0x3f, len(data)) + data
+
+
+def rect(x_min, x_max, y_min, y_max):
+    bits = 12
+    value = bits
+    for coordinate in (x_min, x_max, y_min, y_max):
+        value = value << bits | coordinate & (1 << bits) - 1
+    length = 5 + 4 * bits
+    padding = -length % 8
+    return (value << padding).to_bytes((length + padding) // 8, 'big')
+
+
+def push(*values):
+    data = b''
+    for value in values:
+        if isinstance(value, int):
+            data += struct.pack('<Bi', 7, value)  # Integer
+        else:
+            data += b'\0' + value.encode() + b'\0'  # String
+    return struct.pack('<BH', 0x96, len(data)) + data  # ActionPush
+
+
+def actions():
+    return b''.join([
+        # trace("date: " + new Date().getTime());
+        push('date: ', 0, 0, 'Date'),
+        bytes([0x40]),  # ActionNewObject
+        push('getTime'),
+        bytes([0x52]),  # ActionCallMethod
+        bytes([0x47]),  # ActionAdd2
+        bytes([0x26]),  # ActionTrace
+        # trace("timer: " + getTimer());
+        push('timer: '),
+        bytes([0x34]),  # ActionGetTime
+        bytes([0x47]),  # ActionAdd2
+        bytes([0x26]),  # ActionTrace
+        bytes([0]),  # End
+    ])
+
+
+def movie():
+    tags = [tag(9, bytes([255, 255, 255]))]  # SetBackgroundColor
+    for _ in range(NUM_FRAMES):
+        tags.append(tag(12, actions()))  # DoAction
+        tags.append(tag(1, b''))  # ShowFrame
+    tags.append(tag(0, b''))  # End
+    body = rect(0, 2000, 0, 2000) + struct.pack('<HH', FRAME_RATE << 8, NUM_FRAMES) + b''.join(tags)
+    return b'FWS' + bytes([8]) + struct.pack('<I', 8 + len(body)) + body
+
+
+with open('test.swf', 'wb') as f:
+    f.write(movie())
@@ -0,0 +1,73 @@
+# Generates test.swf.
+#
+# The movie traces a line with the current date and the time it took to start,
+# which differ on every run, so that they have to be left out or normalized
+# by ignored_line_patterns and output_substitutions.
+
+import struct
+
+FRAME_RATE = 24
+
+
+def tag(code, data):
+    if len(data) < 0x3f:
+        return struct.pack('<H', code << 6 | len(data)) + data
+    return struct.pack('<HI', code << 6 | 0x3f, len(data)) + data
+
+
+def rect(x_min, x_max, y_min, y_max):
+    bits = 12
+    value = bits
+    for coordinate in (x_min, x_max, y_min, y_max):
+        value = value << bits | coordinate & (1 << bits) - 1
+    length = 5 + 4 * bits
+    padding = -length % 8
+    return (value << padding).to_bytes((length + padding) // 8, 'big')
+
+
+def push(*values):
+    data = b''
+    for value in values:
+        if isinstance(value, int):
+            data += struct.pack('<Bi', 7, value)  # Integer
+        else:
+            data += b'\0' + value.encode() + b'\0'  # String
+    return struct.pack('<BH', 0x96, len(data)) + data  # ActionPush
+
+
+def actions():
+    return b''.join([
+        # trace("started at " + new Date().getTime());
+        push('started at ', 0, 0, 'Date'),
+        bytes([0x40]),  # ActionNewObject
+        push('getTime'),
+        bytes([0x52]),  # ActionCallMethod
+        bytes([0x47]),  # ActionAdd2
+        bytes([0x26]),  # ActionTrace
+        # trace("took " + getTimer() + " ms");
+        push('took '),
+        bytes([0x34]),  # ActionGetTime
+        bytes([0x47]),  # ActionAdd2
+        push(' ms'),
+        bytes([0x47]),  # ActionAdd2
+        bytes([0x26]),  # ActionTrace
+        # trace("done");
+        push('done'),
+        bytes([0x26]),  # ActionTrace
+        bytes([0]),  # End
+    ])
+
+
+def movie():
+    tags = [
+        tag(9, bytes([255, 255, 255])),  # SetBackgroundColor
+        tag(12, actions()),  # DoAction
+        tag(1, b''),  # ShowFrame
+        tag(0, b''),  # End
+    ]
+    body = rect(0, 2000, 0, 2000) + struct.pack('<HH', FRAME_RATE << 8, 1) + b''.join(tags)
+    return b'FWS' + bytes([8]) + struct.pack('<I', 8 + len(body)) + body
+
+
+with open('test.swf', 'wb') as f:
+    f.write(movie())
@@ -0,0 +1,46 @@
+# Generates on/test.swf and off/test.swf, which are the same movie.
+#
+# The movie has three frames, each tracing its number, and no script that stops it,
+# so only the loop player option decides whether it goes back to its first frame.
+
+import struct
+
+FRAME_RATE = 24
+NUM_FRAMES = 3
+
+
+def tag(code, data):
+    if len(data) < 0x3f:
+        return struct.pack('<H', code << 6 | len(data)) + data
+    return struct.pack('<HI', code << 6 | 0x3f, len(data)) + data
+
+
+def rect(x_min, x_max, y_min, y_max):
+    bits = 12
+    value = bits
+    for coordinate in (x_min, x_max, y_min, y_max):
+        value = value << bits | coordinate & (1 << bits) - 1
+    length = 5 + 4 * bits
+    padding = -length % 8
+    return (value << padding).to_bytes((length + padding) // 8, 'big')
+
+
+def trace(message):
+    string = message.encode() + b'\0'
+    push = struct.pack('<BHB', 0x96, 1 + len(string), 0) + string  # ActionPush
+    return push + bytes([0x26])  # ActionTrace
+
+
+def movie():
+    tags = [tag(9, bytes([255, 255, 255]))]  # SetBackgroundColor
+    for frame in range(1, NUM_FRAMES + 1):
+        tags.append(tag(12, trace(f'frame {frame}') + bytes([0])))  # DoAction
+        tags.append(tag(1, b''))  # ShowFrame
+    tags.append(tag(0, b''))  # End
+    body = rect(0, 2000, 0, 2000) + struct.pack('<HH', FRAME_RATE << 8, NUM_FRAMES) + b''.join(tags)
+    return b'FWS' + bytes([8]) + struct.pack('<I', 8 + len(body)) + body
+
+
+for directory in ('on', 'off'):
+    with open(f'{directory}/test.swf', 'wb') as f:
+        f.write(movie())
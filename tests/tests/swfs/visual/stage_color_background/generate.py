@@ -0,0 +1,38 @@
+# Generates test.swf.
+#
+# The movie only sets its background color, which the stage_color check compares
+# without needing a renderer.
+
+import struct
+
+FRAME_RATE = 24
+
+
+def tag(code, data):
+    if len(data) < 0x3f:
+        return struct.pack('<H', code << 6 | len(data)) + data
+    return struct.pack('<HI', code << 6 | 0x3f, len(data)) + data
+
+
+def rect(x_min, x_max, y_min, y_max):
+    bits = 12
+    value = bits
+    for coordinate in (x_min, x_max, y_min, y_max):
+        value = value << bits | coordinate & (1 << bits) - 1
+    length = 5 + 4 * bits
+    padding = -length % 8
+    return (value << padding).to_bytes((length + padding) // 8, 'big')
+
+
+def movie():
+    tags = [
+        tag(9, bytes([51, 102, 153])),  # SetBackgroundColor
+        tag(1, b''),  # ShowFrame
+        tag(0, b''),  # End
+    ]
+    body = rect(0, 2000, 0, 2000) + struct.pack('<HH', FRAME_RATE << 8, 1) + b''.join(tags)
+    return b'FWS' + bytes([8]) + struct.pack('<I', 8 + len(body)) + body
+
+
+with open('test.swf', 'wb') as f:
+    f.write(movie())
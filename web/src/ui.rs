@@ -198,6 +198,14 @@ impl UiBackend for WebUiBackend {
         self.update_mouse_cursor();
     }
 
+    fn set_mouse_lock(&mut self, _locked: bool) {
+        // TODO: Use the Pointer Lock API.
+    }
+
+    fn mouse_locked(&self) -> bool {
+        false
+    }
+
     fn set_mouse_cursor(&mut self, cursor: MouseCursor) {
         self.cursor = cursor;
         self.update_mouse_cursor();